@@ -435,6 +435,11 @@ impl<Window: WindowMethods> IOCompositor<Window> {
                 send.send(rect).unwrap();
             }
 
+            (Msg::GetScreenSize(send),
+             ShutdownState::NotShuttingDown) => {
+                send.send(self.window.screen_size()).unwrap();
+            }
+
             (Msg::Status(message), ShutdownState::NotShuttingDown) => {
                 self.window.status(message);
             }
@@ -1057,6 +1062,10 @@ impl<Window: WindowMethods> IOCompositor<Window> {
                 self.on_key_event(key, state, modifiers);
             }
 
+            WindowEvent::SetOnlineStatus(online) => {
+                self.on_online_status_window_event(online);
+            }
+
             WindowEvent::Quit => {
                 if !self.has_seen_quit_event {
                     self.has_seen_quit_event = true;
@@ -1095,7 +1104,7 @@ impl<Window: WindowMethods> IOCompositor<Window> {
         let url = Url::parse(&url_string).unwrap();
         self.window.set_page_url(url.clone());
         let msg = match self.scene.root {
-            Some(ref layer) => ConstellationMsg::LoadUrl(layer.pipeline_id(), LoadData::new(url)),
+            Some(ref layer) => ConstellationMsg::LoadUrl(layer.pipeline_id(), LoadData::new(url), false),
             None => ConstellationMsg::InitLoadUrl(url)
         };
 
@@ -1125,6 +1134,14 @@ impl<Window: WindowMethods> IOCompositor<Window> {
     fn on_scroll_window_event(&mut self,
                               delta: TypedPoint2D<DevicePixel, f32>,
                               cursor: TypedPoint2D<DevicePixel, i32>) {
+        // Windowing scroll deltas move the content, so they have the opposite sign
+        // from wheel event deltas.
+        let wheel_delta = -delta / self.scene.scale;
+        match self.find_topmost_layer_at_point(cursor.as_f32() / self.scene.scale) {
+            Some(result) => result.layer.send_wheel_event(self, wheel_delta, result.point),
+            None => {},
+        }
+
         self.pending_scroll_events.push(ScrollEvent {
             delta: delta,
             cursor: cursor,
@@ -1322,6 +1339,11 @@ impl<Window: WindowMethods> IOCompositor<Window> {
         chan.send(ConstellationMsg::KeyEvent(key, state, modifiers)).unwrap()
     }
 
+    fn on_online_status_window_event(&self, online: bool) {
+        let ConstellationChan(ref chan) = self.constellation_chan;
+        chan.send(ConstellationMsg::SetOnlineStatus(online)).unwrap()
+    }
+
     fn fill_paint_request_with_cached_layer_buffers(&mut self, paint_request: &mut PaintRequest) {
         for buffer_request in &mut paint_request.buffer_requests {
             if self.surface_map.mem() == 0 {
@@ -14,6 +14,7 @@ use layers::layers::{Layer, LayerBufferSet};
 use msg::compositor_msg::{Epoch, LayerId, LayerProperties, ScrollPolicy};
 use msg::constellation_msg::{PipelineId, SubpageId};
 use script_traits::CompositorEvent::{ClickEvent, MouseDownEvent, MouseMoveEvent, MouseUpEvent};
+use script_traits::CompositorEvent::WheelEvent;
 use script_traits::ConstellationControlMsg;
 use std::rc::Rc;
 use windowing::{MouseWindowEvent, WindowMethods};
@@ -134,6 +135,14 @@ pub trait CompositorLayer {
                                      cursor: TypedPoint2D<LayerPixel, f32>)
                                      where Window: WindowMethods;
 
+    // Sends a wheel event for a scroll by `delta` to this layer's pipeline. NB: the delta
+    // uses the DOM convention, where positive values scroll down and to the right.
+    fn send_wheel_event<Window>(&self,
+                                compositor: &IOCompositor<Window>,
+                                delta: TypedPoint2D<LayerPixel, f32>,
+                                cursor: TypedPoint2D<LayerPixel, f32>)
+                                where Window: WindowMethods;
+
     fn clamp_scroll_offset_and_scroll_layer(&self,
                                             new_offset: TypedPoint2D<LayerPixel, f32>)
                                             -> ScrollEventResult;
@@ -394,6 +403,19 @@ impl CompositorLayer for Layer<CompositorData> {
         }
     }
 
+    fn send_wheel_event<Window>(&self,
+                                compositor: &IOCompositor<Window>,
+                                delta: TypedPoint2D<LayerPixel, f32>,
+                                cursor: TypedPoint2D<LayerPixel, f32>)
+                                where Window: WindowMethods {
+        let message = WheelEvent(delta.to_untyped(), cursor.to_untyped());
+        if let Some(pipeline) = compositor.pipeline(self.pipeline_id()) {
+            pipeline.script_chan
+                    .send(ConstellationControlMsg::SendEvent(pipeline.id.clone(), message))
+                    .unwrap();
+        }
+    }
+
     fn scroll_layer_and_all_child_layers(&self, new_offset: TypedPoint2D<LayerPixel, f32>)
                                          -> bool {
         let mut result = false;
@@ -74,6 +74,10 @@ pub fn run_script_listener_thread(compositor_proxy: Box<CompositorProxy + 'stati
                 compositor_proxy.send(Msg::GetClientWindow(send));
             }
 
+            ScriptToCompositorMsg::GetScreenSize(send) => {
+                compositor_proxy.send(Msg::GetScreenSize(send));
+            }
+
             ScriptToCompositorMsg::MoveTo(point) => {
                 compositor_proxy.send(Msg::MoveTo(point));
             }
@@ -213,6 +217,8 @@ pub enum Msg {
     Status(Option<String>),
     /// Get Window Informations size and position
     GetClientWindow(IpcSender<(Size2D<u32>, Point2D<i32>)>),
+    /// Get the size of the screen and of the area of it available to windows
+    GetScreenSize(IpcSender<(Size2D<u32>, Size2D<u32>)>),
     /// Move the window to a point
     MoveTo(Point2D<i32>),
     /// Resize the window to size
@@ -254,6 +260,7 @@ impl Debug for Msg {
             Msg::CollectMemoryReports(..) => write!(f, "CollectMemoryReports"),
             Msg::Status(..) => write!(f, "Status"),
             Msg::GetClientWindow(..) => write!(f, "GetClientWindow"),
+            Msg::GetScreenSize(..) => write!(f, "GetScreenSize"),
             Msg::MoveTo(..) => write!(f, "MoveTo"),
             Msg::ResizeTo(..) => write!(f, "ResizeTo"),
             Msg::PipelineExited(..) => write!(f, "PipelineExited"),
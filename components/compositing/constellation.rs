@@ -20,6 +20,7 @@ use euclid::scale_factor::ScaleFactor;
 use euclid::size::{Size2D, TypedSize2D};
 use gfx::font_cache_task::FontCacheTask;
 use ipc_channel::ipc::{self, IpcSender};
+use ipc_channel::router::ROUTER;
 use layout_traits::{LayoutControlChan, LayoutTaskFactory};
 use msg::compositor_msg::Epoch;
 use msg::constellation_msg::AnimationState;
@@ -186,6 +187,14 @@ impl Frame {
         self.current = pipeline_id;
         replace(&mut self.next, vec!())
     }
+
+    /// Like `load`, but the new pipeline takes the place of the current one in
+    /// the session history, which is evicted along with the forward entries.
+    fn replace_current(&mut self, pipeline_id: PipelineId) -> Vec<PipelineId> {
+        let mut evicted = replace(&mut self.next, vec!());
+        evicted.push(replace(&mut self.current, pipeline_id));
+        evicted
+    }
 }
 
 /// Represents a pending change in the frame tree, that will be applied
@@ -194,6 +203,9 @@ struct FrameChange {
     old_pipeline_id: Option<PipelineId>,
     new_pipeline_id: PipelineId,
     painter_ready: bool,
+    /// Whether the new pipeline replaces the current session history entry
+    /// rather than being added after it.
+    replace: bool,
 }
 
 /// An iterator over a frame tree, returning nodes in depth-first order.
@@ -285,6 +297,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                 webgl_paint_tasks: Vec::new(),
                 subpage_id_senders: HashMap::new(),
             };
+            constellation.register_online_status_listener();
             constellation.run();
         });
         constellation_chan
@@ -304,7 +317,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                     parent_info: Option<(PipelineId, SubpageId)>,
                     initial_window_size: Option<TypedSize2D<PagePx, f32>>,
                     script_channel: Option<Sender<ConstellationControlMsg>>,
-                    load_data: LoadData)
+                    load_data: LoadData,
+                    scripting_enabled: bool)
                     -> PipelineId {
         let pipeline_id = self.next_pipeline_id;
         let PipelineId(ref mut i) = self.next_pipeline_id;
@@ -328,6 +342,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                 script_chan: script_channel,
                 load_data: load_data,
                 device_pixel_ratio: self.window_size.device_pixel_ratio,
+                scripting_enabled: scripting_enabled,
             });
 
         // TODO(pcwalton): In multiprocess mode, send that `PipelineContent` instance over to
@@ -345,11 +360,13 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
 
     // Push a new (loading) pipeline to the list of pending frame changes
     fn push_pending_frame(&mut self, new_pipeline_id: PipelineId,
-                          old_pipeline_id: Option<PipelineId>) {
+                          old_pipeline_id: Option<PipelineId>,
+                          replace: bool) {
         self.pending_frames.push(FrameChange {
             old_pipeline_id: old_pipeline_id,
             new_pipeline_id: new_pipeline_id,
             painter_ready: false,
+            replace: replace,
         });
     }
 
@@ -410,7 +427,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                                                       source_pipeline_id,
                                                       new_subpage_id,
                                                       old_subpage_id,
-                                                      sandbox) => {
+                                                      sandbox,
+                                                      replace) => {
                 debug!("constellation got iframe URL load message {:?} {:?} {:?}",
                        source_pipeline_id,
                        old_subpage_id,
@@ -419,7 +437,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                                                             source_pipeline_id,
                                                             new_subpage_id,
                                                             old_subpage_id,
-                                                            sandbox);
+                                                            sandbox,
+                                                            replace);
             }
             ConstellationMsg::SetCursor(cursor) => {
                 self.handle_set_cursor_msg(cursor)
@@ -433,9 +452,9 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             // Load a new page, usually -- but not always -- from a mouse click or typed url
             // If there is already a pending page (self.pending_frames), it will not be overridden;
             // However, if the id is not encompassed by another change, it will be.
-            ConstellationMsg::LoadUrl(source_id, load_data) => {
+            ConstellationMsg::LoadUrl(source_id, load_data, replace) => {
                 debug!("constellation got URL load message");
-                self.handle_load_url_msg(source_id, load_data);
+                self.handle_load_url_msg(source_id, load_data, replace);
             }
             // A page loaded through one of several methods above has completed all parsing,
             // script, and reflow messages have been sent.
@@ -453,6 +472,18 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                 debug!("constellation got navigation message");
                 self.handle_navigate_msg(pipeline_info, direction);
             }
+            ConstellationMsg::TraverseHistory(pipeline_info, direction, steps) => {
+                debug!("constellation got traverse history message");
+                self.handle_traverse_history_msg(pipeline_info, direction, steps);
+            }
+            ConstellationMsg::SessionHistoryChanged(pipeline_id, index, length) => {
+                debug!("constellation got session history changed message");
+                self.handle_session_history_changed_msg(pipeline_id, index, length);
+            }
+            ConstellationMsg::GetSessionHistoryLength(pipeline_id, resp_chan) => {
+                debug!("constellation got get session history length message");
+                self.handle_get_session_history_length(pipeline_id, resp_chan);
+            }
             // Notification that painting has finished and is requesting permission to paint.
             ConstellationMsg::PainterReady(pipeline_id) => {
                 debug!("constellation got painter ready message");
@@ -549,6 +580,10 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                         subpage_id,
                         self.subpage_map.get(&(pipeline_id, subpage_id)).map(|x| *x)))
             }
+            ConstellationMsg::SetOnlineStatus(online) => {
+                debug!("constellation got online status message");
+                self.handle_set_online_status_msg(online);
+            }
         }
         true
     }
@@ -602,17 +637,18 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             self.new_pipeline(parent_info,
                               window_size,
                               None,
-                              LoadData::new(Url::parse("about:failure").unwrap()));
+                              LoadData::new(Url::parse("about:failure").unwrap()),
+                              true);
 
-        self.push_pending_frame(new_pipeline_id, Some(pipeline_id));
+        self.push_pending_frame(new_pipeline_id, Some(pipeline_id), false);
     }
 
     fn handle_init_load(&mut self, url: Url) {
         let window_size = self.window_size.visible_viewport;
         let root_pipeline_id =
-            self.new_pipeline(None, Some(window_size), None, LoadData::new(url.clone()));
+            self.new_pipeline(None, Some(window_size), None, LoadData::new(url.clone()), true);
         self.handle_load_start_msg(&root_pipeline_id);
-        self.push_pending_frame(root_pipeline_id, None);
+        self.push_pending_frame(root_pipeline_id, None, false);
         self.compositor_proxy.send(CompositorMsg::ChangePageUrl(root_pipeline_id, url));
     }
 
@@ -652,7 +688,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                                               containing_pipeline_id: PipelineId,
                                               new_subpage_id: SubpageId,
                                               old_subpage_id: Option<SubpageId>,
-                                              sandbox: IFrameSandboxState) {
+                                              sandbox: IFrameSandboxState,
+                                              replace: bool) {
         // Compare the pipeline's url to the new url. If the origin is the same,
         // then reuse the script task in creating the new pipeline
         let script_chan = {
@@ -677,6 +714,13 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             }
         };
 
+        // A new script task is always created for sandboxed iframes, so this
+        // disables scripting for the documents of that task only.
+        let scripting_enabled = match sandbox {
+            IFrameSandboxState::IFrameSandboxed { allow_scripts } => allow_scripts,
+            IFrameSandboxState::IFrameUnsandboxed => true,
+        };
+
         // Create the new pipeline, attached to the parent and push to pending frames
         let old_pipeline_id = old_subpage_id.map(|old_subpage_id| {
             self.find_subpage(containing_pipeline_id, old_subpage_id).id
@@ -687,7 +731,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
         let new_pipeline_id = self.new_pipeline(Some((containing_pipeline_id, new_subpage_id)),
                                                 window_size,
                                                 script_chan,
-                                                LoadData::new(url));
+                                                LoadData::new(url),
+                                                scripting_enabled);
 
         self.subpage_map.insert((containing_pipeline_id, new_subpage_id), new_pipeline_id);
 
@@ -699,7 +744,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             }
         }
 
-        self.push_pending_frame(new_pipeline_id, old_pipeline_id);
+        self.push_pending_frame(new_pipeline_id, old_pipeline_id, replace);
     }
 
     fn handle_set_cursor_msg(&mut self, cursor: Cursor) {
@@ -721,11 +766,11 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             .unwrap();
     }
 
-    fn handle_load_url_msg(&mut self, source_id: PipelineId, load_data: LoadData) {
-        self.load_url(source_id, load_data);
+    fn handle_load_url_msg(&mut self, source_id: PipelineId, load_data: LoadData, replace: bool) {
+        self.load_url(source_id, load_data, replace);
     }
 
-    fn load_url(&mut self, source_id: PipelineId, load_data: LoadData) -> Option<PipelineId> {
+    fn load_url(&mut self, source_id: PipelineId, load_data: LoadData, replace: bool) -> Option<PipelineId> {
         // If this load targets an iframe, its framing element may exist
         // in a separate script task than the framed document that initiated
         // the new load. The framing element must be notified about the
@@ -739,7 +784,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                 let script_channel = &parent_pipeline.script_chan;
                 script_channel.send(ConstellationControlMsg::Navigate(parent_pipeline_id,
                                                                       subpage_id,
-                                                                      load_data)).unwrap();
+                                                                      load_data,
+                                                                      replace)).unwrap();
                 Some(source_id)
             }
             None => {
@@ -757,8 +803,8 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
 
                 // Create the new pipeline
                 let window_size = self.pipeline(source_id).size;
-                let new_pipeline_id = self.new_pipeline(None, window_size, None, load_data);
-                self.push_pending_frame(new_pipeline_id, Some(source_id));
+                let new_pipeline_id = self.new_pipeline(None, window_size, None, load_data, true);
+                self.push_pending_frame(new_pipeline_id, Some(source_id), replace);
 
                 // Send message to ScriptTask that will suspend all timers
                 let old_pipeline = self.pipelines.get(&source_id).unwrap();
@@ -805,9 +851,11 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
         }
     }
 
+    /// Returns the pipeline that became current, if the frame had a document to navigate to.
     fn handle_navigate_msg(&mut self,
                            pipeline_info: Option<(PipelineId, SubpageId)>,
-                           direction: constellation_msg::NavigationDirection) {
+                           direction: constellation_msg::NavigationDirection)
+                           -> Option<PipelineId> {
         debug!("received message to navigate {:?}", direction);
 
         // Get the frame id associated with the pipeline that sent
@@ -831,7 +879,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                     match frame.next.pop() {
                         None => {
                             debug!("no next page to navigate to");
-                            return;
+                            return None;
                         },
                         Some(next) => {
                             frame.prev.push(frame.current);
@@ -843,7 +891,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                     match frame.prev.pop() {
                         None => {
                             debug!("no previous page to navigate to");
-                            return;
+                            return None;
                         },
                         Some(prev) => {
                             frame.next.push(frame.current);
@@ -885,6 +933,59 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
             // This is the result of a back/forward navigation.
             self.trigger_mozbrowserlocationchange(next_pipeline_id);
         }
+
+        Some(next_pipeline_id)
+    }
+
+    fn handle_traverse_history_msg(&mut self,
+                                   pipeline_info: Option<(PipelineId, SubpageId)>,
+                                   direction: NavigationDirection,
+                                   steps: u32) {
+        let pipeline_id = match self.handle_navigate_msg(pipeline_info, direction) {
+            Some(pipeline_id) => pipeline_id,
+            None => return,
+        };
+        if steps == 0 {
+            return;
+        }
+
+        // The remaining steps are taken through the entries of the document
+        // that was navigated to, which may in turn ask to navigate further.
+        let delta = match direction {
+            NavigationDirection::Back => -(steps as i32),
+            NavigationDirection::Forward => steps as i32,
+        };
+        let script_chan = &self.pipeline(pipeline_id).script_chan;
+        script_chan.send(ConstellationControlMsg::TraverseHistory(pipeline_id, delta)).unwrap();
+    }
+
+    fn handle_session_history_changed_msg(&mut self,
+                                          pipeline_id: PipelineId,
+                                          index: usize,
+                                          length: usize) {
+        if let Some(pipeline) = self.pipelines.get_mut(&pipeline_id) {
+            pipeline.history_index = index;
+            pipeline.history_length = length;
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#joint-session-history
+    fn handle_get_session_history_length(&self,
+                                         pipeline_id: PipelineId,
+                                         resp_chan: IpcSender<usize>) {
+        let length = match self.pipeline_to_frame_map.get(&pipeline_id) {
+            Some(frame_id) => {
+                let frame = self.frame(*frame_id);
+                // The entries following the current entry of a previous document
+                // were pruned when the next document was loaded.
+                let prev = frame.prev.iter().map(|id| self.pipeline(*id).history_index + 1);
+                let rest = Some(frame.current).into_iter().chain(frame.next.iter().cloned())
+                                              .map(|id| self.pipeline(id).history_length);
+                prev.chain(rest).fold(0, |length, entries| length + entries)
+            }
+            None => self.pipeline(pipeline_id).history_length,
+        };
+        resp_chan.send(length).unwrap();
     }
 
     fn handle_key_msg(&self, key: Key, state: KeyState, mods: KeyModifiers) {
@@ -1054,7 +1155,7 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
                               pipeline_id: PipelineId,
                               load_data: LoadData,
                               reply: IpcSender<webdriver_msg::LoadStatus>) {
-        let new_pipeline_id = self.load_url(pipeline_id, load_data);
+        let new_pipeline_id = self.load_url(pipeline_id, load_data, false);
         if let Some(id) = new_pipeline_id {
             self.webdriver.load_channel = Some((id, reply));
         }
@@ -1080,7 +1181,11 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
 
                 // Add new pipeline to navigation frame, and return frames evicted from history.
                 let frame_id = *self.pipeline_to_frame_map.get(&old_pipeline_id).unwrap();
-                let evicted_frames = self.mut_frame(frame_id).load(frame_change.new_pipeline_id);
+                let evicted_frames = if frame_change.replace {
+                    self.mut_frame(frame_id).replace_current(frame_change.new_pipeline_id)
+                } else {
+                    self.mut_frame(frame_id).load(frame_change.new_pipeline_id)
+                };
                 self.pipeline_to_frame_map.insert(frame_change.new_pipeline_id, frame_id);
 
                 Some(evicted_frames)
@@ -1168,6 +1273,28 @@ impl<LTF: LayoutTaskFactory, STF: ScriptTaskFactory> Constellation<LTF, STF> {
         }
     }
 
+    /// Ask the resource task to report changes in network reachability, which
+    /// are then forwarded to every pipeline.
+    fn register_online_status_listener(&self) {
+        let (online_status_chan, online_status_port) = ipc::channel().unwrap();
+        let ConstellationChan(ref chan) = self.chan;
+        let chan = chan.clone();
+        ROUTER.add_route(online_status_port.to_opaque(), box move |message| {
+            let online: bool = message.to().unwrap();
+            let _ = chan.send(ConstellationMsg::SetOnlineStatus(online));
+        });
+        let msg = net_traits::ControlMsg::SetOnlineStatusListener(online_status_chan);
+        self.resource_task.send(msg).unwrap();
+    }
+
+    /// Called when the network connectivity of the user agent changes.
+    fn handle_set_online_status_msg(&mut self, online: bool) {
+        for (_id, ref pipeline) in &self.pipelines {
+            let msg = ConstellationControlMsg::SetOnlineStatus(pipeline.id, online);
+            let _ = pipeline.script_chan.send(msg);
+        }
+    }
+
     /// Called when the window is resized.
     fn handle_resized_window_msg(&mut self, new_size: WindowSizeData) {
         debug!("handle_resized_window_msg: {:?} {:?}", new_size.initial_viewport.to_untyped(),
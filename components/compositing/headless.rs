@@ -85,6 +85,10 @@ impl CompositorEventListener for NullCompositor {
                 send.send(rect).unwrap();
             }
 
+            Msg::GetScreenSize(send) => {
+                send.send((Size2D::zero(), Size2D::zero())).unwrap();
+            }
+
             Msg::ChangeRunningAnimationsState(pipeline_id, animation_state) => {
                 match animation_state {
                     AnimationState::AnimationsPresent |
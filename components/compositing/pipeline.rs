@@ -54,6 +54,10 @@ pub struct Pipeline {
     /// animations cause composites to be continually scheduled.
     pub running_animations: bool,
     pub children: Vec<FrameId>,
+    /// The index of the current session history entry of this pipeline's document.
+    pub history_index: usize,
+    /// The number of session history entries of this pipeline's document.
+    pub history_length: usize,
 }
 
 /// The subset of the pipeline that is needed for layer composition.
@@ -99,6 +103,8 @@ pub struct InitialPipelineState {
     pub script_chan: Option<Sender<ConstellationControlMsg>>,
     /// Information about the page to load.
     pub load_data: LoadData,
+    /// Whether scripts may run in the script task, if a new one is spawned.
+    pub scripting_enabled: bool,
 }
 
 impl Pipeline {
@@ -189,6 +195,7 @@ impl Pipeline {
             window_size: window_size,
             script_chan: script_chan,
             load_data: state.load_data,
+            scripting_enabled: state.scripting_enabled,
             failure: failure,
             script_port: script_port,
             layout_to_paint_chan: layout_to_paint_chan,
@@ -228,6 +235,8 @@ impl Pipeline {
             children: vec!(),
             size: size,
             running_animations: false,
+            history_index: 0,
+            history_length: 1,
         }
     }
 
@@ -323,6 +332,7 @@ pub struct PipelineContent {
     window_size: Option<WindowSizeData>,
     script_chan: Sender<ConstellationControlMsg>,
     load_data: LoadData,
+    scripting_enabled: bool,
     failure: Failure,
     script_port: Option<Receiver<ConstellationControlMsg>>,
     layout_to_paint_chan: OptionalIpcSender<LayoutToPaintMsg>,
@@ -364,6 +374,7 @@ impl PipelineContent {
             mem_profiler_chan: self.mem_profiler_chan.clone(),
             devtools_chan: self.devtools_chan,
             window_size: self.window_size,
+            scripting_enabled: self.scripting_enabled,
         }, &layout_pair, self.load_data.clone());
 
         LayoutTaskFactory::create(None::<&mut LTF>,
@@ -72,6 +72,8 @@ pub enum WindowEvent {
     Quit,
     /// Sent when a key input state changes
     KeyEvent(Key, KeyState, KeyModifiers),
+    /// Sent when the network connectivity of the user agent changes.
+    SetOnlineStatus(bool),
 }
 
 impl Debug for WindowEvent {
@@ -91,6 +93,7 @@ impl Debug for WindowEvent {
             WindowEvent::ResetZoom => write!(f, "ResetZoom"),
             WindowEvent::Navigation(..) => write!(f, "Navigation"),
             WindowEvent::Quit => write!(f, "Quit"),
+            WindowEvent::SetOnlineStatus(..) => write!(f, "SetOnlineStatus"),
         }
     }
 }
@@ -105,6 +108,9 @@ pub trait WindowMethods {
 
     /// Return the size of the window with head and borders and position of the window values
     fn client_window(&self) -> (Size2D<u32>, Point2D<i32>);
+    /// Returns the size of the screen and of the area of it available to windows,
+    /// in hardware pixels.
+    fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>);
     /// Set the size inside of borders and head
     fn set_inner_size(&self, size: Size2D<u32>);
     /// Set the window position
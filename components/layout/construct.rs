@@ -1439,7 +1439,7 @@ impl<'a> PostorderNodeMutTraversal for FlowConstructor<'a> {
             Some(NodeTypeId::CharacterData(CharacterDataTypeId::Comment)) |
             Some(NodeTypeId::CharacterData(CharacterDataTypeId::ProcessingInstruction)) |
             Some(NodeTypeId::DocumentType) |
-            Some(NodeTypeId::DocumentFragment) |
+            Some(NodeTypeId::DocumentFragment(_)) |
             Some(NodeTypeId::Document) => {
                 (display::T::none, float::T::none, position::T::static_)
             }
@@ -1587,7 +1587,7 @@ impl<'ln> NodeUtils for ThreadSafeLayoutNode<'ln> {
             None |
             Some(NodeTypeId::CharacterData(_)) |
             Some(NodeTypeId::DocumentType) |
-            Some(NodeTypeId::DocumentFragment) |
+            Some(NodeTypeId::DocumentFragment(_)) |
             Some(NodeTypeId::Document) |
             Some(NodeTypeId::Element(ElementTypeId::HTMLElement(
                         HTMLElementTypeId::HTMLImageElement))) |
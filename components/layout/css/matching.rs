@@ -248,7 +248,7 @@ impl StyleSharingCandidate {
             }
         };
 
-        if element.style_attribute().is_some() {
+        if element.style_attribute().is_some() || element.animation_declarations().is_some() {
             return None
         }
 
@@ -550,21 +550,25 @@ impl<'ln> MatchMethods for LayoutNode<'ln> {
                   shareable: &mut bool) {
         let element = self.as_element().unwrap();
         let style_attribute = element.style_attribute().as_ref();
+        let animation_declarations = element.animation_declarations().as_ref();
 
         applicable_declarations.normal_shareable =
             stylist.push_applicable_declarations(&element,
                                                  parent_bf,
                                                  style_attribute,
+                                                 animation_declarations,
                                                  None,
                                                  &mut applicable_declarations.normal);
         stylist.push_applicable_declarations(&element,
                                              parent_bf,
                                              None,
+                                             None,
                                              Some(PseudoElement::Before),
                                              &mut applicable_declarations.before);
         stylist.push_applicable_declarations(&element,
                                              parent_bf,
                                              None,
+                                             None,
                                              Some(PseudoElement::After),
                                              &mut applicable_declarations.after);
 
@@ -584,6 +588,7 @@ impl<'ln> MatchMethods for LayoutNode<'ln> {
         let ok = {
             if let Some(element) = self.as_element() {
                 element.style_attribute().is_none() &&
+                    element.animation_declarations().is_none() &&
                     element.get_attr(&ns!(""), &atom!("id")).is_none()
             } else {
                 false
@@ -69,7 +69,7 @@ use std::collections::HashMap;
 use std::collections::hash_state::DefaultState;
 use std::mem::transmute;
 use std::ops::{Deref, DerefMut};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Sender, Receiver, Select};
 use std::sync::{Arc, Mutex, MutexGuard};
 use string_cache::Atom;
@@ -611,13 +611,18 @@ impl LayoutTask {
             Msg::AddStylesheet(sheet, mq) => {
                 self.handle_add_stylesheet(sheet, mq, possibly_locked_rw_data)
             }
-            Msg::LoadStylesheet(url, mq, pending, link_element) => {
+            Msg::LoadStylesheet(url, mq, pending, link_element, disabled) => {
                 self.handle_load_stylesheet(url,
                                             mq,
                                             pending,
                                             link_element,
+                                            disabled,
                                             possibly_locked_rw_data)
             }
+            Msg::RemoveStylesheet(disabled) => {
+                self.handle_remove_stylesheet(disabled, possibly_locked_rw_data)
+            }
+            Msg::StylesheetsChanged => self.handle_stylesheets_changed(possibly_locked_rw_data),
             Msg::SetQuirksMode => self.handle_set_quirks_mode(possibly_locked_rw_data),
             Msg::AddMetaViewport(translated_rule) => {
                 self.handle_add_meta_viewport(translated_rule, possibly_locked_rw_data)
@@ -782,6 +787,7 @@ impl LayoutTask {
                                   mq: MediaQueryList,
                                   pending: PendingAsyncLoad,
                                   responder: Box<StylesheetLoadResponder + Send>,
+                                  disabled: Arc<AtomicBool>,
                                   possibly_locked_rw_data:
                                     &mut Option<MutexGuard<'a, LayoutTaskData>>) {
         // TODO: Get the actual value. http://dev.w3.org/csswg/css-syntax/#environment-encoding
@@ -792,22 +798,25 @@ impl LayoutTask {
         let protocol_encoding_label = metadata.charset.as_ref().map(|s| &**s);
         let final_url = metadata.final_url;
 
-        let sheet = Stylesheet::from_bytes_iter(iter,
-                                                final_url,
-                                                protocol_encoding_label,
-                                                Some(environment_encoding),
-                                                Origin::Author);
+        let mut sheet = Stylesheet::from_bytes_iter(iter,
+                                                    final_url,
+                                                    protocol_encoding_label,
+                                                    Some(environment_encoding),
+                                                    Origin::Author);
+        // Share the disabled flag with the owner node, so that the sheet can be
+        // detached again if the node stops referring to it.
+        sheet.disabled = disabled;
 
         //TODO: mark critical subresources as blocking load as well (#5974)
         self.script_chan.send(ConstellationControlMsg::StylesheetLoadComplete(self.id,
                                                                               url,
                                                                               responder)).unwrap();
 
-        self.handle_add_stylesheet(sheet, mq, possibly_locked_rw_data);
+        self.handle_add_stylesheet(Arc::new(sheet), mq, possibly_locked_rw_data);
     }
 
     fn handle_add_stylesheet<'a>(&'a self,
-                                 sheet: Stylesheet,
+                                 sheet: Arc<Stylesheet>,
                                  mq: MediaQueryList,
                                  possibly_locked_rw_data:
                                     &mut Option<MutexGuard<'a, LayoutTaskData>>) {
@@ -821,12 +830,31 @@ impl LayoutTask {
                                 &self.font_cache_task,
                                 &self.font_cache_sender,
                                 &rw_data.outstanding_web_fonts);
-            rw_data.stylist.add_stylesheet(sheet);
+            rw_data.stylist.add_shared_stylesheet(sheet);
         }
 
         LayoutTask::return_rw_data(possibly_locked_rw_data, rw_data);
     }
 
+    /// Removes the stylesheet whose owner node left the document.
+    fn handle_remove_stylesheet<'a>(&'a self,
+                                    disabled: Arc<AtomicBool>,
+                                    possibly_locked_rw_data:
+                                      &mut Option<MutexGuard<'a, LayoutTaskData>>) {
+        let mut rw_data = self.lock_rw_data(possibly_locked_rw_data);
+        rw_data.stylist.remove_stylesheet(&disabled);
+        LayoutTask::return_rw_data(possibly_locked_rw_data, rw_data);
+    }
+
+    /// Forces the stylist to rebuild its selector maps, e.g. because a stylesheet was disabled.
+    fn handle_stylesheets_changed<'a>(&'a self,
+                                      possibly_locked_rw_data:
+                                        &mut Option<MutexGuard<'a, LayoutTaskData>>) {
+        let mut rw_data = self.lock_rw_data(possibly_locked_rw_data);
+        rw_data.stylist.mark_dirty();
+        LayoutTask::return_rw_data(possibly_locked_rw_data, rw_data);
+    }
+
     fn handle_add_meta_viewport<'a>(&'a self,
                                     translated_rule: ViewportRule,
                                     possibly_locked_rw_data:
@@ -835,7 +863,8 @@ impl LayoutTask {
         let mut rw_data = self.lock_rw_data(possibly_locked_rw_data);
         rw_data.stylist.add_stylesheet(Stylesheet {
             rules: vec![CSSRule::Viewport(translated_rule)],
-            origin: Origin::Author
+            origin: Origin::Author,
+            disabled: Arc::new(AtomicBool::new(false)),
         });
         LayoutTask::return_rw_data(possibly_locked_rw_data, rw_data);
     }
@@ -1052,7 +1081,13 @@ impl LayoutTask {
         let requested_node: OpaqueNode = OpaqueNodeMethods::from_script_node(requested_node);
         let mut iterator = ParentOffsetBorderBoxIterator::new(requested_node);
         sequential::iterate_through_flow_tree_fragment_border_boxes(layout_root, &mut iterator);
-        let parent_info_index = iterator.parent_nodes.iter().rposition(|info| info.is_some());
+        // An element that generated no fragment (e.g. `display: none`) has no offset parent and
+        // an empty rect.
+        let parent_info_index = if iterator.has_found_node {
+            iterator.parent_nodes.iter().rposition(|info| info.is_some())
+        } else {
+            None
+        };
         match parent_info_index {
             Some(parent_info_index) => {
                 let parent = iterator.parent_nodes[parent_info_index].as_ref().unwrap();
@@ -1550,7 +1585,6 @@ struct ParentBorderBoxInfo {
 
 struct ParentOffsetBorderBoxIterator {
     node_address: OpaqueNode,
-    last_level: i32,
     has_found_node: bool,
     node_border_box: Rect<Au>,
     parent_nodes: Vec<Option<ParentBorderBoxInfo>>,
@@ -1560,7 +1594,6 @@ impl ParentOffsetBorderBoxIterator {
     fn new(node_address: OpaqueNode) -> ParentOffsetBorderBoxIterator {
         ParentOffsetBorderBoxIterator {
             node_address: node_address,
-            last_level: -1,
             has_found_node: false,
             node_border_box: Rect::zero(),
             parent_nodes: Vec::new(),
@@ -1591,6 +1624,10 @@ impl FragmentBorderBoxIterator for FragmentLocatingFragmentIterator {
 // https://drafts.csswg.org/cssom-view/#extensions-to-the-htmlelement-interface
 impl FragmentBorderBoxIterator for ParentOffsetBorderBoxIterator {
     fn process(&mut self, fragment: &Fragment, level: i32, border_box: &Rect<Au>) {
+        // The stack holds one entry per flow tree level, so anything at or below the level of
+        // this fragment belongs to a previous sibling subtree rather than to an ancestor.
+        self.parent_nodes.truncate(level as usize);
+
         if fragment.node == self.node_address {
             // Found the fragment in the flow tree that matches the
             // DOM node being looked for.
@@ -1601,7 +1638,7 @@ impl FragmentBorderBoxIterator for ParentOffsetBorderBoxIterator {
             if fragment.style.get_box().position == computed_values::position::T::fixed {
                 self.parent_nodes.clear();
             }
-        } else if level > self.last_level {
+        } else {
             // TODO(gw): Is there a less fragile way of checking whether this
             // fragment is the body element, rather than just checking that
             // the parent nodes stack contains the root node only?
@@ -1635,8 +1672,6 @@ impl FragmentBorderBoxIterator for ParentOffsetBorderBoxIterator {
             };
 
             self.parent_nodes.push(parent_info);
-        } else if level < self.last_level {
-            self.parent_nodes.pop();
         }
     }
 
@@ -17,6 +17,7 @@ use opaque_node::OpaqueNodeMethods;
 use script::layout_interface::{ContentBoxResponse, ContentBoxesResponse, NodeGeometryResponse};
 use script::layout_interface::{HitTestResponse, LayoutRPC, MouseOverResponse, OffsetParentResponse};
 use script::layout_interface::{ResolvedStyleResponse, ScriptLayoutChan, TrustedNodeAddress};
+use script_traits::UntrustedNodeAddress;
 use sequential;
 use std::sync::{Arc, Mutex};
 use util::cursor::Cursor;
@@ -117,6 +118,19 @@ impl LayoutRPC for LayoutRPCImpl {
         }
     }
 
+    fn nodes_from_point(&self, point: Point2D<f32>) -> Vec<UntrustedNodeAddress> {
+        let point = Point2D::new(Au::from_f32_px(point.x), Au::from_f32_px(point.y));
+        let mut result = Vec::new();
+        {
+            let &LayoutRPCImpl(ref rw_data) = self;
+            let rw_data = rw_data.lock().unwrap();
+            if let Some(ref stacking_context) = rw_data.stacking_context {
+                stacking_context.hit_test(point, &mut result, false);
+            }
+        }
+        result.iter().map(|metadata| metadata.node.to_untrusted_node_address()).collect()
+    }
+
     fn offset_parent(&self) -> OffsetParentResponse {
         let &LayoutRPCImpl(ref rw_data) = self;
         let rw_data = rw_data.lock().unwrap();
@@ -358,6 +358,12 @@ impl<'le> LayoutElement<'le> {
         }
     }
 
+    pub fn animation_declarations(&self) -> &'le Option<Arc<Vec<PropertyDeclaration>>> {
+        unsafe {
+            &*self.element.animation_declarations()
+        }
+    }
+
     pub fn as_node(&self) -> LayoutNode<'le> {
         LayoutNode {
             node: NodeCast::from_layout_js(&self.element),
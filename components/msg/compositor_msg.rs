@@ -166,6 +166,7 @@ pub enum ScriptToCompositorMsg {
     SetTitle(PipelineId, Option<String>),
     SendKeyEvent(Key, KeyState, KeyModifiers),
     GetClientWindow(IpcSender<(Size2D<u32>, Point2D<i32>)>),
+    GetScreenSize(IpcSender<(Size2D<u32>, Size2D<u32>)>),
     MoveTo(Point2D<i32>),
     ResizeTo(Size2D<u32>),
     Exit,
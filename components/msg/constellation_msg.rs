@@ -36,7 +36,9 @@ impl ConstellationChan {
 
 #[derive(PartialEq, Eq, Copy, Clone, Debug, Deserialize, Serialize)]
 pub enum IFrameSandboxState {
-    IFrameSandboxed,
+    /// The iframe is sandboxed. Scripts may only run in it if its `sandbox`
+    /// attribute has the `allow-scripts` keyword.
+    IFrameSandboxed { allow_scripts: bool },
     IFrameUnsandboxed
 }
 
@@ -221,9 +223,22 @@ pub enum Msg {
     /// Dispatched after the DOM load event has fired on a document
     DOMLoad(PipelineId),
     FrameSize(PipelineId, SubpageId, Size2D<f32>),
-    LoadUrl(PipelineId, LoadData),
-    ScriptLoadedURLInIFrame(Url, PipelineId, SubpageId, Option<SubpageId>, IFrameSandboxState),
+    /// Requests a navigation of the given pipeline. The flag is true when the new
+    /// document replaces the current session history entry instead of adding one.
+    LoadUrl(PipelineId, LoadData, bool),
+    /// Requests a navigation of an iframe's browsing context. The flag is true when the
+    /// new document replaces the current session history entry instead of adding one.
+    ScriptLoadedURLInIFrame(Url, PipelineId, SubpageId, Option<SubpageId>, IFrameSandboxState, bool),
     Navigate(Option<(PipelineId, SubpageId)>, NavigationDirection),
+    /// Requests a navigation like `Navigate`, followed by a traversal of the given number of
+    /// session history entries in the same direction within the document navigated to.
+    TraverseHistory(Option<(PipelineId, SubpageId)>, NavigationDirection, u32),
+    /// Notifies the constellation of the index of the current session history entry of a
+    /// pipeline's document, and of the number of entries that document has.
+    SessionHistoryChanged(PipelineId, usize, usize),
+    /// Requests the length of the joint session history of the frame containing the
+    /// given pipeline.
+    GetSessionHistoryLength(PipelineId, IpcSender<usize>),
     PainterReady(PipelineId),
     ResizedWindow(WindowSizeData),
     KeyEvent(Key, KeyState, KeyModifiers),
@@ -275,6 +290,8 @@ pub enum Msg {
     /// Requests that the pipeline ID of the subpage identified by a (pipeline ID, subpage ID)
     /// pair be sent to the compositor via a `CreateLayerForSubpage` message.
     PrepareForSubpageLayerCreation(PipelineId, SubpageId),
+    /// Notifies the constellation that the network connectivity of the user agent has changed.
+    SetOnlineStatus(bool),
 }
 
 #[derive(Clone, Eq, PartialEq, Deserialize, Serialize, Debug)]
@@ -368,6 +385,8 @@ pub struct LoadData {
     pub method: Method,
     pub headers: Headers,
     pub data: Option<Vec<u8>>,
+    /// The URL of the document that initiated this load, if it should be exposed.
+    pub referrer: Option<Url>,
 }
 
 impl LoadData {
@@ -377,6 +396,7 @@ impl LoadData {
             method: Method::Get,
             headers: Headers::new(),
             data: None,
+            referrer: None,
         }
     }
 }
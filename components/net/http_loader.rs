@@ -26,14 +26,14 @@ use net_traits::ProgressMsg::{Done, Payload};
 use net_traits::hosts::replace_hosts;
 use net_traits::{CookieSource, IncludeSubdomains, LoadConsumer, LoadData, Metadata};
 use openssl::ssl::{SSL_VERIFY_PEER, SslContext, SslMethod};
-use resource_task::{start_sending_opt, start_sending_sniffed_opt};
+use resource_task::{OnlineStatus, start_sending_opt, start_sending_sniffed_opt};
 use std::borrow::ToOwned;
 use std::boxed::FnBox;
 use std::collections::HashSet;
 use std::error::Error;
 use std::io::{self, Read, Write};
 use std::sync::mpsc::Sender;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use url::{Url, UrlParser};
 use util::resource_files::resources_dir_path;
 use util::task::spawn_named;
@@ -55,7 +55,8 @@ pub fn create_http_connector() -> Arc<Pool<Connector>> {
 pub fn factory(hsts_list: Arc<RwLock<HSTSList>>,
                cookie_jar: Arc<RwLock<CookieStorage>>,
                devtools_chan: Option<Sender<DevtoolsControlMsg>>,
-               connector: Arc<Pool<Connector>>)
+               connector: Arc<Pool<Connector>>,
+               online_status: Arc<Mutex<OnlineStatus>>)
                -> Box<FnBox(LoadData, LoadConsumer, Arc<MIMEClassifier>, String) + Send> {
     box move |load_data: LoadData, senders, classifier, user_agent| {
         spawn_named(format!("http_loader for {}", load_data.url.serialize()), move || {
@@ -66,6 +67,7 @@ pub fn factory(hsts_list: Arc<RwLock<HSTSList>>,
                               hsts_list,
                               cookie_jar,
                               devtools_chan,
+                              online_status,
                               user_agent)
         })
     }
@@ -111,6 +113,7 @@ fn load_for_consumer(load_data: LoadData,
                      hsts_list: Arc<RwLock<HSTSList>>,
                      cookie_jar: Arc<RwLock<CookieStorage>>,
                      devtools_chan: Option<Sender<DevtoolsControlMsg>>,
+                     online_status: Arc<Mutex<OnlineStatus>>,
                      user_agent: String) {
 
     let factory = NetworkHttpRequestFactory {
@@ -122,6 +125,7 @@ fn load_for_consumer(load_data: LoadData,
             send_error(url, s, start_chan)
         }
         Err(LoadError::Connection(url, e)) => {
+            online_status.lock().unwrap().set_online(false);
             send_error(url, e, start_chan)
         }
         Err(LoadError::MaxRedirects(url)) => {
@@ -144,6 +148,7 @@ fn load_for_consumer(load_data: LoadData,
         }
         Err(LoadError::ConnectionAborted(_)) => unreachable!(),
         Ok(mut load_response) => {
+            online_status.lock().unwrap().set_online(true);
             let metadata = load_response.metadata.clone();
             send_data(&mut load_response, start_chan, metadata, classifier)
         }
@@ -37,7 +37,6 @@ pub mod hsts;
 pub mod http_loader;
 pub mod image_cache_task;
 pub mod mime_classifier;
-pub mod pub_domains;
 pub mod resource_task;
 pub mod storage_task;
 
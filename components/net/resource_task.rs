@@ -23,7 +23,7 @@ use net_traits::{ControlMsg, CookieSource, LoadConsumer, LoadData, LoadResponse}
 use std::borrow::ToOwned;
 use std::boxed::FnBox;
 use std::sync::mpsc::{Sender, channel};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use url::Url;
 use util::opts;
 use util::task::spawn_named;
@@ -177,6 +177,9 @@ impl ResourceChannelManager {
                     let mut cookie_jar = cookie_jar.write().unwrap();
                     consumer.send(cookie_jar.cookies_for_url(&url, source)).unwrap();
                 }
+                ControlMsg::SetOnlineStatusListener(listener) => {
+                    self.resource_manager.online_status.lock().unwrap().listener = Some(listener)
+                }
                 ControlMsg::Exit => {
                     break
                 }
@@ -185,6 +188,33 @@ impl ResourceChannelManager {
     }
 }
 
+/// Whether the network was reachable the last time a load tried to use it.
+pub struct OnlineStatus {
+    online: bool,
+    listener: Option<IpcSender<bool>>,
+}
+
+impl OnlineStatus {
+    fn new() -> OnlineStatus {
+        OnlineStatus {
+            online: true,
+            listener: None,
+        }
+    }
+
+    /// Record the outcome of a network load, notifying the listener if the
+    /// reachability of the network changed.
+    pub fn set_online(&mut self, online: bool) {
+        if self.online == online {
+            return
+        }
+        self.online = online;
+        if let Some(ref listener) = self.listener {
+            let _ = listener.send(online);
+        }
+    }
+}
+
 pub struct ResourceManager {
     user_agent: String,
     cookie_storage: Arc<RwLock<CookieStorage>>,
@@ -192,6 +222,7 @@ pub struct ResourceManager {
     devtools_chan: Option<Sender<DevtoolsControlMsg>>,
     hsts_list: Arc<RwLock<HSTSList>>,
     connector: Arc<Pool<Connector>>,
+    online_status: Arc<Mutex<OnlineStatus>>,
 }
 
 impl ResourceManager {
@@ -205,6 +236,7 @@ impl ResourceManager {
             devtools_chan: devtools_channel,
             hsts_list: Arc::new(RwLock::new(hsts_list)),
             connector: create_http_connector(),
+            online_status: Arc::new(Mutex::new(OnlineStatus::new())),
         }
     }
 }
@@ -238,7 +270,8 @@ impl ResourceManager {
                 http_loader::factory(self.hsts_list.clone(),
                                      self.cookie_storage.clone(),
                                      self.devtools_chan.clone(),
-                                     self.connector.clone()),
+                                     self.connector.clone(),
+                                     self.online_status.clone()),
             "data" => from_factory(data_loader::factory),
             "about" => from_factory(about_loader::factory),
             _ => {
@@ -12,6 +12,14 @@ use url::Url;
 use util::str::DOMString;
 use util::task::spawn_named;
 
+/// The maximum number of bytes of keys and values that each origin may store
+/// in each storage area.
+const QUOTA_SIZE_LIMIT: usize = 5 * 1024 * 1024;
+
+/// The contents of a storage area for one origin, along with the total size in bytes
+/// of its keys and values.
+type StorageData = HashMap<String, (usize, BTreeMap<DOMString, DOMString>)>;
+
 pub trait StorageTaskFactory {
     fn new() -> Self;
 }
@@ -29,8 +37,12 @@ impl StorageTaskFactory for StorageTask {
 
 struct StorageManager {
     port: IpcReceiver<StorageTaskMsg>,
-    session_data: HashMap<String, BTreeMap<DOMString, DOMString>>,
-    local_data: HashMap<String, BTreeMap<DOMString, DOMString>>,
+    /// The session storage areas, by origin. A constellation has a single top-level
+    /// browsing context, so this is the session storage of that context, and every
+    /// document it loads sees the same area for its origin.
+    session_data: StorageData,
+    /// The local storage areas, by origin.
+    local_data: StorageData,
 }
 
 impl StorageManager {
@@ -53,6 +65,9 @@ impl StorageManager {
                 StorageTaskMsg::Key(sender, url, storage_type, index) => {
                     self.key(sender, url, storage_type, index)
                 }
+                StorageTaskMsg::Keys(sender, url, storage_type) => {
+                    self.keys(sender, url, storage_type)
+                }
                 StorageTaskMsg::SetItem(sender, url, storage_type, name, value) => {
                     self.set_item(sender, url, storage_type, name, value)
                 }
@@ -72,16 +87,14 @@ impl StorageManager {
         }
     }
 
-    fn select_data(&self, storage_type: StorageType)
-                   -> &HashMap<String, BTreeMap<DOMString, DOMString>> {
+    fn select_data(&self, storage_type: StorageType) -> &StorageData {
         match storage_type {
             StorageType::Session => &self.session_data,
             StorageType::Local => &self.local_data
         }
     }
 
-    fn select_data_mut(&mut self, storage_type: StorageType)
-                       -> &mut HashMap<String, BTreeMap<DOMString, DOMString>> {
+    fn select_data_mut(&mut self, storage_type: StorageType) -> &mut StorageData {
         match storage_type {
             StorageType::Session => &mut self.session_data,
             StorageType::Local => &mut self.local_data
@@ -91,7 +104,7 @@ impl StorageManager {
     fn length(&self, sender: IpcSender<usize>, url: Url, storage_type: StorageType) {
         let origin = self.origin_as_string(url);
         let data = self.select_data(storage_type);
-        sender.send(data.get(&origin).map_or(0, |entry| entry.len())).unwrap();
+        sender.send(data.get(&origin).map_or(0, |&(_, ref entry)| entry.len())).unwrap();
     }
 
     fn key(&self,
@@ -102,34 +115,51 @@ impl StorageManager {
         let origin = self.origin_as_string(url);
         let data = self.select_data(storage_type);
         sender.send(data.get(&origin)
-                    .and_then(|entry| entry.keys().nth(index as usize))
+                    .and_then(|&(_, ref entry)| entry.keys().nth(index as usize))
                     .map(|key| key.clone())).unwrap();
     }
 
+    fn keys(&self,
+            sender: IpcSender<Vec<DOMString>>,
+            url: Url,
+            storage_type: StorageType) {
+        let origin = self.origin_as_string(url);
+        let data = self.select_data(storage_type);
+        sender.send(data.get(&origin)
+                    .map_or(vec![], |&(_, ref entry)| entry.keys().cloned().collect())).unwrap();
+    }
+
     /// Sends Some(old_value) in case there was a previous value with the same key name but with different
-    /// value name, otherwise sends None
+    /// value name, otherwise sends None. Sends Err(()) if storing the value would exceed the quota.
     fn set_item(&mut self,
-                sender: IpcSender<(bool, Option<DOMString>)>,
+                sender: IpcSender<Result<(bool, Option<DOMString>), ()>>,
                 url: Url,
                 storage_type: StorageType,
                 name: DOMString,
                 value: DOMString) {
         let origin = self.origin_as_string(url);
         let data = self.select_data_mut(storage_type);
-        if !data.contains_key(&origin) {
-            data.insert(origin.clone(), BTreeMap::new());
+        let &mut (ref mut total, ref mut entry) =
+            data.entry(origin).or_insert_with(|| (0, BTreeMap::new()));
+
+        let new_total = match entry.get(&name) {
+            Some(old) => *total - old.len() + value.len(),
+            None => *total + name.len() + value.len(),
+        };
+        if new_total > QUOTA_SIZE_LIMIT {
+            sender.send(Err(())).unwrap();
+            return;
         }
-
-        let (changed, old_value) = data.get_mut(&origin).map(|entry| {
-            entry.insert(name, value.clone()).map_or(
-                (true, None),
-                |old| if old == value {
-                    (false, None)
-                } else {
-                    (true, Some(old))
-                })
-        }).unwrap();
-        sender.send((changed, old_value)).unwrap();
+        *total = new_total;
+
+        let result = entry.insert(name, value.clone()).map_or(
+            (true, None),
+            |old| if old == value {
+                (false, None)
+            } else {
+                (true, Some(old))
+            });
+        sender.send(Ok(result)).unwrap();
     }
 
     fn request_item(&self,
@@ -140,7 +170,7 @@ impl StorageManager {
         let origin = self.origin_as_string(url);
         let data = self.select_data(storage_type);
         sender.send(data.get(&origin)
-                    .and_then(|entry| entry.get(&name))
+                    .and_then(|&(_, ref entry)| entry.get(&name))
                     .map(|value| value.to_string())).unwrap();
     }
 
@@ -152,8 +182,11 @@ impl StorageManager {
                    name: DOMString) {
         let origin = self.origin_as_string(url);
         let data = self.select_data_mut(storage_type);
-        let old_value = data.get_mut(&origin).and_then(|entry| {
-            entry.remove(&name)
+        let old_value = data.get_mut(&origin).and_then(|&mut (ref mut total, ref mut entry)| {
+            entry.remove(&name).map(|old| {
+                *total -= name.len() + old.len();
+                old
+            })
         });
         sender.send(old_value).unwrap();
     }
@@ -162,9 +195,10 @@ impl StorageManager {
         let origin = self.origin_as_string(url);
         let data = self.select_data_mut(storage_type);
         sender.send(data.get_mut(&origin)
-                    .map_or(false, |entry| {
+                    .map_or(false, |&mut (ref mut total, ref mut entry)| {
                         if !entry.is_empty() {
                             entry.clear();
+                            *total = 0;
                             true
                         } else {
                             false
@@ -42,6 +42,7 @@ use util::mem::HeapSizeOf;
 pub mod hosts;
 pub mod image_cache_task;
 pub mod net_error_list;
+pub mod pub_domains;
 pub mod storage_task;
 
 pub static IPV4_REGEX: Regex = regex!(
@@ -232,6 +233,8 @@ pub enum ControlMsg {
     SetCookiesForUrl(Url, String, CookieSource),
     /// Retrieve the stored cookies for a given URL
     GetCookiesForUrl(Url, IpcSender<Option<String>>, CookieSource),
+    /// Register a channel to be told whenever the network becomes reachable or unreachable
+    SetOnlineStatusListener(IpcSender<bool>),
     Exit
 }
 
@@ -2,6 +2,27 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+/// Returns whether `domain` is a public suffix, using the algorithm described at
+/// https://publicsuffix.org/list/. `PUB_DOMAINS` holds the rules of the list,
+/// with `*.` marking wildcard rules and `!` marking exception rules.
+pub fn is_pub_domain(domain: &str) -> bool {
+    let has_rule = |rule: &str| PUB_DOMAINS.iter().any(|pub_domain| *pub_domain == rule);
+
+    // An exception rule makes its domain registrable.
+    if has_rule(&format!("!{}", domain)) {
+        return false;
+    }
+    if has_rule(domain) {
+        return true;
+    }
+    match domain.find('.') {
+        // A wildcard rule stands for any label in place of its `*`.
+        Some(index) => has_rule(&format!("*{}", &domain[index..])),
+        // Every top-level domain is a public suffix.
+        None => true,
+    }
+}
+
 pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "com.ac",
 "edu.ac",
@@ -219,7 +240,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "net.bb",
 "org.bb",
 "store.bb",
-"bd",
+"*.bd",
 "ac.be",
 "gov.bf",
 "a.bg",
@@ -276,7 +297,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "gov.bm",
 "net.bm",
 "org.bm",
-"bn",
+"*.bn",
 "com.bo",
 "edu.bo",
 "gov.bo",
@@ -332,7 +353,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "mp.br",
 "mus.br",
 "net.br",
-"nom.br",
+"*.nom.br",
 "not.br",
 "ntr.br",
 "odo.br",
@@ -408,8 +429,8 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "presse.ci",
 "md.ci",
 "gouv.ci",
-"ck",
-"www.ck",
+"*.ck",
+"!www.ck",
 "gov.cl",
 "gob.cl",
 "co.cl",
@@ -490,7 +511,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "net.cw",
 "org.cw",
 "gov.cx",
-"cy",
+"*.cy",
 "com.dm",
 "net.dm",
 "org.dm",
@@ -545,16 +566,16 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "net.eg",
 "org.eg",
 "sci.eg",
-"er",
+"*.er",
 "com.es",
 "nom.es",
 "org.es",
 "gob.es",
 "edu.es",
-"et",
+"*.et",
 "aland.fi",
-"fj",
-"fk",
+"*.fj",
+"*.fk",
 "com.fr",
 "asso.fr",
 "nom.fr",
@@ -623,7 +644,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "mil.gt",
 "net.gt",
 "org.gt",
-"gu",
+"*.gu",
 "co.gy",
 "com.gy",
 "net.gy",
@@ -718,7 +739,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "sch.id",
 "web.id",
 "gov.ie",
-"il",
+"*.il",
 "ac.im",
 "co.im",
 "com.im",
@@ -1134,7 +1155,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "co.je",
 "net.je",
 "org.je",
-"jm",
+"*.jm",
 "com.jo",
 "org.jo",
 "net.jo",
@@ -1199,20 +1220,20 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "yamagata.jp",
 "yamaguchi.jp",
 "yamanashi.jp",
-"kawasaki.jp",
-"kitakyushu.jp",
-"kobe.jp",
-"nagoya.jp",
-"sapporo.jp",
-"sendai.jp",
-"yokohama.jp",
-"city.kawasaki.jp",
-"city.kitakyushu.jp",
-"city.kobe.jp",
-"city.nagoya.jp",
-"city.sapporo.jp",
-"city.sendai.jp",
-"city.yokohama.jp",
+"*.kawasaki.jp",
+"*.kitakyushu.jp",
+"*.kobe.jp",
+"*.nagoya.jp",
+"*.sapporo.jp",
+"*.sendai.jp",
+"*.yokohama.jp",
+"!city.kawasaki.jp",
+"!city.kitakyushu.jp",
+"!city.kobe.jp",
+"!city.nagoya.jp",
+"!city.sapporo.jp",
+"!city.sendai.jp",
+"!city.yokohama.jp",
 "aisai.aichi.jp",
 "ama.aichi.jp",
 "anjo.aichi.jp",
@@ -2890,14 +2911,14 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "uenohara.yamanashi.jp",
 "yamanakako.yamanashi.jp",
 "yamanashi.yamanashi.jp",
-"ke",
+"*.ke",
 "org.kg",
 "net.kg",
 "com.kg",
 "edu.kg",
 "gov.kg",
 "mil.kg",
-"kh",
+"*.kh",
 "edu.ki",
 "biz.ki",
 "net.ki",
@@ -2961,7 +2982,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "jeonnam.kr",
 "seoul.kr",
 "ulsan.kr",
-"kw",
+"*.kw",
 "edu.ky",
 "gov.ky",
 "com.ky",
@@ -3070,7 +3091,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "net.ml",
 "org.ml",
 "presse.ml",
-"mm",
+"*.mm",
 "gov.mn",
 "edu.mn",
 "org.mn",
@@ -3681,7 +3702,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "edu.my",
 "mil.my",
 "name.my",
-"mz",
+"*.mz",
 "teledata.mz",
 "info.na",
 "pro.na",
@@ -3720,7 +3741,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "gov.ng",
 "mil.ng",
 "mobi.ng",
-"ni",
+"*.ni",
 "bv.nl",
 "fhs.no",
 "vgs.no",
@@ -4475,7 +4496,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "xn--vler-qoa.xn--stfold-9xa.no",
 "valer.hedmark.no",
 "xn--vler-qoa.hedmark.no",
-"np",
+"*.np",
 "biz.nr",
 "info.nr",
 "gov.nr",
@@ -4483,7 +4504,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "org.nr",
 "net.nr",
 "com.nr",
-"nz",
+"*.nz",
 "co.om",
 "com.om",
 "edu.om",
@@ -4514,7 +4535,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "com.pf",
 "org.pf",
 "edu.pf",
-"pg",
+"*.pg",
 "com.ph",
 "net.ph",
 "org.ph",
@@ -5114,7 +5135,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "org.to",
 "edu.to",
 "mil.to",
-"tr",
+"*.tr",
 "nic.tr",
 "gov.nc.tr",
 "co.tt",
@@ -5245,8 +5266,8 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "ne.ug",
 "com.ug",
 "org.ug",
-"uk",
-"sch.uk",
+"*.uk",
+"*.sch.uk",
 "bl.uk",
 "british-library.uk",
 "jet.uk",
@@ -5528,14 +5549,14 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "org.ws",
 "gov.ws",
 "edu.ws",
-"ye",
-"za",
-"zm",
-"zw",
+"*.ye",
+"*.za",
+"*.zm",
+"*.zw",
 "cloudfront.net",
-"compute.amazonaws.com",
+"*.compute.amazonaws.com",
 "us-east-1.amazonaws.com",
-"compute-1.amazonaws.com",
+"*.compute-1.amazonaws.com",
 "z-1.compute-1.amazonaws.com",
 "z-2.compute-1.amazonaws.com",
 "ap-northeast-1.compute.amazonaws.com",
@@ -5547,7 +5568,7 @@ pub static PUB_DOMAINS: [&'static str; 5953] =  [
 "us-west-1.compute.amazonaws.com",
 "us-west-2.compute.amazonaws.com",
 "elasticbeanstalk.com",
-"elb.amazonaws.com",
+"*.elb.amazonaws.com",
 "s3.amazonaws.com",
 "s3-us-west-2.amazonaws.com",
 "s3-us-west-1.amazonaws.com",
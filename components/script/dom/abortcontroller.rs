@@ -0,0 +1,50 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::abortsignal::AbortSignal;
+use dom::bindings::codegen::Bindings::AbortControllerBinding;
+use dom::bindings::codegen::Bindings::AbortControllerBinding::AbortControllerMethods;
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+
+// https://dom.spec.whatwg.org/#interface-abortcontroller
+#[dom_struct]
+pub struct AbortController {
+    reflector_: Reflector,
+    signal: JS<AbortSignal>,
+}
+
+impl AbortController {
+    fn new_inherited(signal: &AbortSignal) -> AbortController {
+        AbortController {
+            reflector_: Reflector::new(),
+            signal: JS::from_ref(signal),
+        }
+    }
+
+    pub fn new(global: GlobalRef) -> Root<AbortController> {
+        let signal = AbortSignal::new(global);
+        reflect_dom_object(box AbortController::new_inherited(signal.r()),
+                           global, AbortControllerBinding::Wrap)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-abortcontroller-abortcontroller
+    pub fn Constructor(global: GlobalRef) -> Fallible<Root<AbortController>> {
+        Ok(AbortController::new(global))
+    }
+}
+
+impl AbortControllerMethods for AbortController {
+    // https://dom.spec.whatwg.org/#dom-abortcontroller-signal
+    fn Signal(&self) -> Root<AbortSignal> {
+        self.signal.root()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-abortcontroller-abort
+    fn Abort(&self) {
+        self.signal.root().r().signal_abort();
+    }
+}
@@ -0,0 +1,115 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::AbortSignalBinding;
+use dom::bindings::codegen::Bindings::AbortSignalBinding::AbortSignalMethods;
+use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
+use dom::bindings::codegen::InheritTypes::EventTargetCast;
+use dom::bindings::global::{GlobalRef, global_object_for_reflector};
+use dom::bindings::js::{JS, Root};
+use dom::bindings::refcounted::Trusted;
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable};
+use dom::eventtarget::{EventListenerEntry, EventTarget};
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::cmp;
+use std::i32;
+use timers::TimerCallback;
+use util::str::DOMString;
+
+/// An event listener added with this signal, to be removed when it is aborted.
+#[must_root]
+#[derive(JSTraceable, HeapSizeOf)]
+#[privatize]
+struct AbortListener {
+    target: JS<EventTarget>,
+    type_: DOMString,
+    entry: EventListenerEntry,
+}
+
+// https://dom.spec.whatwg.org/#interface-AbortSignal
+#[dom_struct]
+pub struct AbortSignal {
+    eventtarget: EventTarget,
+    aborted: Cell<bool>,
+    listeners: DOMRefCell<Vec<AbortListener>>,
+}
+
+impl AbortSignal {
+    fn new_inherited() -> AbortSignal {
+        AbortSignal {
+            eventtarget: EventTarget::new_inherited(),
+            aborted: Cell::new(false),
+            listeners: DOMRefCell::new(vec![]),
+        }
+    }
+
+    pub fn new(global: GlobalRef) -> Root<AbortSignal> {
+        reflect_dom_object(box AbortSignal::new_inherited(),
+                           global, AbortSignalBinding::Wrap)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-abortsignal-timeout
+    pub fn Timeout(global: GlobalRef, milliseconds: u64) -> Root<AbortSignal> {
+        // Step 1.
+        let signal = AbortSignal::new(global);
+
+        // Step 3.
+        let trusted = Trusted::new(global.get_cx(), signal.r(), global.script_chan());
+        let timeout = cmp::min(milliseconds, i32::MAX as u64) as i32;
+        global.schedule_callback(TimerCallback::AbortSignalTimerCallback(trusted), timeout);
+
+        // Step 4.
+        signal
+    }
+
+    /// Records that the given listener must be removed from `target` once
+    /// this signal is aborted.
+    pub fn add_listener(&self, target: &EventTarget, type_: DOMString, entry: EventListenerEntry) {
+        self.listeners.borrow_mut().push(AbortListener {
+            target: JS::from_ref(target),
+            type_: type_,
+            entry: entry,
+        });
+    }
+
+    // https://dom.spec.whatwg.org/#abortsignal-signal-abort
+    pub fn signal_abort(&self) {
+        // Step 1.
+        if self.aborted.get() {
+            return;
+        }
+
+        // Step 2.
+        self.aborted.set(true);
+
+        // Step 3.
+        let listeners = self.listeners.borrow().iter().map(|listener| {
+            (listener.target.root(), listener.type_.clone(), listener.entry.clone())
+        }).collect::<Vec<_>>();
+        self.listeners.borrow_mut().clear();
+        for (target, type_, entry) in listeners {
+            target.r().remove_listener(&type_, &entry);
+        }
+
+        // Step 5.
+        let global = global_object_for_reflector(self);
+        let event = Event::new(global.r(), "abort".to_owned(),
+                               EventBubbles::DoesNotBubble,
+                               EventCancelable::NotCancelable);
+        event.r().fire(EventTargetCast::from_ref(self));
+    }
+}
+
+impl AbortSignalMethods for AbortSignal {
+    // https://dom.spec.whatwg.org/#dom-abortsignal-aborted
+    fn Aborted(&self) -> bool {
+        self.aborted.get()
+    }
+
+    // https://dom.spec.whatwg.org/#handler-abortsignal-onabort
+    event_handler!(abort, GetOnabort, SetOnabort);
+}
@@ -32,36 +32,51 @@ pub trait Activatable {
 
     // https://html.spec.whatwg.org/multipage/#run-synthetic-click-activation-steps
     fn synthetic_click_activation(&self, ctrlKey: bool, shiftKey: bool, altKey: bool, metaKey: bool) {
-        let element = self.as_element();
-        // Step 1
-        if element.click_in_progress() {
-            return;
-        }
-        // Step 2
-        element.set_click_in_progress(true);
-        // Step 3
-        self.pre_click_activation();
+        synthetic_click_activation(self.as_element(), ctrlKey, shiftKey, altKey, metaKey)
+    }
+}
+
+/// Runs the synthetic click activation steps on `element`, firing a `click` event at it
+/// even if it has no activation behavior of its own.
+// https://html.spec.whatwg.org/multipage/#run-synthetic-click-activation-steps
+pub fn synthetic_click_activation(element: &Element,
+                                  ctrlKey: bool,
+                                  shiftKey: bool,
+                                  altKey: bool,
+                                  metaKey: bool) {
+    // Step 1
+    if element.click_in_progress() {
+        return;
+    }
+    // Step 2
+    element.set_click_in_progress(true);
+    // Step 3
+    let activatable = element.as_maybe_activatable();
+    if let Some(a) = activatable {
+        a.pre_click_activation();
+    }
 
-        // Step 4
-        // https://html.spec.whatwg.org/multipage/#fire-a-synthetic-mouse-event
-        let win = window_from_node(element);
-        let target = EventTargetCast::from_ref(element);
-        let mouse = MouseEvent::new(win.r(), "click".to_owned(),
-                                    EventBubbles::DoesNotBubble, EventCancelable::NotCancelable, Some(win.r()), 1,
-                                    0, 0, 0, 0, ctrlKey, shiftKey, altKey, metaKey,
-                                    0, None);
-        let event = EventCast::from_ref(mouse.r());
-        event.fire(target);
+    // Step 4
+    // https://html.spec.whatwg.org/multipage/#fire-a-synthetic-mouse-event
+    let win = window_from_node(element);
+    let target = EventTargetCast::from_ref(element);
+    let mouse = MouseEvent::new(win.r(), "click".to_owned(),
+                                EventBubbles::Bubbles, EventCancelable::Cancelable, Some(win.r()), 1,
+                                0, 0, 0, 0, ctrlKey, shiftKey, altKey, metaKey,
+                                0, None);
+    let event = EventCast::from_ref(mouse.r());
+    event.fire(target);
 
-        // Step 5
+    // Step 5
+    if let Some(a) = activatable {
         if event.DefaultPrevented() {
-            self.canceled_activation();
+            a.canceled_activation();
         } else {
             // post click activation
-            self.activation_behavior(event, target);
+            a.activation_behavior(event, target);
         }
-
-        // Step 6
-        element.set_click_in_progress(false);
     }
+
+    // Step 6
+    element.set_click_in_progress(false);
 }
@@ -0,0 +1,367 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use cssparser::{Parser as CssParser, ToCss};
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::AnimatableBinding::{FillMode, KeyframeAnimationOptions};
+use dom::bindings::codegen::Bindings::AnimationBinding;
+use dom::bindings::codegen::Bindings::AnimationBinding::{AnimationMethods, AnimationPlayState};
+use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
+use dom::bindings::codegen::Bindings::PerformanceBinding::PerformanceMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::InheritTypes::{EventTargetCast, NodeCast};
+use dom::bindings::conversions::{FromJSValConvertible, StringificationBehavior};
+use dom::bindings::error::Error::{InvalidState, JSFailed};
+use dom::bindings::error::{Error, ErrorResult, Fallible};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::refcounted::Trusted;
+use dom::bindings::utils::{get_dictionary_property, reflect_dom_object};
+use dom::element::Element;
+use dom::event::{Event, EventBubbles, EventCancelable};
+use dom::eventtarget::EventTarget;
+use dom::node::{NodeDamage, document_from_node, window_from_node};
+use js::jsapi::{HandleObject, JSContext, JSObject, RootedObject, RootedValue};
+use js::jsval::UndefinedValue;
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::cmp;
+use string_cache::Atom;
+use style::animation::timing_function_progress;
+use style::properties::longhands::transition_property::computed_value::ALL_TRANSITION_PROPERTIES;
+use style::properties::longhands::transition_timing_function;
+use style::properties::longhands::transition_timing_function::computed_value::TransitionTimingFunction;
+use style::properties::parse_one_declaration;
+use util::str::DOMString;
+
+/// Returns the simple iteration progress of an effect that runs `iterations`
+/// times for `duration` milliseconds, at `local_time` milliseconds after it
+/// started. Returns `None` once the effect is over, unless it fills forwards.
+// https://drafts.csswg.org/web-animations/#calculating-the-simple-iteration-progress
+pub fn simple_iteration_progress(local_time: f64, duration: f64, iterations: f64, fill: FillMode) -> Option<f64> {
+    let active_duration = if duration == 0. { 0. } else { duration * iterations };
+    if local_time < active_duration {
+        return Some((local_time / duration) % 1.);
+    }
+
+    match fill {
+        FillMode::Forwards | FillMode::Both => {},
+        FillMode::None | FillMode::Backwards | FillMode::Auto => return None,
+    }
+    if iterations != 0. && iterations % 1. == 0. {
+        Some(1.)
+    } else {
+        Some(iterations % 1.)
+    }
+}
+
+/// Splits a value such as `12.5px` into its number and unit.
+fn split_number(value: &str) -> Option<(f64, &str)> {
+    let value = value.trim();
+    let end = value.find(|c: char| !(c.is_digit(10) || c == '.' || c == '-' || c == '+'))
+                   .unwrap_or(value.len());
+    value[..end].parse().ok().map(|number| (number, &value[end..]))
+}
+
+/// Interpolates between two values of a property. Numbers with the same unit
+/// are interpolated linearly; any other values flip halfway through.
+// https://drafts.csswg.org/web-animations/#animating-properties
+pub fn interpolate(from: &str, to: &str, progress: f64) -> DOMString {
+    match (split_number(from), split_number(to)) {
+        (Some((from, from_unit)), Some((to, to_unit))) if from_unit == to_unit => {
+            format!("{}{}", from + (to - from) * progress, from_unit)
+        },
+        _ => if progress < 0.5 { from.to_owned() } else { to.to_owned() },
+    }
+}
+
+/// Returns the value of a property whose keyframes are spaced evenly over an
+/// iteration, at the given iteration progress.
+pub fn keyframe_value(values: &[DOMString], progress: f64) -> DOMString {
+    if values.len() == 1 {
+        return values[0].clone();
+    }
+    let segments = values.len() - 1;
+    let position = progress * segments as f64;
+    let index = cmp::min(position.floor().max(0.) as usize, segments - 1);
+    interpolate(&values[index], &values[index + 1], position - index as f64)
+}
+
+/// Returns the values of `property` given by a keyframes object, either as a
+/// list of values or as a single value.
+fn property_values(cx: *mut JSContext, object: HandleObject, property: &str) -> Fallible<Vec<DOMString>> {
+    let mut value = RootedValue::new(cx, UndefinedValue());
+    if !try!(get_dictionary_property(cx, object, property, value.handle_mut()).map_err(|_| JSFailed)) {
+        return Ok(vec![]);
+    }
+
+    if !value.ptr.is_object() {
+        let value = try!(DOMString::from_jsval(cx, value.handle(), StringificationBehavior::Default)
+                                   .map_err(|_| JSFailed));
+        return Ok(vec![value]);
+    }
+
+    let list = RootedObject::new(cx, value.ptr.to_object());
+    let length = try!(list_length(cx, list.handle()));
+    let mut values = vec![];
+    for index in 0..length.unwrap_or(0) {
+        let mut item = RootedValue::new(cx, UndefinedValue());
+        try!(get_dictionary_property(cx, list.handle(), &index.to_string(), item.handle_mut())
+                 .map_err(|_| JSFailed));
+        values.push(try!(DOMString::from_jsval(cx, item.handle(), StringificationBehavior::Default)
+                                   .map_err(|_| JSFailed)));
+    }
+    Ok(values)
+}
+
+/// Returns the length of `object` if it is list-like.
+fn list_length(cx: *mut JSContext, object: HandleObject) -> Fallible<Option<u32>> {
+    let mut length = RootedValue::new(cx, UndefinedValue());
+    if !try!(get_dictionary_property(cx, object, "length", length.handle_mut()).map_err(|_| JSFailed)) ||
+       !length.ptr.is_number() {
+        return Ok(None);
+    }
+    Ok(Some(length.ptr.to_number() as u32))
+}
+
+/// Returns the name of the IDL attribute of a CSS property, such as
+/// `backgroundColor` for `background-color`.
+fn idl_attribute_name(property: &str) -> DOMString {
+    let mut parts = property.split('-');
+    let mut name = parts.next().unwrap_or("").to_owned();
+    for part in parts {
+        if let Some(first) = part.chars().next() {
+            name.extend(first.to_uppercase());
+            name.push_str(&part[first.len_utf8()..]);
+        }
+    }
+    name
+}
+
+/// Reads the keyframes of each animatable property from either a list of
+/// keyframe objects or a property-indexed keyframes object. Offsets aren't
+/// supported, so the keyframes of a property are spaced evenly.
+// https://drafts.csswg.org/web-animations/#processing-a-keyframes-argument
+fn process_keyframes(cx: *mut JSContext, object: *mut JSObject) -> Fallible<Vec<(DOMString, Vec<DOMString>)>> {
+    let mut keyframes = vec![];
+    if object.is_null() {
+        return Ok(keyframes);
+    }
+    let object = RootedObject::new(cx, object);
+    let length = try!(list_length(cx, object.handle()));
+    for property in ALL_TRANSITION_PROPERTIES.iter() {
+        let property = property.to_css_string();
+        let name = idl_attribute_name(&property);
+        let values = match length {
+            Some(length) => {
+                let mut values = vec![];
+                for index in 0..length {
+                    let mut keyframe = RootedValue::new(cx, UndefinedValue());
+                    try!(get_dictionary_property(cx, object.handle(), &index.to_string(), keyframe.handle_mut())
+                             .map_err(|_| JSFailed));
+                    if !keyframe.ptr.is_object() {
+                        return Err(Error::Type("Keyframes must be objects".to_owned()));
+                    }
+                    let keyframe = RootedObject::new(cx, keyframe.ptr.to_object());
+                    values.extend(try!(property_values(cx, keyframe.handle(), &name)));
+                }
+                values
+            },
+            None => try!(property_values(cx, object.handle(), &name)),
+        };
+        if !values.is_empty() {
+            keyframes.push((property, values));
+        }
+    }
+    Ok(keyframes)
+}
+
+/// The keyframes of one property of an animation.
+#[derive(JSTraceable, HeapSizeOf)]
+struct PropertyKeyframes {
+    property: DOMString,
+    values: Vec<DOMString>,
+}
+
+// https://drafts.csswg.org/web-animations/#the-animation-interface
+#[dom_struct]
+pub struct Animation {
+    eventtarget: EventTarget,
+    target: JS<Element>,
+    keyframes: DOMRefCell<Vec<PropertyKeyframes>>,
+    duration: f64,
+    iterations: f64,
+    easing: TransitionTimingFunction,
+    fill: FillMode,
+    /// The value of `performance.now()` when the animation started.
+    start_time: f64,
+    play_state: Cell<AnimationPlayState>,
+}
+
+impl Animation {
+    fn new_inherited(target: &Element,
+                     keyframes: Vec<PropertyKeyframes>,
+                     options: &KeyframeAnimationOptions,
+                     easing: TransitionTimingFunction,
+                     start_time: f64) -> Animation {
+        Animation {
+            eventtarget: EventTarget::new_inherited(),
+            target: JS::from_ref(target),
+            keyframes: DOMRefCell::new(keyframes),
+            duration: options.duration,
+            iterations: options.iterations,
+            easing: easing,
+            fill: options.fill,
+            start_time: start_time,
+            play_state: Cell::new(AnimationPlayState::Running),
+        }
+    }
+
+    // https://drafts.csswg.org/web-animations/#dom-animatable-animate
+    pub fn animate(cx: *mut JSContext,
+                   target: &Element,
+                   keyframes: *mut JSObject,
+                   options: &KeyframeAnimationOptions) -> Fallible<Root<Animation>> {
+        if options.duration.is_nan() || options.duration < 0. {
+            return Err(Error::Type("duration must be a non-negative number".to_owned()));
+        }
+        if options.iterations.is_nan() || options.iterations < 0. {
+            return Err(Error::Type("iterations must be a non-negative number".to_owned()));
+        }
+        let easing = match transition_timing_function::parse_one(&mut CssParser::new(&options.easing)) {
+            Ok(easing) => easing,
+            Err(()) => return Err(Error::Type(format!("{} is not a valid easing", options.easing))),
+        };
+
+        let node = NodeCast::from_ref(target);
+        let window = window_from_node(target);
+        let keyframes = try!(process_keyframes(cx, keyframes)).into_iter().map(|(property, mut values)| {
+            // A single value animates from the current value of the property.
+            if values.len() == 1 {
+                let current = window.r().resolved_style_query(node.to_trusted_node_address(), None,
+                                                              &Atom::from_slice(&property))
+                                        .unwrap_or_else(|| values[0].clone());
+                values.insert(0, current);
+            }
+            PropertyKeyframes {
+                property: property,
+                values: values,
+            }
+        }).collect();
+
+        let start_time = *window.r().Performance().r().Now();
+        let animation = Animation::new_inherited(target, keyframes, options, easing, start_time);
+        let animation = reflect_dom_object(box animation, GlobalRef::Window(window.r()), AnimationBinding::Wrap);
+        animation.r().tick(start_time);
+        Ok(animation)
+    }
+
+    fn active_duration(&self) -> f64 {
+        if self.duration == 0. { 0. } else { self.duration * self.iterations }
+    }
+
+    /// Applies the animation as of the given value of `performance.now()`,
+    /// and either waits for the next animation frame or finishes.
+    fn tick(&self, now: f64) {
+        if self.play_state.get() != AnimationPlayState::Running {
+            return;
+        }
+        let local_time = now - self.start_time;
+        self.apply(local_time);
+        if local_time >= self.active_duration() {
+            self.set_finished();
+            return;
+        }
+
+        let target = self.target.root();
+        let window = window_from_node(target.r());
+        let document = document_from_node(target.r());
+        let animation = Trusted::new(window.r().get_cx(), self, window.r().script_chan());
+        document.r().request_animation_frame(box move |now| {
+            animation.root().r().tick(now);
+        });
+    }
+
+    /// Sets the animated properties of the target to their values at the
+    /// given local time, or stops animating them if the animation doesn't apply.
+    // https://drafts.csswg.org/web-animations/#the-effect-value-of-a-keyframe-animation-effect
+    fn apply(&self, local_time: f64) {
+        let progress = simple_iteration_progress(local_time, self.duration, self.iterations, self.fill);
+        let progress = progress.map(|progress| {
+            timing_function_progress(self.easing, self.duration / 1000., progress)
+        });
+        for keyframes in &*self.keyframes.borrow() {
+            let value = progress.map(|progress| keyframe_value(&keyframes.values, progress));
+            self.set_animated_value(&keyframes.property, value.as_ref());
+        }
+    }
+
+    /// Sets the animated value of `property` on the target, which applies at
+    /// the animation origin of the cascade rather than in its inline style.
+    fn set_animated_value(&self, property: &str, value: Option<&DOMString>) {
+        let target = self.target.root();
+        let target = target.r();
+        let declarations = value.and_then(|value| {
+            let window = window_from_node(target);
+            parse_one_declaration(property, value, &window.r().get_url()).ok()
+        }).unwrap_or(vec![]);
+        target.set_animation_declarations(property, declarations);
+        let node = NodeCast::from_ref(target);
+        document_from_node(target).r().content_changed(node, NodeDamage::NodeStyleDamaged);
+    }
+
+    fn set_finished(&self) {
+        self.play_state.set(AnimationPlayState::Finished);
+        self.fire_event("finish");
+    }
+
+    fn fire_event(&self, type_: &str) {
+        let window = window_from_node(self.target.root().r());
+        let event = Event::new(GlobalRef::Window(window.r()), type_.to_owned(),
+                               EventBubbles::DoesNotBubble,
+                               EventCancelable::NotCancelable);
+        event.r().fire(EventTargetCast::from_ref(self));
+    }
+}
+
+impl AnimationMethods for Animation {
+    // https://drafts.csswg.org/web-animations/#dom-animation-playstate
+    fn PlayState(&self) -> AnimationPlayState {
+        self.play_state.get()
+    }
+
+    // https://drafts.csswg.org/web-animations/#dom-animation-onfinish
+    event_handler!(finish, GetOnfinish, SetOnfinish);
+
+    // https://drafts.csswg.org/web-animations/#dom-animation-oncancel
+    event_handler!(cancel, GetOncancel, SetOncancel);
+
+    // https://drafts.csswg.org/web-animations/#dom-animation-cancel
+    fn Cancel(&self) {
+        if self.play_state.get() == AnimationPlayState::Idle {
+            return;
+        }
+        self.play_state.set(AnimationPlayState::Idle);
+        for keyframes in &*self.keyframes.borrow() {
+            self.set_animated_value(&keyframes.property, None);
+        }
+        self.fire_event("cancel");
+    }
+
+    // https://drafts.csswg.org/web-animations/#dom-animation-finish
+    fn Finish(&self) -> ErrorResult {
+        // Step 1.
+        let active_duration = self.active_duration();
+        if active_duration.is_infinite() {
+            return Err(InvalidState);
+        }
+
+        if self.play_state.get() == AnimationPlayState::Finished {
+            return Ok(());
+        }
+        self.apply(active_duration);
+        self.set_finished();
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use dom::bindings::js::{JS, MutNullableHeap};
 use dom::bindings::js::{LayoutJS, Root, RootedReference};
 use dom::bindings::utils::{Reflector, reflect_dom_object};
 use dom::element::{AttributeMutation, Element};
+use dom::mutationobserver::{Mutation, MutationObserver};
 use dom::virtualmethods::vtable_for;
 use dom::window::Window;
 use std::borrow::ToOwned;
@@ -240,6 +241,11 @@ impl AttrMethods for Attr {
 impl Attr {
     pub fn set_value(&self, mut value: AttrValue, owner: &Element) {
         assert!(Some(owner) == self.owner().r());
+        MutationObserver::queue_a_mutation_record(NodeCast::from_ref(owner), Mutation::Attribute {
+            name: self.local_name(),
+            namespace: self.namespace(),
+            old_value: Some(&**self.value()),
+        });
         mem::swap(&mut *self.value.borrow_mut(), &mut value);
         if self.namespace == ns!("") {
             vtable_for(NodeCast::from_ref(owner)).attribute_mutated(
@@ -247,6 +253,13 @@ impl Attr {
         }
     }
 
+    /// Replaces the value without notifying the owner, for an attribute
+    /// that is about to be attached to an element.
+    pub fn swap_value(&self, value: &mut AttrValue) {
+        assert!(self.owner().is_none());
+        mem::swap(&mut *self.value.borrow_mut(), value);
+    }
+
     pub fn value(&self) -> Ref<AttrValue> {
         self.value.borrow()
     }
@@ -266,7 +279,7 @@ impl Attr {
             }
             (Some(old), None) => {
                 // Already gone from the list of attributes of old owner.
-                assert!(old.get_attribute(&ns, &self.local_name).is_none())
+                assert!(old.get_attribute(&ns, &self.local_name) != Some(Root::from_ref(self)))
             }
             (old, new) => assert!(old == new)
         }
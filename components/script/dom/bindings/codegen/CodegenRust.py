@@ -737,7 +737,18 @@ def getJSToNativeConversionInfo(type, descriptorProvider, failureCode=None,
                         "    Err(()) => { %s },\n"
                         "}" % exceptionCode)
 
-        return handleOptional(templateBody, declType, handleDefaultNull("None"))
+        if defaultValue is not None and not type.nullable() and type.hasDictionaryType():
+            # A null default for a union with a dictionary member means the
+            # empty dictionary.
+            assert isinstance(defaultValue, IDLNullValue)
+            dictionary = [t for t in type.flatMemberTypes if t.isDictionary()][0].inner
+            default = "%s::e%s(%s::empty(cx))" % (union_native_type(type),
+                                                  dictionary.identifier.name,
+                                                  CGDictionary.makeDictionaryName(dictionary))
+        else:
+            default = handleDefaultNull("None")
+
+        return handleOptional(templateBody, declType, default)
 
     if type.isGeckoInterface():
         assert not isEnforceRange and not isClamp
@@ -1306,7 +1317,14 @@ def getRetvalDeclarationForType(returnType, descriptorProvider):
     if returnType.isObject() or returnType.isSpiderMonkeyInterface():
         return CGGeneric("*mut JSObject")
     if returnType.isSequence():
-        raise TypeError("We don't support sequence return values")
+        nullable = returnType.nullable()
+        if nullable:
+            returnType = returnType.inner
+        result = getRetvalDeclarationForType(returnType.inner, descriptorProvider)
+        result = CGWrapper(result, pre="Vec<", post=">")
+        if nullable:
+            result = CGWrapper(result, pre="Option<", post=">")
+        return result
     if returnType.isDictionary():
         nullable = returnType.nullable()
         dictName = returnType.inner.name if nullable else returnType.name
@@ -2012,6 +2030,11 @@ def UnionTypes(descriptors, dictionaries, callbacks, config):
             continue
         name = str(t)
         if name not in unionStructs:
+            for memberType in t.flatMemberTypes:
+                if memberType.isDictionary():
+                    imports.append('dom::bindings::codegen::Bindings::%s::%s' % (
+                        CGDictionary.makeModuleName(memberType.inner),
+                        CGDictionary.makeDictionaryName(memberType.inner)))
             provider = descriptor or config.getDescriptorProvider()
             unionStructs[name] = CGList([
                 CGUnionStruct(t, provider),
@@ -3362,12 +3385,25 @@ class CGConstant(CGThing):
 
 
 def getUnionTypeTemplateVars(type, descriptorProvider):
-    # For dictionaries and sequences we need to pass None as the failureCode
+    # For sequences we need to pass None as the failureCode
     # for getJSToNativeConversionInfo.
-    # Also, for dictionaries we would need to handle conversion of
-    # null/undefined to the dictionary correctly.
-    if type.isDictionary() or type.isSequence():
-        raise TypeError("Can't handle dictionaries or sequences in unions")
+    if type.isSequence():
+        raise TypeError("Can't handle sequences in unions")
+
+    if type.isDictionary():
+        # Dictionaries can't use a failureCode, and their conversion already
+        # handles null/undefined, so generate the conversion directly.
+        name = type.inner.identifier.name
+        typeName = CGDictionary.makeDictionaryName(type.inner)
+        jsConversion = CGGeneric("match %s::new(cx, value) {\n"
+                                 "    Ok(dictionary) => Ok(Some(dictionary)),\n"
+                                 "    Err(_) => Err(()),\n"
+                                 "}" % typeName)
+        return {
+            "name": name,
+            "typeName": typeName,
+            "jsConversion": jsConversion,
+        }
 
     if type.isGeckoInterface():
         name = type.inner.identifier.name
@@ -3456,6 +3492,14 @@ class CGUnionConversionStruct(CGThing):
         names = []
         conversions = []
 
+        def get_match(name):
+            return (
+                "match %s::TryConvertTo%s(cx, value) {\n"
+                "    Err(_) => return Err(()),\n"
+                "    Ok(Some(value)) => return Ok(%s::e%s(value)),\n"
+                "    Ok(None) => (),\n"
+                "}\n") % (self.type, name, self.type, name)
+
         interfaceMemberTypes = filter(lambda t: t.isNonCallbackInterface(), memberTypes)
         if len(interfaceMemberTypes) > 0:
             def get_name(memberType):
@@ -3464,14 +3508,6 @@ class CGUnionConversionStruct(CGThing):
 
                 return memberType.name
 
-            def get_match(name):
-                return (
-                    "match %s::TryConvertTo%s(cx, value) {\n"
-                    "    Err(_) => return Err(()),\n"
-                    "    Ok(Some(value)) => return Ok(%s::e%s(value)),\n"
-                    "    Ok(None) => (),\n"
-                    "}\n") % (self.type, name, self.type, name)
-
             typeNames = [get_name(memberType) for memberType in interfaceMemberTypes]
             interfaceObject = CGList(CGGeneric(get_match(typeName)) for typeName in typeNames)
             names.extend(typeNames)
@@ -3501,12 +3537,14 @@ class CGUnionConversionStruct(CGThing):
 
         dictionaryMemberTypes = filter(lambda t: t.isDictionary(), memberTypes)
         if len(dictionaryMemberTypes) > 0:
-            raise TypeError("No support for unwrapping dictionaries as member "
-                            "of a union")
+            assert len(dictionaryMemberTypes) == 1
+            typeName = dictionaryMemberTypes[0].inner.identifier.name
+            dictionaryObject = CGGeneric(get_match(typeName))
+            names.append(typeName)
         else:
             dictionaryObject = None
 
-        if callbackObject or dictionaryObject:
+        if callbackObject:
             assert False, "Not currently supported"
         else:
             nonPlatformObject = None
@@ -3517,10 +3555,14 @@ class CGUnionConversionStruct(CGThing):
         else:
             object = None
 
+        # Null and undefined are converted to the dictionary, if any.
+        if dictionaryObject:
+            conversions.append(CGIfWrapper(dictionaryObject, "value.get().is_null_or_undefined()"))
+
         hasObjectTypes = interfaceObject or arrayObject or dateObject or nonPlatformObject or object
-        if hasObjectTypes:
-            assert interfaceObject
-            templateBody = CGList([interfaceObject], "\n")
+        if hasObjectTypes or dictionaryObject:
+            assert interfaceObject or dictionaryObject
+            templateBody = CGList([o for o in [interfaceObject, dictionaryObject] if o], "\n")
             conversions.append(CGIfWrapper(templateBody, "value.get().is_object()"))
 
         otherMemberTypes = [
@@ -4937,7 +4979,7 @@ class CGDictionary(CGThing):
     @staticmethod
     def makeMemberName(name):
         # Can't use Rust keywords as member names.
-        if name == "type":
+        if name in ["box", "type"]:
             return name + "_"
         return name
 
@@ -5152,10 +5194,16 @@ class CGBindingRoot(CGThing):
         return stripTrailingWhitespace(self.root.define())
 
 
-def argument_type(descriptorProvider, ty, optional=False, defaultValue=None, variadic=False):
-    info = getJSToNativeConversionInfo(
-        ty, descriptorProvider, isArgument=True)
-    declType = info.declType
+def argument_type(descriptorProvider, ty, optional=False, defaultValue=None, variadic=False,
+                  isCallbackArgument=False):
+    if isCallbackArgument and ty.isSequence():
+        # Callbacks convert their sequence arguments to JS values, so the
+        # sequence is passed like a return value.
+        declType = getRetvalDeclarationForType(ty, descriptorProvider)
+    else:
+        info = getJSToNativeConversionInfo(
+            ty, descriptorProvider, isArgument=True)
+        declType = info.declType
 
     if variadic:
         declType = CGWrapper(declType, pre="Vec<", post=">")
@@ -5168,13 +5216,15 @@ def argument_type(descriptorProvider, ty, optional=False, defaultValue=None, var
     return declType.define()
 
 
-def method_arguments(descriptorProvider, returnType, arguments, passJSBits=True, trailing=None):
+def method_arguments(descriptorProvider, returnType, arguments, passJSBits=True, trailing=None,
+                     isCallbackArgument=False):
     if needCx(returnType, arguments, passJSBits):
         yield "cx", "*mut JSContext"
 
     for argument in arguments:
         ty = argument_type(descriptorProvider, argument.type, argument.optional,
-                           argument.defaultValue, argument.variadic)
+                           argument.defaultValue, argument.variadic,
+                           isCallbackArgument=isCallbackArgument)
         yield CGDictionary.makeMemberName(argument.identifier.name), ty
 
     if trailing:
@@ -5544,7 +5594,11 @@ class CallbackMember(CGNativeMember):
         return conversion
 
     def getArgs(self, returnType, argList):
-        args = CGNativeMember.getArgs(self, returnType, argList)
+        args = [Argument(arg[1], arg[0]) for arg in method_arguments(self.descriptorProvider,
+                                                                     returnType,
+                                                                     argList,
+                                                                     self.passJSBitsAsNeeded,
+                                                                     isCallbackArgument=True)]
         if not self.needThisHandling:
             # Since we don't need this handling, we're the actual method that
             # will be called, so we need an aRethrowExceptions argument.
@@ -40,10 +40,12 @@ use dom::bindings::num::Finite;
 use dom::bindings::str::{ByteString, USVString};
 use dom::bindings::utils::{DOMClass, Reflectable, Reflector};
 use js;
+use js::JSPROP_ENUMERATE;
 use js::glue::{GetProxyPrivate, IsWrapper, RUST_JS_NumberValue};
 use js::glue::{RUST_JSID_IS_STRING, RUST_JSID_TO_STRING, UnwrapObject};
 use js::jsapi::{HandleId, HandleObject, HandleValue, JS_GetClass};
 use js::jsapi::{JSClass, JSContext, JSObject, JSString, MutableHandleValue};
+use js::jsapi::{JS_DefineElement, JS_NewArrayObject1, RootedObject, RootedValue};
 use js::jsapi::{JS_GetLatin1StringCharsAndLength, JS_GetReservedSlot};
 use js::jsapi::{JS_GetTwoByteStringCharsAndLength, JS_NewStringCopyN};
 use js::jsapi::{JS_NewUCStringCopyN, JS_StringHasLatin1Chars, JS_WrapValue};
@@ -770,6 +772,26 @@ impl<T: FromJSValConvertible> FromJSValConvertible for Option<T> {
     }
 }
 
+impl<T: ToJSValConvertible> ToJSValConvertible for Vec<T> {
+    fn to_jsval(&self, cx: *mut JSContext, rval: MutableHandleValue) {
+        let js_array = RootedObject::new(cx, unsafe {
+            JS_NewArrayObject1(cx, self.len() as libc::size_t)
+        });
+        assert!(!js_array.handle().get().is_null());
+
+        for (index, item) in self.iter().enumerate() {
+            let mut value = RootedValue::new(cx, UndefinedValue());
+            item.to_jsval(cx, value.handle_mut());
+            unsafe {
+                assert!(JS_DefineElement(cx, js_array.handle(), index as u32, value.handle(),
+                                         JSPROP_ENUMERATE, None, None) != 0);
+            }
+        }
+
+        rval.set(ObjectValue(unsafe { &*js_array.handle().get() }));
+    }
+}
+
 impl ToJSValConvertible for *mut JSObject {
     fn to_jsval(&self, cx: *mut JSContext, rval: MutableHandleValue) {
         rval.set(ObjectOrNullValue(*self));
@@ -22,6 +22,7 @@ use msg::constellation_msg::{ConstellationChan, PipelineId, WorkerId};
 use net_traits::ResourceTask;
 use profile_traits::mem;
 use script_task::{CommonScriptMsg, ScriptChan, ScriptPort, ScriptTask};
+use timers::TimerCallback;
 use url::Url;
 use util::mem::HeapSizeOf;
 
@@ -88,6 +89,15 @@ impl<'a> GlobalRef<'a> {
         }
     }
 
+    /// Get the time origin of this global scope, as a `time::precise_time_ns()` value.
+    // https://w3c.github.io/hr-time/#time-origin
+    pub fn time_origin(&self) -> f64 {
+        match *self {
+            GlobalRef::Window(window) => window.navigation_start_precise(),
+            GlobalRef::Worker(worker) => worker.time_origin(),
+        }
+    }
+
     /// Get a `ConstellationChan` to send messages to the constellation channel when available.
     pub fn constellation_chan(&self) -> ConstellationChan {
         match *self {
@@ -151,6 +161,15 @@ impl<'a> GlobalRef<'a> {
         }
     }
 
+    /// Runs `callback` after `timeout` milliseconds, through the timers of this
+    /// global object.
+    pub fn schedule_callback(&self, callback: TimerCallback, timeout: i32) -> i32 {
+        match *self {
+            GlobalRef::Window(window) => window.schedule_callback(callback, timeout),
+            GlobalRef::Worker(worker) => worker.schedule_callback(callback, timeout),
+        }
+    }
+
     /// Create a new sender/receiver pair that can be used to implement an on-demand
     /// event loop. Used for implementing web APIs that require blocking semantics
     /// without resorting to nested event loops.
@@ -45,7 +45,7 @@ impl StructuredCloneData {
     /// Reads a structured clone.
     ///
     /// Panics if `JS_ReadStructuredClone` fails.
-    pub fn read(self, global: GlobalRef, rval: MutableHandleValue) {
+    pub fn read(&self, global: GlobalRef, rval: MutableHandleValue) {
         unsafe {
             assert!(JS_ReadStructuredClone(
                 global.get_cx(), self.data, self.nbytes,
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use dom::bindings::conversions::native_from_handleobject;
+use dom::bindings::conversions::{jsid_to_str, native_from_handleobject};
 use dom::bindings::conversions::{ToJSValConvertible};
 use dom::bindings::js::{JS, Root};
 use dom::bindings::proxyhandler::{fill_property_descriptor, get_property_descriptor};
@@ -12,13 +12,13 @@ use dom::document::Document;
 use dom::element::Element;
 use dom::window::Window;
 use js::glue::{CreateWrapperProxyHandler, ProxyTraps, WrapperNew};
-use js::glue::{GetProxyPrivate};
+use js::glue::{GetProxyPrivate, RUST_JSID_IS_STRING};
 use js::jsapi::{Handle, HandleValue, Heap, JS_ForwardSetPropertyTo, ObjectOpResult, RootedObject, RootedValue};
 use js::jsapi::{HandleId, HandleObject, MutableHandle, MutableHandleValue};
 use js::jsapi::{JSAutoCompartment, JSAutoRequest};
 use js::jsapi::{JSContext, JSErrNum, JSObject, JSPropertyDescriptor};
 use js::jsapi::{JS_AlreadyHasOwnPropertyById, JS_ForwardGetPropertyTo};
-use js::jsapi::{JS_DefinePropertyById6, JS_GetPropertyDescriptorById};
+use js::jsapi::{JS_DefinePropertyById6, JS_GetPropertyDescriptorById, JS_HasPropertyById};
 use js::jsval::{ObjectValue, UndefinedValue};
 use js::{JSFalse, JSTrue};
 use std::default::Default;
@@ -110,6 +110,19 @@ unsafe fn GetSubframeWindow(cx: *mut JSContext, proxy: HandleObject, id: HandleI
         return win.r().IndexedGetter(index, &mut found);
     }
 
+    // Named child frames don't shadow the window's own properties.
+    if RUST_JSID_IS_STRING(id) != 0 {
+        let target = RootedObject::new(cx, GetProxyPrivate(*proxy.ptr).to_object());
+        let mut has_property = 0;
+        if JS_HasPropertyById(cx, target.handle(), id, &mut has_property) == 0 ||
+           has_property != 0 {
+            return None;
+        }
+        let win: Root<Window> = native_from_handleobject(target.handle()).unwrap();
+        let mut found = false;
+        return win.r().NamedGetter(jsid_to_str(cx, id), &mut found);
+    }
+
     None
 }
 
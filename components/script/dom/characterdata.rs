@@ -15,9 +15,12 @@ use dom::bindings::js::{LayoutJS, Root};
 use dom::document::Document;
 use dom::element::Element;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
+use dom::mutationobserver::{Mutation, MutationObserver};
 use dom::node::{Node, NodeDamage, NodeTypeId};
+use dom::range::for_each_live_range;
 use std::borrow::ToOwned;
 use std::cell::Ref;
+use std::cmp;
 use util::str::DOMString;
 
 // https://dom.spec.whatwg.org/#characterdata
@@ -53,6 +56,7 @@ impl CharacterDataMethods for CharacterData {
 
     // https://dom.spec.whatwg.org/#dom-characterdata-data
     fn SetData(&self, data: DOMString) {
+        self.queue_mutation_record();
         *self.data.borrow_mut() = data;
         self.content_changed();
     }
@@ -97,6 +101,8 @@ impl CharacterDataMethods for CharacterData {
 
     // https://dom.spec.whatwg.org/#dom-characterdata-replacedata
     fn ReplaceData(&self, offset: u32, count: u32, arg: DOMString) -> ErrorResult {
+        // Step 1.
+        let length = self.Length();
         let new_data = {
             let data = self.data.borrow();
             let (prefix, data_from_offset) = match find_utf16_code_unit_offset(&data, offset) {
@@ -109,7 +115,8 @@ impl CharacterDataMethods for CharacterData {
                 None => "",
                 Some(count_bytes) => &data_from_offset[count_bytes..],
             };
-            // Step 4: Mutation observers.
+            // Step 4.
+            self.queue_mutation_record();
             // Step 5 to 7.
             let mut new_data = String::with_capacity(prefix.len() + arg.len() + suffix.len());
             new_data.push_str(prefix);
@@ -119,7 +126,11 @@ impl CharacterDataMethods for CharacterData {
         };
         *self.data.borrow_mut() = new_data;
         self.content_changed();
-        // FIXME: Once we have `Range`, we should implement step 8 to step 11
+        // Steps 8-11.
+        let count = cmp::min(count, length - offset);
+        let arg_length = arg.chars().map(|c| c.len_utf16()).sum::<usize>() as u32;
+        let node = NodeCast::from_ref(self);
+        for_each_live_range(|range| range.data_replaced(node, offset, count, arg_length));
         Ok(())
     }
 
@@ -173,10 +184,20 @@ impl CharacterData {
     }
     #[inline]
     pub fn append_data(&self, data: &str) {
+        self.queue_mutation_record();
         self.data.borrow_mut().push_str(data);
         self.content_changed();
     }
 
+    /// Queues a `characterData` mutation record, with the current data as the
+    /// old value. Must be called before the data is replaced.
+    fn queue_mutation_record(&self) {
+        let old_value = self.data.borrow();
+        MutationObserver::queue_a_mutation_record(NodeCast::from_ref(self), Mutation::CharacterData {
+            old_value: &old_value,
+        });
+    }
+
     fn content_changed(&self) {
         let node = NodeCast::from_ref(self);
         let document = node.owner_doc();
@@ -201,7 +222,7 @@ impl LayoutCharacterDataHelpers for LayoutJS<CharacterData> {
 /// return the corresponding number of UTF-8 bytes.
 ///
 /// s[find_utf16_code_unit_offset(s, o).unwrap()..] == s.to_utf16()[o..].to_utf8()
-fn find_utf16_code_unit_offset(s: &str, offset: u32) -> Option<usize> {
+pub fn find_utf16_code_unit_offset(s: &str, offset: u32) -> Option<usize> {
     let mut code_units = 0;
     for (i, c) in s.char_indices() {
         if code_units == offset {
@@ -38,6 +38,7 @@ impl Comment {
                            document, CommentBinding::Wrap)
     }
 
+    // https://dom.spec.whatwg.org/#dom-comment-comment
     pub fn Constructor(global: GlobalRef, data: DOMString) -> Fallible<Root<Comment>> {
         let document = global.as_window().Document();
         Ok(Comment::new(data, document.r()))
@@ -13,9 +13,13 @@ use js::jsapi::{JSContext, JSObject};
 use js::jsapi::{JS_GetArrayBufferViewType, JS_GetObjectAsArrayBufferView, Type};
 use rand::{OsRng, Rng};
 use std::{ptr, slice};
+use util::str::DOMString;
 
 no_jsmanaged_fields!(OsRng);
 
+/// The largest number of bytes `getRandomValues` fills in a single call.
+pub const MAX_RANDOM_VALUES_LENGTH: usize = 65536;
+
 // https://developer.mozilla.org/en-US/docs/Web/API/Crypto
 #[dom_struct]
 pub struct Crypto {
@@ -46,21 +50,64 @@ impl CryptoMethods for Crypto {
         if unsafe { JS_GetObjectAsArrayBufferView(input, &mut length, &mut data).is_null() } {
             return Err(Error::Type("Argument to Crypto.getRandomValues is not an ArrayBufferView".to_owned()));
         }
-        if !is_integer_buffer(input) {
-            return Err(Error::TypeMismatch);
-        }
-        if length > 65536 {
-            return Err(Error::QuotaExceeded);
-        }
-
-        let mut buffer = unsafe {
+        let buffer = unsafe {
             slice::from_raw_parts_mut(data, length as usize)
         };
 
-        self.rng.borrow_mut().fill_bytes(&mut buffer);
+        try!(fill_random_values(&mut *self.rng.borrow_mut(), is_integer_buffer(input), buffer));
 
         Ok(input)
     }
+
+    // https://w3c.github.io/webcrypto/#Crypto-method-randomUUID
+    fn RandomUUID(&self) -> DOMString {
+        random_uuid(&mut *self.rng.borrow_mut())
+    }
+}
+
+/// Generates a version 4 UUID from 16 bytes of `rng`, in its canonical
+/// lowercase string form.
+// https://w3c.github.io/webcrypto/#dfn-generate-a-random-uuid
+pub fn random_uuid<R: Rng>(rng: &mut R) -> DOMString {
+    // Steps 1-2.
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+
+    // Step 3.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+
+    // Step 4.
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    // Step 5.
+    let mut uuid = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if i == 4 || i == 6 || i == 8 || i == 10 {
+            uuid.push('-');
+        }
+        uuid.push_str(&format!("{:02x}", byte));
+    }
+    uuid
+}
+
+/// Fills `buffer`, the bytes of the array passed to `getRandomValues`, with
+/// random bytes from `rng`.
+// https://w3c.github.io/webcrypto/#Crypto-method-getRandomValues
+pub fn fill_random_values<R: Rng>(rng: &mut R, is_integer_array: bool, buffer: &mut [u8])
+                                  -> Fallible<()> {
+    // Step 1.
+    if !is_integer_array {
+        return Err(Error::TypeMismatch);
+    }
+
+    // Step 2.
+    if buffer.len() > MAX_RANDOM_VALUES_LENGTH {
+        return Err(Error::QuotaExceeded);
+    }
+
+    // Step 3.
+    rng.fill_bytes(buffer);
+    Ok(())
 }
 
 #[allow(unsafe_code)]
@@ -0,0 +1,74 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::CSSStyleSheetBinding;
+use dom::bindings::codegen::Bindings::DocumentBinding::DocumentMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::InheritTypes::NodeCast;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::reflect_dom_object;
+use dom::node::NodeDamage;
+use dom::stylesheet::{StyleSheet, StyleSheetTypeId};
+use dom::window::Window;
+use layout_interface::{LayoutChan, Msg};
+use std::borrow::ToOwned;
+use std::sync::Arc;
+use style::stylesheets::Stylesheet;
+use util::str::DOMString;
+
+// https://drafts.csswg.org/cssom/#the-cssstylesheet-interface
+#[dom_struct]
+pub struct CSSStyleSheet {
+    stylesheet: StyleSheet,
+    window: JS<Window>,
+    #[ignore_heap_size_of = "Arc"]
+    style_stylesheet: Arc<Stylesheet>,
+}
+
+impl CSSStyleSheet {
+    fn new_inherited(window: &Window,
+                     href: Option<DOMString>,
+                     title: Option<DOMString>,
+                     stylesheet: Arc<Stylesheet>) -> CSSStyleSheet {
+        CSSStyleSheet {
+            stylesheet: StyleSheet::new_inherited(StyleSheetTypeId::CSSStyleSheet,
+                                                  "text/css".to_owned(), href, title),
+            window: JS::from_ref(window),
+            style_stylesheet: stylesheet,
+        }
+    }
+
+    pub fn new(window: &Window,
+               href: Option<DOMString>,
+               title: Option<DOMString>,
+               stylesheet: Arc<Stylesheet>) -> Root<CSSStyleSheet> {
+        reflect_dom_object(box CSSStyleSheet::new_inherited(window, href, title, stylesheet),
+                           GlobalRef::Window(window),
+                           CSSStyleSheetBinding::Wrap)
+    }
+
+    pub fn disabled(&self) -> bool {
+        self.style_stylesheet.is_disabled()
+    }
+
+    /// Toggles the disabled flag of the underlying stylesheet and asks layout
+    /// to restyle the document.
+    pub fn set_disabled(&self, disabled: bool) {
+        if self.disabled() == disabled {
+            return;
+        }
+        self.style_stylesheet.set_disabled(disabled);
+
+        let window = self.window.root();
+        let LayoutChan(ref layout_chan) = window.r().layout_chan();
+        layout_chan.send(Msg::StylesheetsChanged).unwrap();
+
+        let document = window.r().Document();
+        if let Some(root) = document.r().GetDocumentElement() {
+            document.r().content_changed(NodeCast::from_ref(root.r()),
+                                         NodeDamage::NodeStyleDamaged);
+        }
+    }
+}
@@ -54,11 +54,13 @@ impl CustomEvent {
     pub fn Constructor(global: GlobalRef,
                        type_: DOMString,
                        init: &CustomEventBinding::CustomEventInit) -> Fallible<Root<CustomEvent>>{
-        Ok(CustomEvent::new(global,
-                            type_,
-                            init.parent.bubbles,
-                            init.parent.cancelable,
-                            HandleValue { ptr: &init.detail }))
+        let event = CustomEvent::new(global,
+                                     type_,
+                                     init.parent.bubbles,
+                                     init.parent.cancelable,
+                                     HandleValue { ptr: &init.detail });
+        EventCast::from_ref(event.r()).set_composed(init.parent.composed);
+        Ok(event)
     }
 }
 
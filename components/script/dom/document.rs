@@ -10,9 +10,11 @@ use dom::bindings::codegen::Bindings::DocumentBinding::{DocumentMethods, Documen
 use dom::bindings::codegen::Bindings::EventBinding::EventMethods;
 use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
 use dom::bindings::codegen::Bindings::EventTargetBinding::EventTargetMethods;
+use dom::bindings::codegen::Bindings::HTMLIFrameElementBinding::HTMLIFrameElementMethods;
 use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
 use dom::bindings::codegen::Bindings::NodeFilterBinding::NodeFilter;
 use dom::bindings::codegen::Bindings::PerformanceBinding::PerformanceMethods;
+use dom::bindings::codegen::Bindings::WheelEventBinding::WheelEventConstants;
 use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
 use dom::bindings::codegen::InheritTypes::ElementDerived;
 use dom::bindings::codegen::InheritTypes::HTMLBaseElementCast;
@@ -26,11 +28,12 @@ use dom::bindings::codegen::InheritTypes::{HTMLFormElementDerived, HTMLImageElem
 use dom::bindings::codegen::InheritTypes::{HTMLScriptElementDerived, HTMLTitleElementDerived};
 use dom::bindings::codegen::UnionTypes::NodeOrString;
 use dom::bindings::error::Error::HierarchyRequest;
-use dom::bindings::error::Error::{InvalidCharacter, NotSupported, Security};
+use dom::bindings::error::Error::{InvalidCharacter, InvalidState, NotSupported, Security};
 use dom::bindings::error::{ErrorResult, Fallible};
 use dom::bindings::global::GlobalRef;
 use dom::bindings::js::RootedReference;
 use dom::bindings::js::{JS, LayoutJS, MutNullableHeap, Root};
+use dom::bindings::num::Finite;
 use dom::bindings::refcounted::Trusted;
 use dom::bindings::trace::RootedVec;
 use dom::bindings::utils::XMLName::InvalidXMLName;
@@ -44,6 +47,7 @@ use dom::domimplementation::DOMImplementation;
 use dom::element::{Element, ElementCreator, ElementTypeId};
 use dom::event::{Event, EventBubbles, EventCancelable};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
+use dom::focusevent::FocusEvent;
 use dom::htmlanchorelement::HTMLAnchorElement;
 use dom::htmlbaseelement::HTMLBaseElement;
 use dom::htmlcollection::{CollectionFilter, HTMLCollection};
@@ -59,12 +63,16 @@ use dom::mouseevent::MouseEvent;
 use dom::node::{self, CloneChildrenFlag, Node, NodeDamage, NodeTypeId, window_from_node};
 use dom::nodeiterator::NodeIterator;
 use dom::nodelist::NodeList;
+use dom::performancetiming::milliseconds_since_epoch;
 use dom::processinginstruction::ProcessingInstruction;
 use dom::range::Range;
+use dom::selection::Selection;
 use dom::servohtmlparser::ServoHTMLParser;
 use dom::text::Text;
 use dom::treewalker::TreeWalker;
 use dom::uievent::UIEvent;
+use dom::urlhelper::UrlHelper;
+use dom::wheelevent::WheelEvent;
 use dom::window::{ReflowReason, Window};
 use euclid::point::Point2D;
 use html5ever::tree_builder::{LimitedQuirks, NoQuirks, Quirks, QuirksMode};
@@ -80,24 +88,26 @@ use msg::constellation_msg::{ALT, CONTROL, SHIFT, SUPER};
 use msg::constellation_msg::{ConstellationChan, FocusType, Key, KeyModifiers, KeyState, MozBrowserEvent, SubpageId};
 use net_traits::ControlMsg::{GetCookiesForUrl, SetCookiesForUrl};
 use net_traits::CookieSource::NonHTTP;
+use net_traits::pub_domains::is_pub_domain;
 use net_traits::{AsyncResponseTarget, Metadata, PendingAsyncLoad};
 use num::ToPrimitive;
-use script_task::Runnable;
+use script_task::{CommonScriptMsg, Runnable, ScriptChan, ScriptTaskEventCategory};
 use script_traits::{MouseButton, UntrustedNodeAddress};
 use std::ascii::AsciiExt;
 use std::borrow::ToOwned;
 use std::boxed::FnBox;
 use std::cell::{Cell, Ref, RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::default::Default;
-use std::iter::FromIterator;
+use std::net::Ipv4Addr;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::mpsc::channel;
 use string_cache::{Atom, QualName};
 use time;
-use url::Url;
+use url::{Host, SchemeData, Url};
+use util::prefs;
 use util::str::{DOMString, split_html_space_chars, str_join};
 
 #[derive(JSTraceable, PartialEq, HeapSizeOf)]
@@ -114,11 +124,13 @@ pub struct Document {
     idmap: DOMRefCell<HashMap<Atom, Vec<JS<Element>>>>,
     implementation: MutNullableHeap<JS<DOMImplementation>>,
     location: MutNullableHeap<JS<Location>>,
+    selection: MutNullableHeap<JS<Selection>>,
     content_type: DOMString,
     last_modified: Option<DOMString>,
     encoding_name: DOMRefCell<DOMString>,
     is_html_document: bool,
-    url: Url,
+    /// https://dom.spec.whatwg.org/#concept-document-url
+    url: DOMRefCell<Url>,
     quirks_mode: Cell<QuirksMode>,
     images: MutNullableHeap<JS<HTMLCollection>>,
     embeds: MutNullableHeap<JS<HTMLCollection>>,
@@ -134,6 +146,20 @@ pub struct Document {
     focused: MutNullableHeap<JS<Element>>,
     /// The script element that is currently executing.
     current_script: MutNullableHeap<JS<HTMLScriptElement>>,
+    /// https://html.spec.whatwg.org/multipage/#list-of-scripts-that-will-execute-when-the-document-has-finished-parsing
+    deferred_scripts: DOMRefCell<Vec<JS<HTMLScriptElement>>>,
+    /// Whether the DOMContentLoaded event has been queued.
+    dom_content_loaded_queued: Cell<bool>,
+    /// The times of this document's load milestones, in milliseconds since the Unix
+    /// epoch, or zero if the milestone hasn't been reached yet.
+    /// https://w3c.github.io/navigation-timing/#sec-navigation-timing-interface
+    dom_loading: Cell<u64>,
+    dom_interactive: Cell<u64>,
+    dom_content_loaded_event_start: Cell<u64>,
+    dom_content_loaded_event_end: Cell<u64>,
+    dom_complete: Cell<u64>,
+    load_event_start: Cell<u64>,
+    load_event_end: Cell<u64>,
     /// https://html.spec.whatwg.org/multipage/#concept-n-noscript
     /// True if scripting is enabled for all scripts in this document
     scripting_enabled: Cell<bool>,
@@ -141,9 +167,10 @@ pub struct Document {
     /// Current identifier of animation frame callback
     animation_frame_ident: Cell<u32>,
     /// https://html.spec.whatwg.org/multipage/#list-of-animation-frame-callbacks
-    /// List of animation frame callbacks
+    /// List of animation frame callbacks, in registration order. Cancelled callbacks
+    /// are set to `None` so that cancelling works while the list is being run.
     #[ignore_heap_size_of = "closures are hard"]
-    animation_frame_list: RefCell<HashMap<u32, Box<FnBox(f64)>>>,
+    animation_frame_list: RefCell<VecDeque<(u32, Option<Box<FnBox(f64)>>)>>,
     /// Tracks all outstanding loads related to this document.
     loader: DOMRefCell<DocumentLoader>,
     /// The current active HTML parser, to allow resuming after interruptions.
@@ -155,6 +182,16 @@ pub struct Document {
     /// This field is set to the document itself for inert documents.
     /// https://html.spec.whatwg.org/multipage/#appropriate-template-contents-owner-document
     appropriate_template_contents_owner_document: MutNullableHeap<JS<Document>>,
+    /// https://html.spec.whatwg.org/multipage/#dom-document-referrer
+    referrer: DOMRefCell<Option<DOMString>>,
+    /// The domain set through `document.domain`, if any.
+    /// https://html.spec.whatwg.org/multipage/#concept-origin-domain
+    domain: DOMRefCell<Option<DOMString>>,
+    /// The iframes with `loading="lazy"` whose initial navigation is waiting
+    /// for them to come near the viewport.
+    lazy_iframes: DOMRefCell<Vec<JS<HTMLIFrameElement>>>,
+    /// https://fullscreen.spec.whatwg.org/#fullscreen-element
+    fullscreen_element: MutNullableHeap<JS<Element>>,
 }
 
 impl PartialEq for Document {
@@ -252,6 +289,17 @@ impl Document {
         self.is_html_document
     }
 
+    /// Whether this is the active document of its window's browsing context,
+    /// unlike documents created by `DOMImplementation` or `DOMParser`.
+    pub fn has_browsing_context(&self) -> bool {
+        let window = self.window.root();
+        let browsing_context = window.r().browsing_context();
+        match *browsing_context {
+            Some(ref context) => context.active_document().r() == self,
+            None => false,
+        }
+    }
+
     // https://html.spec.whatwg.org/multipage/#fully-active
     pub fn is_fully_active(&self) -> bool {
         let window = self.window.root();
@@ -268,8 +316,46 @@ impl Document {
     }
 
     // https://dom.spec.whatwg.org/#concept-document-url
-    pub fn url<'a>(&'a self) -> &'a Url {
-        &self.url
+    pub fn url(&self) -> Ref<Url> {
+        self.url.borrow()
+    }
+
+    /// Changes the URL of this document, as fragment navigations do.
+    pub fn set_url(&self, url: Url) {
+        *self.url.borrow_mut() = url;
+    }
+
+    // https://html.spec.whatwg.org/multipage/#cookie-averse-document-object
+    pub fn is_cookie_averse(&self) -> bool {
+        let window = self.window.root();
+        if window.r().browsing_context().is_none() {
+            return true;
+        }
+        match &*self.url().scheme {
+            "ftp" | "http" | "https" => false,
+            _ => true,
+        }
+    }
+
+    /// Whether this document's origin is opaque, either because its URL has no
+    /// host or because it is in an iframe sandboxed without allow-same-origin.
+    // https://html.spec.whatwg.org/multipage/#concept-origin-opaque
+    pub fn has_opaque_origin(&self) -> bool {
+        if !is_scheme_host_port_tuple(&self.url()) {
+            return true;
+        }
+        let window = self.window.root();
+        let browsing_context = window.r().browsing_context();
+        let frame_element = browsing_context.as_ref().and_then(|context| context.frame_element());
+        frame_element.as_ref()
+                     .and_then(|element| HTMLIFrameElementCast::to_ref(element.r()))
+                     .map_or(false, |iframe| iframe.sandboxes_origin())
+    }
+
+    /// Whether script is currently permitted to read and write cookies for this document.
+    pub fn cookies_enabled(&self) -> bool {
+        is_scheme_host_port_tuple(&self.url()) &&
+            prefs::get_pref("network.cookies.enabled").as_boolean().unwrap_or(true)
     }
 
     // https://html.spec.whatwg.org/multipage/#fallback-base-url
@@ -325,6 +411,58 @@ impl Document {
         *self.encoding_name.borrow_mut() = name;
     }
 
+    pub fn set_referrer(&self, referrer: Option<DOMString>) {
+        *self.referrer.borrow_mut() = referrer;
+    }
+
+    /// Returns the referrer to send when this document initiates a load of `target`, following
+    /// the default `no-referrer-when-downgrade` policy.
+    // https://w3c.github.io/webappsec-referrer-policy/#determine-requests-referrer
+    pub fn referrer_for(&self, target: &Url) -> Option<Url> {
+        match &*self.url().scheme {
+            "http" => {},
+            "https" if target.scheme == "https" => {},
+            _ => return None,
+        }
+
+        // https://w3c.github.io/webappsec-referrer-policy/#strip-url
+        let mut referrer = self.url().clone();
+        if let SchemeData::Relative(ref mut data) = referrer.scheme_data {
+            data.username = "".to_owned();
+            data.password = None;
+        }
+        referrer.fragment = None;
+        Some(referrer)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#concept-origin-effective-domain
+    fn effective_domain(&self) -> Option<DOMString> {
+        if !is_scheme_host_port_tuple(&self.url()) {
+            return None;
+        }
+        if let Some(ref domain) = *self.domain.borrow() {
+            return Some(domain.clone());
+        }
+        self.url().host().map(|host| host.serialize())
+    }
+
+    /// Returns whether scripts in this document may access `other`, taking
+    /// any document.domain set by either of them into account.
+    // https://html.spec.whatwg.org/multipage/#same-origin-domain
+    pub fn is_same_origin_domain(&self, other: &Document) -> bool {
+        let (url, other_url) = (self.url(), other.url());
+        if !is_scheme_host_port_tuple(&url) || !is_scheme_host_port_tuple(&other_url) {
+            return UrlHelper::SameOrigin(&url, &other_url);
+        }
+        match (&*self.domain.borrow(), &*other.domain.borrow()) {
+            (&Some(ref domain), &Some(ref other_domain)) => {
+                url.scheme == other_url.scheme && domain == other_domain
+            },
+            (&None, &None) => UrlHelper::SameOrigin(&url, &other_url),
+            _ => false,
+        }
+    }
+
     pub fn content_changed(&self, node: &Node, damage: NodeDamage) {
         node.dirty(damage);
     }
@@ -480,8 +618,48 @@ impl Document {
         }
     }
 
+    /// Returns the elements under the given point in viewport coordinates,
+    /// topmost first, or an empty list if the point is outside the viewport.
+    // https://drafts.csswg.org/cssom-view/#dom-document-elementsfrompoint
+    fn elements_from_point(&self, x: f64, y: f64) -> Vec<Root<Element>> {
+        let window = self.window.root();
+        let window = window.r();
+
+        // Step 1.
+        if x < 0. || y < 0. ||
+           x > window.InnerWidth() as f64 || y > window.InnerHeight() as f64 {
+            return vec!();
+        }
+
+        // Step 2.
+        let point = Point2D::new((x + window.ScrollX() as f64) as f32,
+                                 (y + window.ScrollY() as f64) as f32);
+        let mut elements: Vec<Root<Element>> = vec!();
+        for node in window.nodes_from_point_query(point) {
+            // Text is hit tested too; report the element it belongs to instead.
+            let element = node.r().inclusive_ancestors().filter_map(ElementCast::to_root).next();
+            if let Some(element) = element {
+                if !elements.iter().any(|e| e.r() == element.r()) {
+                    elements.push(element);
+                }
+            }
+        }
+        elements
+    }
+
     // https://html.spec.whatwg.org/multipage/#current-document-readiness
     pub fn set_ready_state(&self, state: DocumentReadyState) {
+        // readystatechange only fires when the readiness actually changes.
+        if self.ready_state.get() == state {
+            return;
+        }
+
+        let milestone = match state {
+            DocumentReadyState::Loading => &self.dom_loading,
+            DocumentReadyState::Interactive => &self.dom_interactive,
+            DocumentReadyState::Complete => &self.dom_complete,
+        };
+        milestone.set(milliseconds_since_epoch());
         self.ready_state.set(state);
 
         let window = self.window.root();
@@ -492,11 +670,45 @@ impl Document {
         let _ = event.r().fire(target);
     }
 
+    pub fn dom_loading(&self) -> u64 {
+        self.dom_loading.get()
+    }
+
+    pub fn dom_interactive(&self) -> u64 {
+        self.dom_interactive.get()
+    }
+
+    pub fn dom_content_loaded_event_start(&self) -> u64 {
+        self.dom_content_loaded_event_start.get()
+    }
+
+    pub fn dom_content_loaded_event_end(&self) -> u64 {
+        self.dom_content_loaded_event_end.get()
+    }
+
+    pub fn dom_complete(&self) -> u64 {
+        self.dom_complete.get()
+    }
+
+    pub fn load_event_start(&self) -> u64 {
+        self.load_event_start.get()
+    }
+
+    pub fn load_event_end(&self) -> u64 {
+        self.load_event_end.get()
+    }
+
     /// Return whether scripting is enabled or not
     pub fn is_scripting_enabled(&self) -> bool {
         self.scripting_enabled.get()
     }
 
+    /// Disables scripting, e.g. for documents in iframes sandboxed without
+    /// `allow-scripts`.
+    pub fn disable_scripting(&self) {
+        self.scripting_enabled.set(false);
+    }
+
     /// Return the element that currently has focus.
     // https://dvcs.w3.org/hg/dom3events/raw-file/tip/html/DOM3-Events.html#events-focusevent-doc-focus
     pub fn get_focused_element(&self) -> Option<Root<Element>> {
@@ -519,19 +731,34 @@ impl Document {
     /// Reassign the focus context to the element that last requested focus during this
     /// transaction, or none if no elements requested it.
     pub fn commit_focus_transaction(&self, focus_type: FocusType) {
-        //TODO: dispatch blur, focus, focusout, and focusin events
+        if self.focused.get() == self.possibly_focused.get() {
+            return
+        }
 
-        if let Some(ref elem) = self.focused.get().map(|t| t.root()) {
+        let old_focused = self.focused.get().map(|t| t.root());
+        let new_focused = self.possibly_focused.get().map(|t| t.root());
+
+        if let Some(ref elem) = old_focused {
             let node = NodeCast::from_ref(elem.r());
             node.set_focus_state(false);
+            self.focused.set(None);
+
+            // https://html.spec.whatwg.org/multipage/#focus-update-steps
+            let related_target = new_focused.r().map(EventTargetCast::from_ref);
+            self.fire_focus_event("blur", EventBubbles::DoesNotBubble, node, related_target);
+            self.fire_focus_event("focusout", EventBubbles::Bubbles, node, related_target);
         }
 
-        self.focused.set(self.possibly_focused.get());
+        self.focused.set(new_focused.r().map(JS::from_ref));
 
-        if let Some(ref elem) = self.focused.get().map(|t| t.root()) {
+        if let Some(ref elem) = new_focused {
             let node = NodeCast::from_ref(elem.r());
             node.set_focus_state(true);
 
+            let related_target = old_focused.r().map(EventTargetCast::from_ref);
+            self.fire_focus_event("focus", EventBubbles::DoesNotBubble, node, related_target);
+            self.fire_focus_event("focusin", EventBubbles::Bubbles, node, related_target);
+
             // Update the focus state for all elements in the focus chain.
             // https://html.spec.whatwg.org/multipage/#focus-chain
             if focus_type == FocusType::Element {
@@ -543,6 +770,21 @@ impl Document {
         }
     }
 
+    // https://html.spec.whatwg.org/multipage/#fire-a-focus-event
+    fn fire_focus_event(&self, event_type: &str, bubbles: EventBubbles, node: &Node,
+                        related_target: Option<&EventTarget>) {
+        let window = self.window.root();
+        let event = FocusEvent::new(window.r(),
+                                    event_type.to_owned(),
+                                    bubbles,
+                                    EventCancelable::NotCancelable,
+                                    Some(window.r()),
+                                    0,
+                                    related_target);
+        let event = EventCast::from_ref(event.r());
+        event.fire(EventTargetCast::from_ref(node));
+    }
+
     /// Handles any updates when the document's title has changed.
     pub fn title_changed(&self) {
         // https://developer.mozilla.org/en-US/docs/Web/Events/mozbrowsertitlechange
@@ -608,6 +850,11 @@ impl Document {
 
         let window = self.window.root();
 
+        // https://html.spec.whatwg.org/multipage/#activation-triggering-input-event
+        if let MouseEventType::MouseDown = mouse_event_type {
+            window.r().notify_activation();
+        }
+
         // https://dvcs.w3.org/hg/dom3events/raw-file/tip/html/DOM3-Events.html#event-type-click
         let x = point.x as i32;
         let y = point.y as i32;
@@ -641,6 +888,41 @@ impl Document {
         window.r().reflow(ReflowGoal::ForDisplay, ReflowQueryType::NoQuery, ReflowReason::MouseEvent);
     }
 
+    /// The entry point for wheel input, fired at the node under the pointer.
+    pub fn handle_wheel_event(&self, js_runtime: *mut JSRuntime,
+                              delta: Point2D<f32>, point: Point2D<f32>) {
+        let node = match self.hit_test(&point) {
+            Some(node_address) => node::from_untrusted_node_address(js_runtime, node_address),
+            None => return,
+        };
+        let el = match node.r().inclusive_ancestors().filter_map(ElementCast::to_root).next() {
+            Some(el) => el,
+            None => return,
+        };
+        debug!("wheel on {:?}", NodeCast::from_ref(el.r()).debug_str());
+
+        let window = self.window.root();
+
+        // https://w3c.github.io/uievents/#event-type-wheel
+        let x = point.x as i32;
+        let y = point.y as i32;
+        let event = WheelEvent::new(window.r(),
+                                    "wheel".to_owned(),
+                                    EventBubbles::Bubbles,
+                                    EventCancelable::Cancelable,
+                                    Some(window.r()),
+                                    0i32,
+                                    x, y, x, y,
+                                    false, false, false, false,
+                                    0i16,
+                                    None,
+                                    delta.x as f64, delta.y as f64, 0.0,
+                                    WheelEventConstants::DOM_DELTA_PIXEL);
+        let event = EventCast::from_ref(event.r());
+        event.set_trusted(true);
+        event.fire(EventTargetCast::from_ref(el.r()));
+    }
+
     pub fn fire_mouse_event(&self,
                         point: Point2D<f32>,
                         target: &EventTarget,
@@ -757,6 +1039,11 @@ impl Document {
 
         let props = KeyboardEvent::key_properties(key, modifiers);
 
+        // https://html.spec.whatwg.org/multipage/#activation-triggering-input-event
+        if state == KeyState::Pressed && key != Key::Escape {
+            window.r().notify_activation();
+        }
+
         let keyevent = KeyboardEvent::new(window.r(), ev_type, true, true,
                                           Some(window.r()), 0, Some(key),
                                           props.key_string.to_owned(), props.code.to_owned(),
@@ -861,6 +1148,140 @@ impl Document {
         self.current_script.set(script.map(JS::from_ref));
     }
 
+    pub fn add_lazy_iframe(&self, iframe: &HTMLIFrameElement) {
+        let iframe = JS::from_ref(iframe);
+        let mut lazy_iframes = self.lazy_iframes.borrow_mut();
+        if !lazy_iframes.contains(&iframe) {
+            lazy_iframes.push(iframe);
+        }
+    }
+
+    /// Stops waiting for the given iframe to come near the viewport, returning
+    /// whether it was waiting.
+    pub fn remove_lazy_iframe(&self, iframe: &HTMLIFrameElement) -> bool {
+        let iframe = JS::from_ref(iframe);
+        let mut lazy_iframes = self.lazy_iframes.borrow_mut();
+        let len = lazy_iframes.len();
+        lazy_iframes.retain(|lazy_iframe| *lazy_iframe != iframe);
+        lazy_iframes.len() != len
+    }
+
+    /// Starts the initial navigation of the lazy iframes that are now near the
+    /// viewport.
+    // https://html.spec.whatwg.org/multipage/#lazy-load-resumption-steps
+    pub fn process_lazy_iframes(&self) {
+        if self.lazy_iframes.borrow().is_empty() {
+            return;
+        }
+
+        let iframes = self.lazy_iframes.borrow().iter().map(|iframe| iframe.root()).collect::<Vec<_>>();
+        for iframe in iframes {
+            if iframe.r().is_near_viewport() {
+                self.remove_lazy_iframe(iframe.r());
+                iframe.r().process_the_iframe_attributes();
+            }
+        }
+    }
+
+    /// Makes `element` this document's fullscreen element, if it may go
+    /// fullscreen, and queues the `fullscreenchange` or `fullscreenerror`
+    /// event accordingly.
+    // https://fullscreen.spec.whatwg.org/#dom-element-requestfullscreen
+    pub fn request_fullscreen(&self, element: &Element) {
+        let window = self.window.root();
+
+        // Step 4.
+        let node = NodeCast::from_ref(element);
+        let error = *element.namespace() != ns!(HTML) ||
+                    !node.is_in_doc() ||
+                    !self.FullscreenEnabled() ||
+                    (trusted_fullscreen_requests_only() && !window.r().has_transient_activation());
+
+        // Step 5.
+        if !error {
+            window.r().consume_user_activation();
+        }
+
+        // Step 10.
+        if error {
+            self.queue_fullscreen_event(EventTargetCast::from_ref(element), "fullscreenerror");
+            return;
+        }
+
+        // Steps 12-13.
+        if self.fullscreen_element.get().map_or(false, |fullscreen| fullscreen.root().r() == element) {
+            return;
+        }
+        self.fullscreen_element.set(Some(JS::from_ref(element)));
+        self.queue_fullscreen_event(EventTargetCast::from_ref(element), "fullscreenchange");
+    }
+
+    /// Clears this document's fullscreen element, if it has one, and queues
+    /// the `fullscreenchange` event at it, or at this document if it was
+    /// removed from it.
+    // https://fullscreen.spec.whatwg.org/#exit-fullscreen
+    pub fn exit_fullscreen(&self) {
+        let element = match self.fullscreen_element.get() {
+            Some(element) => element.root(),
+            None => return,
+        };
+        self.fullscreen_element.set(None);
+        if NodeCast::from_ref(element.r()).is_in_doc() {
+            self.queue_fullscreen_event(EventTargetCast::from_ref(element.r()), "fullscreenchange");
+        } else {
+            self.queue_fullscreen_event(EventTargetCast::from_ref(self), "fullscreenchange");
+        }
+    }
+
+    /// Queues a task to fire a bubbling event of the given type at `target`.
+    fn queue_fullscreen_event(&self, target: &EventTarget, event_type: &'static str) {
+        let window = self.window.root();
+        let chan = window.r().script_chan();
+        let task = box FullscreenEventTask {
+            document: Trusted::new(window.r().get_cx(), self, chan.clone()),
+            target: Trusted::new(window.r().get_cx(), target, chan.clone()),
+            event_type: event_type,
+        };
+        chan.send(CommonScriptMsg::RunnableMsg(ScriptTaskEventCategory::DocumentEvent, task)).unwrap();
+    }
+
+    pub fn add_deferred_script(&self, script: &HTMLScriptElement) {
+        self.deferred_scripts.borrow_mut().push(JS::from_ref(script));
+    }
+
+    /// Executes the deferred scripts that are ready, in order, once parsing has finished, and
+    /// queues the DOMContentLoaded event after the last one.
+    /// https://html.spec.whatwg.org/multipage/#the-end
+    pub fn process_deferred_scripts(&self) {
+        if self.ready_state.get() != DocumentReadyState::Interactive {
+            return;
+        }
+
+        // Step 3.
+        loop {
+            let script = match self.deferred_scripts.borrow().first() {
+                Some(script) => script.root(),
+                None => break,
+            };
+            if !script.r().is_ready_to_be_parser_executed() {
+                return;
+            }
+            self.deferred_scripts.borrow_mut().remove(0);
+            script.r().execute_pending_load();
+        }
+
+        // Step 4.
+        if self.dom_content_loaded_queued.get() {
+            return;
+        }
+        self.dom_content_loaded_queued.set(true);
+        let window = self.window.root();
+        let chan = window.r().script_chan();
+        let addr: Trusted<Document> = Trusted::new(window.r().get_cx(), self, chan.clone());
+        let handler = box DocumentProgressHandler::new(addr, DocumentProgressTask::DOMContentLoaded);
+        chan.send(CommonScriptMsg::RunnableMsg(ScriptTaskEventCategory::DocumentEvent, handler)).unwrap();
+    }
+
     pub fn trigger_mozbrowser_event(&self, event: MozBrowserEvent) {
         if htmliframeelement::mozbrowser_enabled() {
             let window = self.window.root();
@@ -882,7 +1303,7 @@ impl Document {
         let ident = self.animation_frame_ident.get() + 1;
 
         self.animation_frame_ident.set(ident);
-        self.animation_frame_list.borrow_mut().insert(ident, callback);
+        self.animation_frame_list.borrow_mut().push_back((ident, Some(callback)));
 
         // TODO: Should tick animation only when document is visible
         let ConstellationChan(ref chan) = window.constellation_chan();
@@ -895,8 +1316,14 @@ impl Document {
 
     /// https://html.spec.whatwg.org/multipage/#dom-window-cancelanimationframe
     pub fn cancel_animation_frame(&self, ident: u32) {
-        self.animation_frame_list.borrow_mut().remove(&ident);
-        if self.animation_frame_list.borrow().is_empty() {
+        let no_callbacks_left = {
+            let mut list = self.animation_frame_list.borrow_mut();
+            if let Some(entry) = list.iter_mut().find(|entry| entry.0 == ident) {
+                entry.1 = None;
+            }
+            list.iter().all(|entry| entry.1.is_none())
+        };
+        if no_callbacks_left {
             let window = self.window.root();
             let window = window.r();
             let ConstellationChan(ref chan) = window.constellation_chan();
@@ -908,26 +1335,26 @@ impl Document {
 
     /// https://html.spec.whatwg.org/multipage/#run-the-animation-frame-callbacks
     pub fn run_the_animation_frame_callbacks(&self) {
-        let animation_frame_list;
+        let window = self.window.root();
+        let window = window.r();
         {
-            let mut list = self.animation_frame_list.borrow_mut();
-            animation_frame_list = Vec::from_iter(list.drain());
-
-            let window = self.window.root();
-            let window = window.r();
             let ConstellationChan(ref chan) = window.constellation_chan();
             let event = ConstellationMsg::ChangeRunningAnimationsState(window.pipeline(),
                                                                        AnimationState::NoAnimationCallbacksPresent);
             chan.send(event).unwrap();
         }
-        let window = self.window.root();
-        let window = window.r();
         let performance = window.Performance();
         let performance = performance.r();
         let timing = performance.Now();
 
-        for (_, callback) in animation_frame_list {
-            callback(*timing);
+        // Only the callbacks registered before this point run in this frame; those
+        // added by the callbacks themselves are appended and wait for the next one.
+        let count = self.animation_frame_list.borrow().len();
+        for _ in 0..count {
+            let (_, callback) = self.animation_frame_list.borrow_mut().pop_front().unwrap();
+            if let Some(callback) = callback {
+                callback(*timing);
+            }
         }
 
         window.reflow(ReflowGoal::ForDisplay,
@@ -1023,6 +1450,10 @@ impl Document {
         } else {
             DocumentReadyState::Complete
         };
+        // The document starts out with this readiness without going through
+        // set_ready_state, so record the matching milestone now.
+        let now = milliseconds_since_epoch();
+        let milestone = |state| if state == ready_state { now } else { 0 };
 
         Document {
             node: Node::new_without_doc(NodeTypeId::Document),
@@ -1030,6 +1461,7 @@ impl Document {
             idmap: DOMRefCell::new(HashMap::new()),
             implementation: Default::default(),
             location: Default::default(),
+            selection: Default::default(),
             content_type: match content_type {
                 Some(string) => string,
                 None => match is_html_document {
@@ -1040,7 +1472,7 @@ impl Document {
                 }
             },
             last_modified: last_modified,
-            url: url,
+            url: DOMRefCell::new(url),
             // https://dom.spec.whatwg.org/#concept-document-quirks
             quirks_mode: Cell::new(NoQuirks),
             // https://dom.spec.whatwg.org/#concept-document-encoding
@@ -1057,14 +1489,27 @@ impl Document {
             possibly_focused: Default::default(),
             focused: Default::default(),
             current_script: Default::default(),
+            deferred_scripts: DOMRefCell::new(vec![]),
+            dom_content_loaded_queued: Cell::new(false),
+            dom_loading: Cell::new(milestone(DocumentReadyState::Loading)),
+            dom_interactive: Cell::new(0),
+            dom_content_loaded_event_start: Cell::new(0),
+            dom_content_loaded_event_end: Cell::new(0),
+            dom_complete: Cell::new(milestone(DocumentReadyState::Complete)),
+            load_event_start: Cell::new(0),
+            load_event_end: Cell::new(0),
             scripting_enabled: Cell::new(true),
             animation_frame_ident: Cell::new(0),
-            animation_frame_list: RefCell::new(HashMap::new()),
+            animation_frame_list: RefCell::new(VecDeque::new()),
             loader: DOMRefCell::new(doc_loader),
             current_parser: Default::default(),
             reflow_timeout: Cell::new(None),
             base_element: Default::default(),
             appropriate_template_contents_owner_document: Default::default(),
+            referrer: DOMRefCell::new(None),
+            domain: DOMRefCell::new(None),
+            lazy_iframes: DOMRefCell::new(vec![]),
+            fullscreen_element: Default::default(),
         }
     }
 
@@ -1174,22 +1619,37 @@ impl DocumentMethods for Document {
 
     // https://html.spec.whatwg.org/#dom-document-hasfocus
     fn HasFocus(&self) -> bool {
-        let target = self;                                                        // Step 1.
+        // Step 1.
+        let target = self;
         let window = self.window.root();
-        let window = window.r();
-        let browsing_context = window.browsing_context();
-        let browsing_context = browsing_context.as_ref();
-
-        match browsing_context {
-            Some(browsing_context) => {
-                let condidate = browsing_context.active_document();                        // Step 2.
-                if condidate.node.get_unique_id() == target.node.get_unique_id() {           // Step 3.
-                    true
-                } else {
-                    false //TODO  Step 4.
-                }
+        if window.r().browsing_context().is_none() {
+            return false;
+        }
+
+        // TODO: Step 2, check whether the top-level browsing context has system focus.
+
+        // Step 3.
+        let top = window.r().Top();
+        let mut candidate = top.r().Document();
+
+        // Step 4.
+        loop {
+            // Step 4.1.
+            if candidate.r() == target {
+                return true;
+            }
+
+            // Step 4.2.
+            let nested = candidate.r().get_focused_element().and_then(|focused| {
+                HTMLIFrameElementCast::to_ref(focused.r()).and_then(|iframe| {
+                    iframe.GetContentWindow()
+                }).map(|window| window.r().Document())
+            });
+            match nested {
+                Some(document) => candidate = document,
+                // Step 4.3.
+                None => return false,
             }
-            None => false
         }
     }
 
@@ -1211,9 +1671,14 @@ impl DocumentMethods for Document {
         self.encoding_name.borrow().clone()
     }
 
+    // https://dom.spec.whatwg.org/#dom-document-charset
+    fn Charset(&self) -> DOMString {
+        self.CharacterSet()
+    }
+
     // https://dom.spec.whatwg.org/#dom-document-inputencoding
     fn InputEncoding(&self) -> DOMString {
-        self.encoding_name.borrow().clone()
+        self.CharacterSet()
     }
 
     // https://dom.spec.whatwg.org/#dom-document-content_type
@@ -1286,11 +1751,19 @@ impl DocumentMethods for Document {
 
     // https://dom.spec.whatwg.org/#dom-document-createattribute
     fn CreateAttribute(&self, local_name: DOMString) -> Fallible<Root<Attr>> {
+        // Step 1.
         if xml_name_type(&local_name) == InvalidXMLName {
             debug!("Not a valid element name");
             return Err(InvalidCharacter);
         }
 
+        // Step 2.
+        let local_name = if self.is_html_document {
+            local_name.to_ascii_lowercase()
+        } else {
+            local_name
+        };
+
         let window = self.window.root();
         let name = Atom::from_slice(&local_name);
         // repetition used because string_cache::atom::Atom is non-copyable
@@ -1382,6 +1855,8 @@ impl DocumentMethods for Document {
         match &*interface {
             "uievents" | "uievent" => Ok(EventCast::from_root(
                 UIEvent::new_uninitialized(window.r()))),
+            "focusevent" => Ok(EventCast::from_root(
+                FocusEvent::new_uninitialized(window.r()))),
             "mouseevents" | "mouseevent" => Ok(EventCast::from_root(
                 MouseEvent::new_uninitialized(window.r()))),
             "customevent" => Ok(EventCast::from_root(
@@ -1467,9 +1942,10 @@ impl DocumentMethods for Document {
                     let name = QualName::new(ns!(SVG), atom!("title"));
                     let elem = Element::create(name, None, self,
                                                ElementCreator::ScriptCreated);
-                    NodeCast::from_ref(root.r())
-                             .AppendChild(NodeCast::from_ref(elem.r()))
-                             .unwrap()
+                    let parent = NodeCast::from_ref(root.r());
+                    let child = parent.GetFirstChild();
+                    parent.InsertBefore(NodeCast::from_ref(elem.r()), child.r())
+                          .unwrap()
                 }
             }
         } else if root.r().namespace() == &ns!(HTML) {
@@ -1720,18 +2196,151 @@ impl DocumentMethods for Document {
         self.ready_state.get()
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-document-open
+    fn Open(&self, _type: DOMString, _replace: DOMString) -> Fallible<Root<Document>> {
+        // Step 1.
+        if !self.is_html_document() {
+            return Err(InvalidState);
+        }
+        // Step 5.
+        // Aborting a parser that is still loading the document from the network is
+        // not supported either, so that is treated the same way.
+        if let Some(parser) = self.get_current_parser() {
+            if parser.r().is_executing_script() || !parser.r().is_script_created() {
+                return Ok(Root::from_ref(self));
+            }
+        }
+        // Step 12.
+        Node::replace_all(None, NodeCast::from_ref(self));
+        self.set_quirks_mode(NoQuirks);
+        // Steps 15-16.
+        let parser = ServoHTMLParser::new_script_created(self);
+        self.set_current_parser(Some(parser.r()));
+        // Step 19.
+        self.set_ready_state(DocumentReadyState::Loading);
+        // Step 23.
+        Ok(Root::from_ref(self))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-close
+    fn Close(&self) -> ErrorResult {
+        // Step 1.
+        if !self.is_html_document() {
+            return Err(InvalidState);
+        }
+        // Step 2.
+        let parser = match self.get_current_parser() {
+            Some(parser) => parser,
+            None => return Ok(()),
+        };
+        if !parser.r().is_script_created() {
+            return Ok(());
+        }
+        // Steps 3-4.
+        parser.r().close();
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-write
+    fn Write(&self, text: Vec<DOMString>) -> ErrorResult {
+        // Step 1.
+        if !self.is_html_document() {
+            return Err(InvalidState);
+        }
+        // Implicitly reopening a document that has finished parsing is not supported;
+        // call document.open() first.
+        let parser = match self.get_current_parser() {
+            Some(parser) => parser,
+            None => return Err(InvalidState),
+        };
+        // Step 4.
+        // Scripts the parser isn't executing, such as async ones, have no
+        // insertion point; their writes are ignored.
+        if !parser.r().has_insertion_point() {
+            return Ok(());
+        }
+        // Step 6.
+        parser.r().write(text.concat());
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-writeln
+    fn Writeln(&self, mut text: Vec<DOMString>) -> ErrorResult {
+        text.push("\n".to_owned());
+        self.Write(text)
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-document-defaultview
     fn DefaultView(&self) -> Root<Window> {
         self.window.root()
     }
 
+    // https://w3c.github.io/selection-api/#dom-document-getselection
+    fn GetSelection(&self) -> Option<Root<Selection>> {
+        if !self.has_browsing_context() {
+            return None;
+        }
+        Some(self.selection.or_init(|| Selection::new(self)))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-domain
+    fn Domain(&self) -> DOMString {
+        // Steps 1-3.
+        self.effective_domain().unwrap_or("".to_owned())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-domain
+    fn SetDomain(&self, value: DOMString) -> ErrorResult {
+        // Step 1.
+        if self.window.root().r().browsing_context().is_none() {
+            return Err(Security);
+        }
+
+        // TODO: Step 2, the sandboxed document.domain browsing context flag.
+
+        // Step 3.
+        let effective_domain = match self.effective_domain() {
+            Some(domain) => domain,
+            None => return Err(Security),
+        };
+
+        // Step 4.
+        let host = match Host::parse(&value) {
+            Ok(host) => host.serialize(),
+            Err(_) => return Err(Security),
+        };
+        if !is_registrable_domain_suffix_of_or_equal_to(&host, &effective_domain) {
+            return Err(Security);
+        }
+
+        // Step 5.
+        *self.domain.borrow_mut() = Some(host);
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-document-referrer
+    fn Referrer(&self) -> DOMString {
+        match *self.referrer.borrow() {
+            Some(ref referrer) => referrer.clone(),
+            None => "".to_owned(),
+        }
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-document-cookie
     fn GetCookie(&self) -> Fallible<DOMString> {
-        //TODO: return empty string for cookie-averse Document
-        let url = self.url();
-        if !is_scheme_host_port_tuple(&url) {
+        // Step 1.
+        if self.is_cookie_averse() {
+            return Ok("".to_owned());
+        }
+        // Step 2.
+        if self.has_opaque_origin() {
             return Err(Security);
         }
+        // Step 3.
+        if !self.cookies_enabled() {
+            return Ok("".to_owned());
+        }
+        let url = self.url();
         let window = self.window.root();
         let (tx, rx) = ipc::channel().unwrap();
         let _ = window.r().resource_task().send(GetCookiesForUrl((*url).clone(), tx, NonHTTP));
@@ -1741,11 +2350,21 @@ impl DocumentMethods for Document {
 
     // https://html.spec.whatwg.org/multipage/#dom-document-cookie
     fn SetCookie(&self, cookie: DOMString) -> ErrorResult {
-        //TODO: ignore for cookie-averse Document
-        let url = self.url();
-        if !is_scheme_host_port_tuple(url) {
+        // Step 1.
+        if self.is_cookie_averse() {
+            return Ok(());
+        }
+        // Step 2.
+        if self.has_opaque_origin() {
             return Err(Security);
         }
+        // Step 3.
+        if !self.cookies_enabled() {
+            return Ok(());
+        }
+        // The HttpOnly, Secure, Domain and Path attributes are applied by the
+        // cookie store; HttpOnly cookies are rejected for non-HTTP sources.
+        let url = self.url();
         let window = self.window.root();
         let _ = window.r().resource_task().send(SetCookiesForUrl((*url).clone(), cookie, NonHTTP));
         Ok(())
@@ -1873,12 +2492,77 @@ impl DocumentMethods for Document {
 
     // https://html.spec.whatwg.org/multipage/#handler-onreadystatechange
     event_handler!(readystatechange, GetOnreadystatechange, SetOnreadystatechange);
+
+    // https://fullscreen.spec.whatwg.org/#dom-document-fullscreenenabled
+    fn FullscreenEnabled(&self) -> bool {
+        // Only top-level browsing contexts may go fullscreen until iframes
+        // support the allowfullscreen attribute.
+        self.window.root().r().parent_info().is_none()
+    }
+
+    // https://fullscreen.spec.whatwg.org/#dom-document-fullscreenelement
+    fn GetFullscreenElement(&self) -> Option<Root<Element>> {
+        self.fullscreen_element.get().map(|element| element.root())
+    }
+
+    // https://fullscreen.spec.whatwg.org/#dom-document-exitfullscreen
+    fn ExitFullscreen(&self) {
+        self.exit_fullscreen();
+    }
+
+    // https://fullscreen.spec.whatwg.org/#handler-document-onfullscreenchange
+    event_handler!(fullscreenchange, GetOnfullscreenchange, SetOnfullscreenchange);
+
+    // https://fullscreen.spec.whatwg.org/#handler-document-onfullscreenerror
+    event_handler!(fullscreenerror, GetOnfullscreenerror, SetOnfullscreenerror);
+
+    // https://drafts.csswg.org/cssom-view/#dom-document-elementfrompoint
+    fn ElementFromPoint(&self, x: Finite<f64>, y: Finite<f64>) -> Option<Root<Element>> {
+        self.elements_from_point(*x, *y).into_iter().next()
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-document-elementsfrompoint
+    fn ElementsFromPoint(&self, x: Finite<f64>, y: Finite<f64>) -> Vec<Root<Element>> {
+        self.elements_from_point(*x, *y)
+    }
 }
 
 fn is_scheme_host_port_tuple(url: &Url) -> bool {
     url.host().is_some() && url.port_or_default().is_some()
 }
 
+/// Whether fullscreen requests need transient user activation. Tests turn
+/// this off to exercise granted requests without real input events.
+fn trusted_fullscreen_requests_only() -> bool {
+    prefs::get_pref("dom.fullscreen.trusted-requests-only").as_boolean().unwrap_or(true)
+}
+
+// https://html.spec.whatwg.org/multipage/#is-a-registrable-domain-suffix-of-or-is-equal-to
+pub fn is_registrable_domain_suffix_of_or_equal_to(host_suffix: &str, original_host: &str) -> bool {
+    // Step 1.
+    if host_suffix.is_empty() {
+        return false;
+    }
+
+    // Step 2.
+    if host_suffix == original_host {
+        return true;
+    }
+
+    // Step 3.1.
+    if original_host.parse::<Ipv4Addr>().is_ok() || original_host.starts_with("[") {
+        return false;
+    }
+
+    // Step 3.2.
+    if !original_host.ends_with(&format!(".{}", host_suffix)) {
+        return false;
+    }
+
+    // Step 3.3.
+    !is_pub_domain(host_suffix)
+}
+
 #[derive(HeapSizeOf)]
 pub enum DocumentProgressTask {
     DOMContentLoaded,
@@ -1905,7 +2589,9 @@ impl DocumentProgressHandler {
                                EventBubbles::DoesNotBubble,
                                EventCancelable::NotCancelable);
         let doctarget = EventTargetCast::from_ref(document.r());
+        document.r().dom_content_loaded_event_start.set(milliseconds_since_epoch());
         let _ = doctarget.DispatchEvent(event.r());
+        document.r().dom_content_loaded_event_end.set(milliseconds_since_epoch());
 
         window.r().reflow(ReflowGoal::ForDisplay, ReflowQueryType::NoQuery, ReflowReason::DOMContentLoaded);
     }
@@ -1924,7 +2610,9 @@ impl DocumentProgressHandler {
         let wintarget = EventTargetCast::from_ref(window.r());
         let doctarget = EventTargetCast::from_ref(document.r());
         event.r().set_trusted(true);
+        document.r().load_event_start.set(milliseconds_since_epoch());
         let _ = wintarget.dispatch_event_with_target(doctarget, event.r());
+        document.r().load_event_end.set(milliseconds_since_epoch());
 
         let window_ref = window.r();
         let browsing_context = window_ref.browsing_context();
@@ -1950,6 +2638,25 @@ impl DocumentProgressHandler {
     }
 }
 
+/// A task that fires a `fullscreenchange` or `fullscreenerror` event.
+// https://fullscreen.spec.whatwg.org/#run-the-fullscreen-steps
+struct FullscreenEventTask {
+    document: Trusted<Document>,
+    target: Trusted<EventTarget>,
+    event_type: &'static str,
+}
+
+impl Runnable for FullscreenEventTask {
+    fn handler(self: Box<FullscreenEventTask>) {
+        let document = self.document.root();
+        let window = document.r().window();
+        let event = Event::new(GlobalRef::Window(window.r()), self.event_type.to_owned(),
+                               EventBubbles::Bubbles,
+                               EventCancelable::NotCancelable);
+        event.r().fire(self.target.root().r());
+    }
+}
+
 impl Runnable for DocumentProgressHandler {
     fn handler(self: Box<DocumentProgressHandler>) {
         let document = self.addr.root();
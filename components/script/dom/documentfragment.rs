@@ -28,20 +28,30 @@ pub struct DocumentFragment {
 
 impl DocumentFragmentDerived for EventTarget {
     fn is_documentfragment(&self) -> bool {
-        *self.type_id() == EventTargetTypeId::Node(NodeTypeId::DocumentFragment)
+        match *self.type_id() {
+            EventTargetTypeId::Node(NodeTypeId::DocumentFragment(_)) => true,
+            _ => false
+        }
     }
 }
 
+/// The different types of DocumentFragment.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DocumentFragmentTypeId {
+    DocumentFragment,
+    ShadowRoot,
+}
+
 impl DocumentFragment {
     /// Creates a new DocumentFragment.
-    fn new_inherited(document: &Document) -> DocumentFragment {
+    pub fn new_inherited(type_id: DocumentFragmentTypeId, document: &Document) -> DocumentFragment {
         DocumentFragment {
-            node: Node::new_inherited(NodeTypeId::DocumentFragment, document),
+            node: Node::new_inherited(NodeTypeId::DocumentFragment(type_id), document),
         }
     }
 
     pub fn new(document: &Document) -> Root<DocumentFragment> {
-        Node::reflect_node(box DocumentFragment::new_inherited(document),
+        Node::reflect_node(box DocumentFragment::new_inherited(DocumentFragmentTypeId::DocumentFragment, document),
                            document, DocumentFragmentBinding::Wrap)
     }
 
@@ -12,7 +12,7 @@ use dom::bindings::error::Fallible;
 use dom::bindings::global::GlobalRef;
 use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::validate_qualified_name;
-use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::bindings::utils::{Reflector, namespace_from_domstring, reflect_dom_object};
 use dom::document::DocumentSource;
 use dom::document::{Document, IsHTMLDocument};
 use dom::documenttype::DocumentType;
@@ -65,9 +65,17 @@ impl DOMImplementationMethods for DOMImplementation {
         let win = doc.window();
         let loader = DocumentLoader::new(&*doc.loader());
 
+        // Step 7 (the content type is fixed when the document is created).
+        let content_type = match namespace_from_domstring(namespace.clone()) {
+            ns!(HTML) => "application/xhtml+xml",
+            ns!(SVG) => "image/svg+xml",
+            _ => "application/xml",
+        };
+
         // Step 1.
         let doc = Document::new(win.r(), None, IsHTMLDocument::NonHTMLDocument,
-                                None, None, DocumentSource::NotFromParser, loader);
+                                Some(content_type.to_owned()), None,
+                                DocumentSource::NotFromParser, loader);
         // Step 2-3.
         let maybe_elem = if qname.is_empty() {
             None
@@ -102,7 +110,7 @@ impl DOMImplementationMethods for DOMImplementation {
         // Step 6.
         // FIXME: https://github.com/mozilla/servo/issues/1522
 
-        // Step 7.
+        // Step 8.
         Ok(doc)
     }
 
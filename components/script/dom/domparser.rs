@@ -5,7 +5,8 @@
 use document_loader::DocumentLoader;
 use dom::bindings::codegen::Bindings::DOMParserBinding;
 use dom::bindings::codegen::Bindings::DOMParserBinding::DOMParserMethods;
-use dom::bindings::codegen::Bindings::DOMParserBinding::SupportedType::{Text_html, Text_xml};
+use dom::bindings::codegen::Bindings::DOMParserBinding::SupportedType::{Application_xhtml_xml, Application_xml};
+use dom::bindings::codegen::Bindings::DOMParserBinding::SupportedType::{Image_svg_xml, Text_html, Text_xml};
 use dom::bindings::codegen::Bindings::DocumentBinding::DocumentReadyState;
 use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
 use dom::bindings::error::Fallible;
@@ -16,6 +17,7 @@ use dom::document::DocumentSource;
 use dom::document::{Document, IsHTMLDocument};
 use dom::window::Window;
 use parse::html::{ParseContext, parse_html};
+use parse::xml::parse_xml_into;
 use std::borrow::ToOwned;
 use util::str::DOMString;
 
@@ -67,14 +69,16 @@ impl DOMParserMethods for DOMParser {
                 document.r().set_ready_state(DocumentReadyState::Complete);
                 Ok(document)
             }
-            Text_xml => {
-                //FIXME: this should probably be FromParser when we actually parse the string (#3756).
-                Ok(Document::new(window.r(), Some(url.clone()),
-                                 IsHTMLDocument::NonHTMLDocument,
-                                 Some(content_type),
-                                 None,
-                                 DocumentSource::NotFromParser,
-                                 loader))
+            Text_xml | Application_xml | Application_xhtml_xml | Image_svg_xml => {
+                let document = Document::new(window.r(), Some(url.clone()),
+                                             IsHTMLDocument::NonHTMLDocument,
+                                             Some(content_type),
+                                             None,
+                                             DocumentSource::FromParser,
+                                             loader);
+                parse_xml_into(document.r(), &s);
+                document.r().set_ready_state(DocumentReadyState::Complete);
+                Ok(document)
             }
         }
     }
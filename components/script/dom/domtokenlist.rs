@@ -139,6 +139,16 @@ impl DOMTokenListMethods for DOMTokenList {
         }
     }
 
+    // https://dom.spec.whatwg.org/#dom-domtokenlist-value
+    fn Value(&self) -> DOMString {
+        self.element.root().r().get_string_attribute(&self.local_name)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-domtokenlist-value
+    fn SetValue(&self, value: DOMString) {
+        self.element.root().r().set_tokenlist_attribute(&self.local_name, value);
+    }
+
     // https://dom.spec.whatwg.org/#stringification-behavior
     fn Stringifier(&self) -> DOMString {
         let tokenlist = self.element.root().r().get_tokenlist_attribute(&self.local_name);
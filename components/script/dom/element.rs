@@ -8,28 +8,33 @@ use app_units::Au;
 use cssparser::Color;
 use devtools_traits::AttrInfo;
 use dom::activation::Activatable;
+use dom::animation::Animation;
 use dom::attr::AttrValue;
 use dom::attr::{Attr, AttrHelpersForLayout};
 use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::AnimatableBinding::KeyframeAnimationOptions;
 use dom::bindings::codegen::Bindings::AttrBinding::AttrMethods;
+use dom::bindings::codegen::Bindings::DocumentBinding::DocumentMethods;
 use dom::bindings::codegen::Bindings::ElementBinding;
-use dom::bindings::codegen::Bindings::ElementBinding::ElementMethods;
+use dom::bindings::codegen::Bindings::ElementBinding::{ElementMethods, ShadowRootInit};
 use dom::bindings::codegen::Bindings::EventBinding::EventMethods;
+use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
 use dom::bindings::codegen::Bindings::HTMLInputElementBinding::HTMLInputElementMethods;
 use dom::bindings::codegen::Bindings::HTMLTemplateElementBinding::HTMLTemplateElementMethods;
 use dom::bindings::codegen::Bindings::NamedNodeMapBinding::NamedNodeMapMethods;
 use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
+use dom::bindings::codegen::Bindings::ShadowRootBinding::ShadowRootMode;
 use dom::bindings::codegen::InheritTypes::{CharacterDataCast, DocumentDerived, ElementCast};
 use dom::bindings::codegen::InheritTypes::{ElementDerived, EventTargetCast, HTMLAnchorElementCast};
-use dom::bindings::codegen::InheritTypes::{HTMLBodyElementCast, HTMLFontElementCast};
-use dom::bindings::codegen::InheritTypes::{HTMLIFrameElementCast, HTMLInputElementCast};
+use dom::bindings::codegen::InheritTypes::{HTMLBodyElementCast, HTMLButtonElementCast, HTMLFontElementCast};
+use dom::bindings::codegen::InheritTypes::{HTMLIFrameElementCast, HTMLInputElementCast, HTMLLabelElementCast};
 use dom::bindings::codegen::InheritTypes::{HTMLTableCellElementCast, HTMLTableElementCast};
 use dom::bindings::codegen::InheritTypes::{HTMLTableRowElementCast, HTMLTableSectionElementCast};
 use dom::bindings::codegen::InheritTypes::{HTMLTemplateElementCast, HTMLTextAreaElementCast};
 use dom::bindings::codegen::InheritTypes::{NodeCast, TextCast};
 use dom::bindings::codegen::UnionTypes::NodeOrString;
-use dom::bindings::error::Error::NoModificationAllowed;
-use dom::bindings::error::Error::{InvalidCharacter, Syntax};
+use dom::bindings::error::Error::{NoModificationAllowed, NotSupported};
+use dom::bindings::error::Error::{InUseAttribute, InvalidCharacter, NotFound, Syntax};
 use dom::bindings::error::{ErrorResult, Fallible};
 use dom::bindings::js::{JS, LayoutJS, MutNullableHeap};
 use dom::bindings::js::{Root, RootedReference};
@@ -47,17 +52,17 @@ use dom::htmlelement::HTMLElementTypeId;
 use dom::htmlinputelement::{HTMLInputElement, RawLayoutHTMLInputElementHelpers};
 use dom::htmltableelement::HTMLTableElement;
 use dom::htmltextareaelement::RawLayoutHTMLTextAreaElementHelpers;
+use dom::mutationobserver::{Mutation, MutationObserver};
 use dom::namednodemap::NamedNodeMap;
 use dom::node::{CLICK_IN_PROGRESS, LayoutNodeHelpers, Node, NodeTypeId, SEQUENTIALLY_FOCUSABLE};
 use dom::node::{NodeDamage, document_from_node};
 use dom::node::{window_from_node};
 use dom::nodelist::NodeList;
+use dom::shadowroot::{ShadowRoot, is_valid_shadow_host_name};
 use dom::virtualmethods::{VirtualMethods, vtable_for};
-use html5ever::serialize;
-use html5ever::serialize::SerializeOpts;
-use html5ever::serialize::TraversalScope;
 use html5ever::serialize::TraversalScope::{ChildrenOnly, IncludeNode};
 use html5ever::tree_builder::{LimitedQuirks, NoQuirks, Quirks};
+use js::jsapi::{JSContext, JSObject};
 use selectors::matching::{DeclarationBlock, matches};
 use selectors::parser::parse_author_origin_selector_list_from_str;
 use selectors::parser::{AttrSelector, NamespaceConstraint};
@@ -76,7 +81,7 @@ use style::properties::{PropertyDeclaration, PropertyDeclarationBlock, parse_sty
 use style::values::CSSFloat;
 use style::values::specified::{self, CSSColor, CSSRGBA};
 use url::UrlParser;
-use util::str::{DOMString, LengthOrPercentageOrAuto};
+use util::str::{DOMString, LengthOrPercentageOrAuto, parse_floating_point_number};
 
 #[dom_struct]
 pub struct Element {
@@ -87,8 +92,13 @@ pub struct Element {
     attrs: DOMRefCell<Vec<JS<Attr>>>,
     id_attribute: DOMRefCell<Option<Atom>>,
     style_attribute: DOMRefCell<Option<PropertyDeclarationBlock>>,
+    /// The declarations of the script animations running on this element,
+    /// which apply at the animation origin of the cascade.
+    animation_declarations: DOMRefCell<Option<Arc<Vec<PropertyDeclaration>>>>,
     attr_list: MutNullableHeap<JS<NamedNodeMap>>,
     class_list: MutNullableHeap<JS<DOMTokenList>>,
+    /// https://dom.spec.whatwg.org/#concept-element-shadow-root
+    shadow_root: MutNullableHeap<JS<ShadowRoot>>,
 }
 
 impl ElementDerived for EventTarget {
@@ -140,8 +150,10 @@ impl Element {
             attrs: DOMRefCell::new(vec!()),
             attr_list: Default::default(),
             class_list: Default::default(),
+            shadow_root: Default::default(),
             id_attribute: DOMRefCell::new(None),
             style_attribute: DOMRefCell::new(None),
+            animation_declarations: DOMRefCell::new(None),
         }
     }
 
@@ -484,6 +496,7 @@ pub trait LayoutElementHelpers {
     unsafe fn has_attr_for_layout(&self, namespace: &Namespace, name: &Atom) -> bool;
     fn id_attribute(&self) -> *const Option<Atom>;
     fn style_attribute(&self) -> *const Option<PropertyDeclarationBlock>;
+    fn animation_declarations(&self) -> *const Option<Arc<Vec<PropertyDeclaration>>>;
     fn local_name(&self) -> &Atom;
     fn namespace(&self) -> &Namespace;
     fn get_checked_state_for_layout(&self) -> bool;
@@ -520,6 +533,13 @@ impl LayoutElementHelpers for LayoutJS<Element> {
         }
     }
 
+    #[allow(unsafe_code)]
+    fn animation_declarations(&self) -> *const Option<Arc<Vec<PropertyDeclaration>>> {
+        unsafe {
+            (*self.unsafe_get()).animation_declarations.borrow_for_layout()
+        }
+    }
+
     #[allow(unsafe_code)]
     fn local_name(&self) -> &Atom {
         unsafe {
@@ -623,6 +643,25 @@ impl Element {
         }
     }
 
+    /// Replaces the animated value of `property` with `declarations`, or stops
+    /// animating it if `declarations` is empty.
+    // https://drafts.csswg.org/css-cascade/#cascade-origin
+    pub fn set_animation_declarations(&self, property: &str, declarations: Vec<PropertyDeclaration>) {
+        let mut animation_declarations = self.animation_declarations.borrow_mut();
+        let is_empty = {
+            if animation_declarations.is_none() {
+                *animation_declarations = Some(Arc::new(vec![]));
+            }
+            let animated = Arc::make_mut(animation_declarations.as_mut().unwrap());
+            animated.retain(|declaration| !declaration.matches(property));
+            animated.extend(declarations);
+            animated.is_empty()
+        };
+        if is_empty {
+            *animation_declarations = None;
+        }
+    }
+
     pub fn remove_inline_style_property(&self, property: &str) {
         let mut inline_declarations = self.style_attribute.borrow_mut();
         if let &mut Some(ref mut declarations) = &mut *inline_declarations {
@@ -728,19 +767,7 @@ impl Element {
         })
     }
 
-    pub fn serialize(&self, traversal_scope: TraversalScope) -> Fallible<DOMString> {
-        let node = NodeCast::from_ref(self);
-        let mut writer = vec![];
-        match serialize(&mut writer, &node,
-                        SerializeOpts {
-                            traversal_scope: traversal_scope,
-                            .. Default::default()
-                        }) {
-            Ok(()) => Ok(String::from_utf8(writer).unwrap()),
-            Err(_) => panic!("Cannot serialize element"),
-        }
-    }
-
+    // https://w3c.github.io/DOM-Parsing/#dfn-fragment-serializing-algorithm
     // https://html.spec.whatwg.org/multipage/#root-element
     pub fn get_root_element(&self) -> Root<Element> {
         let node = NodeCast::from_ref(self);
@@ -830,15 +857,75 @@ impl Element {
                               namespace: Namespace,
                               prefix: Option<Atom>) {
         let window = window_from_node(self);
-        let in_empty_ns = namespace == ns!("");
-        let attr = Attr::new(&window, local_name, value, name, namespace, prefix, Some(self));
-        self.attrs.borrow_mut().push(JS::from_rooted(&attr));
-        if in_empty_ns {
+        let attr = Attr::new(&window, local_name, value, name, namespace, prefix, None);
+        self.push_attribute(attr.r());
+    }
+
+    // https://dom.spec.whatwg.org/#concept-element-attributes-append
+    pub fn push_attribute(&self, attr: &Attr) {
+        assert!(attr.owner().is_none());
+        MutationObserver::queue_a_mutation_record(NodeCast::from_ref(self), Mutation::Attribute {
+            name: attr.local_name(),
+            namespace: attr.namespace(),
+            old_value: None,
+        });
+        self.attrs.borrow_mut().push(JS::from_ref(attr));
+        attr.set_owner(Some(self));
+        if attr.namespace() == &ns!("") {
+            vtable_for(NodeCast::from_ref(self)).attribute_mutated(
+                attr, AttributeMutation::Set(None));
+        }
+    }
+
+    // https://dom.spec.whatwg.org/#concept-element-attributes-replace
+    fn replace_attribute(&self, old_attr: &Attr, new_attr: &Attr) {
+        assert!(new_attr.owner().is_none());
+        MutationObserver::queue_a_mutation_record(NodeCast::from_ref(self), Mutation::Attribute {
+            name: old_attr.local_name(),
+            namespace: old_attr.namespace(),
+            old_value: Some(&**old_attr.value()),
+        });
+        {
+            let old = JS::from_ref(old_attr);
+            let mut attrs = self.attrs.borrow_mut();
+            let idx = attrs.iter().position(|attr| *attr == old).unwrap();
+            attrs[idx] = JS::from_ref(new_attr);
+        }
+        old_attr.set_owner(None);
+        new_attr.set_owner(Some(self));
+        if new_attr.namespace() == &ns!("") {
             vtable_for(NodeCast::from_ref(self)).attribute_mutated(
-                &attr, AttributeMutation::Set(None));
+                new_attr, AttributeMutation::Set(Some(&old_attr.value())));
         }
     }
 
+    // https://dom.spec.whatwg.org/#concept-element-attributes-set
+    pub fn set_attribute_node(&self, attr: &Attr) -> Fallible<Option<Root<Attr>>> {
+        // Step 1.
+        if let Some(owner) = attr.owner() {
+            if owner.r() != self {
+                return Err(InUseAttribute);
+            }
+        }
+        // Step 2.
+        let old_attr = self.get_attribute(attr.namespace(), attr.local_name());
+        // Step 3.
+        if old_attr == Some(Root::from_ref(attr)) {
+            return Ok(old_attr);
+        }
+        // The value of a detached attribute was never parsed for this element.
+        let mut value = self.parse_attribute(attr.namespace(), attr.local_name(), attr.Value());
+        attr.swap_value(&mut value);
+        match old_attr {
+            // Step 4.
+            Some(ref old_attr) => self.replace_attribute(old_attr.r(), attr),
+            // Step 5.
+            None => self.push_attribute(attr),
+        }
+        // Step 6.
+        Ok(old_attr)
+    }
+
     pub fn get_attribute(&self, namespace: &Namespace, local_name: &Atom) -> Option<Root<Attr>> {
         self.attrs.borrow().iter().map(JS::root).find(|attr| {
             attr.local_name() == local_name && attr.namespace() == namespace
@@ -879,7 +966,7 @@ impl Element {
 
         self.set_first_matching_attribute(
             name.clone(), value, name.clone(), ns!(""), None,
-            |attr| attr.local_name() == name);
+            |attr| attr.local_name() == name && *attr.namespace() == ns!(""));
     }
 
     // https://html.spec.whatwg.org/multipage/#attr-data-*
@@ -944,9 +1031,14 @@ impl Element {
 
         idx.map(|idx| {
             let attr = (*self.attrs.borrow())[idx].root();
+            let node = NodeCast::from_ref(self);
+            MutationObserver::queue_a_mutation_record(node, Mutation::Attribute {
+                name: attr.local_name(),
+                namespace: attr.namespace(),
+                old_value: Some(&**attr.value()),
+            });
             self.attrs.borrow_mut().remove(idx);
             attr.set_owner(None);
-            let node = NodeCast::from_ref(self);
             if attr.namespace() == &ns!("") {
                 vtable_for(node).attribute_mutated(&attr, AttributeMutation::Removed);
             }
@@ -1060,6 +1152,15 @@ impl Element {
         assert!(&**local_name == local_name.to_ascii_lowercase());
         self.set_attribute(local_name, AttrValue::UInt(value.to_string(), value));
     }
+
+    pub fn get_float_attribute(&self, local_name: &Atom) -> Option<f64> {
+        self.get_attribute(&ns!(""), local_name).and_then(|attr| {
+            parse_floating_point_number(&**attr.r().value())
+        })
+    }
+    pub fn set_float_attribute(&self, local_name: &Atom, value: f64) {
+        self.set_string_attribute(local_name, value.to_string());
+    }
 }
 
 impl ElementMethods for Element {
@@ -1145,6 +1246,29 @@ impl ElementMethods for Element {
                      .map(|attr| attr.r().Value())
     }
 
+    // https://dom.spec.whatwg.org/#dom-element-getattributenode
+    fn GetAttributeNode(&self, name: DOMString) -> Option<Root<Attr>> {
+        self.get_attribute_by_name(name)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-element-getattributenodens
+    fn GetAttributeNodeNS(&self,
+                          namespace: Option<DOMString>,
+                          local_name: DOMString) -> Option<Root<Attr>> {
+        let namespace = &namespace_from_domstring(namespace);
+        self.get_attribute(namespace, &Atom::from_slice(&local_name))
+    }
+
+    // https://dom.spec.whatwg.org/#dom-element-setattributenode
+    fn SetAttributeNode(&self, attr: &Attr) -> Fallible<Option<Root<Attr>>> {
+        self.set_attribute_node(attr)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-element-setattributenodens
+    fn SetAttributeNodeNS(&self, attr: &Attr) -> Fallible<Option<Root<Attr>>> {
+        self.set_attribute_node(attr)
+    }
+
     // https://dom.spec.whatwg.org/#dom-element-setattribute
     fn SetAttribute(&self,
                     name: DOMString,
@@ -1170,10 +1294,14 @@ impl ElementMethods for Element {
                       namespace: Option<DOMString>,
                       qualified_name: DOMString,
                       value: DOMString) -> ErrorResult {
+        // Step 1.
         let (namespace, prefix, local_name) =
             try!(validate_and_extract(namespace, &qualified_name));
         let qualified_name = Atom::from_slice(&qualified_name);
         let value = self.parse_attribute(&namespace, &local_name, value);
+        // Step 2.
+        // An existing attribute keeps its prefix, even if it differs from
+        // the one in `qualified_name`.
         self.set_first_matching_attribute(
             local_name.clone(), value, qualified_name, namespace.clone(), prefix,
             |attr| *attr.local_name() == local_name && *attr.namespace() == namespace);
@@ -1195,6 +1323,13 @@ impl ElementMethods for Element {
         self.remove_attribute(&namespace, &local_name);
     }
 
+    // https://dom.spec.whatwg.org/#dom-element-removeattributenode
+    fn RemoveAttributeNode(&self, attr: &Attr) -> Fallible<Root<Attr>> {
+        // Step 1.
+        self.remove_first_matching_attribute(|a| a as *const Attr == attr as *const Attr)
+            .ok_or(NotFound)
+    }
+
     // https://dom.spec.whatwg.org/#dom-element-hasattribute
     fn HasAttribute(&self, name: DOMString) -> bool {
         self.GetAttribute(name).is_some()
@@ -1226,6 +1361,58 @@ impl ElementMethods for Element {
         HTMLCollection::by_class_name(window.r(), NodeCast::from_ref(self), classes)
     }
 
+    // https://dom.spec.whatwg.org/#dom-element-attachshadow
+    fn AttachShadow(&self, init: &ShadowRootInit) -> Fallible<Root<ShadowRoot>> {
+        // Step 1.
+        if self.namespace != ns!(HTML) {
+            return Err(NotSupported);
+        }
+        // Step 2.
+        if !is_valid_shadow_host_name(&self.local_name) {
+            return Err(NotSupported);
+        }
+        // Step 3.
+        if self.shadow_root.get().is_some() {
+            return Err(NotSupported);
+        }
+        // Steps 4-7.
+        // TODO: layout keeps rendering the host's light tree children. Shadow
+        // trees are not in the document, so style sheets inside them never
+        // apply, rather than applying to the shadow tree only.
+        let doc = document_from_node(self);
+        let shadow_root = ShadowRoot::new(self, init.mode, doc.r());
+        self.shadow_root.set(Some(JS::from_rooted(&shadow_root)));
+        // Step 8.
+        Ok(shadow_root)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-element-shadowroot
+    fn GetShadowRoot(&self) -> Option<Root<ShadowRoot>> {
+        self.shadow_root.get_rooted().and_then(|shadow_root| {
+            match shadow_root.mode() {
+                ShadowRootMode::Open => Some(shadow_root),
+                ShadowRootMode::Closed => None,
+            }
+        })
+    }
+
+    // https://drafts.csswg.org/web-animations/#dom-animatable-animate
+    fn Animate(&self, cx: *mut JSContext, keyframes: *mut JSObject,
+               options: &KeyframeAnimationOptions) -> Fallible<Root<Animation>> {
+        Animation::animate(cx, self, keyframes, options)
+    }
+
+    // https://fullscreen.spec.whatwg.org/#dom-element-requestfullscreen
+    fn RequestFullscreen(&self) {
+        document_from_node(self).r().request_fullscreen(self);
+    }
+
+    // https://fullscreen.spec.whatwg.org/#handler-element-onfullscreenchange
+    event_handler!(fullscreenchange, GetOnfullscreenchange, SetOnfullscreenchange);
+
+    // https://fullscreen.spec.whatwg.org/#handler-element-onfullscreenerror
+    event_handler!(fullscreenerror, GetOnfullscreenerror, SetOnfullscreenerror);
+
     // https://drafts.csswg.org/cssom-view/#dom-element-getclientrects
     fn GetClientRects(&self) -> Root<DOMRectList> {
         let win = window_from_node(self);
@@ -1278,8 +1465,7 @@ impl ElementMethods for Element {
 
     /// https://w3c.github.io/DOM-Parsing/#widl-Element-innerHTML
     fn GetInnerHTML(&self) -> Fallible<DOMString> {
-        //XXX TODO: XML case
-        self.serialize(ChildrenOnly)
+        NodeCast::from_ref(self).serialize(ChildrenOnly)
     }
 
     /// https://w3c.github.io/DOM-Parsing/#widl-Element-innerHTML
@@ -1298,12 +1484,12 @@ impl ElementMethods for Element {
         Ok(())
     }
 
-    // https://dvcs.w3.org/hg/innerhtml/raw-file/tip/index.html#widl-Element-outerHTML
+    // https://w3c.github.io/DOM-Parsing/#widl-Element-outerHTML
     fn GetOuterHTML(&self) -> Fallible<DOMString> {
-        self.serialize(IncludeNode)
+        NodeCast::from_ref(self).serialize(IncludeNode)
     }
 
-    // https://dvcs.w3.org/hg/innerhtml/raw-file/tip/index.html#widl-Element-outerHTML
+    // https://w3c.github.io/DOM-Parsing/#widl-Element-outerHTML
     fn SetOuterHTML(&self, value: DOMString) -> Fallible<()> {
         let context_document = document_from_node(self);
         let context_node = NodeCast::from_ref(self);
@@ -1311,6 +1497,8 @@ impl ElementMethods for Element {
         let context_parent = match context_node.GetParentNode() {
             None => {
                 // Step 2.
+                // There would be no way to reach the parsed nodes, so this is
+                // deliberately not an error.
                 return Ok(());
             },
             Some(parent) => parent,
@@ -1321,7 +1509,7 @@ impl ElementMethods for Element {
             NodeTypeId::Document => return Err(NoModificationAllowed),
 
             // Step 4.
-            NodeTypeId::DocumentFragment => {
+            NodeTypeId::DocumentFragment(_) => {
                 let body_elem = Element::create(QualName::new(ns!(HTML), atom!(body)),
                                                 None, context_document.r(),
                                                 ElementCreator::ScriptCreated);
@@ -1535,10 +1723,24 @@ impl VirtualMethods for Element {
 
         if !tree_in_doc { return; }
 
+        let doc = document_from_node(self);
         if let Some(ref value) = *self.id_attribute.borrow() {
-            let doc = document_from_node(self);
             doc.unregister_named_element(self, value.clone());
         }
+
+        // https://fullscreen.spec.whatwg.org/#removing-steps
+        if doc.r().GetFullscreenElement().r() == Some(self) {
+            doc.r().exit_fullscreen();
+        }
+    }
+
+    fn adopting_steps(&self, old_doc: &Document) {
+        self.super_type().unwrap().adopting_steps(old_doc);
+        // A shadow root follows its host into the new document.
+        if let Some(shadow_root) = self.shadow_root.get_rooted() {
+            let doc = document_from_node(self);
+            Node::adopt(NodeCast::from_ref(shadow_root.r()), doc.r());
+        }
     }
 }
 
@@ -1717,6 +1919,14 @@ impl Element {
                 let element = HTMLAnchorElementCast::to_ref(self).unwrap();
                 Some(element as &'a (Activatable + 'a))
             },
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLButtonElement)) => {
+                let element = HTMLButtonElementCast::to_ref(self).unwrap();
+                Some(element as &'a (Activatable + 'a))
+            },
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLLabelElement)) => {
+                let element = HTMLLabelElementCast::to_ref(self).unwrap();
+                Some(element as &'a (Activatable + 'a))
+            },
             _ => {
                 None
             }
@@ -6,10 +6,12 @@ use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::EventBinding;
 use dom::bindings::codegen::Bindings::EventBinding::{EventConstants, EventMethods};
 use dom::bindings::error::Fallible;
-use dom::bindings::global::GlobalRef;
+use dom::bindings::global::{GlobalRef, global_object_for_reflector};
 use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::bindings::num::Finite;
 use dom::bindings::utils::{Reflector, reflect_dom_object};
 use dom::eventtarget::EventTarget;
+use dom::performance::DOMHighResTimeStamp;
 use dom::uievent::{UIEventTypeId};
 use std::borrow::ToOwned;
 use std::cell::Cell;
@@ -36,7 +38,8 @@ pub enum EventTypeId {
     StorageEvent,
     UIEvent(UIEventTypeId),
     ErrorEvent,
-    CloseEvent
+    CloseEvent,
+    PopStateEvent
 }
 
 #[derive(PartialEq, HeapSizeOf)]
@@ -57,6 +60,9 @@ pub struct Event {
     type_id: EventTypeId,
     current_target: MutNullableHeap<JS<EventTarget>>,
     target: MutNullableHeap<JS<EventTarget>>,
+    /// The targets the event is being dispatched along, starting with the target.
+    /// Only non-empty during dispatch.
+    path: DOMRefCell<Vec<JS<EventTarget>>>,
     type_: DOMRefCell<DOMString>,
     phase: Cell<EventPhase>,
     canceled: Cell<bool>,
@@ -64,10 +70,14 @@ pub struct Event {
     stop_immediate: Cell<bool>,
     cancelable: Cell<bool>,
     bubbles: Cell<bool>,
+    composed: Cell<bool>,
     trusted: Cell<bool>,
     dispatching: Cell<bool>,
+    /// Whether the listener currently being invoked was added as passive.
+    in_passive_listener: Cell<bool>,
     initialized: Cell<bool>,
-    timestamp: u64,
+    /// The value of `time::precise_time_ns()` when this event was created.
+    timestamp: f64,
 }
 
 impl Event {
@@ -77,17 +87,20 @@ impl Event {
             type_id: type_id,
             current_target: Default::default(),
             target: Default::default(),
+            path: DOMRefCell::new(vec![]),
             phase: Cell::new(EventPhase::None),
             type_: DOMRefCell::new("".to_owned()),
             canceled: Cell::new(false),
             cancelable: Cell::new(false),
             bubbles: Cell::new(false),
+            composed: Cell::new(false),
             trusted: Cell::new(false),
             dispatching: Cell::new(false),
+            in_passive_listener: Cell::new(false),
             stop_propagation: Cell::new(false),
             stop_immediate: Cell::new(false),
             initialized: Cell::new(false),
-            timestamp: time::get_time().sec as u64,
+            timestamp: time::precise_time_ns() as f64,
         }
     }
 
@@ -111,7 +124,9 @@ impl Event {
                        init: &EventBinding::EventInit) -> Fallible<Root<Event>> {
         let bubbles = if init.bubbles { EventBubbles::Bubbles } else { EventBubbles::DoesNotBubble };
         let cancelable = if init.cancelable { EventCancelable::Cancelable } else { EventCancelable::NotCancelable };
-        Ok(Event::new(global, type_, bubbles, cancelable))
+        let event = Event::new(global, type_, bubbles, cancelable);
+        event.r().set_composed(init.composed);
+        Ok(event)
     }
 
     #[inline]
@@ -134,6 +149,14 @@ impl Event {
         self.target.set(Some(JS::from_ref(val)));
     }
 
+    pub fn set_path(&self, path: &[&EventTarget]) {
+        *self.path.borrow_mut() = path.iter().map(|target| JS::from_ref(*target)).collect();
+    }
+
+    pub fn clear_path(&self) {
+        self.path.borrow_mut().clear();
+    }
+
     #[inline]
     pub fn phase(&self) -> EventPhase {
         self.phase.get()
@@ -154,11 +177,22 @@ impl Event {
         self.stop_immediate.get()
     }
 
+    #[inline]
+    pub fn clear_propagation_flags(&self) {
+        self.stop_propagation.set(false);
+        self.stop_immediate.set(false);
+    }
+
     #[inline]
     pub fn bubbles(&self) -> bool {
         self.bubbles.get()
     }
 
+    #[inline]
+    pub fn set_composed(&self, composed: bool) {
+        self.composed.set(composed)
+    }
+
     #[inline]
     pub fn dispatching(&self) -> bool {
         self.dispatching.get()
@@ -169,6 +203,11 @@ impl Event {
         self.dispatching.set(val)
     }
 
+    #[inline]
+    pub fn set_in_passive_listener(&self, val: bool) {
+        self.in_passive_listener.set(val)
+    }
+
     #[inline]
     pub fn initialized(&self) -> bool {
         self.initialized.get()
@@ -196,6 +235,11 @@ impl EventMethods for Event {
         self.current_target.get().map(Root::from_rooted)
     }
 
+    // https://dom.spec.whatwg.org/#dom-event-composedpath
+    fn ComposedPath(&self) -> Vec<Root<EventTarget>> {
+        self.path.borrow().iter().map(|target| target.root()).collect()
+    }
+
     // https://dom.spec.whatwg.org/#dom-event-defaultprevented
     fn DefaultPrevented(&self) -> bool {
         self.canceled.get()
@@ -203,7 +247,7 @@ impl EventMethods for Event {
 
     // https://dom.spec.whatwg.org/#dom-event-preventdefault
     fn PreventDefault(&self) {
-        if self.cancelable.get() {
+        if self.cancelable.get() && !self.in_passive_listener.get() {
             self.canceled.set(true)
         }
     }
@@ -229,9 +273,15 @@ impl EventMethods for Event {
         self.cancelable.get()
     }
 
+    // https://dom.spec.whatwg.org/#dom-event-composed
+    fn Composed(&self) -> bool {
+        self.composed.get()
+    }
+
     // https://dom.spec.whatwg.org/#dom-event-timestamp
-    fn TimeStamp(&self) -> u64 {
-        self.timestamp
+    fn TimeStamp(&self) -> DOMHighResTimeStamp {
+        let global = global_object_for_reflector(self);
+        Finite::wrap((self.timestamp - global.r().time_origin()) / 1000000.0)
     }
 
     // https://dom.spec.whatwg.org/#dom-event-initevent
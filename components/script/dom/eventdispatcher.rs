@@ -10,8 +10,8 @@ use dom::bindings::global::{GlobalRoot, global_object_for_reflector};
 use dom::bindings::js::{JS, Root, RootedReference};
 use dom::bindings::trace::RootedVec;
 use dom::event::{Event, EventPhase};
-use dom::eventtarget::{EventListenerType, EventTarget, ListenerPhase};
-use dom::node::Node;
+use dom::eventtarget::{EventListenerEntry, EventTarget, ListenerPhase};
+use dom::node::{Node, document_from_node};
 use dom::virtualmethods::vtable_for;
 use dom::window::Window;
 
@@ -35,14 +35,55 @@ impl Drop for AutoDOMEventMarker {
     }
 }
 
-fn handle_event(window: Option<&Window>, listener: &EventListenerType,
+// https://dom.spec.whatwg.org/#concept-event-listener-inner-invoke
+fn handle_event(window: Option<&Window>, listener: &EventListenerEntry,
                 current_target: &EventTarget, event: &Event) {
     let _marker;
     if let Some(window) = window {
         _marker = AutoDOMEventMarker::new(window);
     }
 
-    listener.call_or_handle_event(current_target, event, Report);
+    if listener.once() {
+        current_target.remove_listener(&event.Type(), listener);
+    }
+
+    event.set_in_passive_listener(listener.passive());
+    listener.listener().call_or_handle_event(current_target, event, Report);
+    event.set_in_passive_listener(false);
+}
+
+// https://dom.spec.whatwg.org/#concept-event-listener-invoke
+// Returns false if the propagation of the event has been stopped.
+fn invoke(window: Option<&Window>, event: &Event, cur_target: &EventTarget,
+          phase: ListenerPhase) -> bool {
+    if event.stop_propagation() {
+        return false;
+    }
+
+    event.set_current_target(cur_target);
+
+    // The listeners are cloned, so that listeners added by one of them are
+    // not invoked during this dispatch.
+    let type_ = event.Type();
+    let listeners = match cur_target.get_listeners_for(&type_, phase) {
+        Some(listeners) => listeners,
+        None => return true,
+    };
+
+    for listener in &listeners {
+        // Listeners removed by a previous listener are not invoked.
+        if !cur_target.has_listener(&type_, listener) {
+            continue;
+        }
+
+        handle_event(window, listener, cur_target, event);
+
+        if event.stop_immediate() {
+            return false;
+        }
+    }
+
+    !event.stop_propagation()
 }
 
 fn dispatch_to_listeners(event: &Event, target: &EventTarget, chain: &[&EventTarget]) {
@@ -60,70 +101,32 @@ fn dispatch_to_listeners(event: &Event, target: &EventTarget, chain: &[&EventTar
         _ => None,
     };
 
-    let type_ = event.Type();
-
-    /* capturing */
+    /* capturing, from the root down to the target's parent */
     event.set_phase(EventPhase::Capturing);
     for cur_target in chain.iter().rev() {
-        if let Some(listeners) = cur_target.get_listeners_for(&type_, ListenerPhase::Capturing) {
-            event.set_current_target(cur_target);
-            for listener in &listeners {
-                handle_event(window.r(), listener, *cur_target, event);
-
-                if event.stop_immediate() {
-                    return;
-                }
-            }
-
-            if event.stop_propagation() {
-                return;
-            }
+        if !invoke(window.r(), event, *cur_target, ListenerPhase::Capturing) {
+            return;
         }
     }
 
-    assert!(!event.stop_propagation());
-    assert!(!event.stop_immediate());
-
-    /* at target */
+    /* at target, capturing listeners first */
     event.set_phase(EventPhase::AtTarget);
-    event.set_current_target(target);
-
-    if let Some(listeners) = target.get_listeners(&type_) {
-        for listener in listeners {
-            handle_event(window.r(), &listener, target, event);
-
-            if event.stop_immediate() {
-                return;
-            }
-        }
-        if event.stop_propagation() {
-            return;
-        }
+    if !invoke(window.r(), event, target, ListenerPhase::Capturing) {
+        return;
+    }
+    if !invoke(window.r(), event, target, ListenerPhase::Bubbling) {
+        return;
     }
 
-    assert!(!event.stop_propagation());
-    assert!(!event.stop_immediate());
-
-    /* bubbling */
+    /* bubbling, from the target's parent up to the root */
     if !event.bubbles() {
         return;
     }
 
     event.set_phase(EventPhase::Bubbling);
     for cur_target in chain {
-        if let Some(listeners) = cur_target.get_listeners_for(&type_, ListenerPhase::Bubbling) {
-            event.set_current_target(cur_target);
-            for listener in &listeners {
-                handle_event(window.r(), listener, *cur_target, event);
-
-                if event.stop_immediate() {
-                    return;
-                }
-            }
-
-            if event.stop_propagation() {
-                return;
-            }
+        if !invoke(window.r(), event, *cur_target, ListenerPhase::Bubbling) {
+            return;
         }
     }
 }
@@ -153,6 +156,24 @@ pub fn dispatch_event(target: &EventTarget, pseudo_target: Option<&EventTarget>,
             let ancestor_target = EventTargetCast::from_ref(ancestor.r());
             chain.push(JS::from_ref(ancestor_target))
         }
+        // The parent of a document in the event path is its window, unless the
+        // document has no browsing context or the event is a `load` event. The
+        // exception keeps the `load` events of images, scripts and other
+        // subresources from reaching the window's `onload`.
+        // https://html.spec.whatwg.org/multipage/#events-and-the-window-object
+        let document = document_from_node(target_node);
+        let root = target_node.inclusive_ancestors().last().unwrap();
+        if root.r() == NodeCast::from_ref(document.r()) &&
+           document.r().has_browsing_context() && event.Type() != "load" {
+            let window = document.r().window();
+            chain.push(JS::from_ref(EventTargetCast::from_ref(window.r())));
+        }
+    }
+
+    {
+        let mut path = vec![target];
+        path.extend(chain.r().iter().cloned());
+        event.set_path(&path);
     }
 
     dispatch_to_listeners(event, target, chain.r());
@@ -176,6 +197,8 @@ pub fn dispatch_event(target: &EventTarget, pseudo_target: Option<&EventTarget>,
     event.set_dispatching(false);
     event.set_phase(EventPhase::None);
     event.clear_current_target();
+    event.clear_path();
+    event.clear_propagation_flags();
 
     !event.DefaultPrevented()
 }
@@ -4,9 +4,13 @@
 
 use dom::bindings::callback::{CallbackContainer, ExceptionHandling};
 use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::AbortSignalBinding::AbortSignalMethods;
 use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
 use dom::bindings::codegen::Bindings::EventListenerBinding::EventListener;
 use dom::bindings::codegen::Bindings::EventTargetBinding::EventTargetMethods;
+use dom::bindings::codegen::InheritTypes::MediaQueryListCast;
+use dom::bindings::codegen::UnionTypes::AddEventListenerOptionsOrBoolean;
+use dom::bindings::codegen::UnionTypes::EventListenerOptionsOrBoolean;
 use dom::bindings::conversions::get_dom_class;
 use dom::bindings::error::Error::InvalidState;
 use dom::bindings::error::{Fallible, report_pending_exception};
@@ -45,11 +49,14 @@ pub enum ListenerPhase {
 
 #[derive(Copy, Clone)]
 pub enum EventTargetTypeId {
+    AbortSignal,
+    Animation,
     Node(NodeTypeId),
     WebSocket,
     Window,
     Worker,
     FileReader,
+    MediaQueryList,
     WorkerGlobalScope(WorkerGlobalScopeTypeId),
     XMLHttpRequestEventTarget(XMLHttpRequestEventTargetTypeId)
 }
@@ -119,11 +126,41 @@ impl EventListenerType {
     }
 }
 
-#[derive(JSTraceable, Clone, PartialEq, HeapSizeOf)]
+impl ListenerPhase {
+    fn from_capture(capture: bool) -> ListenerPhase {
+        if capture { ListenerPhase::Capturing } else { ListenerPhase::Bubbling }
+    }
+}
+
+#[derive(JSTraceable, Clone, HeapSizeOf)]
 #[privatize]
 pub struct EventListenerEntry {
     phase: ListenerPhase,
-    listener: EventListenerType
+    listener: EventListenerType,
+    once: bool,
+    passive: bool,
+}
+
+// https://dom.spec.whatwg.org/#concept-event-listener
+// Listeners are identified by their callback and capture flag only.
+impl PartialEq for EventListenerEntry {
+    fn eq(&self, other: &EventListenerEntry) -> bool {
+        self.phase == other.phase && self.listener == other.listener
+    }
+}
+
+impl EventListenerEntry {
+    pub fn listener(&self) -> &EventListenerType {
+        &self.listener
+    }
+
+    pub fn once(&self) -> bool {
+        self.once
+    }
+
+    pub fn passive(&self) -> bool {
+        self.passive
+    }
 }
 
 #[dom_struct]
@@ -140,20 +177,43 @@ impl EventTarget {
         }
     }
 
-    pub fn get_listeners(&self, type_: &str) -> Option<Vec<EventListenerType>> {
-        self.handlers.borrow().get(type_).map(|listeners| {
-            listeners.iter().map(|entry| entry.listener.clone()).collect()
-        })
+    pub fn get_listeners(&self, type_: &str) -> Option<Vec<EventListenerEntry>> {
+        self.handlers.borrow().get(type_).map(|listeners| listeners.clone())
     }
 
     pub fn get_listeners_for(&self, type_: &str, desired_phase: ListenerPhase)
-        -> Option<Vec<EventListenerType>> {
+        -> Option<Vec<EventListenerEntry>> {
         self.handlers.borrow().get(type_).map(|listeners| {
             let filtered = listeners.iter().filter(|entry| entry.phase == desired_phase);
-            filtered.map(|entry| entry.listener.clone()).collect()
+            filtered.cloned().collect()
         })
     }
 
+    pub fn has_listener(&self, type_: &str, entry: &EventListenerEntry) -> bool {
+        self.handlers.borrow().get(type_).map_or(false, |entries| entries.contains(entry))
+    }
+
+    pub fn remove_listener(&self, type_: &str, entry: &EventListenerEntry) {
+        {
+            let mut handlers = self.handlers.borrow_mut();
+            if let Some(entries) = handlers.get_mut(type_) {
+                if let Some(position) = entries.iter().position(|e| e == entry) {
+                    entries.remove(position);
+                }
+            }
+        }
+        self.listeners_changed(type_);
+    }
+
+    /// Called whenever a listener for `type_` is added or removed.
+    fn listeners_changed(&self, type_: &str) {
+        if let Some(media_query_list) = MediaQueryListCast::to_ref(self) {
+            if type_ == "change" {
+                media_query_list.change_listeners_changed();
+            }
+        }
+    }
+
     #[allow(unsafe_code)]
     pub fn type_id(&self) -> &EventTargetTypeId {
         let domclass = unsafe {
@@ -175,8 +235,15 @@ impl EventTarget {
     pub fn set_inline_event_listener(&self,
                                  ty: DOMString,
                                  listener: Option<Rc<EventHandler>>) {
+        self.set_inline_event_listener_entry(&ty, listener);
+        self.listeners_changed(&ty);
+    }
+
+    fn set_inline_event_listener_entry(&self,
+                                       ty: &DOMString,
+                                       listener: Option<Rc<EventHandler>>) {
         let mut handlers = self.handlers.borrow_mut();
-        let entries = match handlers.entry(ty) {
+        let entries = match handlers.entry(ty.clone()) {
             Occupied(entry) => entry.into_mut(),
             Vacant(entry) => entry.insert(vec!()),
         };
@@ -202,6 +269,8 @@ impl EventTarget {
                     entries.push(EventListenerEntry {
                         phase: ListenerPhase::Bubbling,
                         listener: EventListenerType::Inline(listener.unwrap()),
+                        once: false,
+                        passive: false,
                     });
                 }
             }
@@ -284,42 +353,60 @@ impl EventTargetMethods for EventTarget {
     fn AddEventListener(&self,
                         ty: DOMString,
                         listener: Option<Rc<EventListener>>,
-                        capture: bool) {
+                        options: AddEventListenerOptionsOrBoolean) {
+        let (capture, once, passive, signal) = match options {
+            AddEventListenerOptionsOrBoolean::eAddEventListenerOptions(options) =>
+                (options.parent.capture, options.once, options.passive, options.signal),
+            AddEventListenerOptionsOrBoolean::eBoolean(capture) => (capture, false, false, None),
+        };
+        if signal.as_ref().map_or(false, |signal| signal.r().Aborted()) {
+            return;
+        }
         match listener {
             Some(listener) => {
                 let mut handlers = self.handlers.borrow_mut();
-                let entry = match handlers.entry(ty) {
+                let entry = match handlers.entry(ty.clone()) {
                     Occupied(entry) => entry.into_mut(),
                     Vacant(entry) => entry.insert(vec!()),
                 };
 
-                let phase = if capture { ListenerPhase::Capturing } else { ListenerPhase::Bubbling };
                 let new_entry = EventListenerEntry {
-                    phase: phase,
-                    listener: EventListenerType::Additive(listener)
+                    phase: ListenerPhase::from_capture(capture),
+                    listener: EventListenerType::Additive(listener),
+                    once: once,
+                    passive: passive,
                 };
                 if !entry.contains(&new_entry) {
+                    if let Some(ref signal) = signal {
+                        signal.r().add_listener(self, ty.clone(), new_entry.clone());
+                    }
                     entry.push(new_entry);
                 }
             },
             _ => (),
         }
+        self.listeners_changed(&ty);
     }
 
     // https://dom.spec.whatwg.org/#dom-eventtarget-removeeventlistener
     fn RemoveEventListener(&self,
                            ty: DOMString,
                            listener: Option<Rc<EventListener>>,
-                           capture: bool) {
+                           options: EventListenerOptionsOrBoolean) {
+        let capture = match options {
+            EventListenerOptionsOrBoolean::eEventListenerOptions(options) => options.capture,
+            EventListenerOptionsOrBoolean::eBoolean(capture) => capture,
+        };
         match listener {
             Some(ref listener) => {
                 let mut handlers = self.handlers.borrow_mut();
                 let entry = handlers.get_mut(&ty);
                 for entry in entry {
-                    let phase = if capture { ListenerPhase::Capturing } else { ListenerPhase::Bubbling };
                     let old_entry = EventListenerEntry {
-                        phase: phase,
-                        listener: EventListenerType::Additive(listener.clone())
+                        phase: ListenerPhase::from_capture(capture),
+                        listener: EventListenerType::Additive(listener.clone()),
+                        once: false,
+                        passive: false,
                     };
                     if let Some(position) = entry.iter().position(|e| *e == old_entry) {
                         entry.remove(position);
@@ -328,6 +415,7 @@ impl EventTargetMethods for EventTarget {
             },
             _ => (),
         }
+        self.listeners_changed(&ty);
     }
 
     // https://dom.spec.whatwg.org/#dom-eventtarget-dispatchevent
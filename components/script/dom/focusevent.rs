@@ -0,0 +1,91 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::FocusEventBinding;
+use dom::bindings::codegen::Bindings::FocusEventBinding::FocusEventMethods;
+use dom::bindings::codegen::Bindings::UIEventBinding::UIEventMethods;
+use dom::bindings::codegen::InheritTypes::{FocusEventDerived, UIEventCast};
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, MutNullableHeap, Root, RootedReference};
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
+use dom::eventtarget::EventTarget;
+use dom::uievent::{UIEvent, UIEventTypeId};
+use dom::window::Window;
+use std::default::Default;
+use util::str::DOMString;
+
+// https://w3c.github.io/uievents/#interface-FocusEvent
+#[dom_struct]
+pub struct FocusEvent {
+    uievent: UIEvent,
+    related_target: MutNullableHeap<JS<EventTarget>>,
+}
+
+impl FocusEventDerived for Event {
+    fn is_focusevent(&self) -> bool {
+        *self.type_id() == EventTypeId::UIEvent(UIEventTypeId::FocusEvent)
+    }
+}
+
+impl FocusEvent {
+    fn new_inherited() -> FocusEvent {
+        FocusEvent {
+            uievent: UIEvent::new_inherited(UIEventTypeId::FocusEvent),
+            related_target: Default::default(),
+        }
+    }
+
+    pub fn new_uninitialized(window: &Window) -> Root<FocusEvent> {
+        reflect_dom_object(box FocusEvent::new_inherited(),
+                           GlobalRef::Window(window),
+                           FocusEventBinding::Wrap)
+    }
+
+    pub fn new(window: &Window,
+               type_: DOMString,
+               can_bubble: EventBubbles,
+               cancelable: EventCancelable,
+               view: Option<&Window>,
+               detail: i32,
+               related_target: Option<&EventTarget>) -> Root<FocusEvent> {
+        let ev = FocusEvent::new_uninitialized(window);
+        {
+            let ev = ev.r();
+            let uievent = UIEventCast::from_ref(ev);
+            uievent.InitUIEvent(type_, can_bubble == EventBubbles::Bubbles,
+                                cancelable == EventCancelable::Cancelable, view, detail);
+            ev.related_target.set(related_target.map(JS::from_ref));
+        }
+        ev
+    }
+
+    pub fn Constructor(global: GlobalRef,
+                       type_: DOMString,
+                       init: &FocusEventBinding::FocusEventInit) -> Fallible<Root<FocusEvent>> {
+        let bubbles = if init.parent.parent.bubbles {
+            EventBubbles::Bubbles
+        } else {
+            EventBubbles::DoesNotBubble
+        };
+        let cancelable = if init.parent.parent.cancelable {
+            EventCancelable::Cancelable
+        } else {
+            EventCancelable::NotCancelable
+        };
+        let event = FocusEvent::new(global.as_window(), type_,
+                                    bubbles, cancelable,
+                                    init.parent.view.r(), init.parent.detail,
+                                    init.relatedTarget.r());
+        Ok(event)
+    }
+}
+
+impl FocusEventMethods for FocusEvent {
+    // https://w3c.github.io/uievents/#widl-FocusEvent-relatedTarget
+    fn GetRelatedTarget(&self) -> Option<Root<EventTarget>> {
+        self.related_target.get().map(Root::from_rooted)
+    }
+}
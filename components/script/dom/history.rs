@@ -0,0 +1,300 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::HistoryBinding;
+use dom::bindings::codegen::Bindings::HistoryBinding::HistoryMethods;
+use dom::bindings::codegen::Bindings::LocationBinding::LocationMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::InheritTypes::{EventCast, EventTargetCast};
+use dom::bindings::error::Error::Security;
+use dom::bindings::error::ErrorResult;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, MutHeapJSVal, Root};
+use dom::bindings::refcounted::Trusted;
+use dom::bindings::structuredclone::StructuredCloneData;
+use dom::bindings::utils::{Reflectable, Reflector, reflect_dom_object};
+use dom::event::{EventBubbles, EventCancelable};
+use dom::popstateevent::PopStateEvent;
+use dom::window::Window;
+use ipc_channel::ipc;
+use js::jsapi::{HandleValue, JSAutoCompartment, JSAutoRequest, JSContext, RootedValue};
+use js::jsval::{JSVal, NullValue};
+use msg::constellation_msg::ConstellationChan;
+use msg::constellation_msg::Msg as ConstellationMsg;
+use msg::constellation_msg::NavigationDirection;
+use script_task::ScriptTaskEventCategory::DomEvent;
+use script_task::{CommonScriptMsg, Runnable, ScriptChan};
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use url::{Url, UrlParser};
+use util::str::DOMString;
+
+/// A session history entry added by `pushState()`, or the entry the document
+/// was originally loaded into.
+#[derive(JSTraceable, HeapSizeOf)]
+struct SessionHistoryEntry {
+    url: Url,
+    /// The serialized state, or `None` if the entry has no state.
+    #[ignore_heap_size_of = "Defined in rust-mozjs"]
+    state: Option<StructuredCloneData>,
+}
+
+// https://html.spec.whatwg.org/multipage/#the-history-interface
+#[dom_struct]
+pub struct History {
+    reflector_: Reflector,
+    window: JS<Window>,
+    /// The session history entries belonging to this document. Entries of
+    /// other documents are kept by the constellation.
+    entries: DOMRefCell<Vec<SessionHistoryEntry>>,
+    /// The index of the current entry in `entries`.
+    current: Cell<usize>,
+    /// The deserialized state of the current entry.
+    #[ignore_heap_size_of = "Defined in rust-mozjs"]
+    state: MutHeapJSVal,
+}
+
+impl History {
+    fn new_inherited(window: &Window) -> History {
+        let entry = SessionHistoryEntry {
+            url: window.get_url(),
+            state: None,
+        };
+        let state = MutHeapJSVal::new();
+        state.set(NullValue());
+        History {
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
+            entries: DOMRefCell::new(vec![entry]),
+            current: Cell::new(0),
+            state: state,
+        }
+    }
+
+    pub fn new(window: &Window) -> Root<History> {
+        reflect_dom_object(box History::new_inherited(window),
+                           GlobalRef::Window(window),
+                           HistoryBinding::Wrap)
+    }
+
+    /// Sets `state` to a fresh deserialization of the current entry's state.
+    fn restore_state(&self, cx: *mut JSContext) {
+        let window = self.window.root();
+        let mut state = RootedValue::new(cx, NullValue());
+        if let Some(ref data) = self.entries.borrow()[self.current.get()].state {
+            data.read(GlobalRef::Window(window.r()), state.handle_mut());
+        }
+        self.state.set(state.ptr);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-pushstate
+    fn add_state(&self, cx: *mut JSContext, data: HandleValue,
+                 url: Option<DOMString>, replace: bool) -> ErrorResult {
+        let window = self.window.root();
+        let document = window.Document();
+
+        // Step 4.
+        let data = try!(StructuredCloneData::write(cx, data));
+
+        // Step 5.
+        let new_url = match url {
+            Some(url) => {
+                // Steps 5.1-5.2.
+                let base_url = document.r().base_url();
+                let new_url = match UrlParser::new().base_url(&base_url).parse(&url) {
+                    Ok(new_url) => new_url,
+                    Err(_) => return Err(Security),
+                };
+                // Step 5.3.
+                {
+                    let document_url = document.r().url();
+                    if new_url.scheme != document_url.scheme ||
+                       new_url.username() != document_url.username() ||
+                       new_url.password() != document_url.password() ||
+                       new_url.host() != document_url.host() ||
+                       new_url.port() != document_url.port() {
+                        return Err(Security);
+                    }
+                }
+                new_url
+            },
+            // Step 6.
+            None => document.r().url().clone(),
+        };
+
+        let entry = SessionHistoryEntry {
+            url: new_url.clone(),
+            state: Some(data),
+        };
+        {
+            let mut entries = self.entries.borrow_mut();
+            let current = self.current.get();
+            if replace {
+                // Step 8.
+                entries[current] = entry;
+            } else {
+                // Step 7.
+                entries.truncate(current + 1);
+                entries.push(entry);
+                self.current.set(current + 1);
+            }
+        }
+        self.notify_constellation();
+
+        // Step 9.
+        document.r().set_url(new_url);
+
+        // Step 10.
+        self.restore_state(cx);
+        Ok(())
+    }
+
+    /// Tells the constellation which entry of this document is current and how
+    /// many entries it has, so that it can compute the joint session history.
+    fn notify_constellation(&self) {
+        let window = self.window.root();
+        let ConstellationChan(ref chan) = window.r().constellation_chan();
+        let msg = ConstellationMsg::SessionHistoryChanged(window.r().pipeline(),
+                                                          self.current.get(),
+                                                          self.entries.borrow().len());
+        chan.send(msg).unwrap();
+    }
+
+    /// Makes the entry at `index` current, restoring its URL and state.
+    fn set_current(&self, index: usize) {
+        self.current.set(index);
+        self.notify_constellation();
+
+        let window = self.window.root();
+        let window = window.r();
+        let url = self.entries.borrow()[index].url.clone();
+        window.Document().r().set_url(url);
+
+        let cx = window.get_cx();
+        let _ar = JSAutoRequest::new(cx);
+        let _ac = JSAutoCompartment::new(cx, window.reflector().get_jsobject().get());
+        self.restore_state(cx);
+    }
+
+    /// Makes the entry at `index` current and fires `popstate` at the window.
+    // https://html.spec.whatwg.org/multipage/#traverse-the-history
+    fn traverse_to(&self, index: usize) {
+        if index == self.current.get() || index >= self.entries.borrow().len() {
+            return;
+        }
+        self.set_current(index);
+
+        let window = self.window.root();
+        let window = window.r();
+        let global = GlobalRef::Window(window);
+        let cx = global.get_cx();
+        let _ar = JSAutoRequest::new(cx);
+        let _ac = JSAutoCompartment::new(cx, window.reflector().get_jsobject().get());
+        let state = RootedValue::new(cx, self.state.get());
+        let popstate = PopStateEvent::new(global, "popstate".to_owned(),
+                                          EventBubbles::DoesNotBubble,
+                                          EventCancelable::NotCancelable,
+                                          state.handle());
+        let event = EventCast::from_ref(popstate.r());
+        event.fire(EventTargetCast::from_ref(window));
+    }
+
+    /// Asks the constellation to move `steps` entries back or forward past the
+    /// first or last entry of this document. The constellation navigates to the
+    /// adjacent document, which takes the remaining steps through its own
+    /// entries.
+    fn traverse_documents(&self, direction: NavigationDirection, steps: u32) {
+        // Coming back to this document lands on the entry it was left from.
+        let boundary = match direction {
+            NavigationDirection::Back => 0,
+            NavigationDirection::Forward => self.entries.borrow().len() - 1,
+        };
+        if boundary != self.current.get() {
+            self.set_current(boundary);
+        }
+
+        let window = self.window.root();
+        let ConstellationChan(ref chan) = window.r().constellation_chan();
+        let msg = ConstellationMsg::TraverseHistory(window.r().parent_info(), direction, steps - 1);
+        chan.send(msg).unwrap();
+    }
+}
+
+impl HistoryMethods for History {
+    // https://html.spec.whatwg.org/multipage/#dom-history-length
+    fn Length(&self) -> u32 {
+        let window = self.window.root();
+        let ConstellationChan(ref chan) = window.r().constellation_chan();
+        let (sender, receiver) = ipc::channel().unwrap();
+        chan.send(ConstellationMsg::GetSessionHistoryLength(window.r().pipeline(), sender)).unwrap();
+        receiver.recv().unwrap() as u32
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-state
+    fn State(&self, _cx: *mut JSContext) -> JSVal {
+        self.state.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-go
+    fn Go(&self, delta: i32) {
+        // Step 2.
+        if delta == 0 {
+            self.window.root().Location().r().Reload();
+            return;
+        }
+
+        // Step 3.
+        let target = self.current.get() as i64 + delta as i64;
+        let len = self.entries.borrow().len() as i64;
+        if target < 0 {
+            self.traverse_documents(NavigationDirection::Back, -target as u32);
+        } else if target >= len {
+            self.traverse_documents(NavigationDirection::Forward, (target - len + 1) as u32);
+        } else {
+            let window = self.window.root();
+            let script_chan = window.r().script_chan();
+            let task = box HistoryTraversalTask {
+                history: Trusted::new(window.r().get_cx(), self, script_chan.clone()),
+                index: target as usize,
+            };
+            let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-back
+    fn Back(&self) {
+        self.Go(-1);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-forward
+    fn Forward(&self) {
+        self.Go(1);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-pushstate
+    fn PushState(&self, cx: *mut JSContext, data: HandleValue, _title: DOMString,
+                 url: Option<DOMString>) -> ErrorResult {
+        self.add_state(cx, data, url, false)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-history-replacestate
+    fn ReplaceState(&self, cx: *mut JSContext, data: HandleValue, _title: DOMString,
+                    url: Option<DOMString>) -> ErrorResult {
+        self.add_state(cx, data, url, true)
+    }
+}
+
+/// The task queued by `go()` to traverse to another entry of the same document.
+struct HistoryTraversalTask {
+    history: Trusted<History>,
+    index: usize,
+}
+
+impl Runnable for HistoryTraversalTask {
+    fn handler(self: Box<HistoryTraversalTask>) {
+        let history = self.history.root();
+        history.r().traverse_to(self.index);
+    }
+}
@@ -14,6 +14,7 @@ use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast};
 use dom::bindings::codegen::InheritTypes::{HTMLAnchorElementDerived, HTMLImageElementDerived};
 use dom::bindings::codegen::InheritTypes::{MouseEventCast, NodeCast};
 use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::bindings::str::USVString;
 use dom::document::Document;
 use dom::domtokenlist::DOMTokenList;
 use dom::element::{Element, ElementTypeId};
@@ -21,11 +22,13 @@ use dom::event::Event;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::node::{Node, NodeTypeId, document_from_node, window_from_node};
+use dom::urlhelper::UrlHelper;
 use dom::virtualmethods::VirtualMethods;
 use num::ToPrimitive;
+use std::borrow::ToOwned;
 use std::default::Default;
 use string_cache::Atom;
-use url::UrlParser;
+use url::{Url, UrlParser};
 use util::str::DOMString;
 
 #[dom_struct]
@@ -60,6 +63,34 @@ impl HTMLAnchorElement {
         let element = HTMLAnchorElement::new_inherited(localName, prefix, document);
         Node::reflect_node(box element, document, HTMLAnchorElementBinding::Wrap)
     }
+
+    // https://html.spec.whatwg.org/multipage/#concept-hyperlink-url-set
+    fn url(&self) -> Option<Url> {
+        let element = ElementCast::from_ref(self);
+        element.get_attribute(&ns!(""), &atom!("href")).and_then(|attribute| {
+            let document = document_from_node(self);
+            UrlParser::new().base_url(&document.r().base_url()).parse(&attribute.r().Value()).ok()
+        })
+    }
+
+    // https://html.spec.whatwg.org/multipage/#update-href
+    fn update_href(&self, url: &Url) {
+        let element = ElementCast::from_ref(self);
+        element.set_string_attribute(&atom!("href"), url.serialize());
+    }
+
+    fn get_url_component(&self, getter: fn(&Url) -> USVString) -> USVString {
+        self.url().map_or(USVString("".to_owned()), |url| getter(&url))
+    }
+
+    fn set_url_component(&self, value: USVString,
+                         setter: fn(&mut Url, USVString)) {
+        // Step 2: if this element's url is null, terminate these steps.
+        if let Some(mut url) = self.url() {
+            setter(&mut url, value);
+            self.update_href(&url);
+        }
+    }
 }
 
 impl VirtualMethods for HTMLAnchorElement {
@@ -89,6 +120,12 @@ impl HTMLAnchorElementMethods for HTMLAnchorElement {
         node.SetTextContent(Some(value))
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-a-rel
+    make_getter!(Rel);
+
+    // https://html.spec.whatwg.org/multipage/#dom-a-rel
+    make_tokenlist_setter!(SetRel, "rel");
+
     // https://html.spec.whatwg.org/multipage/#dom-a-rellist
     fn RelList(&self) -> Root<DOMTokenList> {
         self.rel_list.or_init(|| {
@@ -119,6 +156,123 @@ impl HTMLAnchorElementMethods for HTMLAnchorElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-a-shape
     make_setter!(SetShape, "shape");
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-href
+    fn Href(&self) -> USVString {
+        USVString(match self.url() {
+            Some(url) => url.serialize(),
+            None => {
+                let element = ElementCast::from_ref(self);
+                element.get_string_attribute(&atom!("href"))
+            },
+        })
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-href
+    fn SetHref(&self, value: USVString) {
+        let element = ElementCast::from_ref(self);
+        element.set_string_attribute(&atom!("href"), value.0);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-origin
+    fn Origin(&self) -> USVString {
+        self.get_url_component(UrlHelper::Origin)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-protocol
+    fn Protocol(&self) -> USVString {
+        self.url().map_or(USVString(":".to_owned()), |url| UrlHelper::Protocol(&url))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-protocol
+    fn SetProtocol(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetProtocol);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-username
+    fn Username(&self) -> USVString {
+        self.get_url_component(UrlHelper::Username)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-username
+    fn SetUsername(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetUsername);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-password
+    fn Password(&self) -> USVString {
+        self.get_url_component(UrlHelper::Password)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-password
+    fn SetPassword(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetPassword);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-host
+    fn Host(&self) -> USVString {
+        self.get_url_component(UrlHelper::Host)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-host
+    fn SetHost(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetHost);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-hostname
+    fn Hostname(&self) -> USVString {
+        self.get_url_component(UrlHelper::Hostname)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-hostname
+    fn SetHostname(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetHostname);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-port
+    fn Port(&self) -> USVString {
+        self.get_url_component(UrlHelper::Port)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-port
+    fn SetPort(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetPort);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-pathname
+    fn Pathname(&self) -> USVString {
+        self.get_url_component(UrlHelper::Pathname)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-pathname
+    fn SetPathname(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetPathname);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-search
+    fn Search(&self) -> USVString {
+        self.get_url_component(UrlHelper::Search)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-search
+    fn SetSearch(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetSearch);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-hash
+    fn Hash(&self) -> USVString {
+        self.get_url_component(UrlHelper::Hash)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-hash
+    fn SetHash(&self, value: USVString) {
+        self.set_url_component(value, UrlHelper::SetHash);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-hyperlink-href
+    fn Stringifier(&self) -> DOMString {
+        self.Href().0
+    }
 }
 
 impl Activatable for HTMLAnchorElement {
@@ -206,5 +360,5 @@ fn follow_hyperlink(subject: &Element, hyperlink_suffix: Option<DOMString>) {
     // Step 7.
     debug!("following hyperlink to {}", url.serialize());
     let window = document.window();
-    window.load_url(url);
+    window.load_url(url, false);
 }
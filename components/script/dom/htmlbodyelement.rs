@@ -84,6 +84,30 @@ impl HTMLBodyElementMethods for HTMLBodyElement {
         let win = window_from_node(self);
         win.r().SetOnunload(listener)
     }
+
+    // https://html.spec.whatwg.org/multipage/#the-body-element
+    fn GetOnonline(&self) -> Option<Rc<EventHandlerNonNull>> {
+        let win = window_from_node(self);
+        win.r().GetOnonline()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#the-body-element
+    fn SetOnonline(&self, listener: Option<Rc<EventHandlerNonNull>>) {
+        let win = window_from_node(self);
+        win.r().SetOnonline(listener)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#the-body-element
+    fn GetOnoffline(&self) -> Option<Rc<EventHandlerNonNull>> {
+        let win = window_from_node(self);
+        win.r().GetOnoffline()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#the-body-element
+    fn SetOnoffline(&self, listener: Option<Rc<EventHandlerNonNull>>) {
+        let win = window_from_node(self);
+        win.r().SetOnoffline(listener)
+    }
 }
 
 
@@ -139,6 +163,9 @@ impl VirtualMethods for HTMLBodyElement {
                 });
             },
             (name, AttributeMutation::Set(_)) if name.starts_with("on") => {
+                if !document_from_node(self).is_scripting_enabled() {
+                    return;
+                }
                 let window = window_from_node(self);
                 let (cx, url, reflector) = (window.get_cx(),
                                             window.get_url(),
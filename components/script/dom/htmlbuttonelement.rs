@@ -15,7 +15,7 @@ use dom::event::Event;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::htmlformelement::{FormControl, FormSubmitter};
-use dom::htmlformelement::{SubmittedFrom, HTMLFormElement};
+use dom::htmlformelement::{ResetFrom, SubmittedFrom, HTMLFormElement};
 use dom::node::{Node, NodeTypeId, document_from_node, window_from_node};
 use dom::validitystate::ValidityState;
 use dom::virtualmethods::VirtualMethods;
@@ -25,7 +25,6 @@ use std::cell::Cell;
 use util::str::DOMString;
 
 #[derive(JSTraceable, PartialEq, Copy, Clone)]
-#[allow(dead_code)]
 #[derive(HeapSizeOf)]
 enum ButtonType {
     ButtonSubmit,
@@ -55,7 +54,6 @@ impl HTMLButtonElement {
         HTMLButtonElement {
             htmlelement:
                 HTMLElement::new_inherited(HTMLElementTypeId::HTMLButtonElement, localName, prefix, document),
-            //TODO: implement button_type in attribute_mutated
             button_type: Cell::new(ButtonType::ButtonSubmit)
         }
     }
@@ -121,6 +119,12 @@ impl HTMLButtonElementMethods for HTMLButtonElement {
     // https://html.spec.whatwg.org/multipage/#dom-fs-formmethod
     make_setter!(SetFormMethod, "formmethod");
 
+    // https://html.spec.whatwg.org/multipage/#dom-fs-formnovalidate
+    make_bool_getter!(FormNoValidate);
+
+    // https://html.spec.whatwg.org/multipage/#dom-fs-formnovalidate
+    make_bool_setter!(SetFormNoValidate, "formnovalidate");
+
     // https://html.spec.whatwg.org/multipage/#dom-fs-formtarget
     make_getter!(FormTarget);
 
@@ -164,6 +168,21 @@ impl VirtualMethods for HTMLButtonElement {
                     }
                 }
             },
+            &atom!(type) => {
+                // https://html.spec.whatwg.org/multipage/#attr-button-type
+                let button_type = match mutation {
+                    AttributeMutation::Set(_) => {
+                        match &*attr.value().to_ascii_lowercase() {
+                            "reset" => ButtonType::ButtonReset,
+                            "button" => ButtonType::ButtonButton,
+                            "menu" => ButtonType::ButtonMenu,
+                            _ => ButtonType::ButtonSubmit,
+                        }
+                    },
+                    AttributeMutation::Removed => ButtonType::ButtonSubmit,
+                };
+                self.button_type.set(button_type);
+            },
             _ => {},
         }
     }
@@ -193,14 +212,14 @@ impl VirtualMethods for HTMLButtonElement {
 
 impl FormControl for HTMLButtonElement {}
 
-impl<'a> Activatable for &'a HTMLButtonElement {
+impl Activatable for HTMLButtonElement {
     fn as_element<'b>(&'b self) -> &'b Element {
-        ElementCast::from_ref(*self)
+        ElementCast::from_ref(self)
     }
 
     fn is_instance_activatable(&self) -> bool {
         //https://html.spec.whatwg.org/multipage/#the-button-element
-        let node = NodeCast::from_ref(*self);
+        let node = NodeCast::from_ref(self);
         !(node.get_disabled_state())
     }
 
@@ -221,7 +240,13 @@ impl<'a> Activatable for &'a HTMLButtonElement {
             ButtonType::ButtonSubmit => {
                 self.form_owner().map(|o| {
                     o.r().submit(SubmittedFrom::NotFromFormSubmitMethod,
-                                 FormSubmitter::ButtonElement(self.clone()))
+                                 FormSubmitter::ButtonElement(self))
+                });
+            },
+            //https://html.spec.whatwg.org/multipage/#attr-button-type-reset-state
+            ButtonType::ButtonReset => {
+                self.form_owner().map(|o| {
+                    o.r().reset(ResetFrom::NotFromFormResetMethod)
                 });
             },
             _ => ()
@@ -231,10 +256,10 @@ impl<'a> Activatable for &'a HTMLButtonElement {
     // https://html.spec.whatwg.org/multipage/#implicit-submission
     #[allow(unsafe_code)]
     fn implicit_submission(&self, ctrlKey: bool, shiftKey: bool, altKey: bool, metaKey: bool) {
-        let doc = document_from_node(*self);
+        let doc = document_from_node(self);
         let node = NodeCast::from_ref(doc.r());
         let owner = self.form_owner();
-        let elem = ElementCast::from_ref(*self);
+        let elem = ElementCast::from_ref(self);
         if owner.is_none() || elem.click_in_progress() {
             return;
         }
@@ -212,8 +212,8 @@ impl HTMLCollectionMethods for HTMLCollection {
 
         // Step 2.
         self.elements_iter().find(|elem| {
-            elem.r().get_string_attribute(&atom!("name")) == key ||
-            elem.r().get_string_attribute(&atom!("id")) == key
+            elem.r().get_string_attribute(&atom!("id")) == key ||
+            (*elem.r().namespace() == ns!(HTML) && elem.r().get_string_attribute(&atom!("name")) == key)
         })
     }
 
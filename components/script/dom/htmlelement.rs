@@ -2,16 +2,17 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use dom::activation;
 use dom::attr::Attr;
 use dom::attr::AttrValue;
 use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
 use dom::bindings::codegen::Bindings::HTMLElementBinding;
-use dom::bindings::codegen::Bindings::HTMLElementBinding::HTMLElementMethods;
-use dom::bindings::codegen::Bindings::HTMLInputElementBinding::HTMLInputElementMethods;
-use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::Bindings::HTMLElementBinding::{FocusOptions, HTMLElementMethods};
+use dom::bindings::codegen::Bindings::WindowBinding::{ScrollBehavior, WindowMethods};
 use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLFrameSetElementDerived};
-use dom::bindings::codegen::InheritTypes::{EventTargetCast, HTMLInputElementCast, NodeCast};
+use dom::bindings::codegen::InheritTypes::{EventTargetCast, HTMLElementCast, NodeCast};
 use dom::bindings::codegen::InheritTypes::{HTMLBodyElementDerived, HTMLElementDerived, HTMLHtmlElementDerived};
+use dom::bindings::codegen::InheritTypes::HTMLLabelElementCast;
 use dom::bindings::error::Error::Syntax;
 use dom::bindings::error::ErrorResult;
 use dom::bindings::js::{JS, MutNullableHeap, Root};
@@ -21,18 +22,19 @@ use dom::document::Document;
 use dom::domstringmap::DOMStringMap;
 use dom::element::{AttributeMutation, Element, ElementTypeId};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
-use dom::htmlinputelement::HTMLInputElement;
 use dom::htmlmediaelement::HTMLMediaElementTypeId;
 use dom::htmltablecellelement::HTMLTableCellElementTypeId;
 use dom::node::{Node, NodeTypeId, SEQUENTIALLY_FOCUSABLE, document_from_node, window_from_node};
+use dom::nodelist::NodeList;
 use dom::virtualmethods::VirtualMethods;
 use msg::constellation_msg::FocusType;
+use std::ascii::AsciiExt;
 use std::borrow::ToOwned;
 use std::default::Default;
 use std::intrinsics;
 use std::rc::Rc;
 use string_cache::Atom;
-use util::str::DOMString;
+use util::str::{DOMString, parse_integer};
 
 #[dom_struct]
 pub struct HTMLElement {
@@ -116,6 +118,39 @@ impl HTMLElement {
             }
         }
     }
+
+    // https://html.spec.whatwg.org/multipage/#attr-contenteditable
+    fn content_editable_state(&self) -> Option<bool> {
+        let element = ElementCast::from_ref(self);
+        element.get_attribute(&ns!(""), &atom!("contenteditable")).and_then(|attr| {
+            let attr = attr.r();
+            let value = attr.value();
+            if value.is_empty() || value.eq_ignore_ascii_case("true") {
+                Some(true)
+            } else if value.eq_ignore_ascii_case("false") {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Scrolls the viewport so that this element's top left corner is at its
+    /// top left, unless the element is already entirely in view.
+    fn scroll_into_view_if_needed(&self) {
+        let window = window_from_node(self);
+        let window = window.r();
+        let rect = NodeCast::from_ref(self).get_bounding_content_box();
+        let (left, top) = (rect.origin.x.to_f64_px(), rect.origin.y.to_f64_px());
+        let (right, bottom) = (rect.max_x().to_f64_px(), rect.max_y().to_f64_px());
+        let (scroll_x, scroll_y) = (window.ScrollX() as f64, window.ScrollY() as f64);
+
+        let x = if left < scroll_x || right > scroll_x + window.InnerWidth() as f64 { left } else { scroll_x };
+        let y = if top < scroll_y || bottom > scroll_y + window.InnerHeight() as f64 { top } else { scroll_y };
+        if x != scroll_x || y != scroll_y {
+            window.scroll(x, y, ScrollBehavior::Instant);
+        }
+    }
 }
 
 impl HTMLElementMethods for HTMLElement {
@@ -142,6 +177,75 @@ impl HTMLElementMethods for HTMLElement {
     // https://html.spec.whatwg.org/multipage/#dom-hidden
     make_bool_setter!(SetHidden, "hidden");
 
+    // https://html.spec.whatwg.org/multipage/#dom-tabindex
+    fn TabIndex(&self) -> i32 {
+        let element = ElementCast::from_ref(self);
+        let tabindex = element.get_attribute(&ns!(""), &atom!("tabindex")).and_then(|attr| {
+            parse_integer(attr.r().value().chars())
+        });
+        if let Some(tabindex) = tabindex {
+            return tabindex;
+        }
+        let node = NodeCast::from_ref(self);
+        match node.type_id() {
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLAnchorElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLAreaElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLButtonElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLFrameElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLIFrameElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLInputElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLObjectElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLSelectElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLTextAreaElement)) => 0,
+            _ => -1,
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-tabindex
+    fn SetTabIndex(&self, tabindex: i32) {
+        let element = ElementCast::from_ref(self);
+        element.set_string_attribute(&atom!("tabindex"), tabindex.to_string());
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-contenteditable
+    fn ContentEditable(&self) -> DOMString {
+        match self.content_editable_state() {
+            Some(true) => "true".to_owned(),
+            Some(false) => "false".to_owned(),
+            None => "inherit".to_owned(),
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-contenteditable
+    fn SetContentEditable(&self, value: DOMString) -> ErrorResult {
+        let element = ElementCast::from_ref(self);
+        if value.eq_ignore_ascii_case("inherit") {
+            element.remove_attribute(&ns!(""), &atom!("contenteditable"));
+        } else if value.eq_ignore_ascii_case("true") {
+            element.set_string_attribute(&atom!("contenteditable"), "true".to_owned());
+        } else if value.eq_ignore_ascii_case("false") {
+            element.set_string_attribute(&atom!("contenteditable"), "false".to_owned());
+        } else {
+            return Err(Syntax);
+        }
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-iscontenteditable
+    fn IsContentEditable(&self) -> bool {
+        // The element is editable if its nearest ancestor HTML element with a
+        // contenteditable state other than "inherit" is in the "true" state.
+        let node = NodeCast::from_ref(self);
+        for ancestor in node.inclusive_ancestors() {
+            if let Some(element) = HTMLElementCast::to_ref(ancestor.r()) {
+                if let Some(state) = element.content_editable_state() {
+                    return state;
+                }
+            }
+        }
+        false
+    }
+
     // https://html.spec.whatwg.org/multipage/#globaleventhandlers
     global_event_handlers!(NoOnload);
 
@@ -174,27 +278,33 @@ impl HTMLElementMethods for HTMLElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-click
     fn Click(&self) {
-        let maybe_input: Option<&HTMLInputElement> = HTMLInputElementCast::to_ref(self);
-        if let Some(i) = maybe_input {
-            if i.Disabled() {
-                return;
-            }
-        }
         let element = ElementCast::from_ref(self);
-        // https://www.w3.org/Bugs/Public/show_bug.cgi?id=27430 ?
-        element.as_maybe_activatable().map(|a| a.synthetic_click_activation(false, false, false, false));
+        // Step 1.
+        if element.is_actually_disabled() {
+            return;
+        }
+        // Step 2.
+        activation::synthetic_click_activation(element, false, false, false, false);
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-focus
-    fn Focus(&self) {
+    fn Focus(&self, options: &FocusOptions) {
         // TODO: Mark the element as locked for focus and run the focusing steps.
         // https://html.spec.whatwg.org/multipage/#focusing-steps
         let element = ElementCast::from_ref(self);
+        // Step 1.
+        if !element.is_focusable_area() {
+            return;
+        }
         let document = document_from_node(self);
         let document = document.r();
         document.begin_focus_transaction();
         document.request_focus(element);
         document.commit_focus_transaction(FocusType::Element);
+
+        if !options.preventScroll {
+            self.scroll_into_view_if_needed();
+        }
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-blur
@@ -271,12 +381,13 @@ impl HTMLElementMethods for HTMLElement {
 
 // https://html.spec.whatwg.org/#attr-data-*
 
+// https://html.spec.whatwg.org/multipage/#dom-domstringmap-setitem
 fn to_snake_case(name: DOMString) -> DOMString {
     let mut attr_name = "data-".to_owned();
     for ch in name.chars() {
-        if ch.is_uppercase() {
+        if ch.is_ascii() && ch.is_uppercase() {
             attr_name.push('\x2d');
-            attr_name.extend(ch.to_lowercase());
+            attr_name.push(ch.to_ascii_lowercase());
         } else {
             attr_name.push(ch);
         }
@@ -284,18 +395,55 @@ fn to_snake_case(name: DOMString) -> DOMString {
     attr_name
 }
 
+// https://html.spec.whatwg.org/multipage/#dom-dataset
+pub fn to_camel_case(name: &str) -> Option<DOMString> {
+    if !name.starts_with("data-") {
+        return None;
+    }
+    let name = &name[5..];
+    if name.chars().any(|ch| ch.is_ascii() && ch.is_uppercase()) {
+        return None;
+    }
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\x2d' {
+            if let Some(&next) = chars.peek() {
+                if next.is_ascii() && next.is_lowercase() {
+                    chars.next();
+                    result.push(next.to_ascii_uppercase());
+                    continue;
+                }
+            }
+        }
+        result.push(ch);
+    }
+    Some(result)
+}
+
+// Whether `name` contains a hyphen followed by an ASCII lowercase letter, which can never be
+// the result of converting a `data-*` attribute name.
+pub fn has_hyphen_before_lowercase(name: &str) -> bool {
+    name.chars().zip(name.chars().skip(1)).any(|(ch, next)| {
+        ch == '\x2d' && next >= 'a' && next <= 'z'
+    })
+}
+
 impl HTMLElement {
     pub fn set_custom_attr(&self, name: DOMString, value: DOMString) -> ErrorResult {
-        if name.chars()
-               .skip_while(|&ch| ch != '\u{2d}')
-               .nth(1).map_or(false, |ch| ch >= 'a' && ch <= 'z') {
+        // Step 1.
+        if has_hyphen_before_lowercase(&name) {
             return Err(Syntax);
         }
+        // Steps 2-5.
         let element = ElementCast::from_ref(self);
         element.set_custom_attribute(to_snake_case(name), value)
     }
 
     pub fn get_custom_attr(&self, local_name: DOMString) -> Option<DOMString> {
+        if has_hyphen_before_lowercase(&local_name) {
+            return None;
+        }
         let element = ElementCast::from_ref(self);
         let local_name = Atom::from_slice(&to_snake_case(local_name));
         element.get_attribute(&ns!(""), &local_name).map(|attr| {
@@ -308,6 +456,51 @@ impl HTMLElement {
         let local_name = Atom::from_slice(&to_snake_case(local_name));
         element.remove_attribute(&ns!(""), &local_name);
     }
+
+    pub fn supported_prop_names_custom_attr(&self) -> Vec<DOMString> {
+        let element = ElementCast::from_ref(self);
+        element.attrs().iter().filter_map(|attr| {
+            let attr = attr.root();
+            if *attr.r().namespace() != ns!("") {
+                return None;
+            }
+            to_camel_case(attr.r().local_name())
+        }).collect()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#category-label
+    pub fn is_labelable_element(&self) -> bool {
+        let node = NodeCast::from_ref(self);
+        match node.type_id() {
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLInputElement)) => {
+                let element = ElementCast::from_ref(self);
+                match element.get_attribute(&ns!(""), &atom!("type")) {
+                    Some(attr) => !attr.r().value().eq_ignore_ascii_case("hidden"),
+                    None => true,
+                }
+            },
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLButtonElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLMeterElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLOutputElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLProgressElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLSelectElement)) |
+            NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLTextAreaElement)) => true,
+            _ => false,
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-lfe-labels
+    pub fn labels(&self) -> Root<NodeList> {
+        let node = NodeCast::from_ref(self);
+        let window = window_from_node(node);
+        let root = node.inclusive_ancestors().last().unwrap();
+        let iter = root.r().traverse_preorder().filter(|candidate| {
+            HTMLLabelElementCast::to_ref(candidate.r()).and_then(|label| {
+                label.labeled_control()
+            }).map_or(false, |control| control.r() == self)
+        });
+        NodeList::new_simple_list(window.r(), iter)
+    }
 }
 
 impl VirtualMethods for HTMLElement {
@@ -320,6 +513,9 @@ impl VirtualMethods for HTMLElement {
         self.super_type().unwrap().attribute_mutated(attr, mutation);
         match (attr.local_name(), mutation) {
             (name, AttributeMutation::Set(_)) if name.starts_with("on") => {
+                if !document_from_node(self).r().is_scripting_enabled() {
+                    return;
+                }
                 let window = window_from_node(self);
                 let (cx, url, reflector) = (window.r().get_cx(),
                                             window.r().get_url(),
@@ -329,6 +525,7 @@ impl VirtualMethods for HTMLElement {
                                                       &name[2..],
                                                       (**attr.value()).to_owned());
             },
+            (&atom!("tabindex"), _) => self.update_sequentially_focusable_status(),
             _ => {}
         }
     }
@@ -17,6 +17,7 @@ use dom::htmlformelement::{FormControl, HTMLFormElement};
 use dom::node::{Node, NodeTypeId, window_from_node};
 use dom::validitystate::ValidityState;
 use dom::virtualmethods::VirtualMethods;
+use std::borrow::ToOwned;
 use util::str::{DOMString, StaticStringVec};
 
 #[dom_struct]
@@ -60,7 +61,8 @@ impl HTMLFieldSetElementMethods for HTMLFieldSetElement {
             fn filter<'a>(&self, elem: &'a Element, _root: &'a Node) -> bool {
                 static TAG_NAMES: StaticStringVec = &["button", "fieldset", "input",
                     "keygen", "object", "output", "select", "textarea"];
-                TAG_NAMES.iter().any(|&tag_name| tag_name == &**elem.local_name())
+                *elem.namespace() == ns!(HTML) &&
+                    TAG_NAMES.iter().any(|&tag_name| tag_name == &**elem.local_name())
             }
         }
         let node = NodeCast::from_ref(self);
@@ -85,6 +87,11 @@ impl HTMLFieldSetElementMethods for HTMLFieldSetElement {
     fn GetForm(&self) -> Option<Root<HTMLFormElement>> {
         self.form_owner()
     }
+
+    // https://html.spec.whatwg.org/multipage/#dom-fieldset-type
+    fn Type(&self) -> DOMString {
+        "fieldset".to_owned()
+    }
 }
 
 impl VirtualMethods for HTMLFieldSetElement {
@@ -9,12 +9,14 @@ use dom::bindings::codegen::Bindings::HTMLButtonElementBinding::HTMLButtonElemen
 use dom::bindings::codegen::Bindings::HTMLFormElementBinding;
 use dom::bindings::codegen::Bindings::HTMLFormElementBinding::HTMLFormElementMethods;
 use dom::bindings::codegen::Bindings::HTMLInputElementBinding::HTMLInputElementMethods;
+use dom::bindings::codegen::Bindings::HTMLTextAreaElementBinding::HTMLTextAreaElementMethods;
 use dom::bindings::codegen::InheritTypes::EventTargetCast;
 use dom::bindings::codegen::InheritTypes::HTMLDataListElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLFormElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLFormElementDerived;
 use dom::bindings::codegen::InheritTypes::HTMLInputElementCast;
+use dom::bindings::codegen::InheritTypes::HTMLOutputElementCast;
 use dom::bindings::codegen::InheritTypes::{ElementBase, ElementCast};
 use dom::bindings::codegen::InheritTypes::{HTMLTextAreaElementCast, NodeCast};
 use dom::bindings::global::GlobalRef;
@@ -164,13 +166,18 @@ pub enum ResetFrom {
 
 
 impl HTMLFormElement {
-    pub fn submit(&self, _submit_method_flag: SubmittedFrom, submitter: FormSubmitter) {
+    pub fn submit(&self, submit_method_flag: SubmittedFrom, submitter: FormSubmitter) {
         // Step 1
         let doc = document_from_node(self);
         let win = window_from_node(self);
-        let base = doc.r().url();
+        let base = doc.r().url().clone();
         // TODO: Handle browsing contexts
-        // TODO: Handle validation
+        // Step 4
+        if let SubmittedFrom::NotFromFormSubmitMethod = submit_method_flag {
+            if !submitter.no_validate(self) && !self.statically_validate_constraints() {
+                return;
+            }
+        }
         let event = Event::new(GlobalRef::Window(win.r()),
                                "submit".to_owned(),
                                EventBubbles::Bubbles,
@@ -190,7 +197,7 @@ impl HTMLFormElement {
         // TODO: Resolve the url relative to the submitter element
         // Step 10-15
         let action_components =
-            UrlParser::new().base_url(base).parse(&action).unwrap_or((*base).clone());
+            UrlParser::new().base_url(&base).parse(&action).unwrap_or((*base).clone());
         let _action = action_components.serialize();
         let scheme = action_components.scheme.clone();
         let enctype = submitter.enctype();
@@ -199,6 +206,7 @@ impl HTMLFormElement {
         // TODO: Handle browsing contexts, partially loaded documents (step 16-17)
 
         let mut load_data = LoadData::new(action_components);
+        load_data.referrer = doc.r().referrer_for(&load_data.url);
 
         let parsed_data = match enctype {
             FormEncType::UrlEncoded => {
@@ -226,7 +234,7 @@ impl HTMLFormElement {
 
         // This is wrong. https://html.spec.whatwg.org/multipage/#planned-navigation
         win.r().main_thread_script_chan().send(MainThreadScriptMsg::Navigate(
-            win.r().pipeline(), load_data)).unwrap();
+            win.r().pipeline(), load_data, false)).unwrap();
     }
 
     pub fn get_form_dataset<'b>(&self, submitter: Option<FormSubmitter<'b>>) -> Vec<FormDatum> {
@@ -399,14 +407,61 @@ impl HTMLFormElement {
                     textarea.reset()
                 }
                 NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLOutputElement)) => {
-                    // Unimplemented
-                    {}
+                    let output = HTMLOutputElementCast::to_ref(child.r()).unwrap();
+                    output.reset()
                 }
                 _ => {}
             }
         };
         self.marked_for_reset.set(false);
     }
+
+    /// Fires an invalid event at every control of this form that does not
+    /// satisfy its constraints, and returns whether there were none.
+    // https://html.spec.whatwg.org/multipage/#statically-validate-the-constraints
+    fn statically_validate_constraints(&self) -> bool {
+        let node = NodeCast::from_ref(self);
+        // TODO: This is an incorrect way of getting controls owned
+        //       by the form, but good enough until html5ever lands
+        // TODO: Only the required attribute is supported, so the only
+        //       constraint a control can fail is suffering from being missing.
+        let invalid_controls: Vec<Root<Node>> = node.traverse_preorder().filter(|child| {
+            if child.r().get_disabled_state() {
+                return false;
+            }
+            match child.r().type_id() {
+                NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLInputElement)) => {
+                    let input = HTMLInputElementCast::to_ref(child.r()).unwrap();
+                    let element = ElementCast::from_ref(input);
+                    if !element.has_attribute(&atom!("required")) || input.ReadOnly() {
+                        return false;
+                    }
+                    match &*input.Type() {
+                        "checkbox" => !input.Checked(),
+                        "text" | "search" | "url" | "tel" | "email" | "password" | "number" => {
+                            input.Value().is_empty()
+                        },
+                        _ => false,
+                    }
+                }
+                NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLTextAreaElement)) => {
+                    let textarea = HTMLTextAreaElementCast::to_ref(child.r()).unwrap();
+                    textarea.Required() && textarea.mutable() && textarea.Value().is_empty()
+                }
+                _ => false,
+            }
+        }).collect();
+
+        let win = window_from_node(self);
+        for control in &invalid_controls {
+            let event = Event::new(GlobalRef::Window(win.r()),
+                                   "invalid".to_owned(),
+                                   EventBubbles::DoesNotBubble,
+                                   EventCancelable::Cancelable);
+            event.r().fire(EventTargetCast::from_ref(control.r()));
+        }
+        invalid_controls.is_empty()
+    }
 }
 
 // TODO: add file support
@@ -510,8 +565,22 @@ impl<'a> FormSubmitter<'a> {
             },
             FormSubmitter::ButtonElement(button_element) => {
                 button_element.get_form_attribute(&atom!("formtarget"),
-                                                  |i| i.FormAction(),
-                                                  |f| f.Action())
+                                                  |i| i.FormTarget(),
+                                                  |f| f.Target())
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#concept-fs-novalidate
+    fn no_validate(&self, form_owner: &HTMLFormElement) -> bool {
+        match *self {
+            FormSubmitter::FormElement(form) => form.NoValidate(),
+            FormSubmitter::InputElement(input_element) => {
+                ElementCast::from_ref(input_element).has_attribute(&atom!("formnovalidate")) ||
+                    form_owner.NoValidate()
+            },
+            FormSubmitter::ButtonElement(button_element) => {
+                button_element.FormNoValidate() || form_owner.NoValidate()
             }
         }
     }
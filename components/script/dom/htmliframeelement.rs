@@ -13,15 +13,16 @@ use dom::bindings::conversions::ToJSValConvertible;
 use dom::bindings::error::Error::NotSupported;
 use dom::bindings::error::{ErrorResult, Fallible};
 use dom::bindings::global::GlobalRef;
-use dom::bindings::js::{Root};
+use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::bindings::refcounted::Trusted;
 use dom::bindings::utils::Reflectable;
 use dom::customevent::CustomEvent;
 use dom::document::Document;
+use dom::domtokenlist::DOMTokenList;
 use dom::element::{self, AttributeMutation, ElementTypeId};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
-use dom::node::{Node, NodeTypeId, window_from_node};
-use dom::urlhelper::UrlHelper;
+use dom::node::{Node, NodeTypeId, document_from_node, window_from_node};
 use dom::virtualmethods::VirtualMethods;
 use dom::window::Window;
 use js::jsapi::{JSAutoCompartment, JSAutoRequest, RootedValue};
@@ -30,9 +31,12 @@ use msg::constellation_msg::IFrameSandboxState::{IFrameSandboxed, IFrameUnsandbo
 use msg::constellation_msg::Msg as ConstellationMsg;
 use msg::constellation_msg::{ConstellationChan, MozBrowserEvent, NavigationDirection, PipelineId, SubpageId};
 use page::IterablePage;
+use script_task::ScriptTaskEventCategory::DomEvent;
+use script_task::{CommonScriptMsg, Runnable, ScriptChan};
 use std::ascii::AsciiExt;
 use std::borrow::ToOwned;
 use std::cell::Cell;
+use std::default::Default;
 use string_cache::Atom;
 use url::{Url, UrlParser};
 use util::prefs;
@@ -43,6 +47,18 @@ pub fn mozbrowser_enabled() -> bool {
     prefs::get_pref("dom.mozbrowser.enabled").as_boolean().unwrap_or(false)
 }
 
+/// How far outside the viewport, in CSS pixels, a lazy iframe can be for its
+/// navigation to start.
+// https://html.spec.whatwg.org/multipage/#lazy-load-root-margin
+const LAZY_LOAD_ROOT_MARGIN: f32 = 1250.;
+
+/// Returns whether a frame spanning `top` to `bottom` intersects the viewport
+/// spanning `viewport_top` to `viewport_bottom`, extended by the lazy load root
+/// margin. All values are in CSS pixels.
+pub fn is_within_lazy_load_margin(top: f32, bottom: f32, viewport_top: f32, viewport_bottom: f32) -> bool {
+    top <= viewport_bottom + LAZY_LOAD_ROOT_MARGIN && bottom >= viewport_top - LAZY_LOAD_ROOT_MARGIN
+}
+
 #[derive(HeapSizeOf)]
 enum SandboxAllowance {
     AllowNothing = 0x00,
@@ -60,6 +76,7 @@ pub struct HTMLIFrameElement {
     subpage_id: Cell<Option<SubpageId>>,
     containing_page_pipeline_id: Cell<Option<PipelineId>>,
     sandbox: Cell<Option<u8>>,
+    sandbox_list: MutNullableHeap<JS<DOMTokenList>>,
 }
 
 impl HTMLIFrameElementDerived for EventTarget {
@@ -77,6 +94,17 @@ impl HTMLIFrameElement {
         self.sandbox.get().is_some()
     }
 
+    /// Returns whether the iframe is sandboxed without the given allowance.
+    fn is_sandboxed_without(&self, allowance: SandboxAllowance) -> bool {
+        self.sandbox.get().map_or(false, |modes| modes & (allowance as u8) == 0)
+    }
+
+    /// Whether documents in this iframe get an opaque origin, because it is
+    /// sandboxed without allow-same-origin.
+    pub fn sandboxes_origin(&self) -> bool {
+        self.is_sandboxed_without(SandboxAllowance::AllowSameOrigin)
+    }
+
     pub fn get_url(&self) -> Option<Url> {
         let element = ElementCast::from_ref(self);
         element.get_attribute(&ns!(""), &atom!("src")).and_then(|src| {
@@ -99,9 +127,11 @@ impl HTMLIFrameElement {
         (subpage_id, old_subpage_id)
     }
 
-    pub fn navigate_child_browsing_context(&self, url: Url) {
+    /// Navigates the child browsing context to `url`. If `replace` is true, the new
+    /// document replaces the current session history entry instead of adding one.
+    pub fn navigate_child_browsing_context(&self, url: Url, replace: bool) {
         let sandboxed = if self.is_sandboxed() {
-            IFrameSandboxed
+            IFrameSandboxed { allow_scripts: !self.is_sandboxed_without(SandboxAllowance::AllowScripts) }
         } else {
             IFrameUnsandboxed
         };
@@ -117,7 +147,8 @@ impl HTMLIFrameElement {
                                                             window.pipeline(),
                                                             new_subpage_id,
                                                             old_subpage_id,
-                                                            sandboxed)).unwrap();
+                                                            sandboxed,
+                                                            replace)).unwrap();
 
         if mozbrowser_enabled() {
             // https://developer.mozilla.org/en-US/docs/Web/Events/mozbrowserloadstart
@@ -125,13 +156,55 @@ impl HTMLIFrameElement {
         }
     }
 
+    /// Returns whether the `loading` attribute is in the lazy state.
+    fn is_lazy(&self) -> bool {
+        let element = ElementCast::from_ref(self);
+        element.get_attribute(&ns!(""), &Atom::from_slice("loading")).map_or(false, |attr| {
+            attr.r().value().eq_ignore_ascii_case("lazy")
+        })
+    }
+
+    /// Returns whether the iframe is within the lazy load root margin of its
+    /// document's viewport. Only the block axis is considered.
+    pub fn is_near_viewport(&self) -> bool {
+        let window = window_from_node(self);
+        let window = window.r();
+        let rect = NodeCast::from_ref(self).get_bounding_content_box();
+        let viewport_top = window.ScrollY() as f32;
+        let viewport_bottom = viewport_top + window.InnerHeight() as f32;
+        is_within_lazy_load_margin(rect.origin.y.to_f32_px(), rect.max_y().to_f32_px(),
+                                   viewport_top, viewport_bottom)
+    }
+
+    /// Processes the iframe attributes, unless the iframe is lazy and hasn't
+    /// navigated yet, in which case its navigation waits until it is near the
+    /// viewport.
+    // https://html.spec.whatwg.org/multipage/#will-lazy-load-element-steps
+    fn process_the_iframe_attributes_or_defer(&self) {
+        if !self.is_lazy() || self.subpage_id.get().is_some() {
+            return self.process_the_iframe_attributes();
+        }
+
+        let document = document_from_node(self);
+        document.r().add_lazy_iframe(self);
+
+        // The iframe may already be in view, but layout can't tell until the
+        // node has been inserted, so check from a task.
+        let window = window_from_node(self);
+        let script_chan = window.r().script_chan();
+        let task = box LazyLoadCheckTask {
+            iframe: Trusted::new(window.r().get_cx(), self, script_chan.clone()),
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
     pub fn process_the_iframe_attributes(&self) {
         let url = match self.get_url() {
             Some(url) => url.clone(),
             None => Url::parse("about:blank").unwrap(),
         };
 
-        self.navigate_child_browsing_context(url);
+        self.navigate_child_browsing_context(url, false);
     }
 
     pub fn dispatch_mozbrowser_event(&self, event: MozBrowserEvent) {
@@ -193,6 +266,7 @@ impl HTMLIFrameElement {
             subpage_id: Cell::new(None),
             containing_page_pipeline_id: Cell::new(None),
             sandbox: Cell::new(None),
+            sandbox_list: Default::default(),
         }
     }
 
@@ -250,15 +324,10 @@ impl HTMLIFrameElementMethods for HTMLIFrameElement {
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-iframe-sandbox
-    fn Sandbox(&self) -> DOMString {
-        let element = ElementCast::from_ref(self);
-        element.get_string_attribute(&atom!("sandbox"))
-    }
-
-    // https://html.spec.whatwg.org/multipage/#dom-iframe-sandbox
-    fn SetSandbox(&self, sandbox: DOMString) {
-        let element = ElementCast::from_ref(self);
-        element.set_tokenlist_attribute(&atom!("sandbox"), sandbox);
+    fn Sandbox(&self) -> Root<DOMTokenList> {
+        self.sandbox_list.or_init(|| {
+            DOMTokenList::new(ElementCast::from_ref(self), &atom!("sandbox"))
+        })
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-iframe-contentwindow
@@ -276,15 +345,16 @@ impl HTMLIFrameElementMethods for HTMLIFrameElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-iframe-contentdocument
     fn GetContentDocument(&self) -> Option<Root<Document>> {
+        // Documents sandboxed without allow-same-origin have a unique origin.
+        if self.is_sandboxed_without(SandboxAllowance::AllowSameOrigin) {
+            return None;
+        }
         self.GetContentWindow().and_then(|window| {
-            let self_url = match self.get_url() {
-                Some(self_url) => self_url,
-                None => return None,
-            };
-            let win_url = window_from_node(self).r().get_url();
-
-            if UrlHelper::SameOrigin(&self_url, &win_url) {
-                Some(window.r().Document())
+            let content_document = window.r().Document();
+            let document = document_from_node(self);
+
+            if document.r().is_same_origin_domain(content_document.r()) {
+                Some(content_document)
             } else {
                 None
             }
@@ -337,6 +407,21 @@ impl HTMLIFrameElementMethods for HTMLIFrameElement {
         Err(NotSupported)
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-iframe-loading
+    fn Loading(&self) -> DOMString {
+        if self.is_lazy() {
+            "lazy".to_owned()
+        } else {
+            "eager".to_owned()
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-iframe-loading
+    fn SetLoading(&self, value: DOMString) {
+        let element = ElementCast::from_ref(self);
+        element.set_string_attribute(&Atom::from_slice("loading"), value);
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-dim-width
     make_getter!(Width);
     // https://html.spec.whatwg.org/multipage/#dom-dim-width
@@ -377,10 +462,16 @@ impl VirtualMethods for HTMLIFrameElement {
             &atom!(src) => {
                 if let AttributeMutation::Set(_) = mutation {
                     if NodeCast::from_ref(self).is_in_doc() {
-                        self.process_the_iframe_attributes();
+                        self.process_the_iframe_attributes_or_defer();
                     }
                 }
             },
+            name if *name == Atom::from_slice("loading") => {
+                // An iframe that stops being lazy loads immediately.
+                if !self.is_lazy() && document_from_node(self).r().remove_lazy_iframe(self) {
+                    self.process_the_iframe_attributes();
+                }
+            },
             _ => {},
         }
     }
@@ -398,7 +489,7 @@ impl VirtualMethods for HTMLIFrameElement {
         }
 
         if tree_in_doc {
-            self.process_the_iframe_attributes();
+            self.process_the_iframe_attributes_or_defer();
         }
     }
 
@@ -407,6 +498,8 @@ impl VirtualMethods for HTMLIFrameElement {
             s.unbind_from_tree(tree_in_doc);
         }
 
+        document_from_node(self).r().remove_lazy_iframe(self);
+
         // https://html.spec.whatwg.org/multipage/#a-browsing-context-is-discarded
         match (self.containing_page_pipeline_id(), self.subpage_id()) {
             (Some(containing_pipeline_id), Some(subpage_id)) => {
@@ -429,3 +522,16 @@ impl VirtualMethods for HTMLIFrameElement {
         }
     }
 }
+
+/// The task queued when a lazy iframe is inserted, to start its navigation if
+/// it is already near the viewport.
+struct LazyLoadCheckTask {
+    iframe: Trusted<HTMLIFrameElement>,
+}
+
+impl Runnable for LazyLoadCheckTask {
+    fn handler(self: Box<LazyLoadCheckTask>) {
+        let iframe = self.iframe.root();
+        document_from_node(iframe.r()).r().process_lazy_iframes();
+    }
+}
@@ -2,9 +2,11 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use cssparser::Parser as CssParser;
 use dom::attr::Attr;
 use dom::attr::AttrValue;
 use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::AttrBinding::AttrMethods;
 use dom::bindings::codegen::Bindings::HTMLImageElementBinding;
 use dom::bindings::codegen::Bindings::HTMLImageElementBinding::HTMLImageElementMethods;
 use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
@@ -28,16 +30,27 @@ use net_traits::image_cache_task::{ImageResponder, ImageResponse};
 use script_task::ScriptTaskEventCategory::UpdateReplacedElement;
 use script_task::{CommonScriptMsg, Runnable, ScriptChan};
 use std::borrow::ToOwned;
+use std::cell::Cell;
 use std::sync::Arc;
 use string_cache::Atom;
+use style::media_queries::{Device, MediaType, parse_media_query_list};
 use url::{Url, UrlParser};
-use util::str::DOMString;
+use util::str::{DOMString, HTML_SPACE_CHARACTERS};
+
+// https://html.spec.whatwg.org/multipage/#img-req-state
+#[derive(JSTraceable, HeapSizeOf, Copy, Clone, PartialEq)]
+enum ImageRequestState {
+    Unavailable,
+    CompletelyAvailable,
+    Broken,
+}
 
 #[dom_struct]
 pub struct HTMLImageElement {
     htmlelement: HTMLElement,
     url: DOMRefCell<Option<Url>>,
     image: DOMRefCell<Option<Arc<Image>>>,
+    state: Cell<ImageRequestState>,
 }
 
 impl HTMLImageElementDerived for EventTarget {
@@ -76,22 +89,29 @@ impl Runnable for ImageResponseHandlerRunnable {
         // Update the image field
         let element = self.element.root();
         let element_ref = element.r();
-        *element_ref.image.borrow_mut() = match self.image {
-            ImageResponse::Loaded(image) | ImageResponse::PlaceholderLoaded(image) => {
-                Some(image)
+        let (image, state) = match self.image {
+            ImageResponse::Loaded(image) => {
+                (Some(image), ImageRequestState::CompletelyAvailable)
             }
-            ImageResponse::None => None,
+            ImageResponse::PlaceholderLoaded(image) => (Some(image), ImageRequestState::Broken),
+            ImageResponse::None => (None, ImageRequestState::Broken),
         };
+        *element_ref.image.borrow_mut() = image;
+        element_ref.state.set(state);
 
         // Mark the node dirty
         let node = NodeCast::from_ref(element.r());
         let document = document_from_node(node);
         document.r().content_changed(node, NodeDamage::OtherNodeDamage);
 
-        // Fire image.onload
+        // Fire image.onload, or image.onerror if the image turned out to be broken.
         let window = window_from_node(document.r());
+        let event_type = match state {
+            ImageRequestState::CompletelyAvailable => "load",
+            _ => "error",
+        };
         let event = Event::new(GlobalRef::Window(window.r()),
-                               "load".to_owned(),
+                               event_type.to_owned(),
                                EventBubbles::DoesNotBubble,
                                EventCancelable::NotCancelable);
         let event = event.r();
@@ -103,7 +123,237 @@ impl Runnable for ImageResponseHandlerRunnable {
     }
 }
 
+/// An image candidate parsed from a `srcset` attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageSource {
+    pub url: DOMString,
+    pub descriptor: ImageSourceDescriptor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageSourceDescriptor {
+    Density(f32),
+    Width(u32),
+}
+
+fn is_html_space(c: char) -> bool {
+    HTML_SPACE_CHARACTERS.contains(&c)
+}
+
+/// https://html.spec.whatwg.org/multipage/#parse-a-srcset-attribute
+pub fn parse_a_srcset_attribute(input: &str) -> Vec<ImageSource> {
+    let mut candidates = vec![];
+    let mut position = input;
+    loop {
+        // Step 4.
+        position = position.trim_left_matches(|c: char| c == ',' || is_html_space(c));
+        // Step 5.
+        if position.is_empty() {
+            return candidates;
+        }
+        // Step 6.
+        let url_end = position.find(is_html_space).unwrap_or(position.len());
+        let (url, rest) = position.split_at(url_end);
+        position = rest;
+        // Step 8.
+        let (url, descriptors) = if url.ends_with(',') {
+            (url.trim_right_matches(','), vec![])
+        } else {
+            // Collect descriptors up to the next comma that isn't inside parentheses.
+            let mut descriptors = vec![];
+            let mut current = String::new();
+            let mut in_parens = false;
+            let mut end = position.len();
+            for (index, c) in position.char_indices() {
+                match c {
+                    ',' if !in_parens => {
+                        end = index + 1;
+                        break;
+                    },
+                    c if is_html_space(c) && !in_parens => {
+                        if !current.is_empty() {
+                            descriptors.push(current);
+                            current = String::new();
+                        }
+                    },
+                    c => {
+                        if c == '(' {
+                            in_parens = true;
+                        } else if c == ')' {
+                            in_parens = false;
+                        }
+                        current.push(c);
+                    },
+                }
+            }
+            if !current.is_empty() {
+                descriptors.push(current);
+            }
+            position = &position[end..];
+            (url, descriptors)
+        };
+        // Steps 9-14.
+        if let Some(descriptor) = parse_srcset_descriptors(&descriptors) {
+            candidates.push(ImageSource {
+                url: url.to_owned(),
+                descriptor: descriptor,
+            });
+        }
+    }
+}
+
+/// https://html.spec.whatwg.org/multipage/#parse-a-srcset-attribute, step 13.
+fn parse_srcset_descriptors(descriptors: &[String]) -> Option<ImageSourceDescriptor> {
+    let mut width = None;
+    let mut density = None;
+    let mut height = None;
+    for descriptor in descriptors {
+        let kind = descriptor.chars().last().unwrap();
+        let value = &descriptor[..descriptor.len() - kind.len_utf8()];
+        match kind {
+            'w' if width.is_none() && density.is_none() => {
+                match value.parse::<u32>() {
+                    Ok(value) if value > 0 => width = Some(value),
+                    _ => return None,
+                }
+            },
+            'x' if width.is_none() && density.is_none() && height.is_none() => {
+                match value.parse::<f32>() {
+                    Ok(value) if value >= 0. && value.is_finite() => density = Some(value),
+                    _ => return None,
+                }
+            },
+            'h' if height.is_none() && density.is_none() => {
+                match value.parse::<u32>() {
+                    Ok(value) if value > 0 => height = Some(value),
+                    _ => return None,
+                }
+            },
+            _ => return None,
+        }
+    }
+    if height.is_some() && width.is_none() {
+        return None;
+    }
+    Some(match (width, density) {
+        (Some(width), _) => ImageSourceDescriptor::Width(width),
+        (None, Some(density)) => ImageSourceDescriptor::Density(density),
+        (None, None) => ImageSourceDescriptor::Density(1.),
+    })
+}
+
+/// https://html.spec.whatwg.org/multipage/#parse-a-sizes-attribute
+///
+/// Returns the source size in CSS pixels, or `None` if no size applies.
+pub fn parse_a_sizes_attribute(input: &str, device: &Device) -> Option<f32> {
+    for size in input.split(',') {
+        let size = size.trim_matches(HTML_SPACE_CHARACTERS);
+        let (condition, length) = match size.rfind(HTML_SPACE_CHARACTERS) {
+            Some(index) => (&size[..index], &size[index + 1..]),
+            None => ("", size),
+        };
+        let length = match parse_source_size_length(length, device) {
+            Some(length) => length,
+            None => continue,
+        };
+        let condition = condition.trim_matches(HTML_SPACE_CHARACTERS);
+        if condition.is_empty() ||
+           parse_media_query_list(&mut CssParser::new(condition)).evaluate(device) {
+            return Some(length);
+        }
+    }
+    None
+}
+
+fn parse_source_size_length(value: &str, device: &Device) -> Option<f32> {
+    let (number, factor) = if value.ends_with("px") {
+        (&value[..value.len() - 2], 1.)
+    } else if value.ends_with("vw") {
+        (&value[..value.len() - 2], device.viewport_size.width.get() / 100.)
+    } else if value.ends_with("vh") {
+        (&value[..value.len() - 2], device.viewport_size.height.get() / 100.)
+    } else if value.ends_with("rem") {
+        (&value[..value.len() - 3], 16.)
+    } else if value.ends_with("em") {
+        (&value[..value.len() - 2], 16.)
+    } else if value == "0" {
+        (value, 0.)
+    } else {
+        return None;
+    };
+    match number.parse::<f32>() {
+        Ok(number) if number >= 0. && number.is_finite() => Some(number * factor),
+        _ => None,
+    }
+}
+
+/// https://html.spec.whatwg.org/multipage/#select-an-image-source
+///
+/// Picks the candidate with the smallest pixel density that is at least `device_pixel_ratio`,
+/// or the densest candidate if none is dense enough.
+pub fn select_image_source(candidates: &[ImageSource],
+                           source_size: f32,
+                           device_pixel_ratio: f32) -> Option<&ImageSource> {
+    let mut best: Option<(&ImageSource, f32)> = None;
+    for candidate in candidates {
+        let density = match candidate.descriptor {
+            ImageSourceDescriptor::Density(density) => density,
+            ImageSourceDescriptor::Width(width) => width as f32 / source_size,
+        };
+        best = match best {
+            Some((_, best_density)) if (best_density < device_pixel_ratio && density > best_density) ||
+                                       (density >= device_pixel_ratio && density < best_density) => {
+                Some((candidate, density))
+            },
+            None => Some((candidate, density)),
+            best => best,
+        };
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
 impl HTMLImageElement {
+    /// https://html.spec.whatwg.org/multipage/#update-the-image-data
+    fn update_the_image_data(&self) {
+        let element = ElementCast::from_ref(self);
+        let window = window_from_node(self);
+        let src = element.get_attribute(&ns!(""), &atom!("src")).map(|attr| attr.r().Value());
+        let srcset = element.get_attribute(&ns!(""), &atom!("srcset")).map(|attr| attr.r().Value());
+        let selected = match srcset {
+            Some(srcset) => {
+                let mut candidates = parse_a_srcset_attribute(&srcset);
+                // The src attribute is a 1x candidate, unless srcset already provides one
+                // or uses width descriptors.
+                if let Some(src) = src {
+                    let has_1x_or_width = candidates.iter().any(|candidate| {
+                        match candidate.descriptor {
+                            ImageSourceDescriptor::Density(density) => density == 1.,
+                            ImageSourceDescriptor::Width(_) => true,
+                        }
+                    });
+                    if !src.is_empty() && !has_1x_or_width {
+                        candidates.push(ImageSource {
+                            url: src,
+                            descriptor: ImageSourceDescriptor::Density(1.),
+                        });
+                    }
+                }
+                let window_size = window.r().window_size();
+                let device = window_size.map(|size| Device::new(MediaType::Screen, size.initial_viewport));
+                let source_size = device.as_ref().map_or(0., |device| {
+                    let sizes = element.get_string_attribute(&atom!("sizes"));
+                    parse_a_sizes_attribute(&sizes, device)
+                        .unwrap_or(device.viewport_size.width.get())
+                });
+                let device_pixel_ratio = window_size.map_or(1., |size| size.device_pixel_ratio.get());
+                select_image_source(&candidates, source_size, device_pixel_ratio)
+                    .map(|candidate| candidate.url.clone())
+            },
+            None => src,
+        };
+        self.update_image(selected.map(|src| (src, window.r().get_url())));
+    }
+
     /// Makes the local `image` member match the status of the `src` attribute and starts
     /// prefetching the image. This method must be called after `src` is changed.
     fn update_image(&self, value: Option<(DOMString, Url)>) {
@@ -112,16 +362,26 @@ impl HTMLImageElement {
         let window = document.r().window();
         let window = window.r();
         let image_cache = window.image_cache_task();
-        match value {
+        let img_url = value.and_then(|(src, base_url)| {
+            if src.is_empty() {
+                return None;
+            }
+            Some(UrlParser::new().base_url(&base_url).parse(&src))
+        });
+        match img_url {
             None => {
                 *self.url.borrow_mut() = None;
                 *self.image.borrow_mut() = None;
+                self.state.set(ImageRequestState::CompletelyAvailable);
+            }
+            Some(Err(_)) => {
+                *self.url.borrow_mut() = None;
+                *self.image.borrow_mut() = None;
+                self.state.set(ImageRequestState::Broken);
             }
-            Some((src, base_url)) => {
-                let img_url = UrlParser::new().base_url(&base_url).parse(&src);
-                // FIXME: handle URL parse errors more gracefully.
-                let img_url = img_url.unwrap();
+            Some(Ok(img_url)) => {
                 *self.url.borrow_mut() = Some(img_url.clone());
+                self.state.set(ImageRequestState::Unavailable);
 
                 let trusted_node = Trusted::new(window.get_cx(), self, window.script_chan());
                 let (responder_sender, responder_receiver) = ipc::channel().unwrap();
@@ -147,6 +407,7 @@ impl HTMLImageElement {
             htmlelement: HTMLElement::new_inherited(HTMLElementTypeId::HTMLImageElement, localName, prefix, document),
             url: DOMRefCell::new(None),
             image: DOMRefCell::new(None),
+            state: Cell::new(ImageRequestState::CompletelyAvailable),
         }
     }
 
@@ -158,18 +419,24 @@ impl HTMLImageElement {
         Node::reflect_node(box element, document, HTMLImageElementBinding::Wrap)
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-image
     pub fn Image(global: GlobalRef,
                  width: Option<u32>,
                  height: Option<u32>) -> Fallible<Root<HTMLImageElement>> {
+        // Step 1.
         let document = global.as_window().Document();
+        // Step 2.
         let image = HTMLImageElement::new("img".to_owned(), None, document.r());
+        // Step 3.
         if let Some(w) = width {
             image.SetWidth(w);
         }
+        // Step 4.
         if let Some(h) = height {
             image.SetHeight(h);
         }
 
+        // Step 5.
         Ok(image)
     }
 }
@@ -205,6 +472,23 @@ impl HTMLImageElementMethods for HTMLImageElement {
     // https://html.spec.whatwg.org/multipage/#dom-img-src
     make_setter!(SetSrc, "src");
 
+    // https://html.spec.whatwg.org/multipage/#dom-img-srcset
+    make_getter!(Srcset);
+
+    // https://html.spec.whatwg.org/multipage/#dom-img-srcset
+    make_setter!(SetSrcset, "srcset");
+
+    // https://html.spec.whatwg.org/multipage/#dom-img-sizes
+    make_getter!(Sizes);
+
+    // https://html.spec.whatwg.org/multipage/#dom-img-sizes
+    make_setter!(SetSizes, "sizes");
+
+    // https://html.spec.whatwg.org/multipage/#dom-img-currentsrc
+    fn CurrentSrc(&self) -> DOMString {
+        self.url.borrow().as_ref().map_or("".to_owned(), |url| url.serialize())
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-img-usemap
     make_getter!(UseMap);
     // https://html.spec.whatwg.org/multipage/#dom-img-usemap
@@ -222,8 +506,18 @@ impl HTMLImageElementMethods for HTMLImageElement {
     // https://html.spec.whatwg.org/multipage/#dom-img-width
     fn Width(&self) -> u32 {
         let node = NodeCast::from_ref(self);
-        let rect = node.get_bounding_content_box();
-        rect.size.width.to_px() as u32
+        if node.is_in_doc() {
+            let rect = node.get_bounding_content_box();
+            return rect.size.width.to_px() as u32;
+        }
+        // An image that isn't being rendered reports its width attribute, falling back to its
+        // natural width.
+        let element = ElementCast::from_ref(self);
+        if element.has_attribute(&atom!("width")) {
+            element.get_uint_attribute(&atom!("width"), 0)
+        } else {
+            self.NaturalWidth()
+        }
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-img-width
@@ -235,8 +529,18 @@ impl HTMLImageElementMethods for HTMLImageElement {
     // https://html.spec.whatwg.org/multipage/#dom-img-height
     fn Height(&self) -> u32 {
         let node = NodeCast::from_ref(self);
-        let rect = node.get_bounding_content_box();
-        rect.size.height.to_px() as u32
+        if node.is_in_doc() {
+            let rect = node.get_bounding_content_box();
+            return rect.size.height.to_px() as u32;
+        }
+        // An image that isn't being rendered reports its height attribute, falling back to its
+        // natural height.
+        let element = ElementCast::from_ref(self);
+        if element.has_attribute(&atom!("height")) {
+            element.get_uint_attribute(&atom!("height"), 0)
+        } else {
+            self.NaturalHeight()
+        }
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-img-height
@@ -247,6 +551,9 @@ impl HTMLImageElementMethods for HTMLImageElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-img-naturalwidth
     fn NaturalWidth(&self) -> u32 {
+        if self.state.get() != ImageRequestState::CompletelyAvailable {
+            return 0;
+        }
         let image = self.image.borrow();
 
         match *image {
@@ -257,6 +564,9 @@ impl HTMLImageElementMethods for HTMLImageElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-img-naturalheight
     fn NaturalHeight(&self) -> u32 {
+        if self.state.get() != ImageRequestState::CompletelyAvailable {
+            return 0;
+        }
         let image = self.image.borrow();
 
         match *image {
@@ -267,8 +577,8 @@ impl HTMLImageElementMethods for HTMLImageElement {
 
     // https://html.spec.whatwg.org/multipage/#dom-img-complete
     fn Complete(&self) -> bool {
-        let image = self.image.borrow();
-        image.is_some()
+        // An image without a src, or whose request has either finished or failed, is complete.
+        self.state.get() != ImageRequestState::Unavailable
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-img-name
@@ -317,11 +627,7 @@ impl VirtualMethods for HTMLImageElement {
     fn attribute_mutated(&self, attr: &Attr, mutation: AttributeMutation) {
         self.super_type().unwrap().attribute_mutated(attr, mutation);
         match attr.local_name() {
-            &atom!(src) => {
-                self.update_image(mutation.new_value(attr).map(|value| {
-                    ((**value).to_owned(), window_from_node(self).get_url())
-                }));
-            },
+            &atom!(src) | &atom!(srcset) | &atom!(sizes) => self.update_the_image_data(),
             _ => {},
         }
     }
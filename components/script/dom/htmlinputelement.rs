@@ -23,6 +23,7 @@ use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::htmlformelement::{FormControl, FormSubmitter, HTMLFormElement};
 use dom::htmlformelement::{ResetFrom, SubmittedFrom};
+use dom::inputevent::InputEvent;
 use dom::keyboardevent::KeyboardEvent;
 use dom::node::{Node, NodeDamage, NodeTypeId};
 use dom::node::{document_from_node, window_from_node};
@@ -608,6 +609,20 @@ impl VirtualMethods for HTMLInputElement {
              self.input_type.get() == InputType::InputPassword) {
                 let keyevent: Option<&KeyboardEvent> = KeyboardEventCast::to_ref(event);
                 keyevent.map(|keyevent| {
+                    // https://w3c.github.io/input-events/#event-type-beforeinput
+                    let edit = keyevent.get_key().and_then(|key| {
+                        self.textinput.borrow().edit_for_key(key, keyevent.get_key_modifiers())
+                    });
+                    let window = window_from_node(self);
+                    let target = EventTargetCast::from_ref(self);
+                    if let Some(ref edit) = edit {
+                        if !InputEvent::fire_for_edit(window.r(), target, "beforeinput",
+                                                      EventCancelable::Cancelable, edit) {
+                            event.PreventDefault();
+                            return;
+                        }
+                    }
+
                     // This can't be inlined, as holding on to textinput.borrow_mut()
                     // during self.implicit_submission will cause a panic.
                     let action = self.textinput.borrow_mut().handle_keydown(keyevent);
@@ -622,6 +637,10 @@ impl VirtualMethods for HTMLInputElement {
                             self.value_changed.set(true);
                             self.force_relayout();
                             event.PreventDefault();
+                            if let Some(ref edit) = edit {
+                                InputEvent::fire_for_edit(window.r(), target, "input",
+                                                          EventCancelable::NotCancelable, edit);
+                            }
                         }
                         RedrawSelection => {
                             self.force_relayout();
@@ -2,16 +2,21 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use dom::activation::{self, Activatable};
+use dom::bindings::codegen::Bindings::DocumentBinding::DocumentMethods;
 use dom::bindings::codegen::Bindings::HTMLLabelElementBinding;
 use dom::bindings::codegen::Bindings::HTMLLabelElementBinding::HTMLLabelElementMethods;
-use dom::bindings::codegen::InheritTypes::HTMLLabelElementDerived;
+use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast, HTMLLabelElementDerived, NodeCast};
 use dom::bindings::js::Root;
 use dom::document::Document;
-use dom::element::ElementTypeId;
+use dom::element::{Element, ElementTypeId};
+use dom::event::Event;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::htmlformelement::{FormControl, HTMLFormElement};
-use dom::node::{Node, NodeTypeId};
+use dom::node::{Node, NodeTypeId, document_from_node};
+use msg::constellation_msg::FocusType;
+use std::borrow::ToOwned;
 use util::str::DOMString;
 
 #[dom_struct]
@@ -46,11 +51,96 @@ impl HTMLLabelElement {
     }
 }
 
+impl HTMLLabelElement {
+    // https://html.spec.whatwg.org/multipage/#labeled-control
+    pub fn labeled_control(&self) -> Option<Root<HTMLElement>> {
+        let element = ElementCast::from_ref(self);
+        if let Some(for_attr) = element.get_attribute(&ns!(""), &atom!("for")) {
+            let doc = document_from_node(self);
+            let id = (**for_attr.r().value()).to_owned();
+            return match doc.r().GetElementById(id) {
+                Some(ref control) => {
+                    match HTMLElementCast::to_ref(control.r()) {
+                        Some(control) if control.is_labelable_element() => Some(Root::from_ref(control)),
+                        _ => None,
+                    }
+                },
+                None => None,
+            };
+        }
+
+        let node = NodeCast::from_ref(self);
+        node.traverse_preorder()
+            .filter_map(HTMLElementCast::to_root)
+            .find(|control| control.r().is_labelable_element())
+    }
+}
+
 impl HTMLLabelElementMethods for HTMLLabelElement {
     // https://html.spec.whatwg.org/multipage#dom-fae-form
     fn GetForm(&self) -> Option<Root<HTMLFormElement>> {
         self.form_owner()
     }
+
+    // https://html.spec.whatwg.org/multipage/#dom-label-htmlfor
+    make_getter!(HtmlFor, "for");
+
+    // https://html.spec.whatwg.org/multipage/#dom-label-htmlfor
+    make_setter!(SetHtmlFor, "for");
+
+    // https://html.spec.whatwg.org/multipage/#dom-label-control
+    fn GetControl(&self) -> Option<Root<HTMLElement>> {
+        self.labeled_control()
+    }
+}
+
+impl Activatable for HTMLLabelElement {
+    fn as_element<'b>(&'b self) -> &'b Element {
+        ElementCast::from_ref(self)
+    }
+
+    fn is_instance_activatable(&self) -> bool {
+        true
+    }
+
+    // https://html.spec.whatwg.org/multipage/#run-pre-click-activation-steps
+    fn pre_click_activation(&self) {
+    }
+
+    // https://html.spec.whatwg.org/multipage/#run-canceled-activation-steps
+    fn canceled_activation(&self) {
+    }
+
+    // https://html.spec.whatwg.org/multipage/#the-label-element:activation-behaviour
+    fn activation_behavior(&self, _event: &Event, target: &EventTarget) {
+        let control = match self.labeled_control() {
+            Some(control) => control,
+            None => return,
+        };
+        let control_element = ElementCast::from_ref(control.r());
+
+        // Clicks on the control itself (or inside it) are handled by the control.
+        let target = NodeCast::to_ref(target);
+        if target.map_or(false, |target| {
+            target.inclusive_ancestors().any(|ancestor| ancestor.r() == NodeCast::from_ref(control.r()))
+        }) {
+            return;
+        }
+
+        if control_element.is_focusable_area() {
+            let document = document_from_node(self);
+            let document = document.r();
+            document.begin_focus_transaction();
+            document.request_focus(control_element);
+            document.commit_focus_transaction(FocusType::Element);
+        }
+
+        activation::synthetic_click_activation(control_element, false, false, false, false);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#implicit-submission
+    fn implicit_submission(&self, _ctrlKey: bool, _shiftKey: bool, _altKey: bool, _metaKey: bool) {
+    }
 }
 
 impl FormControl for HTMLLabelElement {}
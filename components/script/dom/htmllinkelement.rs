@@ -5,6 +5,7 @@
 use cssparser::Parser as CssParser;
 use document_loader::LoadType;
 use dom::attr::{Attr, AttrValue};
+use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::HTMLLinkElementBinding;
 use dom::bindings::codegen::Bindings::HTMLLinkElementBinding::HTMLLinkElementMethods;
 use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
@@ -29,6 +30,8 @@ use script_traits::StylesheetLoadResponder;
 use std::ascii::AsciiExt;
 use std::borrow::ToOwned;
 use std::default::Default;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use string_cache::Atom;
 use style::media_queries::parse_media_query_list;
 use url::UrlParser;
@@ -38,6 +41,9 @@ use util::str::{DOMString, HTML_SPACE_CHARACTERS};
 pub struct HTMLLinkElement {
     htmlelement: HTMLElement,
     rel_list: MutNullableHeap<JS<DOMTokenList>>,
+    /// The disabled flag of the stylesheet most recently requested for this element, if any.
+    #[ignore_heap_size_of = "Arc"]
+    stylesheet_disabled: DOMRefCell<Option<Arc<AtomicBool>>>,
 }
 
 impl HTMLLinkElementDerived for EventTarget {
@@ -53,6 +59,7 @@ impl HTMLLinkElement {
         HTMLLinkElement {
             htmlelement: HTMLElement::new_inherited(HTMLElementTypeId::HTMLLinkElement, localName, prefix, document),
             rel_list: Default::default(),
+            stylesheet_disabled: DOMRefCell::new(None),
         }
     }
 
@@ -104,25 +111,24 @@ impl VirtualMethods for HTMLLinkElement {
 
     fn attribute_mutated(&self, attr: &Attr, mutation: AttributeMutation) {
         self.super_type().unwrap().attribute_mutated(attr, mutation);
-        if !NodeCast::from_ref(self).is_in_doc() || mutation == AttributeMutation::Removed {
+        if !NodeCast::from_ref(self).is_in_doc() {
             return;
         }
-        let rel = get_attr(ElementCast::from_ref(self), &atom!(rel));
         match attr.local_name() {
-            &atom!(href) => {
-                if is_stylesheet(&rel) {
-                    self.handle_stylesheet_url(&attr.value());
-                } else if is_favicon(&rel) {
-                    self.handle_favicon_url(&attr.value());
-                }
-            },
-            &atom!(media) => {
-                if is_stylesheet(&rel) {
-                    self.handle_stylesheet_url(&attr.value());
-                }
+            &atom!(href) | &atom!(rel) | &atom!(media) => {
+                self.update_stylesheet();
             },
             _ => {},
         }
+        if mutation == AttributeMutation::Removed {
+            return;
+        }
+        if let &atom!(href) = attr.local_name() {
+            let rel = get_attr(ElementCast::from_ref(self), &atom!(rel));
+            if !is_stylesheet(&rel) && is_favicon(&rel) {
+                self.handle_favicon_url(&attr.value());
+            }
+        }
     }
 
     fn parse_plain_attribute(&self, name: &Atom, value: DOMString) -> AttrValue {
@@ -154,11 +160,47 @@ impl VirtualMethods for HTMLLinkElement {
             }
         }
     }
+
+    fn unbind_from_tree(&self, tree_in_doc: bool) {
+        if let Some(ref s) = self.super_type() {
+            s.unbind_from_tree(tree_in_doc);
+        }
+
+        if tree_in_doc {
+            self.remove_stylesheet();
+        }
+    }
 }
 
 
 impl HTMLLinkElement {
+    /// Re-evaluates which stylesheet, if any, this element refers to after its
+    /// `href`, `rel` or `media` attribute changed.
+    fn update_stylesheet(&self) {
+        let element = ElementCast::from_ref(self);
+        let rel = get_attr(element, &atom!("rel"));
+        match get_attr(element, &atom!("href")) {
+            Some(ref href) if is_stylesheet(&rel) => self.handle_stylesheet_url(href),
+            _ => self.remove_stylesheet(),
+        }
+    }
+
+    /// Stops the stylesheet previously requested for this element from applying to the
+    /// document, including one that is still loading.
+    fn remove_stylesheet(&self) {
+        if let Some(disabled) = self.stylesheet_disabled.borrow_mut().take() {
+            // Layout handles messages in order, so a sheet that is still loading has been
+            // added by the time this removal is processed.
+            let window = window_from_node(self);
+            let LayoutChan(ref layout_chan) = window.r().layout_chan();
+            layout_chan.send(Msg::RemoveStylesheet(disabled)).unwrap();
+        }
+    }
+
     fn handle_stylesheet_url(&self, href: &str) {
+        // The previously requested stylesheet no longer applies.
+        self.remove_stylesheet();
+
         let window = window_from_node(self);
         let window = window.r();
         match UrlParser::new().base_url(&window.get_url()).parse(href) {
@@ -178,9 +220,13 @@ impl HTMLLinkElement {
                 let link_element = Trusted::new(window.get_cx(), self, window.script_chan().clone());
                 let load_dispatcher = StylesheetLoadDispatcher::new(link_element);
 
+                let disabled = Arc::new(AtomicBool::new(false));
+                *self.stylesheet_disabled.borrow_mut() = Some(disabled.clone());
+
                 let pending = doc.r().prepare_async_load(LoadType::Stylesheet(url.clone()));
                 let LayoutChan(ref layout_chan) = window.layout_chan();
-                layout_chan.send(Msg::LoadStylesheet(url, media, pending, box load_dispatcher)).unwrap();
+                layout_chan.send(Msg::LoadStylesheet(url, media, pending, box load_dispatcher,
+                                                     disabled)).unwrap();
             }
             Err(e) => debug!("Parsing url {} failed: {}", href, e)
         }
@@ -211,7 +257,7 @@ impl HTMLLinkElementMethods for HTMLLinkElement {
     make_getter!(Rel);
 
     // https://html.spec.whatwg.org/multipage/#dom-link-rel
-    make_setter!(SetRel, "rel");
+    make_tokenlist_setter!(SetRel, "rel");
 
     // https://html.spec.whatwg.org/multipage/#dom-link-media
     make_getter!(Media);
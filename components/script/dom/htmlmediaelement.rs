@@ -2,17 +2,42 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use dom::bindings::codegen::InheritTypes::HTMLMediaElementDerived;
+use dom::bindings::codegen::Bindings::HTMLMediaElementBinding::CanPlayTypeResult;
+use dom::bindings::codegen::Bindings::HTMLMediaElementBinding::HTMLMediaElementConstants;
+use dom::bindings::codegen::Bindings::HTMLMediaElementBinding::HTMLMediaElementMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, EventTargetCast, HTMLMediaElementDerived};
+use dom::bindings::error::Error::IndexSize;
+use dom::bindings::error::ErrorResult;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::num::Finite;
+use dom::bindings::refcounted::Trusted;
 use dom::document::Document;
 use dom::element::ElementTypeId;
+use dom::event::{Event, EventBubbles, EventCancelable};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
-use dom::node::NodeTypeId;
+use dom::node::{NodeTypeId, window_from_node};
+use script_task::ScriptTaskEventCategory::DomEvent;
+use script_task::{CommonScriptMsg, Runnable, ScriptChan};
+use std::ascii::AsciiExt;
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::f64;
 use util::str::DOMString;
 
 #[dom_struct]
 pub struct HTMLMediaElement {
     htmlelement: HTMLElement,
+    paused: Cell<bool>,
+    /// Stays HAVE_NOTHING until there is a media backend to load resources.
+    ready_state: Cell<u16>,
+    current_playback_position: Cell<f64>,
+    /// NaN until the media resource's metadata is known.
+    duration: Cell<f64>,
+    volume: Cell<f64>,
+    muted: Cell<bool>,
+    default_playback_rate: Cell<f64>,
+    playback_rate: Cell<f64>,
 }
 
 impl HTMLMediaElementDerived for EventTarget {
@@ -31,7 +56,15 @@ impl HTMLMediaElement {
                          -> HTMLMediaElement {
         HTMLMediaElement {
             htmlelement:
-                HTMLElement::new_inherited(HTMLElementTypeId::HTMLMediaElement(type_id), tag_name, prefix, document)
+                HTMLElement::new_inherited(HTMLElementTypeId::HTMLMediaElement(type_id), tag_name, prefix, document),
+            paused: Cell::new(true),
+            ready_state: Cell::new(HTMLMediaElementConstants::HAVE_NOTHING),
+            current_playback_position: Cell::new(0.),
+            duration: Cell::new(f64::NAN),
+            volume: Cell::new(1.),
+            muted: Cell::new(false),
+            default_playback_rate: Cell::new(1.),
+            playback_rate: Cell::new(1.),
         }
     }
 
@@ -39,6 +72,231 @@ impl HTMLMediaElement {
     pub fn htmlelement(&self) -> &HTMLElement {
         &self.htmlelement
     }
+
+    fn fire_simple_event(&self, type_: &str) {
+        let window = window_from_node(self);
+        let event = Event::new(GlobalRef::Window(window.r()),
+                               type_.to_owned(),
+                               EventBubbles::DoesNotBubble,
+                               EventCancelable::NotCancelable);
+        let target = EventTargetCast::from_ref(self);
+        event.r().fire(target);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#queue-a-media-element-task
+    fn queue_simple_event(&self, type_: &'static str) {
+        let window = window_from_node(self);
+        let script_chan = window.r().script_chan();
+        let task = box MediaElementEventTask {
+            element: Trusted::new(window.r().get_cx(), self, script_chan.clone()),
+            type_: type_,
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-seek
+    fn seek(&self, time: f64) {
+        // Steps 6-8.
+        let duration = self.duration.get();
+        let time = if duration.is_finite() && time > duration { duration } else { time };
+        let time = if time < 0. { 0. } else { time };
+
+        // Step 10.
+        self.queue_simple_event("seeking");
+
+        // Step 11.
+        self.current_playback_position.set(time);
+
+        // Steps 16-17.
+        self.queue_simple_event("timeupdate");
+        self.queue_simple_event("seeked");
+    }
+
+    // https://html.spec.whatwg.org/multipage/#ended-playback
+    fn has_ended_playback(&self) -> bool {
+        let duration = self.duration.get();
+        duration.is_finite() &&
+            self.current_playback_position.get() >= duration &&
+            !self.Loop()
+    }
+}
+
+impl HTMLMediaElementMethods for HTMLMediaElement {
+    // https://html.spec.whatwg.org/multipage/#dom-media-preload
+    fn Preload(&self) -> DOMString {
+        let element = ElementCast::from_ref(self);
+        if !element.has_attribute(&atom!("preload")) {
+            return "metadata".to_owned();
+        }
+        let mut value = element.get_string_attribute(&atom!("preload"));
+        value.make_ascii_lowercase();
+        match &*value {
+            "none" | "metadata" => value,
+            "" | "auto" => "auto".to_owned(),
+            _ => "metadata".to_owned(),
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-preload
+    make_setter!(SetPreload, "preload");
+
+    // https://html.spec.whatwg.org/multipage/#dom-navigator-canplaytype
+    fn CanPlayType(&self, _type_: DOMString) -> CanPlayTypeResult {
+        // There is no media backend, so no type can be played.
+        CanPlayTypeResult::_empty
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-currenttime
+    fn CurrentTime(&self) -> Finite<f64> {
+        Finite::wrap(self.current_playback_position.get())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-currenttime
+    fn SetCurrentTime(&self, time: Finite<f64>) {
+        self.seek(*time);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-duration
+    fn Duration(&self) -> f64 {
+        self.duration.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-defaultplaybackrate
+    fn DefaultPlaybackRate(&self) -> Finite<f64> {
+        Finite::wrap(self.default_playback_rate.get())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-defaultplaybackrate
+    fn SetDefaultPlaybackRate(&self, rate: Finite<f64>) {
+        if *rate != self.default_playback_rate.get() {
+            self.default_playback_rate.set(*rate);
+            self.queue_simple_event("ratechange");
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-playbackrate
+    fn PlaybackRate(&self) -> Finite<f64> {
+        Finite::wrap(self.playback_rate.get())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-playbackrate
+    fn SetPlaybackRate(&self, rate: Finite<f64>) {
+        if *rate != self.playback_rate.get() {
+            self.playback_rate.set(*rate);
+            self.queue_simple_event("ratechange");
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-paused
+    fn Paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-ended
+    fn Ended(&self) -> bool {
+        self.has_ended_playback()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-loop
+    make_bool_getter!(Loop);
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-loop
+    make_bool_setter!(SetLoop, "loop");
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-volume
+    fn Volume(&self) -> Finite<f64> {
+        Finite::wrap(self.volume.get())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-volume
+    fn SetVolume(&self, volume: Finite<f64>) -> ErrorResult {
+        let volume = *volume;
+        if volume < 0. || volume > 1. {
+            return Err(IndexSize);
+        }
+        if volume != self.volume.get() {
+            self.volume.set(volume);
+            self.queue_simple_event("volumechange");
+        }
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-muted
+    fn Muted(&self) -> bool {
+        self.muted.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-muted
+    fn SetMuted(&self, muted: bool) {
+        if muted != self.muted.get() {
+            self.muted.set(muted);
+            self.queue_simple_event("volumechange");
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-defaultmuted
+    make_bool_getter!(DefaultMuted, "muted");
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-defaultmuted
+    make_bool_setter!(SetDefaultMuted, "muted");
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-play
+    fn Play(&self) {
+        // TODO: Step 1: invoke the media element's load algorithm once there is a media backend.
+
+        // Step 2.
+        if self.has_ended_playback() {
+            self.seek(0.);
+        }
+
+        // Step 3.
+        if self.paused.get() {
+            // Step 3.1.
+            self.paused.set(false);
+
+            // Step 3.2.
+            self.queue_simple_event("play");
+
+            // Step 3.4.
+            if self.ready_state.get() >= HTMLMediaElementConstants::HAVE_FUTURE_DATA {
+                // https://html.spec.whatwg.org/multipage/#notify-about-playing
+                self.queue_simple_event("playing");
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-readystate
+    fn ReadyState(&self) -> u16 {
+        self.ready_state.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-media-pause
+    fn Pause(&self) {
+        // TODO: Step 1: invoke the media element's load algorithm once there is a media backend.
+
+        // Step 2.
+        if !self.paused.get() {
+            // Step 2.1.
+            self.paused.set(true);
+
+            // Step 2.2.
+            self.queue_simple_event("timeupdate");
+            self.queue_simple_event("pause");
+        }
+    }
+}
+
+/// A task that fires a simple event at a media element.
+struct MediaElementEventTask {
+    element: Trusted<HTMLMediaElement>,
+    type_: &'static str,
+}
+
+impl Runnable for MediaElementEventTask {
+    fn handler(self: Box<MediaElementEventTask>) {
+        let element = self.element.root();
+        element.r().fire_simple_event(self.type_);
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
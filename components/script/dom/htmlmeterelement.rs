@@ -3,13 +3,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use dom::bindings::codegen::Bindings::HTMLMeterElementBinding;
-use dom::bindings::codegen::InheritTypes::HTMLMeterElementDerived;
+use dom::bindings::codegen::Bindings::HTMLMeterElementBinding::HTMLMeterElementMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast, HTMLMeterElementDerived};
 use dom::bindings::js::Root;
+use dom::bindings::num::Finite;
 use dom::document::Document;
 use dom::element::ElementTypeId;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::node::{Node, NodeTypeId};
+use dom::nodelist::NodeList;
 use util::str::DOMString;
 
 #[dom_struct]
@@ -42,3 +45,169 @@ impl HTMLMeterElement {
         Node::reflect_node(box element, document, HTMLMeterElementBinding::Wrap)
     }
 }
+
+/// The region of the gauge that a meter's value falls into.
+///
+/// https://html.spec.whatwg.org/multipage/#the-meter-element
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GaugeRegion {
+    Optimum,
+    Suboptimal,
+    EvenLessGood,
+}
+
+/// The numeric values of a meter, after applying the defaults and constraints
+/// of https://html.spec.whatwg.org/multipage/#the-meter-element.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MeterValues {
+    pub minimum: f64,
+    pub maximum: f64,
+    pub actual: f64,
+    pub low: f64,
+    pub high: f64,
+    pub optimum: f64,
+}
+
+impl MeterValues {
+    pub fn new(min: Option<f64>, max: Option<f64>, value: Option<f64>,
+               low: Option<f64>, high: Option<f64>, optimum: Option<f64>) -> MeterValues {
+        // https://html.spec.whatwg.org/multipage/#concept-meter-minimum
+        let minimum = min.unwrap_or(0.0);
+        // https://html.spec.whatwg.org/multipage/#concept-meter-maximum
+        let maximum = max.unwrap_or(1.0).max(minimum);
+        // https://html.spec.whatwg.org/multipage/#concept-meter-actual
+        let actual = clamp(value.unwrap_or(0.0), minimum, maximum);
+        // https://html.spec.whatwg.org/multipage/#concept-meter-low
+        let low = clamp(low.unwrap_or(minimum), minimum, maximum);
+        // https://html.spec.whatwg.org/multipage/#concept-meter-high
+        let high = clamp(high.unwrap_or(maximum), low, maximum);
+        // https://html.spec.whatwg.org/multipage/#concept-meter-optimum
+        let optimum = clamp(optimum.unwrap_or((minimum + maximum) / 2.0), minimum, maximum);
+        MeterValues {
+            minimum: minimum,
+            maximum: maximum,
+            actual: actual,
+            low: low,
+            high: high,
+            optimum: optimum,
+        }
+    }
+
+    /// Returns the region of the gauge the actual value is in, as used for rendering.
+    pub fn gauge_region(&self) -> GaugeRegion {
+        let (low, high, value) = (self.low, self.high, self.actual);
+        if self.optimum < low {
+            // The low region is the optimum region.
+            if value <= low {
+                GaugeRegion::Optimum
+            } else if value <= high {
+                GaugeRegion::Suboptimal
+            } else {
+                GaugeRegion::EvenLessGood
+            }
+        } else if self.optimum > high {
+            // The high region is the optimum region.
+            if value >= high {
+                GaugeRegion::Optimum
+            } else if value >= low {
+                GaugeRegion::Suboptimal
+            } else {
+                GaugeRegion::EvenLessGood
+            }
+        } else if value >= low && value <= high {
+            // The middle region is the optimum region.
+            GaugeRegion::Optimum
+        } else {
+            GaugeRegion::Suboptimal
+        }
+    }
+}
+
+fn clamp(value: f64, min: f64, max: f64) -> f64 {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+impl HTMLMeterElement {
+    pub fn values(&self) -> MeterValues {
+        let element = ElementCast::from_ref(self);
+        MeterValues::new(element.get_float_attribute(&atom!("min")),
+                         element.get_float_attribute(&atom!("max")),
+                         element.get_float_attribute(&atom!("value")),
+                         element.get_float_attribute(&atom!("low")),
+                         element.get_float_attribute(&atom!("high")),
+                         element.get_float_attribute(&atom!("optimum")))
+    }
+}
+
+impl HTMLMeterElementMethods for HTMLMeterElement {
+    // https://html.spec.whatwg.org/multipage/#dom-meter-value
+    fn Value(&self) -> Finite<f64> {
+        Finite::wrap(self.values().actual)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-value
+    fn SetValue(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("value"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-min
+    fn Min(&self) -> Finite<f64> {
+        Finite::wrap(self.values().minimum)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-min
+    fn SetMin(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("min"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-max
+    fn Max(&self) -> Finite<f64> {
+        Finite::wrap(self.values().maximum)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-max
+    fn SetMax(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("max"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-low
+    fn Low(&self) -> Finite<f64> {
+        Finite::wrap(self.values().low)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-low
+    fn SetLow(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("low"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-high
+    fn High(&self) -> Finite<f64> {
+        Finite::wrap(self.values().high)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-high
+    fn SetHigh(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("high"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-optimum
+    fn Optimum(&self) -> Finite<f64> {
+        Finite::wrap(self.values().optimum)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-meter-optimum
+    fn SetOptimum(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("optimum"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-lfe-labels
+    fn Labels(&self) -> Root<NodeList> {
+        HTMLElementCast::from_ref(self).labels()
+    }
+}
@@ -2,22 +2,43 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use dom::attr::AttrValue;
+use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::HTMLOutputElementBinding;
 use dom::bindings::codegen::Bindings::HTMLOutputElementBinding::HTMLOutputElementMethods;
-use dom::bindings::codegen::InheritTypes::HTMLOutputElementDerived;
-use dom::bindings::js::Root;
+use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast, HTMLOutputElementDerived, NodeCast};
+use dom::bindings::js::{JS, MutNullableHeap, Root};
 use dom::document::Document;
+use dom::domtokenlist::DOMTokenList;
 use dom::element::ElementTypeId;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::htmlformelement::{FormControl, HTMLFormElement};
 use dom::node::{Node, NodeTypeId, window_from_node};
+use dom::nodelist::NodeList;
 use dom::validitystate::ValidityState;
+use dom::virtualmethods::VirtualMethods;
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::default::Default;
+use string_cache::Atom;
 use util::str::DOMString;
 
+/// https://html.spec.whatwg.org/multipage/#concept-output-mode
+#[derive(JSTraceable, PartialEq, Copy, Clone, HeapSizeOf)]
+enum OutputMode {
+    Default,
+    Value,
+}
+
 #[dom_struct]
 pub struct HTMLOutputElement {
-    htmlelement: HTMLElement
+    htmlelement: HTMLElement,
+    mode: Cell<OutputMode>,
+    /// https://html.spec.whatwg.org/multipage/#concept-output-default-value-override
+    default_value_override: DOMRefCell<Option<DOMString>>,
+    html_for: MutNullableHeap<JS<DOMTokenList>>,
 }
 
 impl HTMLOutputElementDerived for EventTarget {
@@ -34,7 +55,10 @@ impl HTMLOutputElement {
                      document: &Document) -> HTMLOutputElement {
         HTMLOutputElement {
             htmlelement:
-                HTMLElement::new_inherited(HTMLElementTypeId::HTMLOutputElement, localName, prefix, document)
+                HTMLElement::new_inherited(HTMLElementTypeId::HTMLOutputElement, localName, prefix, document),
+            mode: Cell::new(OutputMode::Default),
+            default_value_override: DOMRefCell::new(None),
+            html_for: Default::default(),
         }
     }
 
@@ -45,6 +69,21 @@ impl HTMLOutputElement {
         let element = HTMLOutputElement::new_inherited(localName, prefix, document);
         Node::reflect_node(box element, document, HTMLOutputElementBinding::Wrap)
     }
+
+    fn descendant_text_content(&self) -> DOMString {
+        NodeCast::from_ref(self).GetTextContent().unwrap()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#the-output-element:concept-form-reset-control
+    pub fn reset(&self) {
+        // Step 1.
+        self.mode.set(OutputMode::Default);
+        // Step 2.
+        let default_value = self.DefaultValue();
+        NodeCast::from_ref(self).SetTextContent(Some(default_value));
+        // Step 3.
+        *self.default_value_override.borrow_mut() = None;
+    }
 }
 
 impl HTMLOutputElementMethods for HTMLOutputElement {
@@ -58,6 +97,77 @@ impl HTMLOutputElementMethods for HTMLOutputElement {
     fn GetForm(&self) -> Option<Root<HTMLFormElement>> {
         self.form_owner()
     }
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-htmlfor
+    fn HtmlFor(&self) -> Root<DOMTokenList> {
+        self.html_for.or_init(|| {
+            DOMTokenList::new(ElementCast::from_ref(self), &atom!("for"))
+        })
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-fe-name
+    make_getter!(Name);
+
+    // https://html.spec.whatwg.org/multipage/#dom-fe-name
+    make_setter!(SetName, "name");
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-type
+    fn Type(&self) -> DOMString {
+        "output".to_owned()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-defaultvalue
+    fn DefaultValue(&self) -> DOMString {
+        match *self.default_value_override.borrow() {
+            Some(ref value) => value.clone(),
+            None => self.descendant_text_content(),
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-defaultvalue
+    fn SetDefaultValue(&self, value: DOMString) {
+        if self.mode.get() == OutputMode::Default {
+            NodeCast::from_ref(self).SetTextContent(Some(value));
+        } else {
+            *self.default_value_override.borrow_mut() = Some(value);
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-value
+    fn Value(&self) -> DOMString {
+        self.descendant_text_content()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-output-value
+    fn SetValue(&self, value: DOMString) {
+        // Step 1.
+        self.mode.set(OutputMode::Value);
+        // Step 2.
+        if self.default_value_override.borrow().is_none() {
+            *self.default_value_override.borrow_mut() = Some(self.descendant_text_content());
+        }
+        // Step 3.
+        NodeCast::from_ref(self).SetTextContent(Some(value));
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-lfe-labels
+    fn Labels(&self) -> Root<NodeList> {
+        HTMLElementCast::from_ref(self).labels()
+    }
+}
+
+impl VirtualMethods for HTMLOutputElement {
+    fn super_type<'b>(&'b self) -> Option<&'b VirtualMethods> {
+        let htmlelement: &HTMLElement = HTMLElementCast::from_ref(self);
+        Some(htmlelement as &VirtualMethods)
+    }
+
+    fn parse_plain_attribute(&self, name: &Atom, value: DOMString) -> AttrValue {
+        match name {
+            &atom!("for") => AttrValue::from_serialized_tokenlist(value),
+            _ => self.super_type().unwrap().parse_plain_attribute(name, value),
+        }
+    }
 }
 
 impl FormControl for HTMLOutputElement {}
@@ -3,13 +3,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use dom::bindings::codegen::Bindings::HTMLProgressElementBinding;
-use dom::bindings::codegen::InheritTypes::HTMLProgressElementDerived;
+use dom::bindings::codegen::Bindings::HTMLProgressElementBinding::HTMLProgressElementMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast, HTMLProgressElementDerived};
 use dom::bindings::js::Root;
+use dom::bindings::num::Finite;
 use dom::document::Document;
 use dom::element::ElementTypeId;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::node::{Node, NodeTypeId};
+use dom::nodelist::NodeList;
 use util::str::DOMString;
 
 #[dom_struct]
@@ -43,3 +46,85 @@ impl HTMLProgressElement {
         Node::reflect_node(box element, document, HTMLProgressElementBinding::Wrap)
     }
 }
+
+/// The numeric values of a progress bar, after applying the defaults and constraints
+/// of https://html.spec.whatwg.org/multipage/#the-progress-element.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ProgressValues {
+    /// Whether the progress bar is indeterminate, i.e. has no value attribute.
+    pub indeterminate: bool,
+    pub maximum: f64,
+    pub current: f64,
+}
+
+impl ProgressValues {
+    pub fn new(value: Option<f64>, max: Option<f64>) -> ProgressValues {
+        // https://html.spec.whatwg.org/multipage/#concept-progress-maximum
+        let maximum = match max {
+            Some(max) if max > 0.0 => max,
+            _ => 1.0,
+        };
+        // https://html.spec.whatwg.org/multipage/#concept-progress-value
+        let current = value.unwrap_or(0.0).max(0.0).min(maximum);
+        ProgressValues {
+            indeterminate: value.is_none(),
+            maximum: maximum,
+            current: current,
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-progress-position
+    pub fn position(&self) -> f64 {
+        if self.indeterminate {
+            -1.0
+        } else {
+            self.current / self.maximum
+        }
+    }
+}
+
+impl HTMLProgressElement {
+    pub fn values(&self) -> ProgressValues {
+        let element = ElementCast::from_ref(self);
+        let has_value = element.has_attribute(&atom!("value"));
+        let value = element.get_float_attribute(&atom!("value"));
+        // A value attribute that fails to parse still makes the element determinate.
+        let value = if has_value { Some(value.unwrap_or(0.0)) } else { None };
+        ProgressValues::new(value, element.get_float_attribute(&atom!("max")))
+    }
+}
+
+impl HTMLProgressElementMethods for HTMLProgressElement {
+    // https://html.spec.whatwg.org/multipage/#dom-progress-value
+    fn Value(&self) -> Finite<f64> {
+        Finite::wrap(self.values().current)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-progress-value
+    fn SetValue(&self, value: Finite<f64>) {
+        ElementCast::from_ref(self).set_float_attribute(&atom!("value"), *value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-progress-max
+    fn Max(&self) -> Finite<f64> {
+        Finite::wrap(self.values().maximum)
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-progress-max
+    fn SetMax(&self, max: Finite<f64>) {
+        // Non-positive values are ignored.
+        if *max > 0.0 {
+            ElementCast::from_ref(self).set_float_attribute(&atom!("max"), *max);
+        }
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-progress-position
+    fn Position(&self) -> Finite<f64> {
+        Finite::wrap(self.values().position())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-lfe-labels
+    fn Labels(&self) -> Root<NodeList> {
+        HTMLElementCast::from_ref(self).labels()
+    }
+}
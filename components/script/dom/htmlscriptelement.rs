@@ -57,15 +57,14 @@ pub struct HTMLScriptElement {
     parser_inserted: Cell<bool>,
 
     /// https://html.spec.whatwg.org/multipage/#non-blocking
-    ///
-    /// (currently unused)
     non_blocking: Cell<bool>,
 
     /// https://html.spec.whatwg.org/multipage/#ready-to-be-parser-executed
-    ///
-    /// (currently unused)
     ready_to_be_parser_executed: Cell<bool>,
 
+    /// The fetched source of a deferred script, kept until the script's turn to execute.
+    pending_load: DOMRefCell<Option<ScriptOrigin>>,
+
     /// Document of the parser that created this element
     parser_document: JS<Document>,
 
@@ -92,6 +91,7 @@ impl HTMLScriptElement {
             parser_inserted: Cell::new(creator == ElementCreator::ParserCreated),
             non_blocking: Cell::new(creator != ElementCreator::ParserCreated),
             ready_to_be_parser_executed: Cell::new(false),
+            pending_load: DOMRefCell::new(None),
             parser_document: JS::from_ref(document),
             block_character_encoding: DOMRefCell::new(UTF_8 as EncodingRef),
         }
@@ -133,6 +133,8 @@ pub enum ScriptOrigin {
     External(Result<(Metadata, Vec<u8>), String>),
 }
 
+no_jsmanaged_fields!(ScriptOrigin);
+
 /// The context required for asynchronously loading an external script source.
 struct ScriptContext {
     /// The element that initiated the request.
@@ -143,6 +145,8 @@ struct ScriptContext {
     metadata: RefCell<Option<Metadata>>,
     /// Whether the owning document's parser should resume once the response completes.
     resume_on_completion: bool,
+    /// Whether the script has to wait for the document to finish parsing before executing.
+    deferred: bool,
     /// The initial URL requested.
     url: Url,
 }
@@ -164,14 +168,33 @@ impl AsyncResponseListener for ScriptContext {
             (metadata, data)
         });
         let elem = self.elem.root();
+        let document = document_from_node(elem.r());
 
-        elem.r().execute(ScriptOrigin::External(load));
+        if self.deferred {
+            *elem.r().pending_load.borrow_mut() = Some(ScriptOrigin::External(load));
+            elem.r().ready_to_be_parser_executed.set(true);
+            document.r().process_deferred_scripts();
+        } else if self.resume_on_completion {
+            // This script blocked the parser, so it gets the parser's insertion point,
+            // unless the parser has gone away in the meantime.
+            let parser = document.r().get_current_parser();
+            if let Some(ref parser) = parser {
+                parser.r().enter_script();
+            }
+            elem.r().execute(ScriptOrigin::External(load));
+            if let Some(ref parser) = parser {
+                parser.r().exit_script();
+            }
+        } else {
+            elem.r().execute(ScriptOrigin::External(load));
+        }
 
-        let document = document_from_node(elem.r());
         document.r().finish_load(LoadType::Script(self.url.clone()));
 
         if self.resume_on_completion {
-            document.r().get_current_parser().unwrap().r().resume();
+            if let Some(parser) = document.r().get_current_parser() {
+                parser.r().resume();
+            }
         }
     }
 }
@@ -191,7 +214,7 @@ impl HTMLScriptElement {
 
         // Step 3.
         let element = ElementCast::from_ref(self);
-        if was_parser_inserted && element.has_attribute(&atom!("async")) {
+        if was_parser_inserted && !element.has_attribute(&atom!("async")) {
             self.non_blocking.set(true);
         }
         // Step 4.
@@ -288,6 +311,15 @@ impl HTMLScriptElement {
                         // behaviour set to taint.
                         let doc = document_from_node(self);
 
+                        // Step 15.
+                        let is_async = element.has_attribute(&atom!("async"));
+                        let deferred = self.parser_inserted.get() && !is_async &&
+                                       element.has_attribute(&atom!("defer"));
+                        let blocking = self.parser_inserted.get() && !is_async && !deferred;
+                        // TODO: script-inserted scripts that aren't async should execute in
+                        // insertion order; for now they run as soon as they are fetched, like
+                        // async scripts.
+
                         let script_chan = window.script_chan();
                         let elem = Trusted::new(window.get_cx(), self, script_chan.clone());
 
@@ -295,7 +327,8 @@ impl HTMLScriptElement {
                             elem: elem,
                             data: RefCell::new(vec!()),
                             metadata: RefCell::new(None),
-                            resume_on_completion: self.parser_inserted.get(),
+                            resume_on_completion: blocking,
+                            deferred: deferred,
                             url: url.clone(),
                         }));
 
@@ -313,9 +346,13 @@ impl HTMLScriptElement {
 
                         doc.r().load_async(LoadType::Script(url), response_target);
 
-                        if self.parser_inserted.get() {
-                            doc.r().get_current_parser().unwrap().r().suspend();
+                        if deferred {
+                            doc.r().add_deferred_script(self);
+                        }
+                        if !blocking {
+                            return NextParserState::Continue;
                         }
+                        doc.r().get_current_parser().unwrap().r().suspend();
                         return NextParserState::Suspend;
                     }
                 }
@@ -324,8 +361,7 @@ impl HTMLScriptElement {
         };
 
         // Step 15.
-        // TODO: Add support for the `defer` and `async` attributes.  (For now, we fetch all
-        // scripts synchronously and execute them immediately.)
+        // Inline scripts execute immediately.
         self.execute(load);
         NextParserState::Continue
     }
@@ -427,6 +463,16 @@ impl HTMLScriptElement {
         }
     }
 
+    pub fn is_ready_to_be_parser_executed(&self) -> bool {
+        self.ready_to_be_parser_executed.get()
+    }
+
+    /// Executes a deferred script whose source has already been fetched.
+    pub fn execute_pending_load(&self) {
+        let load = self.pending_load.borrow_mut().take().unwrap();
+        self.execute(load);
+    }
+
     pub fn queue_error_event(&self) {
         let window = window_from_node(self);
         let window = window.r();
@@ -472,6 +518,12 @@ impl HTMLScriptElement {
                 debug!("script type empty, inferring js");
                 true
             },
+            Some(ref s) if s.trim_matches(HTML_SPACE_CHARACTERS).eq_ignore_ascii_case("module") => {
+                // TODO: module scripts need module support from the SpiderMonkey bindings;
+                // until then they are left unexecuted rather than run as classic scripts.
+                debug!("script type=module is not supported yet");
+                false
+            },
             Some(s) => {
                 debug!("script type={}", &**s);
                 SCRIPT_JS_MIMES.contains(&s.to_ascii_lowercase().trim_matches(HTML_SPACE_CHARACTERS))
@@ -583,6 +635,24 @@ impl HTMLScriptElementMethods for HTMLScriptElement {
     // https://html.spec.whatwg.org/multipage/#dom-script-src
     make_setter!(SetSrc, "src");
 
+    // https://html.spec.whatwg.org/multipage/#dom-script-async
+    fn Async(&self) -> bool {
+        let element = ElementCast::from_ref(self);
+        self.non_blocking.get() || element.has_attribute(&atom!("async"))
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-script-async
+    fn SetAsync(&self, value: bool) {
+        self.non_blocking.set(false);
+        let element = ElementCast::from_ref(self);
+        element.set_bool_attribute(&atom!("async"), value);
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-script-defer
+    make_bool_getter!(Defer);
+    // https://html.spec.whatwg.org/multipage/#dom-script-defer
+    make_bool_setter!(SetDefer, "defer");
+
     // https://www.whatwg.org/html/#dom-script-text
     fn Text(&self) -> DOMString {
         Node::collect_text_contents(NodeCast::from_ref(self).children())
@@ -3,18 +3,24 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use cssparser::Parser as CssParser;
+use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::HTMLStyleElementBinding;
+use dom::bindings::codegen::Bindings::HTMLStyleElementBinding::HTMLStyleElementMethods;
 use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
 use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLElementCast, HTMLStyleElementDerived, NodeCast};
-use dom::bindings::js::Root;
+use dom::bindings::codegen::InheritTypes::StyleSheetCast;
+use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::cssstylesheet::CSSStyleSheet;
 use dom::document::Document;
 use dom::element::ElementTypeId;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::node::window_from_node;
 use dom::node::{ChildrenMutation, Node, NodeTypeId};
+use dom::stylesheet::StyleSheet;
 use dom::virtualmethods::VirtualMethods;
 use layout_interface::{LayoutChan, Msg};
+use std::sync::Arc;
 use style::media_queries::parse_media_query_list;
 use style::stylesheets::{Origin, Stylesheet};
 use util::str::DOMString;
@@ -22,6 +28,11 @@ use util::str::DOMString;
 #[dom_struct]
 pub struct HTMLStyleElement {
     htmlelement: HTMLElement,
+    /// The style sheet that was last sent to layout for this element, if any.
+    #[ignore_heap_size_of = "Arc"]
+    stylesheet: DOMRefCell<Option<Arc<Stylesheet>>>,
+    /// The CSSOM object reflecting `stylesheet`.
+    cssom_stylesheet: MutNullableHeap<JS<CSSStyleSheet>>,
 }
 
 impl HTMLStyleElementDerived for EventTarget {
@@ -37,7 +48,9 @@ impl HTMLStyleElement {
                      prefix: Option<DOMString>,
                      document: &Document) -> HTMLStyleElement {
         HTMLStyleElement {
-            htmlelement: HTMLElement::new_inherited(HTMLElementTypeId::HTMLStyleElement, localName, prefix, document)
+            htmlelement: HTMLElement::new_inherited(HTMLElementTypeId::HTMLStyleElement, localName, prefix, document),
+            stylesheet: DOMRefCell::new(None),
+            cssom_stylesheet: Default::default(),
         }
     }
 
@@ -66,11 +79,42 @@ impl HTMLStyleElement {
         let mut css_parser = CssParser::new(&mq_str);
         let media = parse_media_query_list(&mut css_parser);
 
+        // The previous contents of this element no longer apply.
+        self.remove_own_stylesheet();
+
         let data = node.GetTextContent().expect("Element.textContent must be a string");
-        let sheet = Stylesheet::from_str(&data, url, Origin::Author);
+        let sheet = Arc::new(Stylesheet::from_str(&data, url, Origin::Author));
+        *self.stylesheet.borrow_mut() = Some(sheet.clone());
         let LayoutChan(ref layout_chan) = win.layout_chan();
         layout_chan.send(Msg::AddStylesheet(sheet, media)).unwrap();
     }
+
+    /// Stops the style sheet associated with this element from applying to the document.
+    fn remove_own_stylesheet(&self) {
+        self.cssom_stylesheet.set(None);
+        if let Some(sheet) = self.stylesheet.borrow_mut().take() {
+            let win = window_from_node(self);
+            let LayoutChan(ref layout_chan) = win.r().layout_chan();
+            layout_chan.send(Msg::RemoveStylesheet(sheet.disabled.clone())).unwrap();
+        }
+    }
+}
+
+impl HTMLStyleElementMethods for HTMLStyleElement {
+    // https://drafts.csswg.org/cssom/#dom-linkstyle-sheet
+    fn GetSheet(&self) -> Option<Root<StyleSheet>> {
+        let sheet = match *self.stylesheet.borrow() {
+            Some(ref sheet) => sheet.clone(),
+            None => return None,
+        };
+        let sheet = self.cssom_stylesheet.or_init(|| {
+            let element = ElementCast::from_ref(self);
+            let title = element.get_attribute(&ns!(""), &atom!("title"))
+                               .map(|attr| String::from(&**attr.r().value()));
+            CSSStyleSheet::new(window_from_node(self).r(), None, title, sheet)
+        });
+        Some(StyleSheetCast::from_root(sheet))
+    }
 }
 
 impl VirtualMethods for HTMLStyleElement {
@@ -98,4 +142,14 @@ impl VirtualMethods for HTMLStyleElement {
             self.parse_own_css();
         }
     }
+
+    fn unbind_from_tree(&self, tree_in_doc: bool) {
+        if let Some(ref s) = self.super_type() {
+            s.unbind_from_tree(tree_in_doc);
+        }
+
+        if tree_in_doc {
+            self.remove_own_stylesheet();
+        }
+    }
 }
@@ -11,22 +11,19 @@ use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
 use dom::bindings::codegen::InheritTypes::KeyboardEventCast;
 use dom::bindings::codegen::InheritTypes::{ElementCast, EventTargetCast, HTMLElementCast, NodeCast};
 use dom::bindings::codegen::InheritTypes::{HTMLFieldSetElementDerived, HTMLTextAreaElementDerived};
-use dom::bindings::global::GlobalRef;
 use dom::bindings::js::{LayoutJS, Root};
-use dom::bindings::refcounted::Trusted;
 use dom::document::Document;
 use dom::element::{AttributeMutation, ElementTypeId};
-use dom::event::{Event, EventBubbles, EventCancelable};
+use dom::event::{Event, EventCancelable};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::{HTMLElement, HTMLElementTypeId};
 use dom::htmlformelement::{FormControl, HTMLFormElement};
+use dom::inputevent::InputEvent;
 use dom::keyboardevent::KeyboardEvent;
 use dom::node::{ChildrenMutation, Node, NodeDamage};
 use dom::node::{NodeTypeId, document_from_node, window_from_node};
 use dom::virtualmethods::VirtualMethods;
 use msg::constellation_msg::ConstellationChan;
-use script_task::ScriptTaskEventCategory::InputEvent;
-use script_task::{CommonScriptMsg, Runnable};
 use std::borrow::ToOwned;
 use std::cell::Cell;
 use string_cache::Atom;
@@ -229,18 +226,6 @@ impl HTMLTextAreaElement {
         let node = NodeCast::from_ref(self);
         doc.r().content_changed(node, NodeDamage::OtherNodeDamage)
     }
-
-    fn dispatch_change_event(&self) {
-        let window = window_from_node(self);
-        let window = window.r();
-        let event = Event::new(GlobalRef::Window(window),
-                               "input".to_owned(),
-                               EventBubbles::DoesNotBubble,
-                               EventCancelable::NotCancelable);
-
-        let target = EventTargetCast::from_ref(self);
-        target.dispatch_event(event.r());
-    }
 }
 
 impl VirtualMethods for HTMLTextAreaElement {
@@ -335,23 +320,30 @@ impl VirtualMethods for HTMLTextAreaElement {
         } else if &*event.Type() == "keydown" && !event.DefaultPrevented() {
             let keyevent: Option<&KeyboardEvent> = KeyboardEventCast::to_ref(event);
             keyevent.map(|kevent| {
-                match self.textinput.borrow_mut().handle_keydown(kevent) {
+                // https://w3c.github.io/input-events/#event-type-beforeinput
+                let edit = kevent.get_key().and_then(|key| {
+                    self.textinput.borrow().edit_for_key(key, kevent.get_key_modifiers())
+                });
+                let window = window_from_node(self);
+                let target = EventTargetCast::from_ref(self);
+                if let Some(ref edit) = edit {
+                    if !InputEvent::fire_for_edit(window.r(), target, "beforeinput",
+                                                  EventCancelable::Cancelable, edit) {
+                        event.PreventDefault();
+                        return;
+                    }
+                }
+
+                let reaction = self.textinput.borrow_mut().handle_keydown(kevent);
+                match reaction {
                     KeyReaction::TriggerDefaultAction => (),
                     KeyReaction::DispatchInput => {
                         self.value_changed.set(true);
-
-                        if event.IsTrusted() {
-                            let window = window_from_node(self);
-                            let window = window.r();
-                            let chan = window.script_chan();
-                            let handler = Trusted::new(window.get_cx(), self, chan.clone());
-                            let dispatcher = ChangeEventRunnable {
-                                element: handler,
-                            };
-                            let _ = chan.send(CommonScriptMsg::RunnableMsg(InputEvent, box dispatcher));
-                        }
-
                         self.force_relayout();
+                        if let Some(ref edit) = edit {
+                            InputEvent::fire_for_edit(window.r(), target, "input",
+                                                      EventCancelable::NotCancelable, edit);
+                        }
                     }
                     KeyReaction::RedrawSelection => {
                         self.force_relayout();
@@ -364,14 +356,3 @@ impl VirtualMethods for HTMLTextAreaElement {
 }
 
 impl FormControl for HTMLTextAreaElement {}
-
-pub struct ChangeEventRunnable {
-    element: Trusted<HTMLTextAreaElement>,
-}
-
-impl Runnable for ChangeEventRunnable {
-    fn handler(self: Box<ChangeEventRunnable>) {
-        let target = self.element.root();
-        target.r().dispatch_change_event();
-    }
-}
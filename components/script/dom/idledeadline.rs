@@ -0,0 +1,53 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::IdleDeadlineBinding;
+use dom::bindings::codegen::Bindings::IdleDeadlineBinding::IdleDeadlineMethods;
+use dom::bindings::codegen::Bindings::PerformanceBinding::PerformanceMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::num::Finite;
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::window::Window;
+
+// https://w3c.github.io/requestidlecallback/#the-idledeadline-interface
+#[dom_struct]
+pub struct IdleDeadline {
+    reflector_: Reflector,
+    window: JS<Window>,
+    /// The time, relative to the time origin, at which the idle period ends.
+    deadline: f64,
+    did_timeout: bool,
+}
+
+impl IdleDeadline {
+    fn new_inherited(window: &Window, deadline: f64, did_timeout: bool) -> IdleDeadline {
+        IdleDeadline {
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
+            deadline: deadline,
+            did_timeout: did_timeout,
+        }
+    }
+
+    pub fn new(window: &Window, deadline: f64, did_timeout: bool) -> Root<IdleDeadline> {
+        reflect_dom_object(box IdleDeadline::new_inherited(window, deadline, did_timeout),
+                           GlobalRef::Window(window), IdleDeadlineBinding::Wrap)
+    }
+}
+
+impl IdleDeadlineMethods for IdleDeadline {
+    // https://w3c.github.io/requestidlecallback/#dom-idledeadline-timeremaining
+    fn TimeRemaining(&self) -> Finite<f64> {
+        let window = self.window.root();
+        let now = *window.r().Performance().r().Now();
+        Finite::wrap((self.deadline - now).max(0.))
+    }
+
+    // https://w3c.github.io/requestidlecallback/#dom-idledeadline-didtimeout
+    fn DidTimeout(&self) -> bool {
+        self.did_timeout
+    }
+}
@@ -0,0 +1,128 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::InputEventBinding;
+use dom::bindings::codegen::Bindings::InputEventBinding::InputEventMethods;
+use dom::bindings::codegen::Bindings::UIEventBinding::UIEventMethods;
+use dom::bindings::codegen::InheritTypes::{EventCast, InputEventDerived, UIEventCast};
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{Root, RootedReference};
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
+use dom::eventtarget::EventTarget;
+use dom::uievent::{UIEvent, UIEventTypeId};
+use dom::window::Window;
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use textinput::InputEdit;
+use util::str::DOMString;
+
+// https://w3c.github.io/uievents/#interface-InputEvent
+#[dom_struct]
+pub struct InputEvent {
+    uievent: UIEvent,
+    data: DOMRefCell<Option<DOMString>>,
+    is_composing: Cell<bool>,
+    input_type: DOMRefCell<DOMString>,
+}
+
+impl InputEventDerived for Event {
+    fn is_inputevent(&self) -> bool {
+        *self.type_id() == EventTypeId::UIEvent(UIEventTypeId::InputEvent)
+    }
+}
+
+impl InputEvent {
+    fn new_inherited() -> InputEvent {
+        InputEvent {
+            uievent: UIEvent::new_inherited(UIEventTypeId::InputEvent),
+            data: DOMRefCell::new(None),
+            is_composing: Cell::new(false),
+            input_type: DOMRefCell::new("".to_owned()),
+        }
+    }
+
+    pub fn new_uninitialized(window: &Window) -> Root<InputEvent> {
+        reflect_dom_object(box InputEvent::new_inherited(),
+                           GlobalRef::Window(window),
+                           InputEventBinding::Wrap)
+    }
+
+    pub fn new(window: &Window,
+               type_: DOMString,
+               can_bubble: EventBubbles,
+               cancelable: EventCancelable,
+               view: Option<&Window>,
+               detail: i32,
+               data: Option<DOMString>,
+               is_composing: bool,
+               input_type: DOMString) -> Root<InputEvent> {
+        let ev = InputEvent::new_uninitialized(window);
+        {
+            let ev = ev.r();
+            let uievent = UIEventCast::from_ref(ev);
+            uievent.InitUIEvent(type_, can_bubble == EventBubbles::Bubbles,
+                                cancelable == EventCancelable::Cancelable, view, detail);
+            *ev.data.borrow_mut() = data;
+            ev.is_composing.set(is_composing);
+            *ev.input_type.borrow_mut() = input_type;
+        }
+        ev
+    }
+
+    pub fn Constructor(global: GlobalRef,
+                       type_: DOMString,
+                       init: &InputEventBinding::InputEventInit) -> Fallible<Root<InputEvent>> {
+        let bubbles = if init.parent.parent.bubbles {
+            EventBubbles::Bubbles
+        } else {
+            EventBubbles::DoesNotBubble
+        };
+        let cancelable = if init.parent.parent.cancelable {
+            EventCancelable::Cancelable
+        } else {
+            EventCancelable::NotCancelable
+        };
+        let event = InputEvent::new(global.as_window(), type_,
+                                    bubbles, cancelable,
+                                    init.parent.view.r(), init.parent.detail,
+                                    init.data.clone(), init.isComposing,
+                                    init.inputType.clone());
+        Ok(event)
+    }
+
+    /// Fires an input event of the given type, describing `edit`, at `target`.
+    /// Returns false if the event was canceled.
+    pub fn fire_for_edit(window: &Window,
+                         target: &EventTarget,
+                         type_: &str,
+                         cancelable: EventCancelable,
+                         edit: &InputEdit) -> bool {
+        let event = InputEvent::new(window, type_.to_owned(),
+                                    EventBubbles::Bubbles, cancelable,
+                                    Some(window), 0,
+                                    edit.data.clone(), false,
+                                    edit.input_type.to_owned());
+        EventCast::from_ref(event.r()).fire(target)
+    }
+}
+
+impl InputEventMethods for InputEvent {
+    // https://w3c.github.io/uievents/#widl-InputEvent-data
+    fn GetData(&self) -> Option<DOMString> {
+        self.data.borrow().clone()
+    }
+
+    // https://w3c.github.io/uievents/#widl-InputEvent-isComposing
+    fn IsComposing(&self) -> bool {
+        self.is_composing.get()
+    }
+
+    // https://w3c.github.io/input-events/#dom-inputevent-inputtype
+    fn InputType(&self) -> DOMString {
+        self.input_type.borrow().clone()
+    }
+}
@@ -0,0 +1,437 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::Au;
+use dom::bindings::callback::ExceptionHandling;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::IntersectionObserverBinding;
+use dom::bindings::codegen::Bindings::IntersectionObserverBinding::IntersectionObserverCallback;
+use dom::bindings::codegen::Bindings::IntersectionObserverBinding::IntersectionObserverInit;
+use dom::bindings::codegen::Bindings::IntersectionObserverBinding::IntersectionObserverMethods;
+use dom::bindings::codegen::Bindings::PerformanceBinding::PerformanceMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::InheritTypes::NodeCast;
+use dom::bindings::error::{Error, Fallible};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root, RootedReference};
+use dom::bindings::refcounted::Trusted;
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::domrect::DOMRect;
+use dom::element::Element;
+use dom::intersectionobserverentry::IntersectionObserverEntry;
+use dom::node::Node;
+use dom::window::Window;
+use euclid::point::Point2D;
+use euclid::rect::Rect;
+use euclid::size::Size2D;
+use script_task::ScriptTaskEventCategory::DomEvent;
+use script_task::{CommonScriptMsg, Runnable, ScriptChan};
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::cmp;
+use std::rc::Rc;
+use util::str::DOMString;
+
+/// One side of a root margin.
+#[derive(JSTraceable, HeapSizeOf, Clone, Copy, PartialEq, Debug)]
+pub enum MarginLength {
+    Px(f32),
+    Percent(f32),
+}
+
+impl MarginLength {
+    fn parse(token: &str) -> Result<MarginLength, ()> {
+        let (number, constructor): (&str, fn(f32) -> MarginLength) = if token.ends_with("px") {
+            (&token[..token.len() - 2], MarginLength::Px)
+        } else if token.ends_with("%") {
+            (&token[..token.len() - 1], MarginLength::Percent)
+        } else {
+            return Err(());
+        };
+        match number.parse::<f32>() {
+            Ok(value) if value.is_finite() => Ok(constructor(value)),
+            _ => Err(()),
+        }
+    }
+
+    /// Resolves this length, using `basis` for percentages.
+    fn to_au(&self, basis: Au) -> Au {
+        match *self {
+            MarginLength::Px(value) => Au::from_f32_px(value),
+            MarginLength::Percent(value) => basis.scale_by(value / 100.),
+        }
+    }
+
+    fn serialize(&self) -> String {
+        match *self {
+            MarginLength::Px(value) => format!("{}px", value),
+            MarginLength::Percent(value) => format!("{}%", value),
+        }
+    }
+}
+
+/// The margin by which the root's bounds are grown or shrunk before
+/// computing intersections.
+// https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-rootmargin
+#[derive(JSTraceable, HeapSizeOf, Clone, Copy, PartialEq, Debug)]
+pub struct RootMargin {
+    pub top: MarginLength,
+    pub right: MarginLength,
+    pub bottom: MarginLength,
+    pub left: MarginLength,
+}
+
+impl RootMargin {
+    // https://w3c.github.io/IntersectionObserver/#parse-a-root-margin
+    pub fn parse(margin: &str) -> Result<RootMargin, ()> {
+        let mut lengths = vec![];
+        for token in margin.split_whitespace() {
+            lengths.push(try!(MarginLength::parse(token)));
+        }
+        let (top, right, bottom, left) = match lengths.len() {
+            1 => (lengths[0], lengths[0], lengths[0], lengths[0]),
+            2 => (lengths[0], lengths[1], lengths[0], lengths[1]),
+            3 => (lengths[0], lengths[1], lengths[2], lengths[1]),
+            4 => (lengths[0], lengths[1], lengths[2], lengths[3]),
+            _ => return Err(()),
+        };
+        Ok(RootMargin { top: top, right: right, bottom: bottom, left: left })
+    }
+
+    /// Grows `rect` by this margin, resolving percentages against its size.
+    pub fn apply(&self, rect: Rect<Au>) -> Rect<Au> {
+        let top = self.top.to_au(rect.size.height);
+        let right = self.right.to_au(rect.size.width);
+        let bottom = self.bottom.to_au(rect.size.height);
+        let left = self.left.to_au(rect.size.width);
+        let width = cmp::max(rect.size.width + left + right, Au(0));
+        let height = cmp::max(rect.size.height + top + bottom, Au(0));
+        Rect::new(Point2D::new(rect.origin.x - left, rect.origin.y - top), Size2D::new(width, height))
+    }
+
+    fn serialize(&self) -> String {
+        format!("{} {} {} {}", self.top.serialize(), self.right.serialize(),
+                self.bottom.serialize(), self.left.serialize())
+    }
+}
+
+/// Returns the intersection of `target` and `root`, or `None` if they don't
+/// intersect. Rectangles that only share an edge intersect with zero area.
+// https://w3c.github.io/IntersectionObserver/#compute-the-intersection
+pub fn compute_intersection(target: &Rect<Au>, root: &Rect<Au>) -> Option<Rect<Au>> {
+    let left = cmp::max(target.origin.x, root.origin.x);
+    let top = cmp::max(target.origin.y, root.origin.y);
+    let right = cmp::min(target.max_x(), root.max_x());
+    let bottom = cmp::min(target.max_y(), root.max_y());
+    if left > right || top > bottom {
+        return None;
+    }
+    Some(Rect::new(Point2D::new(left, top), Size2D::new(right - left, bottom - top)))
+}
+
+/// Returns how much of `target` is covered by `intersection`.
+pub fn intersection_ratio(target: &Rect<Au>, intersection: &Rect<Au>) -> f64 {
+    let area = |rect: &Rect<Au>| rect.size.width.to_f64_px() * rect.size.height.to_f64_px();
+    let target_area = area(target);
+    if target_area == 0. {
+        // An intersecting target with no area is entirely visible.
+        1.
+    } else {
+        area(intersection) / target_area
+    }
+}
+
+/// Returns the index of the first threshold greater than `ratio`, or the
+/// number of thresholds if there is none.
+pub fn threshold_index(thresholds: &[f64], ratio: f64) -> i32 {
+    thresholds.iter().position(|&threshold| threshold > ratio).unwrap_or(thresholds.len()) as i32
+}
+
+/// A target observed by an `IntersectionObserver`, with the state it was in
+/// when it was last reported.
+// https://w3c.github.io/IntersectionObserver/#intersectionobserverregistration
+#[must_root]
+#[derive(JSTraceable, HeapSizeOf)]
+#[privatize]
+struct IntersectionObservation {
+    target: JS<Element>,
+    previous_threshold_index: i32,
+    previous_is_intersecting: bool,
+}
+
+// https://w3c.github.io/IntersectionObserver/#intersection-observer-interface
+#[dom_struct]
+pub struct IntersectionObserver {
+    reflector_: Reflector,
+    window: JS<Window>,
+    #[ignore_heap_size_of = "Rc<T> is hard"]
+    callback: Rc<IntersectionObserverCallback>,
+    root: Option<JS<Element>>,
+    root_margin: RootMargin,
+    thresholds: Vec<f64>,
+    observation_targets: DOMRefCell<Vec<IntersectionObservation>>,
+    queued_entries: DOMRefCell<Vec<JS<IntersectionObserverEntry>>>,
+    /// Whether a task to invoke the callback with the queued entries has been
+    /// queued and hasn't run yet.
+    notify_task_queued: Cell<bool>,
+}
+
+impl IntersectionObserver {
+    fn new_inherited(window: &Window,
+                     callback: Rc<IntersectionObserverCallback>,
+                     root: Option<&Element>,
+                     root_margin: RootMargin,
+                     thresholds: Vec<f64>) -> IntersectionObserver {
+        IntersectionObserver {
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
+            callback: callback,
+            root: root.map(JS::from_ref),
+            root_margin: root_margin,
+            thresholds: thresholds,
+            observation_targets: DOMRefCell::new(vec![]),
+            queued_entries: DOMRefCell::new(vec![]),
+            notify_task_queued: Cell::new(false),
+        }
+    }
+
+    pub fn new(window: &Window,
+               callback: Rc<IntersectionObserverCallback>,
+               root: Option<&Element>,
+               root_margin: RootMargin,
+               thresholds: Vec<f64>) -> Root<IntersectionObserver> {
+        let observer = IntersectionObserver::new_inherited(window, callback, root, root_margin, thresholds);
+        reflect_dom_object(box observer, GlobalRef::Window(window), IntersectionObserverBinding::Wrap)
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#initialize-a-new-intersectionobserver
+    pub fn Constructor(global: GlobalRef,
+                       callback: Rc<IntersectionObserverCallback>,
+                       options: &IntersectionObserverInit) -> Fallible<Root<IntersectionObserver>> {
+        // Steps 2-3.
+        let root_margin = match RootMargin::parse(&options.rootMargin) {
+            Ok(root_margin) => root_margin,
+            Err(()) => return Err(Error::Syntax),
+        };
+
+        // Steps 4-6.
+        let threshold = *options.threshold;
+        if threshold < 0. || threshold > 1. {
+            return Err(Error::Range("threshold must be between 0 and 1".to_owned()));
+        }
+
+        Ok(IntersectionObserver::new(global.as_window(), callback, options.root.r(),
+                                     root_margin, vec![threshold]))
+    }
+
+    /// Returns the root intersection rectangle, in page coordinates, or
+    /// `None` if the explicit root isn't in a document.
+    // https://w3c.github.io/IntersectionObserver/#intersectionobserver-root-intersection-rectangle
+    fn root_intersection_rect(&self) -> Option<Rect<Au>> {
+        let rect = match self.root {
+            Some(ref root) => {
+                let root = root.root();
+                let node = NodeCast::from_ref(root.r());
+                if !node.is_in_doc() {
+                    return None;
+                }
+                node.get_bounding_content_box()
+            },
+            None => {
+                let window = self.window.root();
+                let window = window.r();
+                Rect::new(Point2D::new(Au::from_px(window.ScrollX()), Au::from_px(window.ScrollY())),
+                          Size2D::new(Au::from_px(window.InnerWidth()), Au::from_px(window.InnerHeight())))
+            },
+        };
+        Some(self.root_margin.apply(rect))
+    }
+
+    /// Returns whether `target` can intersect with the root.
+    fn can_intersect(&self, target: &Node) -> bool {
+        if !target.is_in_doc() {
+            return false;
+        }
+        match self.root {
+            Some(ref root) => {
+                let root = root.root();
+                let root = NodeCast::from_ref(root.r());
+                root != target && root.is_inclusive_ancestor_of(target)
+            },
+            None => target.owner_doc() == self.window.root().r().Document(),
+        }
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#run-the-update-intersection-observations-steps
+    pub fn update_observations(&self, time: f64) {
+        let window = self.window.root();
+        let window = window.r();
+
+        // Step 2.1.
+        let root_bounds = self.root_intersection_rect();
+
+        // Step 2.2.
+        let targets = self.observation_targets.borrow().iter()
+                          .map(|observation| observation.target.root())
+                          .collect::<Vec<_>>();
+        for (index, target) in targets.iter().enumerate() {
+            let node = NodeCast::from_ref(target.r());
+
+            // Steps 2.2.2-2.2.8.
+            let target_rect = node.get_bounding_content_box();
+            let intersection = match root_bounds {
+                Some(ref root_bounds) if self.can_intersect(node) => {
+                    compute_intersection(&target_rect, root_bounds)
+                },
+                _ => None,
+            };
+            let is_intersecting = intersection.is_some();
+            let intersection_rect = intersection.unwrap_or(Rect::zero());
+
+            // Steps 2.2.9-2.2.10.
+            let ratio = if is_intersecting {
+                intersection_ratio(&target_rect, &intersection_rect)
+            } else {
+                0.
+            };
+            let threshold_index = threshold_index(&self.thresholds, ratio);
+
+            // Steps 2.2.11-2.2.14.
+            {
+                let mut observations = self.observation_targets.borrow_mut();
+                let observation = &mut observations[index];
+                if observation.previous_threshold_index == threshold_index &&
+                   observation.previous_is_intersecting == is_intersecting {
+                    continue;
+                }
+                observation.previous_threshold_index = threshold_index;
+                observation.previous_is_intersecting = is_intersecting;
+            }
+
+            // Rectangles are reported relative to the viewport.
+            let (scroll_x, scroll_y) = (Au::from_px(window.ScrollX()), Au::from_px(window.ScrollY()));
+            let to_dom_rect = |rect: &Rect<Au>| {
+                DOMRect::new(window,
+                             rect.origin.y - scroll_y, rect.max_y() - scroll_y,
+                             rect.origin.x - scroll_x, rect.max_x() - scroll_x)
+            };
+            let root_bounds = root_bounds.as_ref().map(|rect| to_dom_rect(rect));
+            let entry = IntersectionObserverEntry::new(window, time, root_bounds.r(),
+                                                       to_dom_rect(&target_rect).r(),
+                                                       to_dom_rect(&intersection_rect).r(),
+                                                       is_intersecting, ratio, target.r());
+            self.queue_entry(entry.r());
+        }
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#queue-an-intersectionobserverentry
+    fn queue_entry(&self, entry: &IntersectionObserverEntry) {
+        self.queued_entries.borrow_mut().push(JS::from_ref(entry));
+        if self.notify_task_queued.get() {
+            return;
+        }
+        self.notify_task_queued.set(true);
+
+        let window = self.window.root();
+        let script_chan = window.r().script_chan();
+        let task = box IntersectionObserverNotifyTask {
+            observer: Trusted::new(window.r().get_cx(), self, script_chan.clone()),
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#notify-intersection-observers-algo
+    fn notify(&self) {
+        self.notify_task_queued.set(false);
+        let entries = self.TakeRecords();
+        if entries.is_empty() {
+            return;
+        }
+        let _ = self.callback.Call_(self, entries, self, ExceptionHandling::Report);
+    }
+}
+
+impl IntersectionObserverMethods for IntersectionObserver {
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-root
+    fn GetRoot(&self) -> Option<Root<Element>> {
+        self.root.as_ref().map(|root| root.root())
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-rootmargin
+    fn RootMargin(&self) -> DOMString {
+        self.root_margin.serialize()
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-observe
+    fn Observe(&self, target: &Element) {
+        // Step 1.
+        let target_ref = JS::from_ref(target);
+        if self.observation_targets.borrow().iter().any(|observation| observation.target == target_ref) {
+            return;
+        }
+
+        // Steps 2-4.
+        self.observation_targets.borrow_mut().push(IntersectionObservation {
+            target: target_ref,
+            previous_threshold_index: -1,
+            previous_is_intersecting: false,
+        });
+        let window = self.window.root();
+        window.r().register_intersection_observer(self);
+
+        // Report the target's initial state from a task, once layout knows
+        // about it.
+        let script_chan = window.r().script_chan();
+        let task = box IntersectionObserverUpdateTask {
+            observer: Trusted::new(window.r().get_cx(), self, script_chan.clone()),
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-unobserve
+    fn Unobserve(&self, target: &Element) {
+        let target = JS::from_ref(target);
+        self.observation_targets.borrow_mut().retain(|observation| observation.target != target);
+        if self.observation_targets.borrow().is_empty() {
+            self.window.root().r().unregister_intersection_observer(self);
+        }
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-disconnect
+    fn Disconnect(&self) {
+        self.observation_targets.borrow_mut().clear();
+        self.window.root().r().unregister_intersection_observer(self);
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserver-takerecords
+    fn TakeRecords(&self) -> Vec<Root<IntersectionObserverEntry>> {
+        self.queued_entries.borrow_mut().drain(..).map(|entry| entry.root()).collect()
+    }
+}
+
+/// The task queued by `observe()` to compute the initial state of a target.
+struct IntersectionObserverUpdateTask {
+    observer: Trusted<IntersectionObserver>,
+}
+
+impl Runnable for IntersectionObserverUpdateTask {
+    fn handler(self: Box<IntersectionObserverUpdateTask>) {
+        let observer = self.observer.root();
+        let window = observer.r().window.root();
+        let time = *window.r().Performance().r().Now();
+        observer.r().update_observations(time);
+    }
+}
+
+/// The task that invokes the callback of an observer with its queued entries.
+struct IntersectionObserverNotifyTask {
+    observer: Trusted<IntersectionObserver>,
+}
+
+impl Runnable for IntersectionObserverNotifyTask {
+    fn handler(self: Box<IntersectionObserverNotifyTask>) {
+        let observer = self.observer.root();
+        observer.r().notify();
+    }
+}
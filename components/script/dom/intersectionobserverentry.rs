@@ -0,0 +1,98 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::IntersectionObserverEntryBinding;
+use dom::bindings::codegen::Bindings::IntersectionObserverEntryBinding::IntersectionObserverEntryMethods;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::num::Finite;
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::domrect::DOMRect;
+use dom::element::Element;
+use dom::window::Window;
+
+// https://w3c.github.io/IntersectionObserver/#intersection-observer-entry
+#[dom_struct]
+pub struct IntersectionObserverEntry {
+    reflector_: Reflector,
+    time: f64,
+    root_bounds: Option<JS<DOMRect>>,
+    bounding_client_rect: JS<DOMRect>,
+    intersection_rect: JS<DOMRect>,
+    is_intersecting: bool,
+    intersection_ratio: f64,
+    target: JS<Element>,
+}
+
+impl IntersectionObserverEntry {
+    fn new_inherited(time: f64,
+                     root_bounds: Option<&DOMRect>,
+                     bounding_client_rect: &DOMRect,
+                     intersection_rect: &DOMRect,
+                     is_intersecting: bool,
+                     intersection_ratio: f64,
+                     target: &Element) -> IntersectionObserverEntry {
+        IntersectionObserverEntry {
+            reflector_: Reflector::new(),
+            time: time,
+            root_bounds: root_bounds.map(JS::from_ref),
+            bounding_client_rect: JS::from_ref(bounding_client_rect),
+            intersection_rect: JS::from_ref(intersection_rect),
+            is_intersecting: is_intersecting,
+            intersection_ratio: intersection_ratio,
+            target: JS::from_ref(target),
+        }
+    }
+
+    pub fn new(window: &Window,
+               time: f64,
+               root_bounds: Option<&DOMRect>,
+               bounding_client_rect: &DOMRect,
+               intersection_rect: &DOMRect,
+               is_intersecting: bool,
+               intersection_ratio: f64,
+               target: &Element) -> Root<IntersectionObserverEntry> {
+        let entry = IntersectionObserverEntry::new_inherited(time, root_bounds, bounding_client_rect,
+                                                             intersection_rect, is_intersecting,
+                                                             intersection_ratio, target);
+        reflect_dom_object(box entry, GlobalRef::Window(window), IntersectionObserverEntryBinding::Wrap)
+    }
+}
+
+impl IntersectionObserverEntryMethods for IntersectionObserverEntry {
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-time
+    fn Time(&self) -> Finite<f64> {
+        Finite::wrap(self.time)
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-rootbounds
+    fn GetRootBounds(&self) -> Option<Root<DOMRect>> {
+        self.root_bounds.as_ref().map(|rect| rect.root())
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-boundingclientrect
+    fn BoundingClientRect(&self) -> Root<DOMRect> {
+        self.bounding_client_rect.root()
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-intersectionrect
+    fn IntersectionRect(&self) -> Root<DOMRect> {
+        self.intersection_rect.root()
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-isintersecting
+    fn IsIntersecting(&self) -> bool {
+        self.is_intersecting
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-intersectionratio
+    fn IntersectionRatio(&self) -> Finite<f64> {
+        Finite::wrap(self.intersection_ratio)
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#dom-intersectionobserverentry-target
+    fn Target(&self) -> Root<Element> {
+        self.target.root()
+    }
+}
@@ -35,6 +35,8 @@ pub struct KeyboardEvent {
     meta: Cell<bool>,
     repeat: Cell<bool>,
     is_composing: Cell<bool>,
+    /// The active modifiers other than Control, Shift, Alt and Meta, by key name.
+    extra_modifiers: RefCell<Vec<DOMString>>,
     char_code: Cell<Option<u32>>,
     key_code: Cell<u32>,
 }
@@ -59,6 +61,7 @@ impl KeyboardEvent {
             meta: Cell::new(false),
             repeat: Cell::new(false),
             is_composing: Cell::new(false),
+            extra_modifiers: RefCell::new(vec![]),
             char_code: Cell::new(None),
             key_code: Cell::new(0),
         }
@@ -119,6 +122,26 @@ impl KeyboardEvent {
                                        init.repeat, init.isComposing, init.parent.ctrlKey,
                                        init.parent.altKey, init.parent.shiftKey, init.parent.metaKey,
                                        None, 0);
+        {
+            let shared = &init.parent;
+            let extra_modifiers = [
+                ("AltGraph", shared.keyModifierStateAltGraph),
+                ("CapsLock", shared.keyModifierStateCapsLock),
+                ("Fn", shared.keyModifierStateFn),
+                ("FnLock", shared.keyModifierStateFnLock),
+                ("Hyper", shared.keyModifierStateHyper),
+                ("NumLock", shared.keyModifierStateNumLock),
+                ("OS", shared.keyModifierStateOS),
+                ("ScrollLock", shared.keyModifierStateScrollLock),
+                ("Super", shared.keyModifierStateSuper),
+                ("Symbol", shared.keyModifierStateSymbol),
+                ("SymbolLock", shared.keyModifierStateSymbolLock),
+            ];
+            *event.r().extra_modifiers.borrow_mut() = extra_modifiers.iter()
+                .filter(|&&(_, active)| active)
+                .map(|&(name, _)| name.to_owned())
+                .collect();
+        }
         Ok(event)
     }
 
@@ -159,6 +182,17 @@ impl KeyboardEvent {
 }
 
 
+// https://w3c.github.io/uievents/#widl-KeyboardEvent-getModifierState
+pub fn modifier_state(mods: KeyModifiers, extra_modifiers: &[DOMString], key_arg: &str) -> bool {
+    match key_arg {
+        "Control" => mods.contains(CONTROL),
+        "Shift" => mods.contains(SHIFT),
+        "Alt" => mods.contains(ALT),
+        "Meta" => mods.contains(SUPER),
+        _ => extra_modifiers.iter().any(|modifier| *modifier == key_arg),
+    }
+}
+
 // https://dvcs.w3.org/hg/dom3events/raw-file/tip/html/DOM3Events-key.html
 pub fn key_value(key: Key, mods: KeyModifiers) -> &'static str {
     let shift = mods.contains(constellation_msg::SHIFT);
@@ -825,17 +859,9 @@ impl KeyboardEventMethods for KeyboardEvent {
         self.is_composing.get()
     }
 
-    // https://dvcs.w3.org/hg/dom3events/raw-file/tip/html/DOM3-Events.html#widl-KeyboardEvent-getModifierState
+    // https://w3c.github.io/uievents/#widl-KeyboardEvent-getModifierState
     fn GetModifierState(&self, keyArg: DOMString) -> bool {
-        match &*keyArg {
-            "Ctrl" => self.CtrlKey(),
-            "Alt" => self.AltKey(),
-            "Shift" => self.ShiftKey(),
-            "Meta" => self.MetaKey(),
-            "AltGraph" | "CapsLock" | "NumLock" | "ScrollLock" | "Accel" |
-            "Fn" | "FnLock" | "Hyper" | "OS" | "Symbol" | "SymbolLock" => false, //FIXME
-            _ => false,
-        }
+        modifier_state(self.get_key_modifiers(), &self.extra_modifiers.borrow(), &keyArg)
     }
 
     // https://w3c.github.io/uievents/#widl-KeyboardEvent-charCode
@@ -4,13 +4,15 @@
 
 use dom::bindings::codegen::Bindings::LocationBinding;
 use dom::bindings::codegen::Bindings::LocationBinding::LocationMethods;
-use dom::bindings::error::ErrorResult;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::error::{Error, ErrorResult, Fallible};
 use dom::bindings::global::GlobalRef;
 use dom::bindings::js::{JS, Root};
 use dom::bindings::str::USVString;
 use dom::bindings::utils::{Reflector, reflect_dom_object};
 use dom::urlhelper::UrlHelper;
 use dom::window::Window;
+use url::urlutils::{UrlUtils, UrlUtilsWrapper};
 use url::{Url, UrlParser};
 use util::str::DOMString;
 
@@ -43,25 +45,41 @@ impl Location {
         let window = self.window.root();
         let mut url = window.get_url();
         setter(&mut url, value);
-        window.load_url(url);
+        window.load_url(url, false);
+    }
+
+    /// Parses `url` relative to the base URL of the window's document.
+    fn parse_url(&self, url: &str) -> Fallible<Url> {
+        // TODO: per spec, we should use the _API base URL_ specified by the
+        //       _entry settings object_.
+        let base_url = self.window.root().Document().r().base_url();
+        UrlParser::new().base_url(&base_url).parse(url).map_err(|_| Error::Syntax)
     }
 }
 
 impl LocationMethods for Location {
     // https://html.spec.whatwg.org/multipage/#dom-location-assign
-    fn Assign(&self, url: DOMString) {
-        let window = self.window.root();
-        // TODO: per spec, we should use the _API base URL_ specified by the
-        //       _entry settings object_.
-        let base_url = window.get_url();
-        if let Ok(url) = UrlParser::new().base_url(&base_url).parse(&url) {
-            window.load_url(url);
-        }
+    fn Assign(&self, url: DOMString) -> ErrorResult {
+        // Steps 1-2.
+        let url = try!(self.parse_url(&url));
+        // Step 3.
+        self.window.root().load_url(url, false);
+        Ok(())
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-location-replace
+    fn Replace(&self, url: DOMString) -> ErrorResult {
+        // Steps 1-2.
+        let url = try!(self.parse_url(&url));
+        // Step 3.
+        self.window.root().load_url(url, true);
+        Ok(())
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-location-reload
     fn Reload(&self) {
-        self.window.root().load_url(self.get_url());
+        // Reloading replaces the current session history entry.
+        self.window.root().load_url(self.get_url(), true);
     }
 
     // https://url.spec.whatwg.org/#dom-urlutils-hash
@@ -103,7 +121,7 @@ impl LocationMethods for Location {
     fn SetHref(&self, value: USVString) -> ErrorResult {
         let window = self.window.root();
         if let Ok(url) = UrlParser::new().base_url(&window.get_url()).parse(&value.0) {
-            window.load_url(url);
+            window.load_url(url, false);
         };
         Ok(())
     }
@@ -143,9 +161,24 @@ impl LocationMethods for Location {
         UrlHelper::Protocol(&self.get_url())
     }
 
-    // https://url.spec.whatwg.org/#dom-urlutils-protocol
-    fn SetProtocol(&self, value: USVString) {
-        self.set_url_component(value, UrlHelper::SetProtocol);
+    // https://html.spec.whatwg.org/multipage/#dom-location-protocol
+    fn SetProtocol(&self, value: USVString) -> ErrorResult {
+        let window = self.window.root();
+        let mut url = window.get_url();
+        // Steps 4-5.
+        {
+            let mut wrapper = UrlUtilsWrapper { url: &mut url, parser: &UrlParser::new() };
+            if wrapper.set_scheme(&value.0).is_err() {
+                return Err(Error::Syntax);
+            }
+        }
+        // Step 6.
+        if url.scheme != "http" && url.scheme != "https" {
+            return Ok(());
+        }
+        // Step 7.
+        window.load_url(url, false);
+        Ok(())
     }
 
     // https://url.spec.whatwg.org/#URLUtils-stringification-behavior
@@ -202,6 +202,19 @@ macro_rules! make_atomic_setter(
     );
 );
 
+#[macro_export]
+macro_rules! make_tokenlist_setter(
+    ( $attr:ident, $htmlname:expr ) => (
+        fn $attr(&self, value: DOMString) {
+            use dom::bindings::codegen::InheritTypes::ElementCast;
+            use string_cache::Atom;
+            let element = ElementCast::from_ref(self);
+            // FIXME(pcwalton): Do this at compile time, not at runtime.
+            element.set_tokenlist_attribute(&Atom::from_slice($htmlname), value)
+        }
+    );
+);
+
 /// For use on non-jsmanaged types
 /// Use #[derive(JSTraceable)] on JS managed types
 macro_rules! no_jsmanaged_fields(
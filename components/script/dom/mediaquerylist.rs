@@ -0,0 +1,111 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use cssparser::Parser as CssParser;
+use dom::bindings::codegen::Bindings::EventHandlerBinding::EventHandlerNonNull;
+use dom::bindings::codegen::Bindings::MediaQueryListBinding;
+use dom::bindings::codegen::Bindings::MediaQueryListBinding::MediaQueryListMethods;
+use dom::bindings::codegen::InheritTypes::{EventTargetCast, MediaQueryListDerived};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable};
+use dom::eventtarget::{EventTarget, EventTargetTypeId};
+use dom::window::Window;
+use std::borrow::ToOwned;
+use std::cell::Cell;
+use style::media_queries::{Device, MediaType, parse_media_query_list};
+use util::str::DOMString;
+
+// https://drafts.csswg.org/cssom-view/#the-mediaquerylist-interface
+#[dom_struct]
+pub struct MediaQueryList {
+    eventtarget: EventTarget,
+    window: JS<Window>,
+    /// The media query list this object was created with, as given to `matchMedia()`.
+    media: DOMString,
+    /// The result of the last evaluation, used to detect when it changes.
+    last_match: Cell<bool>,
+}
+
+impl MediaQueryList {
+    fn new_inherited(window: &Window, media: DOMString) -> MediaQueryList {
+        let matches = evaluate_media_query(window, &media);
+        MediaQueryList {
+            eventtarget: EventTarget::new_inherited(),
+            window: JS::from_ref(window),
+            media: media,
+            last_match: Cell::new(matches),
+        }
+    }
+
+    pub fn new(window: &Window, media: DOMString) -> Root<MediaQueryList> {
+        reflect_dom_object(box MediaQueryList::new_inherited(window, media),
+                           GlobalRef::Window(window), MediaQueryListBinding::Wrap)
+    }
+
+    /// Keeps this list registered with its window for as long as it has change
+    /// listeners. A list registering again starts from the current result, so
+    /// that changes it missed while unregistered are not reported.
+    pub fn change_listeners_changed(&self) {
+        let target = EventTargetCast::from_ref(self);
+        let has_listeners = target.get_listeners("change").map_or(false, |listeners| !listeners.is_empty());
+        let window = self.window.root();
+        if has_listeners {
+            self.last_match.set(evaluate_media_query(window.r(), &self.media));
+            window.r().register_media_query_list(self);
+        } else {
+            window.r().unregister_media_query_list(self);
+        }
+    }
+
+    /// Re-evaluates the media query list against the current viewport and fires a
+    /// `change` event at this object if the result differs from the last one.
+    ///
+    /// https://drafts.csswg.org/cssom-view/#evaluate-media-queries-and-report-changes
+    pub fn evaluate_and_report_changes(&self) {
+        let window = self.window.root();
+        let matches = evaluate_media_query(window.r(), &self.media);
+        if matches == self.last_match.get() {
+            return;
+        }
+        self.last_match.set(matches);
+
+        let event = Event::new(GlobalRef::Window(window.r()), "change".to_owned(),
+                               EventBubbles::DoesNotBubble,
+                               EventCancelable::NotCancelable);
+        event.r().fire(EventTargetCast::from_ref(self));
+    }
+}
+
+/// Parses `media` as a media query list and evaluates it against the viewport of
+/// `window`. A window that has not been sized yet matches nothing.
+fn evaluate_media_query(window: &Window, media: &str) -> bool {
+    window.window_size().map_or(false, |size| {
+        let device = Device::new(MediaType::Screen, size.initial_viewport);
+        parse_media_query_list(&mut CssParser::new(media)).evaluate(&device)
+    })
+}
+
+impl MediaQueryListDerived for EventTarget {
+    fn is_mediaquerylist(&self) -> bool {
+        *self.type_id() == EventTargetTypeId::MediaQueryList
+    }
+}
+
+impl MediaQueryListMethods for MediaQueryList {
+    // https://drafts.csswg.org/cssom-view/#dom-mediaquerylist-media
+    fn Media(&self) -> DOMString {
+        self.media.clone()
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-mediaquerylist-matches
+    fn Matches(&self) -> bool {
+        let window = self.window.root();
+        evaluate_media_query(window.r(), &self.media)
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-mediaquerylist-onchange
+    event_handler!(change, GetOnchange, SetOnchange);
+}
@@ -8,10 +8,11 @@ use dom::bindings::codegen::Bindings::MessageEventBinding::MessageEventMethods;
 use dom::bindings::codegen::InheritTypes::{EventCast, MessageEventDerived};
 use dom::bindings::error::Fallible;
 use dom::bindings::global::GlobalRef;
-use dom::bindings::js::Root;
+use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::reflect_dom_object;
 use dom::event::{Event, EventTypeId};
 use dom::eventtarget::EventTarget;
+use dom::window::Window;
 use js::jsapi::{HandleValue, Heap, JSContext};
 use js::jsval::JSVal;
 use std::borrow::ToOwned;
@@ -24,6 +25,7 @@ pub struct MessageEvent {
     data: Heap<JSVal>,
     origin: DOMString,
     lastEventId: DOMString,
+    source: Option<JS<Window>>,
 }
 
 impl MessageEventDerived for Event {
@@ -34,18 +36,20 @@ impl MessageEventDerived for Event {
 
 impl MessageEvent {
     pub fn new_uninitialized(global: GlobalRef) -> Root<MessageEvent> {
-        MessageEvent::new_initialized(global, HandleValue::undefined(), "".to_owned(), "".to_owned())
+        MessageEvent::new_initialized(global, HandleValue::undefined(), "".to_owned(), "".to_owned(), None)
     }
 
     pub fn new_initialized(global: GlobalRef,
                            data: HandleValue,
                            origin: DOMString,
-                           lastEventId: DOMString) -> Root<MessageEvent> {
+                           lastEventId: DOMString,
+                           source: Option<&Window>) -> Root<MessageEvent> {
         let mut ev = box MessageEvent {
             event: Event::new_inherited(EventTypeId::MessageEvent),
             data: Heap::default(),
             origin: origin,
             lastEventId: lastEventId,
+            source: source.map(JS::from_ref),
         };
         ev.data.set(data.get());
         reflect_dom_object(ev, global, MessageEventBinding::Wrap)
@@ -53,9 +57,10 @@ impl MessageEvent {
 
     pub fn new(global: GlobalRef, type_: DOMString,
                bubbles: bool, cancelable: bool,
-               data: HandleValue, origin: DOMString, lastEventId: DOMString)
+               data: HandleValue, origin: DOMString, lastEventId: DOMString,
+               source: Option<&Window>)
                -> Root<MessageEvent> {
-        let ev = MessageEvent::new_initialized(global, data, origin, lastEventId);
+        let ev = MessageEvent::new_initialized(global, data, origin, lastEventId, source);
         {
             let event = EventCast::from_ref(ev.r());
             event.InitEvent(type_, bubbles, cancelable);
@@ -69,7 +74,7 @@ impl MessageEvent {
                        -> Fallible<Root<MessageEvent>> {
         let ev = MessageEvent::new(global, type_, init.parent.bubbles, init.parent.cancelable,
                                    HandleValue { ptr: &init.data },
-                                   init.origin.clone(), init.lastEventId.clone());
+                                   init.origin.clone(), init.lastEventId.clone(), None);
         Ok(ev)
     }
 }
@@ -80,7 +85,7 @@ impl MessageEvent {
                           message: HandleValue) {
         let messageevent = MessageEvent::new(
             scope, "message".to_owned(), false, false, message,
-            "".to_owned(), "".to_owned());
+            "".to_owned(), "".to_owned(), None);
         let event = EventCast::from_ref(messageevent.r());
         event.fire(target);
     }
@@ -101,4 +106,9 @@ impl MessageEventMethods for MessageEvent {
     fn LastEventId(&self) -> DOMString {
         self.lastEventId.clone()
     }
+
+    // https://html.spec.whatwg.org/multipage/#dom-messageevent-source
+    fn GetSource(&self) -> Option<Root<Window>> {
+        self.source.as_ref().map(|source| source.root())
+    }
 }
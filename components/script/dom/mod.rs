@@ -189,7 +189,10 @@ pub mod types {
     include!(concat!(env!("OUT_DIR"), "/InterfaceTypes.rs"));
 }
 
+pub mod abortcontroller;
+pub mod abortsignal;
 pub mod activation;
+pub mod animation;
 pub mod attr;
 mod create;
 #[allow(unsafe_code)]
@@ -207,6 +210,7 @@ pub mod console;
 pub mod crypto;
 pub mod css;
 pub mod cssstyledeclaration;
+pub mod cssstylesheet;
 pub mod customevent;
 pub mod dedicatedworkerglobalscope;
 pub mod document;
@@ -229,7 +233,9 @@ pub mod eventtarget;
 pub mod file;
 pub mod filelist;
 pub mod filereader;
+pub mod focusevent;
 pub mod formdata;
+pub mod history;
 pub mod htmlanchorelement;
 pub mod htmlappletelement;
 pub mod htmlareaelement;
@@ -300,11 +306,18 @@ pub mod htmltrackelement;
 pub mod htmlulistelement;
 pub mod htmlunknownelement;
 pub mod htmlvideoelement;
+pub mod idledeadline;
 pub mod imagedata;
+pub mod inputevent;
+pub mod intersectionobserver;
+pub mod intersectionobserverentry;
 pub mod keyboardevent;
 pub mod location;
+pub mod mediaquerylist;
 pub mod messageevent;
 pub mod mouseevent;
+pub mod mutationobserver;
+pub mod mutationrecord;
 pub mod namednodemap;
 pub mod navigator;
 pub mod navigatorinfo;
@@ -312,14 +325,21 @@ pub mod node;
 pub mod nodeiterator;
 pub mod nodelist;
 pub mod performance;
+pub mod performanceentry;
 pub mod performancetiming;
+pub mod popstateevent;
 pub mod processinginstruction;
 pub mod progressevent;
 pub mod range;
+pub mod resizeobserver;
+pub mod resizeobserverentry;
 pub mod screen;
+pub mod selection;
 pub mod servohtmlparser;
+pub mod shadowroot;
 pub mod storage;
 pub mod storageevent;
+pub mod stylesheet;
 pub mod testbinding;
 pub mod testbindingproxy;
 pub mod text;
@@ -345,6 +365,7 @@ pub mod webglshaderprecisionformat;
 pub mod webgltexture;
 pub mod webgluniformlocation;
 pub mod websocket;
+pub mod wheelevent;
 pub mod window;
 pub mod worker;
 pub mod workerglobalscope;
@@ -353,3 +374,4 @@ pub mod workernavigator;
 pub mod xmlhttprequest;
 pub mod xmlhttprequesteventtarget;
 pub mod xmlhttprequestupload;
+pub mod xmlserializer;
@@ -2,13 +2,17 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use dom::bindings::codegen::Bindings::ElementBinding::ElementMethods;
+use dom::bindings::codegen::Bindings::EventBinding::EventMethods;
 use dom::bindings::codegen::Bindings::MouseEventBinding;
 use dom::bindings::codegen::Bindings::MouseEventBinding::MouseEventMethods;
 use dom::bindings::codegen::Bindings::UIEventBinding::UIEventMethods;
-use dom::bindings::codegen::InheritTypes::{EventCast, MouseEventDerived, UIEventCast};
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, EventCast, MouseEventDerived, NodeCast, UIEventCast};
 use dom::bindings::error::Fallible;
 use dom::bindings::global::GlobalRef;
 use dom::bindings::js::{JS, MutNullableHeap, Root, RootedReference};
+use dom::bindings::num::Finite;
 use dom::bindings::utils::reflect_dom_object;
 use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
 use dom::eventtarget::EventTarget;
@@ -31,19 +35,32 @@ pub struct MouseEvent {
     alt_key: Cell<bool>,
     meta_key: Cell<bool>,
     button: Cell<i16>,
+    buttons: Cell<u16>,
     related_target: MutNullableHeap<JS<EventTarget>>,
+    movement_x: Cell<i32>,
+    movement_y: Cell<i32>,
 }
 
 impl MouseEventDerived for Event {
     fn is_mouseevent(&self) -> bool {
-        *self.type_id() == EventTypeId::UIEvent(UIEventTypeId::MouseEvent)
+        match *self.type_id() {
+            EventTypeId::UIEvent(UIEventTypeId::MouseEvent(_)) => true,
+            _ => false
+        }
     }
 }
 
+/// The different types of MouseEvent.
+#[derive(JSTraceable, PartialEq, HeapSizeOf)]
+pub enum MouseEventTypeId {
+    MouseEvent,
+    WheelEvent,
+}
+
 impl MouseEvent {
-    fn new_inherited() -> MouseEvent {
+    pub fn new_inherited(type_id: MouseEventTypeId) -> MouseEvent {
         MouseEvent {
-            uievent: UIEvent::new_inherited(UIEventTypeId::MouseEvent),
+            uievent: UIEvent::new_inherited(UIEventTypeId::MouseEvent(type_id)),
             screen_x: Cell::new(0),
             screen_y: Cell::new(0),
             client_x: Cell::new(0),
@@ -53,12 +70,15 @@ impl MouseEvent {
             alt_key: Cell::new(false),
             meta_key: Cell::new(false),
             button: Cell::new(0),
+            buttons: Cell::new(0),
             related_target: Default::default(),
+            movement_x: Cell::new(0),
+            movement_y: Cell::new(0),
         }
     }
 
     pub fn new_uninitialized(window: &Window) -> Root<MouseEvent> {
-        reflect_dom_object(box MouseEvent::new_inherited(),
+        reflect_dom_object(box MouseEvent::new_inherited(MouseEventTypeId::MouseEvent),
                            GlobalRef::Window(window),
                            MouseEventBinding::Wrap)
     }
@@ -99,7 +119,7 @@ impl MouseEvent {
         let cancelable = if init.parent.parent.parent.cancelable {
             EventCancelable::Cancelable
         } else {
-        EventCancelable::NotCancelable
+            EventCancelable::NotCancelable
         };
         let event = MouseEvent::new(global.as_window(), type_,
                                     bubbles,
@@ -110,8 +130,45 @@ impl MouseEvent {
                                     init.clientX, init.clientY, init.parent.ctrlKey,
                                     init.parent.altKey, init.parent.shiftKey, init.parent.metaKey,
                                     init.button, init.relatedTarget.r());
+        event.r().init_extended_members(init);
         Ok(event)
     }
+
+    /// Sets the members of `init` that `initMouseEvent` doesn't take.
+    pub fn init_extended_members(&self, init: &MouseEventBinding::MouseEventInit) {
+        self.buttons.set(init.buttons);
+        self.movement_x.set(init.movementX);
+        self.movement_y.set(init.movementY);
+    }
+
+    /// The position of the padding edge of the target element, relative to
+    /// the viewport. Only available while the event is being dispatched.
+    fn target_padding_edge(&self) -> Option<(i32, i32)> {
+        let event = EventCast::from_ref(self);
+        if !event.dispatching() {
+            return None;
+        }
+        let target = match event.GetTarget() {
+            Some(target) => target,
+            None => return None,
+        };
+        let element = match ElementCast::to_ref(target.r()) {
+            Some(element) => element,
+            None => return None,
+        };
+        let border_box = NodeCast::from_ref(element).get_bounding_content_box();
+        Some((border_box.origin.x.to_nearest_px() + element.ClientLeft(),
+              border_box.origin.y.to_nearest_px() + element.ClientTop()))
+    }
+
+    /// The scroll offset of the window associated with this event, if any.
+    fn scroll_offset(&self) -> (i32, i32) {
+        let uievent = UIEventCast::from_ref(self);
+        match uievent.GetView() {
+            Some(view) => (view.r().ScrollX(), view.r().ScrollY()),
+            None => (0, 0),
+        }
+    }
 }
 
 impl MouseEventMethods for MouseEvent {
@@ -160,6 +217,11 @@ impl MouseEventMethods for MouseEvent {
         self.button.get()
     }
 
+    // https://w3c.github.io/uievents/#widl-MouseEvent-buttons
+    fn Buttons(&self) -> u16 {
+        self.buttons.get()
+    }
+
     // https://w3c.github.io/uievents/#widl-MouseEvent-relatedTarget
     fn GetRelatedTarget(&self) -> Option<Root<EventTarget>> {
         self.related_target.get().map(Root::from_rooted)
@@ -178,6 +240,44 @@ impl MouseEventMethods for MouseEvent {
         }
     }
 
+    // https://drafts.csswg.org/cssom-view/#dom-mouseevent-pagex
+    fn PageX(&self) -> Finite<f64> {
+        let (scroll_x, _) = self.scroll_offset();
+        Finite::wrap((scroll_x + self.client_x.get()) as f64)
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-mouseevent-pagey
+    fn PageY(&self) -> Finite<f64> {
+        let (_, scroll_y) = self.scroll_offset();
+        Finite::wrap((scroll_y + self.client_y.get()) as f64)
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-mouseevent-offsetx
+    fn OffsetX(&self) -> Finite<f64> {
+        match self.target_padding_edge() {
+            Some((x, _)) => Finite::wrap((self.client_x.get() - x) as f64),
+            None => self.PageX(),
+        }
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-mouseevent-offsety
+    fn OffsetY(&self) -> Finite<f64> {
+        match self.target_padding_edge() {
+            Some((_, y)) => Finite::wrap((self.client_y.get() - y) as f64),
+            None => self.PageY(),
+        }
+    }
+
+    // https://w3c.github.io/pointerlock/#widl-MouseEvent-movementX
+    fn MovementX(&self) -> i32 {
+        self.movement_x.get()
+    }
+
+    // https://w3c.github.io/pointerlock/#widl-MouseEvent-movementY
+    fn MovementY(&self) -> i32 {
+        self.movement_y.get()
+    }
+
     // https://w3c.github.io/uievents/#widl-MouseEvent-initMouseEvent
     fn InitMouseEvent(&self,
                       typeArg: DOMString,
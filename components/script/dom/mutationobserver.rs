@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::callback::ExceptionHandling;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::MutationObserverBinding;
+use dom::bindings::codegen::Bindings::MutationObserverBinding::MutationCallback;
+use dom::bindings::codegen::Bindings::MutationObserverBinding::MutationObserverInit;
+use dom::bindings::codegen::Bindings::MutationObserverBinding::MutationObserverMethods;
+use dom::bindings::error::{Error, Fallible};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::mutationrecord::MutationRecord;
+use dom::node::{Node, window_from_node};
+use dom::window::Window;
+use std::mem;
+use std::rc::Rc;
+use string_cache::{Atom, Namespace};
+
+/// The type of a mutation record.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MutationType {
+    Attributes,
+    CharacterData,
+    ChildList,
+}
+
+/// A mutation of a node, as reported to the mutation observers interested in it.
+///
+/// Old values are borrowed, and only copied into a record for the observers
+/// that asked for them.
+pub enum Mutation<'a> {
+    Attribute {
+        name: &'a Atom,
+        namespace: &'a Namespace,
+        old_value: Option<&'a str>,
+    },
+    CharacterData {
+        old_value: &'a str,
+    },
+    ChildList {
+        added: &'a [&'a Node],
+        removed: &'a [&'a Node],
+        previous_sibling: Option<&'a Node>,
+        next_sibling: Option<&'a Node>,
+    },
+}
+
+impl<'a> Mutation<'a> {
+    fn mutation_type(&self) -> MutationType {
+        match *self {
+            Mutation::Attribute { .. } => MutationType::Attributes,
+            Mutation::CharacterData { .. } => MutationType::CharacterData,
+            Mutation::ChildList { .. } => MutationType::ChildList,
+        }
+    }
+}
+
+/// The options a `MutationObserver` observes a node with, once the implied
+/// members of a `MutationObserverInit` dictionary have been filled in.
+#[derive(Clone, Copy, Debug, HeapSizeOf, JSTraceable, PartialEq)]
+pub struct ObserverOptions {
+    pub child_list: bool,
+    pub attributes: bool,
+    pub character_data: bool,
+    pub subtree: bool,
+    pub attribute_old_value: bool,
+    pub character_data_old_value: bool,
+}
+
+impl ObserverOptions {
+    // https://dom.spec.whatwg.org/#dom-mutationobserver-observe
+    pub fn from_init(init: &MutationObserverInit) -> Fallible<ObserverOptions> {
+        // Step 1.
+        let attributes = init.attributes.unwrap_or(init.attributeOldValue.is_some());
+
+        // Step 2.
+        let character_data = init.characterData.unwrap_or(init.characterDataOldValue.is_some());
+
+        // Step 3.
+        if !init.childList && !attributes && !character_data {
+            return Err(Error::Type("One of childList, attributes or characterData must be true".to_owned()));
+        }
+
+        // Step 4.
+        let attribute_old_value = init.attributeOldValue.unwrap_or(false);
+        if attribute_old_value && !attributes {
+            return Err(Error::Type("attributeOldValue requires attributes to be true".to_owned()));
+        }
+
+        // Step 6.
+        let character_data_old_value = init.characterDataOldValue.unwrap_or(false);
+        if character_data_old_value && !character_data {
+            return Err(Error::Type("characterDataOldValue requires characterData to be true".to_owned()));
+        }
+
+        Ok(ObserverOptions {
+            child_list: init.childList,
+            attributes: attributes,
+            character_data: character_data,
+            subtree: init.subtree,
+            attribute_old_value: attribute_old_value,
+            character_data_old_value: character_data_old_value,
+        })
+    }
+
+    /// Returns `None` if an observer registered on a node with these options
+    /// isn't interested in a mutation of the given type, whose target is that
+    /// node if `is_target` is true and one of its descendants otherwise.
+    /// Otherwise, returns whether the observer wants the old value.
+    // https://dom.spec.whatwg.org/#queue-a-mutation-record, step 3.
+    pub fn interest(&self, mutation_type: MutationType, is_target: bool) -> Option<bool> {
+        if !is_target && !self.subtree {
+            return None;
+        }
+        match mutation_type {
+            MutationType::Attributes if self.attributes => Some(self.attribute_old_value),
+            MutationType::CharacterData if self.character_data => Some(self.character_data_old_value),
+            MutationType::ChildList if self.child_list => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A node observed by a `MutationObserver`, with the options it is observed with.
+// https://dom.spec.whatwg.org/#registered-observer
+#[must_root]
+#[derive(JSTraceable, HeapSizeOf)]
+#[privatize]
+struct RegisteredObserver {
+    node: JS<Node>,
+    options: ObserverOptions,
+}
+
+// https://dom.spec.whatwg.org/#interface-mutationobserver
+#[dom_struct]
+pub struct MutationObserver {
+    reflector_: Reflector,
+    window: JS<Window>,
+    #[ignore_heap_size_of = "Rc<T> is hard"]
+    callback: Rc<MutationCallback>,
+    /// https://dom.spec.whatwg.org/#mutationobserver-node-list
+    node_list: DOMRefCell<Vec<RegisteredObserver>>,
+    /// https://dom.spec.whatwg.org/#concept-mo-queue
+    record_queue: DOMRefCell<Vec<JS<MutationRecord>>>,
+}
+
+impl MutationObserver {
+    fn new_inherited(window: &Window, callback: Rc<MutationCallback>) -> MutationObserver {
+        MutationObserver {
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
+            callback: callback,
+            node_list: DOMRefCell::new(vec![]),
+            record_queue: DOMRefCell::new(vec![]),
+        }
+    }
+
+    pub fn new(window: &Window, callback: Rc<MutationCallback>) -> Root<MutationObserver> {
+        reflect_dom_object(box MutationObserver::new_inherited(window, callback),
+                           GlobalRef::Window(window), MutationObserverBinding::Wrap)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationobserver-mutationobserver
+    pub fn Constructor(global: GlobalRef, callback: Rc<MutationCallback>) -> Fallible<Root<MutationObserver>> {
+        Ok(MutationObserver::new(global.as_window(), callback))
+    }
+
+    /// Returns the options `node` is observed with, if it is observed.
+    fn options_for(&self, node: &Node) -> Option<ObserverOptions> {
+        let node = JS::from_ref(node);
+        self.node_list.borrow().iter()
+            .find(|registered| registered.node == node)
+            .map(|registered| registered.options)
+    }
+
+    // https://dom.spec.whatwg.org/#queue-a-mutation-record
+    pub fn queue_a_mutation_record(target: &Node, mutation: Mutation) {
+        let window = window_from_node(target);
+        let observers = window.r().mutation_observers();
+        if observers.is_empty() {
+            return;
+        }
+
+        // Steps 1-3.
+        let mutation_type = mutation.mutation_type();
+        let mut interested = vec![None; observers.len()];
+        for node in target.inclusive_ancestors() {
+            let is_target = node.r() == target;
+            for (observer, interest) in observers.iter().zip(interested.iter_mut()) {
+                let wants_old_value = observer.r().options_for(node.r())
+                                              .and_then(|options| options.interest(mutation_type, is_target));
+                if let Some(wants_old_value) = wants_old_value {
+                    *interest = Some(interest.unwrap_or(false) || wants_old_value);
+                }
+            }
+        }
+
+        // Step 4.
+        for (observer, interest) in observers.iter().zip(interested) {
+            let wants_old_value = match interest {
+                Some(wants_old_value) => wants_old_value,
+                None => continue,
+            };
+            let record = match mutation {
+                Mutation::Attribute { ref name, ref namespace, ref old_value } => {
+                    let old_value = if wants_old_value { old_value.map(|value| value.to_owned()) } else { None };
+                    MutationRecord::attributes(target, name, namespace, old_value)
+                },
+                Mutation::CharacterData { ref old_value } => {
+                    let old_value = if wants_old_value { Some((*old_value).to_owned()) } else { None };
+                    MutationRecord::character_data(target, old_value)
+                },
+                Mutation::ChildList { ref added, ref removed, ref previous_sibling, ref next_sibling } => {
+                    MutationRecord::child_list(target, added, removed, *previous_sibling, *next_sibling)
+                },
+            };
+            observer.r().record_queue.borrow_mut().push(JS::from_rooted(&record));
+        }
+
+        // Step 5.
+        window.r().queue_mutation_observer_microtask();
+    }
+
+    /// Invokes the callback with the queued records, if there are any.
+    // https://dom.spec.whatwg.org/#notify-mutation-observers, step 5.
+    pub fn notify(&self) {
+        let records = self.TakeRecords();
+        if records.is_empty() {
+            return;
+        }
+        let _ = self.callback.Call_(self, records, self, ExceptionHandling::Report);
+    }
+}
+
+impl MutationObserverMethods for MutationObserver {
+    // https://dom.spec.whatwg.org/#dom-mutationobserver-observe
+    fn Observe(&self, target: &Node, options: &MutationObserverInit) -> Fallible<()> {
+        // Steps 1-6.
+        let options = try!(ObserverOptions::from_init(options));
+
+        // Step 7.
+        let node = JS::from_ref(target);
+        {
+            let mut node_list = self.node_list.borrow_mut();
+            if let Some(registered) = node_list.iter_mut().find(|registered| registered.node == node) {
+                registered.options = options;
+                return Ok(());
+            }
+
+            // Step 8.
+            node_list.push(RegisteredObserver {
+                node: node,
+                options: options,
+            });
+        }
+        self.window.root().r().register_mutation_observer(self);
+        Ok(())
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationobserver-disconnect
+    fn Disconnect(&self) {
+        // Step 1.
+        self.node_list.borrow_mut().clear();
+        self.window.root().r().unregister_mutation_observer(self);
+
+        // Step 2.
+        self.record_queue.borrow_mut().clear();
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationobserver-takerecords
+    fn TakeRecords(&self) -> Vec<Root<MutationRecord>> {
+        let records = mem::replace(&mut *self.record_queue.borrow_mut(), vec![]);
+        records.iter().map(|record| record.root()).collect()
+    }
+}
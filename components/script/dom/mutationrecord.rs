@@ -0,0 +1,146 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::MutationRecordBinding;
+use dom::bindings::codegen::Bindings::MutationRecordBinding::MutationRecordMethods;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::node::{Node, window_from_node};
+use dom::nodelist::NodeList;
+use string_cache::{Atom, Namespace};
+use util::str::DOMString;
+
+// https://dom.spec.whatwg.org/#interface-mutationrecord
+#[dom_struct]
+pub struct MutationRecord {
+    reflector_: Reflector,
+    record_type: DOMString,
+    target: JS<Node>,
+    added_nodes: JS<NodeList>,
+    removed_nodes: JS<NodeList>,
+    previous_sibling: Option<JS<Node>>,
+    next_sibling: Option<JS<Node>>,
+    attribute_name: Option<DOMString>,
+    attribute_namespace: Option<DOMString>,
+    old_value: Option<DOMString>,
+}
+
+impl MutationRecord {
+    fn new_inherited(record_type: &str,
+                     target: &Node,
+                     added_nodes: &NodeList,
+                     removed_nodes: &NodeList,
+                     previous_sibling: Option<&Node>,
+                     next_sibling: Option<&Node>,
+                     attribute_name: Option<DOMString>,
+                     attribute_namespace: Option<DOMString>,
+                     old_value: Option<DOMString>) -> MutationRecord {
+        MutationRecord {
+            reflector_: Reflector::new(),
+            record_type: record_type.to_owned(),
+            target: JS::from_ref(target),
+            added_nodes: JS::from_ref(added_nodes),
+            removed_nodes: JS::from_ref(removed_nodes),
+            previous_sibling: previous_sibling.map(JS::from_ref),
+            next_sibling: next_sibling.map(JS::from_ref),
+            attribute_name: attribute_name,
+            attribute_namespace: attribute_namespace,
+            old_value: old_value,
+        }
+    }
+
+    fn new(record_type: &str,
+           target: &Node,
+           added_nodes: &[&Node],
+           removed_nodes: &[&Node],
+           previous_sibling: Option<&Node>,
+           next_sibling: Option<&Node>,
+           attribute_name: Option<DOMString>,
+           attribute_namespace: Option<DOMString>,
+           old_value: Option<DOMString>) -> Root<MutationRecord> {
+        let window = window_from_node(target);
+        let added_nodes = NodeList::new_simple_list(window.r(),
+                                                    added_nodes.iter().map(|node| Root::from_ref(*node)));
+        let removed_nodes = NodeList::new_simple_list(window.r(),
+                                                      removed_nodes.iter().map(|node| Root::from_ref(*node)));
+        let record = MutationRecord::new_inherited(record_type, target, added_nodes.r(), removed_nodes.r(),
+                                                   previous_sibling, next_sibling, attribute_name,
+                                                   attribute_namespace, old_value);
+        reflect_dom_object(box record, GlobalRef::Window(window.r()), MutationRecordBinding::Wrap)
+    }
+
+    pub fn attributes(target: &Node,
+                      name: &Atom,
+                      namespace: &Namespace,
+                      old_value: Option<DOMString>) -> Root<MutationRecord> {
+        let Namespace(ref atom) = *namespace;
+        let namespace = match &**atom {
+            "" => None,
+            url => Some(url.to_owned()),
+        };
+        MutationRecord::new("attributes", target, &[], &[], None, None,
+                            Some((**name).to_owned()), namespace, old_value)
+    }
+
+    pub fn character_data(target: &Node, old_value: Option<DOMString>) -> Root<MutationRecord> {
+        MutationRecord::new("characterData", target, &[], &[], None, None, None, None, old_value)
+    }
+
+    pub fn child_list(target: &Node,
+                      added_nodes: &[&Node],
+                      removed_nodes: &[&Node],
+                      previous_sibling: Option<&Node>,
+                      next_sibling: Option<&Node>) -> Root<MutationRecord> {
+        MutationRecord::new("childList", target, added_nodes, removed_nodes,
+                            previous_sibling, next_sibling, None, None, None)
+    }
+}
+
+impl MutationRecordMethods for MutationRecord {
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-type
+    fn Type(&self) -> DOMString {
+        self.record_type.clone()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-target
+    fn Target(&self) -> Root<Node> {
+        self.target.root()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-addednodes
+    fn AddedNodes(&self) -> Root<NodeList> {
+        self.added_nodes.root()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-removednodes
+    fn RemovedNodes(&self) -> Root<NodeList> {
+        self.removed_nodes.root()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-previoussibling
+    fn GetPreviousSibling(&self) -> Option<Root<Node>> {
+        self.previous_sibling.as_ref().map(|node| node.root())
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-nextsibling
+    fn GetNextSibling(&self) -> Option<Root<Node>> {
+        self.next_sibling.as_ref().map(|node| node.root())
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-attributename
+    fn GetAttributeName(&self) -> Option<DOMString> {
+        self.attribute_name.clone()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-attributenamespace
+    fn GetAttributeNamespace(&self) -> Option<DOMString> {
+        self.attribute_namespace.clone()
+    }
+
+    // https://dom.spec.whatwg.org/#dom-mutationrecord-oldvalue
+    fn GetOldValue(&self) -> Option<DOMString> {
+        self.old_value.clone()
+    }
+}
@@ -11,6 +11,7 @@ use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::{Reflector, namespace_from_domstring, reflect_dom_object};
 use dom::element::Element;
 use dom::window::Window;
+use std::borrow::ToOwned;
 use string_cache::Atom;
 use util::str::DOMString;
 
@@ -71,6 +72,19 @@ impl NamedNodeMapMethods for NamedNodeMap {
         owner.get_attribute(&ns, &Atom::from_slice(&local_name))
     }
 
+    // https://dom.spec.whatwg.org/#dom-namednodemap-setnameditem
+    fn SetNamedItem(&self, attr: &Attr) -> Fallible<Option<Root<Attr>>> {
+        let owner = self.owner.root();
+        // FIXME(https://github.com/rust-lang/rust/issues/23338)
+        let owner = owner.r();
+        owner.set_attribute_node(attr)
+    }
+
+    // https://dom.spec.whatwg.org/#dom-namednodemap-setnameditemns
+    fn SetNamedItemNS(&self, attr: &Attr) -> Fallible<Option<Root<Attr>>> {
+        self.SetNamedItem(attr)
+    }
+
     // https://dom.spec.whatwg.org/#dom-namednodemap-removenameditem
     fn RemoveNamedItem(&self, name: DOMString) -> Fallible<Root<Attr>> {
         let owner = self.owner.root();
@@ -105,7 +119,22 @@ impl NamedNodeMapMethods for NamedNodeMap {
     }
 
     fn SupportedPropertyNames(&self) -> Vec<DOMString> {
-        // FIXME: unimplemented (https://github.com/servo/servo/issues/7273)
-        vec![]
+        let owner = self.owner.root();
+        // FIXME(https://github.com/rust-lang/rust/issues/23338)
+        let owner = owner.r();
+        // Step 1.
+        let mut names: Vec<DOMString> = vec![];
+        for attr in owner.attrs().iter().map(JS::root) {
+            let name = (**attr.name()).to_owned();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        // Step 2.
+        if owner.html_element_in_html_document() {
+            names.retain(|name| !name.chars().any(|c| c >= 'A' && c <= 'Z'));
+        }
+        // Step 3.
+        names
     }
 }
@@ -4,8 +4,9 @@
 
 use dom::bindings::codegen::Bindings::NavigatorBinding;
 use dom::bindings::codegen::Bindings::NavigatorBinding::NavigatorMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
 use dom::bindings::global::GlobalRef;
-use dom::bindings::js::Root;
+use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::{Reflector, reflect_dom_object};
 use dom::navigatorinfo;
 use dom::window::Window;
@@ -14,17 +15,19 @@ use util::str::DOMString;
 #[dom_struct]
 pub struct Navigator {
     reflector_: Reflector,
+    window: JS<Window>,
 }
 
 impl Navigator {
-    fn new_inherited() -> Navigator {
+    fn new_inherited(window: &Window) -> Navigator {
         Navigator {
-            reflector_: Reflector::new()
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
         }
     }
 
     pub fn new(window: &Window) -> Root<Navigator> {
-        reflect_dom_object(box Navigator::new_inherited(),
+        reflect_dom_object(box Navigator::new_inherited(window),
                            GlobalRef::Window(window),
                            NavigatorBinding::Wrap)
     }
@@ -65,4 +68,19 @@ impl NavigatorMethods for Navigator {
     fn AppVersion(&self) -> DOMString {
         navigatorinfo::AppVersion()
     }
+
+    // https://html.spec.whatwg.org/multipage/#dom-navigator-online
+    fn OnLine(&self) -> bool {
+        self.window.root().is_online()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-navigator-cookieenabled
+    fn CookieEnabled(&self) -> bool {
+        self.window.root().Document().cookies_enabled()
+    }
+
+    // https://www.w3.org/TR/tracking-dnt/#dom-navigator-donottrack
+    fn GetDoNotTrack(&self) -> Option<DOMString> {
+        navigatorinfo::DoNotTrack()
+    }
 }
@@ -4,6 +4,7 @@
 
 use std::borrow::ToOwned;
 use util::opts;
+use util::prefs;
 use util::str::DOMString;
 
 pub fn Product() -> DOMString {
@@ -44,3 +45,11 @@ pub fn UserAgent() -> DOMString {
 pub fn AppVersion() -> DOMString {
     "4.0".to_owned()
 }
+
+pub fn DoNotTrack() -> Option<DOMString> {
+    if prefs::get_pref("network.http.donottrack.enabled").as_boolean().unwrap_or(false) {
+        Some("1".to_owned())
+    } else {
+        None
+    }
+}
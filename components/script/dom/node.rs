@@ -25,7 +25,7 @@ use dom::bindings::codegen::InheritTypes::{HTMLOptGroupElementDerived, NodeBase,
 use dom::bindings::codegen::InheritTypes::{ProcessingInstructionCast, TextCast, TextDerived};
 use dom::bindings::codegen::UnionTypes::NodeOrString;
 use dom::bindings::conversions;
-use dom::bindings::error::Error::{HierarchyRequest, NotFound, Syntax};
+use dom::bindings::error::Error::{HierarchyRequest, NotFound, NotSupported, Syntax};
 use dom::bindings::error::{ErrorResult, Fallible};
 use dom::bindings::global::GlobalRef;
 use dom::bindings::js::Root;
@@ -37,17 +37,21 @@ use dom::bindings::utils::{Reflectable, namespace_from_domstring, reflect_dom_ob
 use dom::characterdata::{CharacterData, CharacterDataTypeId};
 use dom::comment::Comment;
 use dom::document::{Document, DocumentSource, IsHTMLDocument};
-use dom::documentfragment::DocumentFragment;
+use dom::documentfragment::{DocumentFragment, DocumentFragmentTypeId};
 use dom::documenttype::DocumentType;
 use dom::element::{Element, ElementCreator, ElementTypeId};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::HTMLElementTypeId;
+use dom::mutationobserver::{Mutation, MutationObserver};
 use dom::nodelist::NodeList;
 use dom::processinginstruction::ProcessingInstruction;
+use dom::range::{for_each_live_range, merged_text_offsets};
 use dom::text::Text;
 use dom::virtualmethods::{VirtualMethods, vtable_for};
 use dom::window::Window;
+use dom::xmlserializer;
 use euclid::rect::Rect;
+use html5ever::serialize::{SerializeOpts, TraversalScope, serialize};
 use js::jsapi::{JSContext, JSObject, JSRuntime};
 use layout_interface::{LayoutChan, Msg};
 use libc::{self, c_void, uintptr_t};
@@ -283,7 +287,7 @@ impl LayoutDataRef {
 pub enum NodeTypeId {
     CharacterData(CharacterDataTypeId),
     DocumentType,
-    DocumentFragment,
+    DocumentFragment(DocumentFragmentTypeId),
     Document,
     Element(ElementTypeId),
 }
@@ -937,6 +941,22 @@ impl Node {
         }
     }
 
+    // https://w3c.github.io/DOM-Parsing/#dfn-fragment-serializing-algorithm
+    pub fn serialize(&self, traversal_scope: TraversalScope) -> Fallible<DOMString> {
+        if !self.owner_doc().r().is_html_document() {
+            return Ok(xmlserializer::serialize_node(self, traversal_scope));
+        }
+        let mut writer = vec![];
+        match serialize(&mut writer, &self,
+                        SerializeOpts {
+                            traversal_scope: traversal_scope,
+                            .. Default::default()
+                        }) {
+            Ok(()) => Ok(String::from_utf8(writer).unwrap()),
+            Err(_) => panic!("Cannot serialize node"),
+        }
+    }
+
     // https://dvcs.w3.org/hg/innerhtml/raw-file/tip/index.html#dfn-concept-parse-fragment
     pub fn parse_fragment(&self, markup: DOMString) -> Fallible<Root<DocumentFragment>> {
         let context_node: &Node = NodeCast::from_ref(self);
@@ -1430,7 +1450,7 @@ impl Node {
         // Step 1.
         match parent.type_id() {
             NodeTypeId::Document |
-            NodeTypeId::DocumentFragment |
+            NodeTypeId::DocumentFragment(_) |
             NodeTypeId::Element(..) => (),
             _ => return Err(HierarchyRequest)
         }
@@ -1459,7 +1479,7 @@ impl Node {
                     return Err(HierarchyRequest);
                 }
             },
-            NodeTypeId::DocumentFragment |
+            NodeTypeId::DocumentFragment(_) |
             NodeTypeId::Element(_) |
             NodeTypeId::CharacterData(CharacterDataTypeId::ProcessingInstruction) |
             NodeTypeId::CharacterData(CharacterDataTypeId::Comment) => (),
@@ -1470,7 +1490,7 @@ impl Node {
         if parent.type_id() == NodeTypeId::Document {
             match node.type_id() {
                 // Step 6.1
-                NodeTypeId::DocumentFragment => {
+                NodeTypeId::DocumentFragment(_) => {
                     // Step 6.1.1(b)
                     if node.children()
                            .any(|c| c.r().is_text())
@@ -1574,10 +1594,16 @@ impl Node {
 
         // Steps 1-2: ranges.
         let mut new_nodes = RootedVec::new();
-        let new_nodes = if let NodeTypeId::DocumentFragment = node.type_id() {
+        let new_nodes = if let NodeTypeId::DocumentFragment(_) = node.type_id() {
             // Step 3.
             new_nodes.extend(node.children().map(|kid| JS::from_rooted(&kid)));
-            // Step 4: mutation observers.
+            // Step 4.
+            MutationObserver::queue_a_mutation_record(node, Mutation::ChildList {
+                added: &[],
+                removed: new_nodes.r(),
+                previous_sibling: None,
+                next_sibling: None,
+            });
             // Step 5.
             for kid in new_nodes.r() {
                 Node::remove(*kid, node, SuppressObserver::Suppressed);
@@ -1588,13 +1614,20 @@ impl Node {
             // Step 3.
             ref_slice(&node)
         };
-        // Step 6: mutation observers.
+        // Step 6.
         let previous_sibling = match suppress_observers {
             SuppressObserver::Unsuppressed => {
-                match child {
+                let previous_sibling = match child {
                     Some(child) => child.GetPreviousSibling(),
                     None => parent.GetLastChild(),
-                }
+                };
+                MutationObserver::queue_a_mutation_record(parent, Mutation::ChildList {
+                    added: new_nodes,
+                    removed: &[],
+                    previous_sibling: previous_sibling.r(),
+                    next_sibling: child,
+                });
+                previous_sibling
             },
             SuppressObserver::Suppressed => None,
         };
@@ -1621,7 +1654,7 @@ impl Node {
         // Step 3.
         let mut added_nodes = RootedVec::new();
         let added_nodes = if let Some(node) = node.as_ref() {
-            if let NodeTypeId::DocumentFragment = node.type_id() {
+            if let NodeTypeId::DocumentFragment(_) = node.type_id() {
                 added_nodes.extend(node.children().map(|child| JS::from_rooted(&child)));
                 added_nodes.r()
             } else {
@@ -1638,7 +1671,15 @@ impl Node {
         if let Some(node) = node {
             Node::insert(node, parent, None, SuppressObserver::Suppressed);
         }
-        // Step 6: mutation observers.
+        // Step 6.
+        if !added_nodes.is_empty() || !removed_nodes.is_empty() {
+            MutationObserver::queue_a_mutation_record(parent, Mutation::ChildList {
+                added: added_nodes,
+                removed: removed_nodes.r(),
+                previous_sibling: None,
+                next_sibling: None,
+            });
+        }
         vtable_for(&parent).children_changed(
             &ChildrenMutation::replace_all(removed_nodes.r(), added_nodes));
     }
@@ -1663,12 +1704,23 @@ impl Node {
     fn remove(node: &Node, parent: &Node, suppress_observers: SuppressObserver) {
         assert!(node.GetParentNode().map_or(false, |node_parent| node_parent.r() == parent));
 
-        // Step 1-5: ranges.
+        // Step 1.
+        let index = node.index();
+        // Steps 2-5.
+        for_each_live_range(|range| range.node_removed(node, parent, index));
         // Step 6.
         let old_previous_sibling = node.GetPreviousSibling();
-        // Steps 7-8: mutation observers.
-        // Step 9.
         let old_next_sibling = node.GetNextSibling();
+        // Steps 7-8.
+        if let SuppressObserver::Unsuppressed = suppress_observers {
+            MutationObserver::queue_a_mutation_record(parent, Mutation::ChildList {
+                added: &[],
+                removed: ref_slice(&node),
+                previous_sibling: old_previous_sibling.r(),
+                next_sibling: old_next_sibling.r(),
+            });
+        }
+        // Step 9.
         parent.remove_child(node);
         if let SuppressObserver::Unsuppressed = suppress_observers {
             vtable_for(&parent).children_changed(
@@ -1698,7 +1750,7 @@ impl Node {
                                                 Some(doctype.system_id().clone()), document.r());
                 NodeCast::from_root(doctype)
             },
-            NodeTypeId::DocumentFragment => {
+            NodeTypeId::DocumentFragment(_) => {
                 let doc_fragment = DocumentFragment::new(document.r());
                 NodeCast::from_root(doc_fragment)
             },
@@ -1716,7 +1768,7 @@ impl Node {
                 let window = document.window();
                 let loader = DocumentLoader::new(&*document.loader());
                 let document = Document::new(window.r(), Some((*document.url()).clone()),
-                                             is_html_doc, None,
+                                             is_html_doc, Some(document.ContentType()),
                                              None, DocumentSource::NotFromParser, loader);
                 NodeCast::from_root(document)
             },
@@ -1865,7 +1917,7 @@ impl Node {
                 }
             },
             NodeTypeId::DocumentType => ns!(""),
-            NodeTypeId::DocumentFragment => ns!(""),
+            NodeTypeId::DocumentFragment(_) => ns!(""),
             _ => match node.GetParentElement() {
                      // Step 1.
                      None => ns!(""),
@@ -1890,7 +1942,7 @@ impl NodeMethods for Node {
                 NodeConstants::DOCUMENT_NODE,
             NodeTypeId::DocumentType =>
                 NodeConstants::DOCUMENT_TYPE_NODE,
-            NodeTypeId::DocumentFragment =>
+            NodeTypeId::DocumentFragment(_) =>
                 NodeConstants::DOCUMENT_FRAGMENT_NODE,
             NodeTypeId::Element(_) =>
                 NodeConstants::ELEMENT_NODE,
@@ -1915,7 +1967,7 @@ impl NodeMethods for Node {
                 let doctype: &DocumentType = DocumentTypeCast::to_ref(self).unwrap();
                 doctype.name().clone()
             },
-            NodeTypeId::DocumentFragment => "#document-fragment".to_owned(),
+            NodeTypeId::DocumentFragment(_) => "#document-fragment".to_owned(),
             NodeTypeId::Document => "#document".to_owned()
         }
     }
@@ -1931,7 +1983,7 @@ impl NodeMethods for Node {
             NodeTypeId::CharacterData(..) |
             NodeTypeId::Element(..) |
             NodeTypeId::DocumentType |
-            NodeTypeId::DocumentFragment => Some(self.owner_doc()),
+            NodeTypeId::DocumentFragment(_) => Some(self.owner_doc()),
             NodeTypeId::Document => None
         }
     }
@@ -2006,7 +2058,7 @@ impl NodeMethods for Node {
     // https://dom.spec.whatwg.org/#dom-node-textcontent
     fn GetTextContent(&self) -> Option<DOMString> {
         match self.type_id() {
-            NodeTypeId::DocumentFragment |
+            NodeTypeId::DocumentFragment(_) |
             NodeTypeId::Element(..) => {
                 let content = Node::collect_text_contents(self.traverse_preorder());
                 Some(content)
@@ -2026,7 +2078,7 @@ impl NodeMethods for Node {
     fn SetTextContent(&self, value: Option<DOMString>) {
         let value = value.unwrap_or(String::new());
         match self.type_id() {
-            NodeTypeId::DocumentFragment |
+            NodeTypeId::DocumentFragment(_) |
             NodeTypeId::Element(..) => {
                 // Step 1-2.
                 let node = if value.is_empty() {
@@ -2068,7 +2120,7 @@ impl NodeMethods for Node {
         // Step 1.
         match self.type_id() {
             NodeTypeId::Document |
-            NodeTypeId::DocumentFragment |
+            NodeTypeId::DocumentFragment(_) |
             NodeTypeId::Element(..) => (),
             _ => return Err(HierarchyRequest)
         }
@@ -2096,7 +2148,7 @@ impl NodeMethods for Node {
         if self.is_document() {
             match node.type_id() {
                 // Step 6.1
-                NodeTypeId::DocumentFragment => {
+                NodeTypeId::DocumentFragment(_) => {
                     // Step 6.1.1(b)
                     if node.children()
                            .any(|c| c.is_text())
@@ -2178,7 +2230,7 @@ impl NodeMethods for Node {
 
         // Step 12.
         let mut nodes = RootedVec::new();
-        let nodes = if node.type_id() == NodeTypeId::DocumentFragment {
+        let nodes = if let NodeTypeId::DocumentFragment(_) = node.type_id() {
             nodes.extend(node.children().map(|node| JS::from_rooted(&node)));
             nodes.r()
         } else {
@@ -2189,6 +2241,12 @@ impl NodeMethods for Node {
         Node::insert(node, self, reference_child, SuppressObserver::Suppressed);
 
         // Step 14.
+        MutationObserver::queue_a_mutation_record(self, Mutation::ChildList {
+            added: nodes,
+            removed: ref_slice(&child),
+            previous_sibling: previous_sibling.r(),
+            next_sibling: reference_child,
+        });
         vtable_for(&self).children_changed(
             &ChildrenMutation::replace(previous_sibling.r(),
                                        &child, nodes,
@@ -2206,40 +2264,58 @@ impl NodeMethods for Node {
 
     // https://dom.spec.whatwg.org/#dom-node-normalize
     fn Normalize(&self) {
-        let mut prev_text: Option<Root<Text>> = None;
-        for child in self.children() {
-            match TextCast::to_ref(child.r()) {
-                Some(text) => {
-                    let characterdata: &CharacterData = CharacterDataCast::from_ref(text);
-                    if characterdata.Length() == 0 {
-                        Node::remove(&*child, self, SuppressObserver::Unsuppressed);
-                    } else {
-                        match prev_text {
-                            Some(ref text_node) => {
-                                let prev_characterdata =
-                                    CharacterDataCast::from_ref(text_node.r());
-                                prev_characterdata.append_data(&**characterdata.data());
-                                Node::remove(&*child, self, SuppressObserver::Unsuppressed);
-                            },
-                            None => prev_text = Some(Root::from_ref(text))
-                        }
-                    }
-                },
-                None => {
-                    child.r().Normalize();
-                    prev_text = None;
-                }
+        let texts = self.traverse_preorder().skip(1).filter(|node| node.is_text())
+                        .collect::<Vec<_>>();
+        for node in texts {
+            // Text nodes merged into a preceding sibling have been removed.
+            let parent = match node.GetParentNode() {
+                Some(parent) => parent,
+                None => continue,
+            };
+            let characterdata = CharacterDataCast::to_ref(node.r()).unwrap();
+            // Step 1.
+            let length = characterdata.Length();
+            // Step 2.
+            if length == 0 {
+                Node::remove(node.r(), parent.r(), SuppressObserver::Unsuppressed);
+                continue;
+            }
+            // Step 3.
+            let contiguous = node.following_siblings().take_while(|sibling| sibling.is_text())
+                                 .collect::<Vec<_>>();
+            let mut data = String::new();
+            for sibling in &contiguous {
+                data.push_str(&CharacterDataCast::to_ref(sibling.r()).unwrap().data());
+            }
+            // Step 4.
+            characterdata.append_data(&data);
+            // Steps 5-6.
+            let lengths = contiguous.iter().map(|sibling| sibling.len()).collect::<Vec<_>>();
+            for (sibling, offset) in contiguous.iter().zip(merged_text_offsets(length, &lengths)) {
+                let index = sibling.index();
+                for_each_live_range(|range| {
+                    range.text_merged(node.r(), sibling.r(), parent.r(), index, offset)
+                });
+            }
+            // Step 7.
+            for sibling in &contiguous {
+                Node::remove(sibling.r(), parent.r(), SuppressObserver::Unsuppressed);
             }
         }
     }
 
     // https://dom.spec.whatwg.org/#dom-node-clonenode
-    fn CloneNode(&self, deep: bool) -> Root<Node> {
-        Node::clone(self, None, if deep {
+    fn CloneNode(&self, deep: bool) -> Fallible<Root<Node>> {
+        // Step 1.
+        if let NodeTypeId::DocumentFragment(DocumentFragmentTypeId::ShadowRoot) = self.type_id() {
+            return Err(NotSupported);
+        }
+        // Step 2.
+        Ok(Node::clone(self, None, if deep {
             CloneChildrenFlag::CloneChildren
         } else {
             CloneChildrenFlag::DoNotCloneChildren
-        })
+        }))
     }
 
     // https://dom.spec.whatwg.org/#dom-node-isequalnode
@@ -2406,7 +2482,7 @@ impl NodeMethods for Node {
                     element.r().lookup_prefix(namespace)
                 })
             },
-            NodeTypeId::DocumentType | NodeTypeId::DocumentFragment => None,
+            NodeTypeId::DocumentType | NodeTypeId::DocumentFragment(_) => None,
             _ => {
                 self.GetParentElement().and_then(|element| {
                     element.r().lookup_prefix(namespace)
@@ -4,20 +4,43 @@
 
 use dom::bindings::codegen::Bindings::PerformanceBinding;
 use dom::bindings::codegen::Bindings::PerformanceBinding::PerformanceMethods;
-use dom::bindings::global::GlobalRef;
+use dom::bindings::codegen::Bindings::PerformanceTimingBinding::PerformanceTimingMethods;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::error::Error::Syntax;
+use dom::bindings::error::Fallible;
+use dom::bindings::global::{GlobalRef, global_object_for_reflector};
 use dom::bindings::js::{JS, Root};
 use dom::bindings::num::Finite;
 use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::performanceentry::PerformanceEntry;
 use dom::performancetiming::PerformanceTiming;
 use dom::window::Window;
+use std::borrow::ToOwned;
+use std::cell::Cell;
 use time;
+use util::str::DOMString;
 
 pub type DOMHighResTimeStamp = Finite<f64>;
 
+/// The names of the attributes of `PerformanceTiming`, which can't be used as mark names.
+// https://w3c.github.io/user-timing/#dom-performance-mark
+const TIMING_ATTRIBUTES: &'static [&'static str] = &[
+    "navigationStart", "unloadEventStart", "unloadEventEnd", "redirectStart",
+    "redirectEnd", "fetchStart", "domainLookupStart", "domainLookupEnd",
+    "connectStart", "connectEnd", "secureConnectionStart", "requestStart",
+    "responseStart", "responseEnd", "domLoading", "domInteractive",
+    "domContentLoadedEventStart", "domContentLoadedEventEnd", "domComplete",
+    "loadEventStart", "loadEventEnd",
+];
+
 #[dom_struct]
 pub struct Performance {
     reflector_: Reflector,
     timing: JS<PerformanceTiming>,
+    /// The last value returned by `now()`, which later calls never go below.
+    last_now: Cell<f64>,
+    /// The performance entry buffer, in chronological order of start time.
+    entries: DOMRefCell<Vec<JS<PerformanceEntry>>>,
 }
 
 impl Performance {
@@ -29,6 +52,8 @@ impl Performance {
             timing: JS::from_rooted(&PerformanceTiming::new(window,
                                                             navigation_start,
                                                             navigation_start_precise)),
+            last_now: Cell::new(0.0),
+            entries: DOMRefCell::new(vec![]),
         }
     }
 
@@ -41,6 +66,51 @@ impl Performance {
                            GlobalRef::Window(window),
                            PerformanceBinding::Wrap)
     }
+
+    /// Creates an entry and adds it to the buffer after any entries that
+    /// start no later than it does.
+    fn queue_entry(&self, name: DOMString, entry_type: &str, start_time: f64, duration: f64) {
+        let global = global_object_for_reflector(self);
+        let entry = PerformanceEntry::new(global.r(), name, entry_type.to_owned(),
+                                          start_time, duration);
+        let mut entries = self.entries.borrow_mut();
+        let index = entries.iter()
+                           .position(|e| e.root().r().start_time() > start_time)
+                           .unwrap_or(entries.len());
+        entries.insert(index, JS::from_rooted(&entry));
+    }
+
+    /// Returns the buffered entries for which `filter` returns true.
+    fn entries_matching<F>(&self, filter: F) -> Vec<Root<PerformanceEntry>>
+        where F: Fn(&PerformanceEntry) -> bool {
+        self.entries.borrow().iter()
+                             .map(|entry| entry.root())
+                             .filter(|entry| filter(entry.r()))
+                             .collect()
+    }
+
+    /// Removes the buffered entries of `entry_type`, limited to those called
+    /// `name` if it is given.
+    fn clear_entries(&self, entry_type: &str, name: Option<DOMString>) {
+        self.entries.borrow_mut().retain(|entry| {
+            let entry = entry.root();
+            let entry = entry.r();
+            !(*entry.entry_type() == entry_type &&
+              name.as_ref().map_or(true, |name| entry.name() == name))
+        });
+    }
+
+    // https://w3c.github.io/user-timing/#dfn-convert-a-name-to-a-timestamp
+    fn convert_mark_to_timestamp(&self, mark: &str) -> Fallible<f64> {
+        let latest = self.entries.borrow().iter().rev().map(|entry| entry.root()).find(|entry| {
+            *entry.r().entry_type() == "mark" && *entry.r().name() == mark
+        });
+        match latest {
+            Some(entry) => Ok(entry.r().start_time()),
+            None if mark == "navigationStart" => Ok(0.0),
+            None => Err(Syntax),
+        }
+    }
 }
 
 impl PerformanceMethods for Performance {
@@ -53,6 +123,76 @@ impl PerformanceMethods for Performance {
     fn Now(&self) -> DOMHighResTimeStamp {
         let navStart = self.timing.root().r().NavigationStartPrecise();
         let now = (time::precise_time_ns() as f64 - navStart) / 1000000 as f64;
+        let now = now.max(self.last_now.get());
+        self.last_now.set(now);
         Finite::wrap(now)
     }
+
+    // https://w3c.github.io/hr-time/#dom-performance-timeorigin
+    fn TimeOrigin(&self) -> DOMHighResTimeStamp {
+        Finite::wrap(self.timing.root().r().NavigationStart() as f64)
+    }
+
+    // https://w3c.github.io/performance-timeline/#dom-performance-getentries
+    fn GetEntries(&self) -> Vec<Root<PerformanceEntry>> {
+        self.entries_matching(|_| true)
+    }
+
+    // https://w3c.github.io/performance-timeline/#dom-performance-getentriesbytype
+    fn GetEntriesByType(&self, type_: DOMString) -> Vec<Root<PerformanceEntry>> {
+        self.entries_matching(|entry| *entry.entry_type() == type_)
+    }
+
+    // https://w3c.github.io/performance-timeline/#dom-performance-getentriesbyname
+    fn GetEntriesByName(&self, name: DOMString, type_: Option<DOMString>)
+                        -> Vec<Root<PerformanceEntry>> {
+        self.entries_matching(|entry| {
+            *entry.name() == name && type_.as_ref().map_or(true, |t| entry.entry_type() == t)
+        })
+    }
+
+    // https://w3c.github.io/user-timing/#dom-performance-mark
+    fn Mark(&self, markName: DOMString) -> Fallible<()> {
+        // Step 1.
+        if TIMING_ATTRIBUTES.contains(&&*markName) {
+            return Err(Syntax);
+        }
+
+        // Steps 2-6.
+        let start_time = *self.Now();
+        self.queue_entry(markName, "mark", start_time, 0.0);
+        Ok(())
+    }
+
+    // https://w3c.github.io/user-timing/#dom-performance-clearmarks
+    fn ClearMarks(&self, markName: Option<DOMString>) {
+        self.clear_entries("mark", markName);
+    }
+
+    // https://w3c.github.io/user-timing/#dom-performance-measure
+    fn Measure(&self,
+               measureName: DOMString,
+               startMark: Option<DOMString>,
+               endMark: Option<DOMString>) -> Fallible<()> {
+        // Step 1.
+        let end_time = match endMark {
+            Some(ref mark) => try!(self.convert_mark_to_timestamp(mark)),
+            None => *self.Now(),
+        };
+
+        // Step 2.
+        let start_time = match startMark {
+            Some(ref mark) => try!(self.convert_mark_to_timestamp(mark)),
+            None => 0.0,
+        };
+
+        // Steps 3-9.
+        self.queue_entry(measureName, "measure", start_time, end_time - start_time);
+        Ok(())
+    }
+
+    // https://w3c.github.io/user-timing/#dom-performance-clearmeasures
+    fn ClearMeasures(&self, measureName: Option<DOMString>) {
+        self.clear_entries("measure", measureName);
+    }
 }
@@ -4,25 +4,36 @@
 
 use dom::bindings::codegen::Bindings::PerformanceTimingBinding;
 use dom::bindings::codegen::Bindings::PerformanceTimingBinding::PerformanceTimingMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
 use dom::bindings::global::GlobalRef;
-use dom::bindings::js::Root;
+use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::document::Document;
 use dom::window::Window;
+use time;
+
+/// The current wall-clock time, in milliseconds since the Unix epoch.
+pub fn milliseconds_since_epoch() -> u64 {
+    let now = time::get_time();
+    now.sec as u64 * 1000 + now.nsec as u64 / 1000000
+}
 
 #[dom_struct]
 pub struct PerformanceTiming {
     reflector_: Reflector,
     navigationStart: u64,
     navigationStartPrecise: f64,
+    document: JS<Document>,
 }
 
 impl PerformanceTiming {
-    fn new_inherited(navStart: u64, navStartPrecise: f64)
+    fn new_inherited(navStart: u64, navStartPrecise: f64, document: &Document)
                          -> PerformanceTiming {
         PerformanceTiming {
             reflector_: Reflector::new(),
             navigationStart: navStart,
             navigationStartPrecise: navStartPrecise,
+            document: JS::from_ref(document),
         }
     }
 
@@ -31,8 +42,10 @@ impl PerformanceTiming {
                navigation_start: u64,
                navigation_start_precise: f64)
                -> Root<PerformanceTiming> {
+        let document = window.Document();
         let timing = PerformanceTiming::new_inherited(navigation_start,
-                                                      navigation_start_precise);
+                                                      navigation_start_precise,
+                                                      document.r());
         reflect_dom_object(box timing, GlobalRef::Window(window),
                            PerformanceTimingBinding::Wrap)
     }
@@ -44,6 +57,48 @@ impl PerformanceTimingMethods for PerformanceTiming {
     fn NavigationStart(&self) -> u64 {
         self.navigationStart
     }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-fetchstart
+    fn FetchStart(&self) -> u64 {
+        // There is no HTTP cache or redirect handling to account for, so the
+        // fetch starts with the navigation.
+        self.navigationStart
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-domloading
+    fn DomLoading(&self) -> u64 {
+        self.document.root().r().dom_loading()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-dominteractive
+    fn DomInteractive(&self) -> u64 {
+        self.document.root().r().dom_interactive()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-domcontentloadedeventstart
+    fn DomContentLoadedEventStart(&self) -> u64 {
+        self.document.root().r().dom_content_loaded_event_start()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-domcontentloadedeventend
+    fn DomContentLoadedEventEnd(&self) -> u64 {
+        self.document.root().r().dom_content_loaded_event_end()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-domcomplete
+    fn DomComplete(&self) -> u64 {
+        self.document.root().r().dom_complete()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-loadeventstart
+    fn LoadEventStart(&self) -> u64 {
+        self.document.root().r().load_event_start()
+    }
+
+    // https://w3c.github.io/navigation-timing/#dom-performancetiming-loadeventend
+    fn LoadEventEnd(&self) -> u64 {
+        self.document.root().r().load_event_end()
+    }
 }
 
 
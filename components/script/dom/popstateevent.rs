@@ -0,0 +1,77 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::EventBinding::EventMethods;
+use dom::bindings::codegen::Bindings::PopStateEventBinding;
+use dom::bindings::codegen::Bindings::PopStateEventBinding::PopStateEventMethods;
+use dom::bindings::codegen::InheritTypes::{EventCast, PopStateEventDerived};
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{MutHeapJSVal, Root};
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
+use js::jsapi::{HandleValue, JSContext};
+use js::jsval::JSVal;
+use util::str::DOMString;
+
+// https://html.spec.whatwg.org/multipage/#the-popstateevent-interface
+#[dom_struct]
+pub struct PopStateEvent {
+    event: Event,
+    #[ignore_heap_size_of = "Defined in rust-mozjs"]
+    state: MutHeapJSVal,
+}
+
+impl PopStateEventDerived for Event {
+    fn is_popstateevent(&self) -> bool {
+        *self.type_id() == EventTypeId::PopStateEvent
+    }
+}
+
+impl PopStateEvent {
+    fn new_inherited() -> PopStateEvent {
+        PopStateEvent {
+            event: Event::new_inherited(EventTypeId::PopStateEvent),
+            state: MutHeapJSVal::new(),
+        }
+    }
+
+    pub fn new(global: GlobalRef,
+               type_: DOMString,
+               bubbles: EventBubbles,
+               cancelable: EventCancelable,
+               state: HandleValue) -> Root<PopStateEvent> {
+        let ev = reflect_dom_object(box PopStateEvent::new_inherited(), global,
+                                    PopStateEventBinding::Wrap);
+        ev.r().state.set(state.get());
+        {
+            let event = EventCast::from_ref(ev.r());
+            event.InitEvent(type_,
+                            bubbles == EventBubbles::Bubbles,
+                            cancelable == EventCancelable::Cancelable);
+        }
+        ev
+    }
+
+    pub fn Constructor(global: GlobalRef,
+                       type_: DOMString,
+                       init: &PopStateEventBinding::PopStateEventInit)
+                       -> Fallible<Root<PopStateEvent>> {
+        let bubbles = if init.parent.bubbles { EventBubbles::Bubbles } else { EventBubbles::DoesNotBubble };
+        let cancelable = if init.parent.cancelable {
+            EventCancelable::Cancelable
+        } else {
+            EventCancelable::NotCancelable
+        };
+        Ok(PopStateEvent::new(global, type_, bubbles, cancelable,
+                              HandleValue { ptr: &init.state }))
+    }
+}
+
+impl PopStateEventMethods for PopStateEvent {
+    // https://html.spec.whatwg.org/multipage/#dom-popstateevent-state
+    fn State(&self, _cx: *mut JSContext) -> JSVal {
+        self.state.get()
+    }
+}
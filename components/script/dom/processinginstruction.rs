@@ -2,14 +2,37 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use cssparser::Parser as CssParser;
+use document_loader::LoadType;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
 use dom::bindings::codegen::Bindings::ProcessingInstructionBinding;
 use dom::bindings::codegen::Bindings::ProcessingInstructionBinding::ProcessingInstructionMethods;
-use dom::bindings::codegen::InheritTypes::ProcessingInstructionDerived;
-use dom::bindings::js::Root;
+use dom::bindings::codegen::InheritTypes::{CharacterDataCast, DocumentDerived, ElementDerived, NodeCast};
+use dom::bindings::codegen::InheritTypes::{ProcessingInstructionDerived, StyleSheetCast};
+use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::bindings::refcounted::Trusted;
 use dom::characterdata::{CharacterData, CharacterDataTypeId};
+use dom::cssstylesheet::CSSStyleSheet;
 use dom::document::Document;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
-use dom::node::{Node, NodeTypeId};
+use dom::node::{Node, NodeTypeId, document_from_node, window_from_node};
+use dom::stylesheet::StyleSheet;
+use dom::virtualmethods::VirtualMethods;
+use encoding::all::UTF_8;
+use encoding::types::EncodingRef;
+use ipc_channel::ipc;
+use ipc_channel::router::ROUTER;
+use layout_interface::{LayoutChan, Msg};
+use net_traits::{AsyncResponseListener, AsyncResponseTarget, Metadata};
+use network_listener::{NetworkListener, PreInvoke};
+use std::ascii::AsciiExt;
+use std::cell::RefCell;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use style::media_queries::parse_media_query_list;
+use style::stylesheets::{Origin, Stylesheet};
+use url::{Url, UrlParser};
 use util::str::DOMString;
 
 /// An HTML processing instruction node.
@@ -17,6 +40,16 @@ use util::str::DOMString;
 pub struct ProcessingInstruction {
     characterdata: CharacterData,
     target: DOMString,
+    /// The URL of the style sheet requested for this `xml-stylesheet`
+    /// processing instruction, while it is loading.
+    #[ignore_heap_size_of = "Defined in rust-url"]
+    pending_stylesheet_url: DOMRefCell<Option<Url>>,
+    /// The style sheet that was last sent to layout for this processing
+    /// instruction, if any.
+    #[ignore_heap_size_of = "Arc"]
+    stylesheet: DOMRefCell<Option<Arc<Stylesheet>>>,
+    /// The CSSOM object reflecting `stylesheet`.
+    cssom_stylesheet: MutNullableHeap<JS<CSSStyleSheet>>,
 }
 
 impl ProcessingInstructionDerived for EventTarget {
@@ -26,11 +59,102 @@ impl ProcessingInstructionDerived for EventTarget {
     }
 }
 
+/// The pseudo-attributes of an `xml-stylesheet` processing instruction that
+/// refers to a CSS style sheet.
+#[derive(Debug, PartialEq)]
+pub struct XmlStylesheet {
+    pub href: String,
+    pub media: String,
+    pub title: Option<String>,
+}
+
+// https://www.w3.org/TR/xml/#NT-S
+const XML_WHITESPACE: &'static [char] = &[' ', '\t', '\n', '\r'];
+
+/// Parses the pseudo-attributes in the data of a processing instruction,
+/// returning `None` if they aren't well-formed.
+// https://www.w3.org/TR/xml-stylesheet/#NT-PseudoAtts
+pub fn parse_pseudo_attributes(data: &str) -> Option<Vec<(String, String)>> {
+    let mut attributes = vec![];
+    let mut rest = data.trim_left_matches(XML_WHITESPACE);
+    while !rest.is_empty() {
+        let equals = match rest.find('=') {
+            Some(equals) => equals,
+            None => return None,
+        };
+        let name = rest[..equals].trim_right_matches(XML_WHITESPACE);
+        if name.is_empty() || name.contains(XML_WHITESPACE) {
+            return None;
+        }
+
+        rest = rest[equals + 1..].trim_left_matches(XML_WHITESPACE);
+        let quote = match rest.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return None,
+        };
+        rest = &rest[1..];
+        let end = match rest.find(quote) {
+            Some(end) => end,
+            None => return None,
+        };
+        let value = rest[..end].replace("&lt;", "<")
+                               .replace("&gt;", ">")
+                               .replace("&quot;", "\"")
+                               .replace("&apos;", "'")
+                               .replace("&amp;", "&");
+        attributes.push((name.to_owned(), value));
+
+        // Pseudo-attributes are separated by whitespace.
+        rest = &rest[end + 1..];
+        if !rest.is_empty() && !rest.starts_with(XML_WHITESPACE) {
+            return None;
+        }
+        rest = rest.trim_left_matches(XML_WHITESPACE);
+    }
+    Some(attributes)
+}
+
+/// Returns the CSS style sheet that an `xml-stylesheet` processing
+/// instruction with the given data refers to, if any. Alternative style
+/// sheets aren't supported, so they are ignored.
+// https://drafts.csswg.org/cssom/#requirements-on-user-agents-implementing-the-xml-stylesheet-processing-instruction
+pub fn parse_xml_stylesheet(data: &str) -> Option<XmlStylesheet> {
+    let attributes = match parse_pseudo_attributes(data) {
+        Some(attributes) => attributes,
+        None => return None,
+    };
+    let get = |name: &str| {
+        attributes.iter().find(|&&(ref attribute, _)| attribute == name).map(|&(_, ref value)| value.clone())
+    };
+
+    if get("type").map_or(false, |type_| !type_.eq_ignore_ascii_case("text/css")) {
+        return None;
+    }
+
+    if get("alternate").map_or(false, |alternate| alternate == "yes") {
+        return None;
+    }
+
+    let href = match get("href") {
+        Some(ref href) if href.is_empty() => return None,
+        Some(href) => href,
+        None => return None,
+    };
+    Some(XmlStylesheet {
+        href: href,
+        media: get("media").unwrap_or(String::new()),
+        title: get("title"),
+    })
+}
+
 impl ProcessingInstruction {
     fn new_inherited(target: DOMString, data: DOMString, document: &Document) -> ProcessingInstruction {
         ProcessingInstruction {
             characterdata: CharacterData::new_inherited(CharacterDataTypeId::ProcessingInstruction, data, document),
-            target: target
+            target: target,
+            pending_stylesheet_url: DOMRefCell::new(None),
+            stylesheet: DOMRefCell::new(None),
+            cssom_stylesheet: Default::default(),
         }
     }
 
@@ -45,6 +169,101 @@ impl ProcessingInstruction {
     pub fn target(&self) -> &DOMString {
         &self.target
     }
+
+    /// Whether this node is a child of its document that precedes the
+    /// document element.
+    fn is_in_prolog(&self) -> bool {
+        let node = NodeCast::from_ref(self);
+        match node.GetParentNode() {
+            Some(ref parent) if parent.r().is_document() => {
+                node.preceding_siblings().all(|sibling| !sibling.r().is_element())
+            },
+            _ => false,
+        }
+    }
+
+    /// Requests the style sheet this node refers to, if it is an
+    /// `xml-stylesheet` processing instruction in the prolog.
+    // https://drafts.csswg.org/cssom/#requirements-on-user-agents-implementing-the-xml-stylesheet-processing-instruction
+    fn update_stylesheet(&self) {
+        self.remove_stylesheet();
+
+        if &*self.target != "xml-stylesheet" || !self.is_in_prolog() {
+            return;
+        }
+
+        let xml_stylesheet = match parse_xml_stylesheet(&CharacterDataCast::from_ref(self).data()) {
+            Some(xml_stylesheet) => xml_stylesheet,
+            None => return,
+        };
+
+        let window = window_from_node(self);
+        let window = window.r();
+        let url = match UrlParser::new().base_url(&window.get_url()).parse(&xml_stylesheet.href) {
+            Ok(url) => url,
+            Err(e) => {
+                debug!("Parsing url {} failed: {}", xml_stylesheet.href, e);
+                return;
+            },
+        };
+        *self.pending_stylesheet_url.borrow_mut() = Some(url.clone());
+
+        let script_chan = window.script_chan();
+        let context = Arc::new(Mutex::new(StylesheetContext {
+            pi: Trusted::new(window.get_cx(), self, script_chan.clone()),
+            data: RefCell::new(vec![]),
+            metadata: RefCell::new(None),
+            url: url.clone(),
+            media: xml_stylesheet.media,
+            title: xml_stylesheet.title,
+        }));
+
+        let (action_sender, action_receiver) = ipc::channel().unwrap();
+        let listener = box NetworkListener {
+            context: context,
+            script_chan: script_chan,
+        };
+        let response_target = AsyncResponseTarget {
+            sender: action_sender,
+        };
+        ROUTER.add_route(action_receiver.to_opaque(), box move |message| {
+            listener.notify(message.to().unwrap());
+        });
+
+        document_from_node(self).r().load_async(LoadType::Stylesheet(url), response_target);
+    }
+
+    /// Adds the style sheet loaded from `url` to the document, unless this
+    /// node no longer refers to it.
+    fn stylesheet_loaded(&self, url: &Url, sheet: Stylesheet, media: &str, title: Option<String>) {
+        if self.pending_stylesheet_url.borrow().as_ref() != Some(url) {
+            return;
+        }
+        *self.pending_stylesheet_url.borrow_mut() = None;
+
+        let mut css_parser = CssParser::new(media);
+        let media = parse_media_query_list(&mut css_parser);
+
+        let window = window_from_node(self);
+        let sheet = Arc::new(sheet);
+        *self.stylesheet.borrow_mut() = Some(sheet.clone());
+        let cssom_stylesheet = CSSStyleSheet::new(window.r(), Some(url.serialize()), title, sheet.clone());
+        self.cssom_stylesheet.set(Some(JS::from_rooted(&cssom_stylesheet)));
+        let LayoutChan(ref layout_chan) = window.r().layout_chan();
+        layout_chan.send(Msg::AddStylesheet(sheet, media)).unwrap();
+    }
+
+    /// Stops the style sheet associated with this node from applying to the
+    /// document, including one that is still loading.
+    fn remove_stylesheet(&self) {
+        *self.pending_stylesheet_url.borrow_mut() = None;
+        self.cssom_stylesheet.set(None);
+        if let Some(sheet) = self.stylesheet.borrow_mut().take() {
+            let window = window_from_node(self);
+            let LayoutChan(ref layout_chan) = window.r().layout_chan();
+            layout_chan.send(Msg::RemoveStylesheet(sheet.disabled.clone())).unwrap();
+        }
+    }
 }
 
 impl ProcessingInstructionMethods for ProcessingInstruction {
@@ -52,4 +271,81 @@ impl ProcessingInstructionMethods for ProcessingInstruction {
     fn Target(&self) -> DOMString {
         self.target.clone()
     }
+
+    // https://drafts.csswg.org/cssom/#dom-linkstyle-sheet
+    fn GetSheet(&self) -> Option<Root<StyleSheet>> {
+        self.cssom_stylesheet.get().map(|sheet| StyleSheetCast::from_root(sheet.root()))
+    }
 }
+
+impl VirtualMethods for ProcessingInstruction {
+    fn super_type<'b>(&'b self) -> Option<&'b VirtualMethods> {
+        let node: &Node = NodeCast::from_ref(self);
+        Some(node as &VirtualMethods)
+    }
+
+    fn bind_to_tree(&self, tree_in_doc: bool) {
+        if let Some(ref s) = self.super_type() {
+            s.bind_to_tree(tree_in_doc);
+        }
+
+        if tree_in_doc {
+            self.update_stylesheet();
+        }
+    }
+
+    fn unbind_from_tree(&self, tree_in_doc: bool) {
+        if let Some(ref s) = self.super_type() {
+            s.unbind_from_tree(tree_in_doc);
+        }
+
+        if tree_in_doc {
+            self.remove_stylesheet();
+        }
+    }
+}
+
+/// The context required for asynchronously loading the style sheet of an
+/// `xml-stylesheet` processing instruction.
+struct StylesheetContext {
+    /// The processing instruction that initiated the request.
+    pi: Trusted<ProcessingInstruction>,
+    /// The response body received to date.
+    data: RefCell<Vec<u8>>,
+    /// The response metadata received to date.
+    metadata: RefCell<Option<Metadata>>,
+    /// The initial URL requested.
+    url: Url,
+    /// The `media` pseudo-attribute of the processing instruction.
+    media: String,
+    /// The `title` pseudo-attribute of the processing instruction.
+    title: Option<String>,
+}
+
+impl AsyncResponseListener for StylesheetContext {
+    fn headers_available(&self, metadata: Metadata) {
+        *self.metadata.borrow_mut() = Some(metadata);
+    }
+
+    fn data_available(&self, payload: Vec<u8>) {
+        let mut payload = payload;
+        self.data.borrow_mut().append(&mut payload);
+    }
+
+    fn response_complete(&self, status: Result<(), String>) {
+        let pi = self.pi.root();
+        if status.is_ok() {
+            let data = mem::replace(&mut *self.data.borrow_mut(), vec!());
+            let metadata = self.metadata.borrow_mut().take().unwrap();
+            let sheet = Stylesheet::from_bytes(&data,
+                                               metadata.final_url,
+                                               metadata.charset.as_ref().map(|s| &**s),
+                                               Some(UTF_8 as EncodingRef),
+                                               Origin::Author);
+            pi.r().stylesheet_loaded(&self.url, sheet, &self.media, self.title.clone());
+        }
+        document_from_node(pi.r()).r().finish_load(LoadType::Stylesheet(self.url.clone()));
+    }
+}
+
+impl PreInvoke for StylesheetContext {}
@@ -22,7 +22,62 @@ use dom::documentfragment::DocumentFragment;
 use dom::node::{Node, NodeTypeId};
 use std::cell::RefCell;
 use std::cmp::{Ord, Ordering, PartialEq, PartialOrd};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use util::str::DOMString;
+
+// https://dom.spec.whatwg.org/#concept-live-range
+// Every range created by this script task, so that node mutations can update
+// their boundary points. Dead ranges are pruned lazily.
+#[allow(unrooted_must_root)]
+thread_local!(static LIVE_RANGES: RefCell<Vec<Weak<RefCell<RangeInner>>>> = RefCell::new(vec![]));
+
+/// Runs `f` on every live range of this script task.
+///
+/// Range methods must not hold a borrow of their boundary points across a
+/// node mutation, since that mutation updates them.
+#[allow(unrooted_must_root)]
+pub fn for_each_live_range<F: FnMut(&mut RangeInner)>(mut f: F) {
+    let ranges = LIVE_RANGES.with(|ranges| {
+        let mut ranges = ranges.borrow_mut();
+        ranges.retain(|range| range.upgrade().is_some());
+        ranges.iter().filter_map(|range| range.upgrade()).collect::<Vec<_>>()
+    });
+    for range in ranges {
+        f(&mut range.borrow_mut());
+    }
+}
+
+/// Returns the new offset of a boundary point in `parent` once the child of
+/// `parent` at `index` has been removed.
+// https://dom.spec.whatwg.org/#concept-node-remove, steps 4-5.
+pub fn offset_after_removal(offset: u32, index: u32) -> u32 {
+    if offset > index { offset - 1 } else { offset }
+}
+
+/// Returns the new offset of a boundary point in a node whose data had
+/// `count` code units at `offset` replaced with `length` code units.
+// https://dom.spec.whatwg.org/#concept-cd-replace, steps 8-11.
+pub fn offset_after_replacement(bp_offset: u32, offset: u32, count: u32, length: u32) -> u32 {
+    if bp_offset > offset + count {
+        bp_offset - count + length
+    } else if bp_offset > offset {
+        offset
+    } else {
+        bp_offset
+    }
+}
+
+/// Returns the offsets at which the data of the contiguous Text siblings of a
+/// Text node with `length` code units ends up, once `Normalize` has appended
+/// it to that node.
+// https://dom.spec.whatwg.org/#dom-node-normalize, steps 5-6.
+pub fn merged_text_offsets(length: u32, sibling_lengths: &[u32]) -> Vec<u32> {
+    sibling_lengths.iter().scan(length, |offset, sibling_length| {
+        let sibling_offset = *offset;
+        *offset += *sibling_length;
+        Some(sibling_offset)
+    }).collect()
+}
 
 #[dom_struct]
 pub struct Range {
@@ -51,10 +106,12 @@ impl Range {
                end_container: &Node, end_offset: u32)
                -> Root<Range> {
         let window = document.window();
-        reflect_dom_object(box Range::new_inherited(start_container, start_offset,
-                                                    end_container, end_offset),
-                           GlobalRef::Window(window.r()),
-                           RangeBinding::Wrap)
+        let range = reflect_dom_object(box Range::new_inherited(start_container, start_offset,
+                                                                end_container, end_offset),
+                                       GlobalRef::Window(window.r()),
+                                       RangeBinding::Wrap);
+        LIVE_RANGES.with(|ranges| ranges.borrow_mut().push(Rc::downgrade(&range.inner)));
+        range
     }
 
     // https://dom.spec.whatwg.org/#dom-range
@@ -343,28 +400,26 @@ impl RangeMethods for Range {
     // https://dom.spec.whatwg.org/#dom-range-clonecontents
     // https://dom.spec.whatwg.org/#concept-range-clone
     fn CloneContents(&self) -> Fallible<Root<DocumentFragment>> {
-        let inner = self.inner.borrow();
-        let start = &inner.start;
-        let end = &inner.end;
-
         // Step 3.
-        let start_node = start.node();
-        let start_offset = start.offset();
-        let end_node = end.node();
-        let end_offset = end.offset();
+        let (start_node, start_offset, end_node, end_offset) = {
+            let inner = self.inner.borrow();
+            let start = &inner.start;
+            let end = &inner.end;
+            (start.node(), start.offset(), end.node(), end.offset())
+        };
 
         // Step 1.
         let fragment = DocumentFragment::new(start_node.owner_doc().r());
 
         // Step 2.
-        if start == end {
+        if self.Collapsed() {
             return Ok(fragment);
         }
 
         if end_node == start_node {
             if let Some(text) = CharacterDataCast::to_ref(start_node.r()) {
                 // Step 4.1.
-                let clone = start_node.CloneNode(true);
+                let clone = try!(start_node.CloneNode(true));
                 // Step 4.2.
                 let text = text.SubstringData(start_offset, end_offset - start_offset);
                 CharacterDataCast::to_ref(clone.r()).unwrap().SetData(text.unwrap());
@@ -384,7 +439,7 @@ impl RangeMethods for Range {
             if let Some(text) = CharacterDataCast::to_ref(child.r()) {
                 assert!(child == start_node);
                 // Step 13.1.
-                let clone = start_node.CloneNode(true); // CharacterData has no children.
+                let clone = try!(start_node.CloneNode(true)); // CharacterData has no children.
                 // Step 13.2
                 let text = text.SubstringData(start_offset, start_node.len() - start_offset);
                 CharacterDataCast::to_ref(clone.r()).unwrap().SetData(text.unwrap());
@@ -392,7 +447,7 @@ impl RangeMethods for Range {
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
             } else {
                 // Step 14.1.
-                let clone = child.CloneNode(false);
+                let clone = try!(child.CloneNode(false));
                 // Step 14.2.
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
                 // Step 14.3.
@@ -411,7 +466,7 @@ impl RangeMethods for Range {
         // Step 15.
         for child in contained_children {
             // Step 15.1.
-            let clone = child.CloneNode(true);
+            let clone = try!(child.CloneNode(true));
             // Step 15.2.
             try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
         }
@@ -421,7 +476,7 @@ impl RangeMethods for Range {
             if let Some(text) = CharacterDataCast::to_ref(child.r()) {
                 assert!(child == end_node);
                 // Step 16.1.
-                let clone = end_node.CloneNode(true); // CharacterData has no children.
+                let clone = try!(end_node.CloneNode(true)); // CharacterData has no children.
                 // Step 16.2.
                 let text = text.SubstringData(0, end_offset);
                 CharacterDataCast::to_ref(clone.r()).unwrap().SetData(text.unwrap());
@@ -429,7 +484,7 @@ impl RangeMethods for Range {
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
             } else {
                 // Step 17.1.
-                let clone = child.CloneNode(false);
+                let clone = try!(child.CloneNode(false));
                 // Step 17.2.
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
                 // Step 17.3.
@@ -472,7 +527,7 @@ impl RangeMethods for Range {
         if end_node == start_node {
             if let Some(end_data) = CharacterDataCast::to_ref(end_node.r()) {
                 // Step 4.1.
-                let clone = end_node.CloneNode(true);
+                let clone = try!(end_node.CloneNode(true));
                 // Step 4.2.
                 let text = end_data.SubstringData(start_offset, end_offset - start_offset);
                 CharacterDataCast::to_ref(clone.r()).unwrap().SetData(text.unwrap());
@@ -507,7 +562,7 @@ impl RangeMethods for Range {
             if let Some(start_data) = CharacterDataCast::to_ref(child.r()) {
                 assert!(child == start_node);
                 // Step 15.1.
-                let clone = start_node.CloneNode(true);
+                let clone = try!(start_node.CloneNode(true));
                 // Step 15.2.
                 let text = start_data.SubstringData(start_offset,
                                                     start_node.len() - start_offset);
@@ -520,7 +575,7 @@ impl RangeMethods for Range {
                                             "".to_owned()));
             } else {
                 // Step 16.1.
-                let clone = child.CloneNode(false);
+                let clone = try!(child.CloneNode(false));
                 // Step 16.2.
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
                 // Step 16.3.
@@ -545,7 +600,7 @@ impl RangeMethods for Range {
             if let Some(end_data) = CharacterDataCast::to_ref(child.r()) {
                 assert!(child == end_node);
                 // Step 18.1.
-                let clone = end_node.CloneNode(true);
+                let clone = try!(end_node.CloneNode(true));
                 // Step 18.2.
                 let text = end_data.SubstringData(0, end_offset);
                 CharacterDataCast::to_ref(clone.r()).unwrap().SetData(text.unwrap());
@@ -555,7 +610,7 @@ impl RangeMethods for Range {
                 try!(end_data.ReplaceData(0, end_offset, "".to_owned()));
             } else {
                 // Step 19.1.
-                let clone = child.CloneNode(false);
+                let clone = try!(child.CloneNode(false));
                 // Step 19.2.
                 try!(NodeCast::from_ref(fragment.r()).AppendChild(clone.r()));
                 // Step 19.3.
@@ -651,7 +706,7 @@ impl RangeMethods for Range {
             reference_node.r().map_or(parent.len(), |node| node.index());
 
         // Step 11
-        let new_offset = new_offset + if node.type_id() == NodeTypeId::DocumentFragment {
+        let new_offset = new_offset + if let NodeTypeId::DocumentFragment(_) = node.type_id() {
             node.len()
         } else {
             1
@@ -683,7 +738,7 @@ impl RangeMethods for Range {
         match new_parent.type_id() {
             NodeTypeId::Document |
             NodeTypeId::DocumentType |
-            NodeTypeId::DocumentFragment => return Err(Error::InvalidNodeType),
+            NodeTypeId::DocumentFragment(_) => return Err(Error::InvalidNodeType),
             _ => ()
         }
 
@@ -702,6 +757,44 @@ impl RangeMethods for Range {
         // Step 7.
         self.SelectNode(new_parent)
     }
+
+    // https://dom.spec.whatwg.org/#dom-range-stringifier
+    fn Stringifier(&self) -> DOMString {
+        let start_node = self.StartContainer();
+        let start_offset = self.StartOffset();
+        let end_node = self.EndContainer();
+        let end_offset = self.EndOffset();
+
+        // Step 1.
+        let mut s = DOMString::new();
+
+        if start_node.is_text() {
+            let text = CharacterDataCast::to_ref(start_node.r()).unwrap();
+            // Step 2.
+            if start_node == end_node {
+                return text.SubstringData(start_offset, end_offset - start_offset).unwrap();
+            }
+            // Step 3.
+            s.push_str(&text.SubstringData(start_offset, text.Length() - start_offset).unwrap());
+        }
+
+        // Step 4.
+        let ancestor = self.CommonAncestorContainer();
+        for node in start_node.following_nodes(ancestor.r()) {
+            if node.is_text() && self.contains(node.r()) {
+                s.push_str(&CharacterDataCast::to_ref(node.r()).unwrap().data());
+            }
+        }
+
+        // Step 5.
+        if end_node.is_text() {
+            let text = CharacterDataCast::to_ref(end_node.r()).unwrap();
+            s.push_str(&text.SubstringData(0, end_offset).unwrap());
+        }
+
+        // Step 6.
+        s
+    }
 }
 
 #[derive(JSTraceable)]
@@ -804,6 +897,40 @@ impl RangeInner {
         Ok(())
     }
 
+    // https://dom.spec.whatwg.org/#concept-node-remove
+    // Steps 2-5, `node` still being the child of `parent` at `index`.
+    pub fn node_removed(&mut self, node: &Node, parent: &Node, index: u32) {
+        self.start.node_removed(node, parent, index);
+        self.end.node_removed(node, parent, index);
+    }
+
+    // https://dom.spec.whatwg.org/#dom-node-normalize
+    // Steps 6.1-6.4, `merged` being the child of `parent` at `index` whose
+    // data was appended to `text` at `offset`.
+    pub fn text_merged(&mut self, text: &Node, merged: &Node, parent: &Node,
+                       index: u32, offset: u32) {
+        self.start.text_merged(text, merged, parent, index, offset);
+        self.end.text_merged(text, merged, parent, index, offset);
+    }
+
+    // https://dom.spec.whatwg.org/#concept-cd-replace
+    // Steps 8-11, `count` code units of the data of `node` at `offset` having
+    // been replaced with `length` code units.
+    pub fn data_replaced(&mut self, node: &Node, offset: u32, count: u32, length: u32) {
+        self.start.data_replaced(node, offset, count, length);
+        self.end.data_replaced(node, offset, count, length);
+    }
+
+    // https://dom.spec.whatwg.org/#dom-text-splittextoffset
+    // Steps 7.2-7.5, `new_node` holding the data of `node`, the child of
+    // `parent` at `index`, from `offset` onwards and having been inserted
+    // after it.
+    pub fn text_split(&mut self, node: &Node, new_node: &Node, parent: &Node,
+                      index: u32, offset: u32) {
+        self.start.text_split(node, new_node, parent, index, offset);
+        self.end.text_split(node, new_node, parent, index, offset);
+    }
+
     // https://dom.spec.whatwg.org/#dom-range-comparepointnode-offset
     fn compare_point(&self, node: &Node, offset: u32) -> Fallible<Ordering> {
         let start = &self.start;
@@ -872,6 +999,43 @@ impl BoundaryPoint {
         self.node = JS::from_ref(node);
         self.offset = offset;
     }
+
+    fn node_removed(&mut self, node: &Node, parent: &Node, index: u32) {
+        let bp_node = self.node();
+        if node.is_inclusive_ancestor_of(bp_node.r()) {
+            self.set(parent, index);
+        } else if bp_node.r() == parent {
+            self.offset = offset_after_removal(self.offset, index);
+        }
+    }
+
+    fn text_merged(&mut self, text: &Node, merged: &Node, parent: &Node,
+                   index: u32, offset: u32) {
+        let bp_node = self.node();
+        if bp_node.r() == merged {
+            let new_offset = self.offset + offset;
+            self.set(text, new_offset);
+        } else if bp_node.r() == parent && self.offset == index {
+            self.set(text, offset);
+        }
+    }
+
+    fn data_replaced(&mut self, node: &Node, offset: u32, count: u32, length: u32) {
+        if self.node().r() == node {
+            self.offset = offset_after_replacement(self.offset, offset, count, length);
+        }
+    }
+
+    fn text_split(&mut self, node: &Node, new_node: &Node, parent: &Node,
+                  index: u32, offset: u32) {
+        let bp_node = self.node();
+        if bp_node.r() == node && self.offset > offset {
+            let new_offset = self.offset - offset;
+            self.set(new_node, new_offset);
+        } else if bp_node.r() == parent && self.offset == index + 1 {
+            self.offset += 1;
+        }
+    }
 }
 
 #[allow(unrooted_must_root)]
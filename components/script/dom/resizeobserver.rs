@@ -0,0 +1,181 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::Au;
+use dom::bindings::callback::ExceptionHandling;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::ResizeObserverBinding;
+use dom::bindings::codegen::Bindings::ResizeObserverBinding::ResizeObserverBoxOptions;
+use dom::bindings::codegen::Bindings::ResizeObserverBinding::ResizeObserverCallback;
+use dom::bindings::codegen::Bindings::ResizeObserverBinding::ResizeObserverMethods;
+use dom::bindings::codegen::Bindings::ResizeObserverBinding::ResizeObserverOptions;
+use dom::bindings::codegen::InheritTypes::NodeCast;
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::domrect::DOMRect;
+use dom::element::Element;
+use dom::resizeobserverentry::ResizeObserverEntry;
+use dom::window::Window;
+use euclid::point::Point2D;
+use euclid::rect::Rect;
+use euclid::side_offsets::SideOffsets2D;
+use euclid::size::Size2D;
+use std::cmp;
+use std::rc::Rc;
+use string_cache::Atom;
+
+/// Returns the content rectangle of a box with the given border box size,
+/// padding and border widths. Its origin is the top left padding edge.
+// https://drafts.csswg.org/resize-observer/#dom-resizeobserverentry-contentrect
+pub fn content_box(border_box: Size2D<Au>, padding: SideOffsets2D<Au>, border: SideOffsets2D<Au>) -> Rect<Au> {
+    let width = border_box.width - padding.left - padding.right - border.left - border.right;
+    let height = border_box.height - padding.top - padding.bottom - border.top - border.bottom;
+    Rect::new(Point2D::new(padding.left, padding.top),
+              Size2D::new(cmp::max(width, Au(0)), cmp::max(height, Au(0))))
+}
+
+/// Parses a resolved length such as `12.5px`.
+fn parse_px(value: &str) -> Option<Au> {
+    if !value.ends_with("px") {
+        return None;
+    }
+    value[..value.len() - 2].parse::<f32>().ok().map(Au::from_f32_px)
+}
+
+/// An element observed by a `ResizeObserver`, with the size it had when it
+/// was last reported.
+// https://drafts.csswg.org/resize-observer/#resizeobservation
+#[must_root]
+#[derive(JSTraceable, HeapSizeOf)]
+#[privatize]
+struct ResizeObservation {
+    target: JS<Element>,
+    observed_box: ResizeObserverBoxOptions,
+    last_reported_size: Size2D<Au>,
+}
+
+// https://drafts.csswg.org/resize-observer/#resize-observer-interface
+#[dom_struct]
+pub struct ResizeObserver {
+    reflector_: Reflector,
+    window: JS<Window>,
+    #[ignore_heap_size_of = "Rc<T> is hard"]
+    callback: Rc<ResizeObserverCallback>,
+    observation_targets: DOMRefCell<Vec<ResizeObservation>>,
+}
+
+impl ResizeObserver {
+    fn new_inherited(window: &Window, callback: Rc<ResizeObserverCallback>) -> ResizeObserver {
+        ResizeObserver {
+            reflector_: Reflector::new(),
+            window: JS::from_ref(window),
+            callback: callback,
+            observation_targets: DOMRefCell::new(vec![]),
+        }
+    }
+
+    pub fn new(window: &Window, callback: Rc<ResizeObserverCallback>) -> Root<ResizeObserver> {
+        reflect_dom_object(box ResizeObserver::new_inherited(window, callback),
+                           GlobalRef::Window(window), ResizeObserverBinding::Wrap)
+    }
+
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserver-resizeobserver
+    pub fn Constructor(global: GlobalRef, callback: Rc<ResizeObserverCallback>) -> Fallible<Root<ResizeObserver>> {
+        Ok(ResizeObserver::new(global.as_window(), callback))
+    }
+
+    /// Returns the content rectangle and border box size of `target`, which
+    /// are empty if it isn't in a document.
+    fn box_sizes(&self, target: &Element) -> (Rect<Au>, Size2D<Au>) {
+        let node = NodeCast::from_ref(target);
+        if !node.is_in_doc() {
+            return (Rect::zero(), Size2D::zero());
+        }
+
+        let window = self.window.root();
+        let address = node.to_trusted_node_address();
+        let resolved_px = |property: &str| {
+            window.r().resolved_style_query(address, None, &Atom::from_slice(property))
+                      .and_then(|value| parse_px(&value))
+                      .unwrap_or(Au(0))
+        };
+        let padding = SideOffsets2D::new(resolved_px("padding-top"), resolved_px("padding-right"),
+                                         resolved_px("padding-bottom"), resolved_px("padding-left"));
+        let border = SideOffsets2D::new(resolved_px("border-top-width"), resolved_px("border-right-width"),
+                                        resolved_px("border-bottom-width"), resolved_px("border-left-width"));
+        let border_box = node.get_bounding_content_box().size;
+        (content_box(border_box, padding, border), border_box)
+    }
+
+    /// Invokes the callback with an entry for each target whose observed box
+    /// changed size since it was last reported.
+    // https://drafts.csswg.org/resize-observer/#gather-active-observations-h
+    // https://drafts.csswg.org/resize-observer/#broadcast-active-resize-observations
+    pub fn broadcast_active_observations(&self) {
+        let window = self.window.root();
+        let targets = self.observation_targets.borrow().iter()
+                          .map(|observation| (observation.target.root(), observation.observed_box))
+                          .collect::<Vec<_>>();
+        let mut entries = vec![];
+        for (index, &(ref target, observed_box)) in targets.iter().enumerate() {
+            let (content_rect, border_box) = self.box_sizes(target.r());
+            let size = match observed_box {
+                ResizeObserverBoxOptions::Content_box => content_rect.size,
+                ResizeObserverBoxOptions::Border_box => border_box,
+            };
+            {
+                let mut observations = self.observation_targets.borrow_mut();
+                let observation = &mut observations[index];
+                if observation.last_reported_size == size {
+                    continue;
+                }
+                observation.last_reported_size = size;
+            }
+            let content_rect = DOMRect::new(window.r(),
+                                            content_rect.origin.y, content_rect.max_y(),
+                                            content_rect.origin.x, content_rect.max_x());
+            entries.push(ResizeObserverEntry::new(window.r(), target.r(), content_rect.r()));
+        }
+
+        if entries.is_empty() {
+            return;
+        }
+        let _ = self.callback.Call_(self, entries, self, ExceptionHandling::Report);
+    }
+}
+
+impl ResizeObserverMethods for ResizeObserver {
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserver-observe
+    fn Observe(&self, target: &Element, options: &ResizeObserverOptions) {
+        // Step 1.
+        self.Unobserve(target);
+
+        // Steps 2-3.
+        self.observation_targets.borrow_mut().push(ResizeObservation {
+            target: JS::from_ref(target),
+            observed_box: options.box_,
+            last_reported_size: Size2D::zero(),
+        });
+        let window = self.window.root();
+        window.r().register_resize_observer(self);
+        window.r().queue_resize_observations();
+    }
+
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserver-unobserve
+    fn Unobserve(&self, target: &Element) {
+        let target = JS::from_ref(target);
+        self.observation_targets.borrow_mut().retain(|observation| observation.target != target);
+        if self.observation_targets.borrow().is_empty() {
+            self.window.root().r().unregister_resize_observer(self);
+        }
+    }
+
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserver-disconnect
+    fn Disconnect(&self) {
+        self.observation_targets.borrow_mut().clear();
+        self.window.root().r().unregister_resize_observer(self);
+    }
+}
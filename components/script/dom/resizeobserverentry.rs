@@ -0,0 +1,47 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::ResizeObserverEntryBinding;
+use dom::bindings::codegen::Bindings::ResizeObserverEntryBinding::ResizeObserverEntryMethods;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::domrect::DOMRect;
+use dom::element::Element;
+use dom::window::Window;
+
+// https://drafts.csswg.org/resize-observer/#resize-observer-entry-interface
+#[dom_struct]
+pub struct ResizeObserverEntry {
+    reflector_: Reflector,
+    target: JS<Element>,
+    content_rect: JS<DOMRect>,
+}
+
+impl ResizeObserverEntry {
+    fn new_inherited(target: &Element, content_rect: &DOMRect) -> ResizeObserverEntry {
+        ResizeObserverEntry {
+            reflector_: Reflector::new(),
+            target: JS::from_ref(target),
+            content_rect: JS::from_ref(content_rect),
+        }
+    }
+
+    pub fn new(window: &Window, target: &Element, content_rect: &DOMRect) -> Root<ResizeObserverEntry> {
+        reflect_dom_object(box ResizeObserverEntry::new_inherited(target, content_rect),
+                           GlobalRef::Window(window), ResizeObserverEntryBinding::Wrap)
+    }
+}
+
+impl ResizeObserverEntryMethods for ResizeObserverEntry {
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserverentry-target
+    fn Target(&self) -> Root<Element> {
+        self.target.root()
+    }
+
+    // https://drafts.csswg.org/resize-observer/#dom-resizeobserverentry-contentrect
+    fn ContentRect(&self) -> Root<DOMRect> {
+        self.content_rect.root()
+    }
+}
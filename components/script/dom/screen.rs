@@ -5,30 +5,83 @@
 use dom::bindings::codegen::Bindings::ScreenBinding;
 use dom::bindings::codegen::Bindings::ScreenBinding::ScreenMethods;
 use dom::bindings::global::GlobalRef;
-use dom::bindings::js::Root;
+use dom::bindings::js::{JS, Root};
 use dom::bindings::utils::{Reflector, reflect_dom_object};
 use dom::window::Window;
+use euclid::size::Size2D;
+use std::cmp;
+
+/// Returns the device pixel ratio to expose to content, falling back to 1 when
+/// it is not known yet or is not a positive number.
+pub fn sanitize_device_pixel_ratio(ratio: Option<f32>) -> f32 {
+    match ratio {
+        Some(ratio) if ratio > 0. => ratio,
+        _ => 1.,
+    }
+}
+
+/// Converts a length in device pixels into CSS pixels.
+pub fn device_pixels_to_css_pixels(length: u32, device_pixel_ratio: f32) -> i32 {
+    (length as f32 / device_pixel_ratio) as i32
+}
 
 #[dom_struct]
 pub struct Screen {
     reflector_: Reflector,
+    window: JS<Window>,
 }
 
 impl Screen {
-    fn new_inherited() -> Screen {
+    fn new_inherited(window: &Window) -> Screen {
         Screen {
             reflector_: Reflector::new(),
+            window: JS::from_ref(window),
         }
     }
 
     pub fn new(window: &Window) -> Root<Screen> {
-        reflect_dom_object(box Screen::new_inherited(),
+        reflect_dom_object(box Screen::new_inherited(window),
                            GlobalRef::Window(window),
                            ScreenBinding::Wrap)
     }
+
+    /// Returns the size of the screen and of its available area in CSS pixels.
+    /// The available area never exceeds the screen.
+    fn screen_size(&self) -> (Size2D<i32>, Size2D<i32>) {
+        let window = self.window.root();
+        let (size, avail_size) = window.r().screen_size();
+        let dpr = window.r().device_pixel_ratio();
+        let css_size = |width: u32, height: u32| {
+            Size2D::new(device_pixels_to_css_pixels(width, dpr),
+                        device_pixels_to_css_pixels(height, dpr))
+        };
+        (css_size(size.width, size.height),
+         css_size(cmp::min(avail_size.width, size.width),
+                  cmp::min(avail_size.height, size.height)))
+    }
 }
 
 impl ScreenMethods for Screen {
+    // https://drafts.csswg.org/cssom-view/#dom-screen-availwidth
+    fn AvailWidth(&self) -> i32 {
+        self.screen_size().1.width
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-screen-availheight
+    fn AvailHeight(&self) -> i32 {
+        self.screen_size().1.height
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-screen-width
+    fn Width(&self) -> i32 {
+        self.screen_size().0.width
+    }
+
+    // https://drafts.csswg.org/cssom-view/#dom-screen-height
+    fn Height(&self) -> i32 {
+        self.screen_size().0.height
+    }
+
     // https://drafts.csswg.org/cssom-view/#dom-screen-colordepth
     fn ColorDepth(&self) -> u32 {
         24
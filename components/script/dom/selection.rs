@@ -0,0 +1,172 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::RangeBinding::RangeMethods;
+use dom::bindings::codegen::Bindings::SelectionBinding;
+use dom::bindings::codegen::Bindings::SelectionBinding::SelectionMethods;
+use dom::bindings::codegen::InheritTypes::NodeCast;
+use dom::bindings::error::Error::{IndexSize, InvalidNodeType, NotFound};
+use dom::bindings::error::{ErrorResult, Fallible};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{JS, MutNullableHeap, Root};
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::document::Document;
+use dom::node::Node;
+use dom::range::Range;
+use std::borrow::ToOwned;
+use std::default::Default;
+use util::str::DOMString;
+
+/// A document's selection. It holds at most one range, which is the live
+/// `Range` object handed to `addRange`. Selections are always forwards, since
+/// nothing can make them backwards yet.
+#[dom_struct]
+pub struct Selection {
+    reflector_: Reflector,
+    document: JS<Document>,
+    range: MutNullableHeap<JS<Range>>,
+}
+
+impl Selection {
+    fn new_inherited(document: &Document) -> Selection {
+        Selection {
+            reflector_: Reflector::new(),
+            document: JS::from_ref(document),
+            range: Default::default(),
+        }
+    }
+
+    pub fn new(document: &Document) -> Root<Selection> {
+        let window = document.window();
+        reflect_dom_object(box Selection::new_inherited(document),
+                           GlobalRef::Window(window.r()),
+                           SelectionBinding::Wrap)
+    }
+
+    fn range(&self) -> Option<Root<Range>> {
+        self.range.get().map(Root::from_rooted)
+    }
+
+    /// Whether `node` is in the document this selection belongs to.
+    fn is_in_document(&self, node: &Node) -> bool {
+        let document = self.document.root();
+        let root = node.inclusive_ancestors().last().unwrap();
+        root.r() == NodeCast::from_ref(document.r())
+    }
+}
+
+impl SelectionMethods for Selection {
+    // https://w3c.github.io/selection-api/#dom-selection-anchornode
+    fn GetAnchorNode(&self) -> Option<Root<Node>> {
+        self.range().map(|range| range.r().StartContainer())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-anchoroffset
+    fn AnchorOffset(&self) -> u32 {
+        self.range().map_or(0, |range| range.r().StartOffset())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-focusnode
+    fn GetFocusNode(&self) -> Option<Root<Node>> {
+        self.range().map(|range| range.r().EndContainer())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-focusoffset
+    fn FocusOffset(&self) -> u32 {
+        self.range().map_or(0, |range| range.r().EndOffset())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-iscollapsed
+    fn IsCollapsed(&self) -> bool {
+        self.range().map_or(true, |range| range.r().Collapsed())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-rangecount
+    fn RangeCount(&self) -> u32 {
+        if self.range.get().is_some() { 1 } else { 0 }
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-type
+    fn Type(&self) -> DOMString {
+        match self.range() {
+            None => "None".to_owned(),
+            Some(ref range) if range.r().Collapsed() => "Caret".to_owned(),
+            Some(_) => "Range".to_owned(),
+        }
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-getrangeat
+    fn GetRangeAt(&self, index: u32) -> Fallible<Root<Range>> {
+        match self.range() {
+            Some(range) if index == 0 => Ok(range),
+            _ => Err(IndexSize),
+        }
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-addrange
+    fn AddRange(&self, range: &Range) {
+        // Step 1.
+        if !self.is_in_document(range.StartContainer().r()) {
+            return;
+        }
+        // Step 2.
+        if self.range.get().is_some() {
+            return;
+        }
+        // Step 3.
+        self.range.set(Some(JS::from_ref(range)));
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-removerange
+    fn RemoveRange(&self, range: &Range) -> ErrorResult {
+        if self.range() != Some(Root::from_ref(range)) {
+            return Err(NotFound);
+        }
+        self.range.set(None);
+        Ok(())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-removeallranges
+    fn RemoveAllRanges(&self) {
+        self.range.set(None);
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-empty
+    fn Empty(&self) {
+        self.RemoveAllRanges();
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-collapse
+    fn Collapse(&self, node: Option<&Node>, offset: u32) -> ErrorResult {
+        let node = match node {
+            Some(node) => node,
+            None => {
+                self.RemoveAllRanges();
+                return Ok(());
+            },
+        };
+        // Step 1.
+        if node.is_doctype() {
+            return Err(InvalidNodeType);
+        }
+        // Step 2.
+        if offset > node.len() {
+            return Err(IndexSize);
+        }
+        // Step 3.
+        if !self.is_in_document(node) {
+            return Ok(());
+        }
+        // Steps 4-6.
+        let document = self.document.root();
+        let range = Range::new(document.r(), node, offset, node, offset);
+        self.range.set(Some(JS::from_ref(range.r())));
+        Ok(())
+    }
+
+    // https://w3c.github.io/selection-api/#dom-selection-stringifier
+    fn Stringifier(&self) -> DOMString {
+        self.range().map_or(String::new(), |range| range.r().Stringifier())
+    }
+}
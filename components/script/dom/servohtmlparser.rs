@@ -7,6 +7,7 @@
 
 use document_loader::LoadType;
 use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::DocumentBinding::DocumentReadyState;
 use dom::bindings::codegen::Bindings::ServoHTMLParserBinding;
 use dom::bindings::codegen::InheritTypes::NodeCast;
 use dom::bindings::global::GlobalRef;
@@ -31,6 +32,7 @@ use net_traits::{AsyncResponseListener, Metadata};
 use network_listener::PreInvoke;
 use parse::Parser;
 use script_task::{ScriptChan, ScriptTask};
+use std::ascii::AsciiExt;
 use std::cell::{Cell, RefCell};
 use std::default::Default;
 use url::Url;
@@ -116,7 +118,7 @@ impl AsyncResponseListener for ParserContext {
                 self.is_image_document.set(true);
                 let page = format!("<html><body><img src='{}' /></body></html>",
                                    self.url.serialize());
-                parser.pending_input.borrow_mut().push(page);
+                parser.pending_input.borrow_mut().push(page.into());
                 parser.parse_sync();
             }
             Some(ContentType(Mime(TopLevel::Text, SubLevel::Plain, _))) => {
@@ -129,7 +131,7 @@ impl AsyncResponseListener for ParserContext {
                 // Spec for text/plain handling is:
                 // https://html.spec.whatwg.org/multipage/#read-text
                 let page = format!("<pre>\u{000A}<plaintext>");
-                parser.pending_input.borrow_mut().push(page);
+                parser.pending_input.borrow_mut().push(page.into());
                 parser.parse_sync();
             },
             _ => {}
@@ -175,7 +177,8 @@ pub struct ServoHTMLParser {
     #[ignore_heap_size_of = "Defined in html5ever"]
     tokenizer: DOMRefCell<Tokenizer>,
     /// Input chunks received but not yet passed to the parser.
-    pending_input: DOMRefCell<Vec<String>>,
+    #[ignore_heap_size_of = "Defined in tendril"]
+    pending_input: DOMRefCell<Vec<StrTendril>>,
     /// The document associated with this parser.
     document: JS<Document>,
     /// True if this parser should avoid passing any further data to the tokenizer.
@@ -185,12 +188,20 @@ pub struct ServoHTMLParser {
     /// The pipeline associated with this parse, unavailable if this parse does not
     /// correspond to a page load.
     pipeline: Option<PipelineId>,
+    /// The number of parser-inserted scripts currently executing. The parser only has
+    /// an insertion point for `document.write` while this is non-zero.
+    script_nesting_level: Cell<usize>,
+    /// The index into `pending_input` at which the next `document.write` inserts.
+    insertion_point: Cell<usize>,
+    /// Whether this parser was created by `document.open`, in which case its input
+    /// comes entirely from `document.write` and ends with `document.close`.
+    script_created: Cell<bool>,
 }
 
 impl<'a> Parser for &'a ServoHTMLParser {
     fn parse_chunk(self, input: String) {
         self.document.root().r().set_current_parser(Some(self));
-        self.pending_input.borrow_mut().push(input);
+        self.pending_input.borrow_mut().push(input.into());
         self.parse_sync();
     }
 
@@ -206,6 +217,8 @@ impl<'a> Parser for &'a ServoHTMLParser {
 
         if let Some(pipeline) = self.pipeline {
             ScriptTask::parsing_complete(pipeline);
+        } else if self.script_created.get() {
+            document.r().set_ready_state(DocumentReadyState::Complete);
         }
     }
 }
@@ -235,6 +248,9 @@ impl ServoHTMLParser {
             suspended: Cell::new(false),
             last_chunk_received: Cell::new(false),
             pipeline: pipeline,
+            script_nesting_level: Cell::new(0),
+            insertion_point: Cell::new(0),
+            script_created: Cell::new(false),
         };
 
         reflect_dom_object(box parser, GlobalRef::Window(window.r()),
@@ -273,12 +289,22 @@ impl ServoHTMLParser {
             suspended: Cell::new(false),
             last_chunk_received: Cell::new(true),
             pipeline: None,
+            script_nesting_level: Cell::new(0),
+            insertion_point: Cell::new(0),
+            script_created: Cell::new(false),
         };
 
         reflect_dom_object(box parser, GlobalRef::Window(window.r()),
                            ServoHTMLParserBinding::Wrap)
     }
 
+    /// Creates the parser `document.open` hands the document to.
+    pub fn new_script_created(document: &Document) -> Root<ServoHTMLParser> {
+        let parser = ServoHTMLParser::new(Some(document.url().clone()), document, None);
+        parser.r().script_created.set(true);
+        parser
+    }
+
     #[inline]
     pub fn tokenizer(&self) -> &DOMRefCell<Tokenizer> {
         &self.tokenizer
@@ -304,12 +330,27 @@ impl ServoHTMLParser {
             let document = self.document.root();
             document.r().reflow_if_reflow_timer_expired();
 
-            let mut pending_input = self.pending_input.borrow_mut();
-            if !pending_input.is_empty() {
-                let chunk = pending_input.remove(0);
-                self.tokenizer.borrow_mut().feed(chunk.into());
-            } else {
-                self.tokenizer.borrow_mut().run();
+            let chunk = {
+                let mut pending_input = self.pending_input.borrow_mut();
+                if pending_input.is_empty() { None } else { Some(pending_input.remove(0)) }
+            };
+            self.insertion_point.set(0);
+            match chunk {
+                Some(mut chunk) => {
+                    // Only feed the tokenizer up to the next script end tag, so that
+                    // whatever the script writes is parsed before the rest of the chunk.
+                    // Splitting a tendril shares its buffer rather than copying it.
+                    if let Some(end) = script_end_tag_end(&chunk) {
+                        let rest = (chunk.len() - end) as u32;
+                        if rest > 0 {
+                            let remainder = chunk.subtendril(end as u32, rest);
+                            self.pending_input.borrow_mut().insert(0, remainder);
+                            chunk.pop_back(rest);
+                        }
+                    }
+                    self.tokenizer.borrow_mut().feed(chunk);
+                },
+                None => self.tokenizer.borrow_mut().run(),
             }
 
             first = false;
@@ -338,6 +379,76 @@ impl ServoHTMLParser {
         self.suspended.set(false);
         self.parse_sync();
     }
+
+    /// Notes that a parser-inserted script is about to execute.
+    pub fn enter_script(&self) {
+        self.script_nesting_level.set(self.script_nesting_level.get() + 1);
+    }
+
+    /// Notes that a parser-inserted script has finished executing.
+    pub fn exit_script(&self) {
+        assert!(self.script_nesting_level.get() > 0);
+        self.script_nesting_level.set(self.script_nesting_level.get() - 1);
+    }
+
+    /// Whether a parser-inserted script is currently executing.
+    pub fn is_executing_script(&self) -> bool {
+        self.script_nesting_level.get() > 0
+    }
+
+    pub fn is_script_created(&self) -> bool {
+        self.script_created.get()
+    }
+
+    /// Whether `document.write` can insert into this parser's input.
+    pub fn has_insertion_point(&self) -> bool {
+        self.is_executing_script() || self.script_created.get()
+    }
+
+    /// Inserts `input` at the insertion point. If a script is executing, it is
+    /// parsed once that script returns control to the parser; otherwise it is
+    /// parsed straight away.
+    pub fn write(&self, input: String) {
+        assert!(self.has_insertion_point());
+        let insertion_point = self.insertion_point.get();
+        self.pending_input.borrow_mut().insert(insertion_point, input.into());
+        self.insertion_point.set(insertion_point + 1);
+        if !self.is_executing_script() && !self.suspended.get() {
+            self.parse_sync();
+        }
+    }
+
+    /// Marks the end of the input of a script-created parser, finishing the
+    /// parse once everything written so far has been parsed.
+    pub fn close(&self) {
+        assert!(self.script_created.get());
+        self.last_chunk_received.set(true);
+        if !self.is_executing_script() && !self.suspended.get() {
+            self.parse_sync();
+        }
+    }
+}
+
+/// Returns the length of the prefix of `input` that ends with its first
+/// `</script>` end tag, if it has one.
+pub fn script_end_tag_end(input: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut from = 0;
+    while let Some(index) = input[from..].find("</") {
+        let name = from + index + "</".len();
+        let after = name + "script".len();
+        from = name;
+        if after > bytes.len() || !bytes[name..after].eq_ignore_ascii_case(b"script") {
+            continue;
+        }
+        match bytes.get(after) {
+            Some(&b'>') | Some(&b'/') | Some(&b'\t') | Some(&b'\n') | Some(&b'\x0C') | Some(&b' ') => {
+                return input[after..].find('>').map(|end| after + end + 1);
+            },
+            _ => from = after,
+        }
+    }
+    None
 }
 
 struct Tracer {
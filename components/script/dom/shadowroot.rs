@@ -0,0 +1,107 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::ShadowRootBinding;
+use dom::bindings::codegen::Bindings::ShadowRootBinding::{ShadowRootMethods, ShadowRootMode};
+use dom::bindings::codegen::InheritTypes::{NodeCast, ShadowRootDerived};
+use dom::bindings::error::Fallible;
+use dom::bindings::js::{JS, Root};
+use dom::document::Document;
+use dom::documentfragment::{DocumentFragment, DocumentFragmentTypeId};
+use dom::element::Element;
+use dom::eventtarget::{EventTarget, EventTargetTypeId};
+use dom::node::{Node, NodeTypeId};
+use html5ever::serialize::TraversalScope::ChildrenOnly;
+use util::str::DOMString;
+
+// https://dom.spec.whatwg.org/#interface-shadowroot
+#[dom_struct]
+pub struct ShadowRoot {
+    document_fragment: DocumentFragment,
+    mode: ShadowRootMode,
+    host: JS<Element>,
+}
+
+impl ShadowRootDerived for EventTarget {
+    fn is_shadowroot(&self) -> bool {
+        *self.type_id() ==
+            EventTargetTypeId::Node(NodeTypeId::DocumentFragment(DocumentFragmentTypeId::ShadowRoot))
+    }
+}
+
+/// Whether an HTML element with the given local name may host a shadow root.
+/// https://dom.spec.whatwg.org/#dom-element-attachshadow (step 2)
+pub fn is_valid_shadow_host_name(local_name: &str) -> bool {
+    match local_name {
+        "article" | "aside" | "blockquote" | "body" | "div" | "footer" |
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "header" | "main" | "nav" |
+        "p" | "section" | "span" => true,
+        _ => is_valid_custom_element_name(local_name),
+    }
+}
+
+// https://html.spec.whatwg.org/multipage/#valid-custom-element-name
+fn is_valid_custom_element_name(name: &str) -> bool {
+    match name {
+        "annotation-xml" | "color-profile" | "font-face" | "font-face-src" |
+        "font-face-uri" | "font-face-format" | "font-face-name" | "missing-glyph" => return false,
+        _ => (),
+    }
+    match name.chars().next() {
+        Some('a'...'z') => (),
+        _ => return false,
+    }
+    name.contains('-') && !name.chars().any(|c| c >= 'A' && c <= 'Z')
+}
+
+impl ShadowRoot {
+    fn new_inherited(host: &Element, mode: ShadowRootMode, document: &Document) -> ShadowRoot {
+        ShadowRoot {
+            document_fragment: DocumentFragment::new_inherited(DocumentFragmentTypeId::ShadowRoot,
+                                                               document),
+            mode: mode,
+            host: JS::from_ref(host),
+        }
+    }
+
+    pub fn new(host: &Element, mode: ShadowRootMode, document: &Document) -> Root<ShadowRoot> {
+        Node::reflect_node(box ShadowRoot::new_inherited(host, mode, document),
+                           document, ShadowRootBinding::Wrap)
+    }
+
+    pub fn mode(&self) -> ShadowRootMode {
+        self.mode
+    }
+
+    pub fn host(&self) -> Root<Element> {
+        self.host.root()
+    }
+}
+
+impl ShadowRootMethods for ShadowRoot {
+    // https://dom.spec.whatwg.org/#dom-shadowroot-mode
+    fn Mode(&self) -> ShadowRootMode {
+        self.mode
+    }
+
+    // https://dom.spec.whatwg.org/#dom-shadowroot-host
+    fn Host(&self) -> Root<Element> {
+        self.host.root()
+    }
+
+    // https://w3c.github.io/DOM-Parsing/#dom-innerhtml-innerhtml
+    fn GetInnerHTML(&self) -> Fallible<DOMString> {
+        NodeCast::from_ref(self).serialize(ChildrenOnly)
+    }
+
+    // https://w3c.github.io/DOM-Parsing/#dom-innerhtml-innerhtml
+    fn SetInnerHTML(&self, value: DOMString) -> Fallible<()> {
+        // Step 1: the context element is the shadow root's host.
+        let host = self.host.root();
+        let frag = try!(NodeCast::from_ref(host.r()).parse_fragment(value));
+        // Step 2.
+        Node::replace_all(Some(NodeCast::from_ref(frag.r())), NodeCast::from_ref(self));
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@
 use dom::bindings::codegen::Bindings::StorageBinding;
 use dom::bindings::codegen::Bindings::StorageBinding::StorageMethods;
 use dom::bindings::codegen::InheritTypes::{EventCast, EventTargetCast};
+use dom::bindings::error::{Error, ErrorResult};
 use dom::bindings::global::{GlobalField, GlobalRef};
 use dom::bindings::js::{Root, RootedReference};
 use dom::bindings::refcounted::Trusted;
@@ -82,15 +83,19 @@ impl StorageMethods for Storage {
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-storage-setitem
-    fn SetItem(&self, name: DOMString, value: DOMString) {
+    fn SetItem(&self, name: DOMString, value: DOMString) -> ErrorResult {
         let (sender, receiver) = ipc::channel().unwrap();
 
         let msg = StorageTaskMsg::SetItem(sender, self.get_url(), self.storage_type, name.clone(), value.clone());
         self.get_storage_task().send(msg).unwrap();
-        let (changed, old_value) = receiver.recv().unwrap();
+        let (changed, old_value) = match receiver.recv().unwrap() {
+            Ok(result) => result,
+            Err(()) => return Err(Error::QuotaExceeded),
+        };
         if changed {
             self.broadcast_change_notification(Some(name), old_value, Some(value));
         }
+        Ok(())
     }
 
     // https://html.spec.whatwg.org/multipage/#dom-storage-removeitem
@@ -116,8 +121,10 @@ impl StorageMethods for Storage {
 
     // https://html.spec.whatwg.org/multipage/#the-storage-interface:supported-property-names
     fn SupportedPropertyNames(&self) -> Vec<DOMString> {
-        // FIXME: unimplemented (https://github.com/servo/servo/issues/7273)
-        vec![]
+        let (sender, receiver) = ipc::channel().unwrap();
+
+        self.get_storage_task().send(StorageTaskMsg::Keys(sender, self.get_url(), self.storage_type)).unwrap();
+        receiver.recv().unwrap()
     }
 
     // check-tidy: no specs after this line
@@ -127,8 +134,8 @@ impl StorageMethods for Storage {
         item
     }
 
-    fn NamedSetter(&self, name: DOMString, value: DOMString) {
-        self.SetItem(name, value);
+    fn NamedSetter(&self, name: DOMString, value: DOMString) -> ErrorResult {
+        self.SetItem(name, value)
     }
 
     fn NamedDeleter(&self, name: DOMString) {
@@ -176,27 +183,27 @@ impl MainThreadRunnable for StorageEventRunnable {
         let ev_window = global_ref.as_window();
         let ev_url = storage.get_url();
 
-        let storage_event = StorageEvent::new(
-            global_ref,
-            "storage".to_owned(),
-            EventBubbles::DoesNotBubble, EventCancelable::NotCancelable,
-            this.key, this.old_value, this.new_value,
-            ev_url.to_string(),
-            Some(storage)
-        );
-        let event = EventCast::from_ref(storage_event.r());
-
         let root_page = script_task.root_page();
         for it_page in root_page.iter() {
             let it_window_root = it_page.window();
             let it_window = it_window_root.r();
-            assert!(UrlHelper::SameOrigin(&ev_url, &it_window.get_url()));
+            if ev_window.pipeline() == it_window.pipeline() ||
+               !UrlHelper::SameOrigin(&ev_url, &it_window.get_url()) {
+                continue;
+            }
             // TODO: Such a Document object is not necessarily fully active, but events fired on such
             // objects are ignored by the event loop until the Document becomes fully active again.
-            if ev_window.pipeline() != it_window.pipeline() {
-                let target = EventTargetCast::from_ref(it_window);
-                event.fire(target);
-            }
+            let storage_event = StorageEvent::new(
+                GlobalRef::Window(it_window),
+                "storage".to_owned(),
+                EventBubbles::DoesNotBubble, EventCancelable::NotCancelable,
+                this.key.clone(), this.old_value.clone(), this.new_value.clone(),
+                ev_url.to_string(),
+                Some(storage)
+            );
+            let event = EventCast::from_ref(storage_event.r());
+            let target = EventTargetCast::from_ref(it_window);
+            event.fire(target);
         }
     }
 }
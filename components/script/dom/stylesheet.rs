@@ -0,0 +1,94 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::StyleSheetBinding;
+use dom::bindings::codegen::Bindings::StyleSheetBinding::StyleSheetMethods;
+use dom::bindings::codegen::InheritTypes::{CSSStyleSheetCast, CSSStyleSheetDerived};
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::Root;
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::window::Window;
+use util::str::DOMString;
+
+#[derive(JSTraceable, HeapSizeOf)]
+pub enum StyleSheetTypeId {
+    StyleSheet,
+    CSSStyleSheet,
+}
+
+// https://drafts.csswg.org/cssom/#the-stylesheet-interface
+#[dom_struct]
+pub struct StyleSheet {
+    reflector_: Reflector,
+    type_id: StyleSheetTypeId,
+    type_: DOMString,
+    href: Option<DOMString>,
+    title: Option<DOMString>,
+}
+
+impl StyleSheet {
+    pub fn new_inherited(type_id: StyleSheetTypeId,
+                         type_: DOMString,
+                         href: Option<DOMString>,
+                         title: Option<DOMString>) -> StyleSheet {
+        StyleSheet {
+            reflector_: Reflector::new(),
+            type_id: type_id,
+            type_: type_,
+            href: href,
+            title: title,
+        }
+    }
+
+    pub fn new(window: &Window,
+               type_: DOMString,
+               href: Option<DOMString>,
+               title: Option<DOMString>) -> Root<StyleSheet> {
+        reflect_dom_object(box StyleSheet::new_inherited(StyleSheetTypeId::StyleSheet,
+                                                         type_, href, title),
+                           GlobalRef::Window(window),
+                           StyleSheetBinding::Wrap)
+    }
+}
+
+impl StyleSheetMethods for StyleSheet {
+    // https://drafts.csswg.org/cssom/#dom-stylesheet-type
+    fn Type(&self) -> DOMString {
+        self.type_.clone()
+    }
+
+    // https://drafts.csswg.org/cssom/#dom-stylesheet-href
+    fn GetHref(&self) -> Option<DOMString> {
+        self.href.clone()
+    }
+
+    // https://drafts.csswg.org/cssom/#dom-stylesheet-title
+    fn GetTitle(&self) -> Option<DOMString> {
+        self.title.clone()
+    }
+
+    // https://drafts.csswg.org/cssom/#dom-stylesheet-disabled
+    fn Disabled(&self) -> bool {
+        match CSSStyleSheetCast::to_ref(self) {
+            Some(sheet) => sheet.disabled(),
+            None => false,
+        }
+    }
+
+    // https://drafts.csswg.org/cssom/#dom-stylesheet-disabled
+    fn SetDisabled(&self, disabled: bool) {
+        if let Some(sheet) = CSSStyleSheetCast::to_ref(self) {
+            sheet.set_disabled(disabled);
+        }
+    }
+}
+
+impl CSSStyleSheetDerived for StyleSheet {
+    fn is_cssstylesheet(&self) -> bool {
+        match self.type_id {
+            StyleSheetTypeId::CSSStyleSheet => true,
+            _ => false
+        }
+    }
+}
@@ -25,6 +25,7 @@ use js::jsval::{JSVal, NullValue};
 use std::borrow::ToOwned;
 use std::ptr;
 use std::rc::Rc;
+use util::prefs::{self, PrefValue};
 use util::str::DOMString;
 
 #[dom_struct]
@@ -370,4 +371,14 @@ impl TestBinding {
     pub fn BooleanAttributeStatic(_: GlobalRef) -> bool { false }
     pub fn SetBooleanAttributeStatic(_: GlobalRef, _: bool) {}
     pub fn ReceiveVoidStatic(_: GlobalRef) {}
+
+    pub fn BooleanMozPreference(_: GlobalRef, pref_name: DOMString) -> bool {
+        prefs::get_pref(&pref_name).as_boolean().unwrap_or(false)
+    }
+    pub fn SetBooleanMozPreference(_: GlobalRef, pref_name: DOMString, value: bool) {
+        prefs::set_pref(&pref_name, PrefValue::Boolean(value));
+    }
+    pub fn ResetMozPreference(_: GlobalRef, pref_name: DOMString) {
+        prefs::reset_pref(&pref_name);
+    }
 }
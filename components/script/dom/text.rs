@@ -17,6 +17,7 @@ use dom::characterdata::{CharacterData, CharacterDataTypeId};
 use dom::document::Document;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::node::{Node, NodeTypeId};
+use dom::range::for_each_live_range;
 use util::str::DOMString;
 
 /// An HTML text node.
@@ -43,6 +44,7 @@ impl Text {
                            document, TextBinding::Wrap)
     }
 
+    // https://dom.spec.whatwg.org/#dom-text-text
     pub fn Constructor(global: GlobalRef, text: DOMString) -> Fallible<Root<Text>> {
         let document = global.as_window().Document();
         Ok(Text::new(text, document.r()))
@@ -70,18 +72,20 @@ impl TextMethods for Text {
         // Step 6.
         let parent = node.GetParentNode();
         if let Some(ref parent) = parent {
-            // Step 7.
+            // Step 7.1.
+            let index = node.index();
             parent.r().InsertBefore(NodeCast::from_ref(new_node.r()),
                                     node.GetNextSibling().r())
                   .unwrap();
-            // TODO: Ranges.
+            // Steps 7.2-7.5.
+            let new_node = NodeCast::from_ref(new_node.r());
+            for_each_live_range(|range| {
+                range.text_split(node, new_node, parent.r(), index, offset)
+            });
         }
         // Step 8.
+        // Replacing the data also updates the ranges for step 9.
         cdata.DeleteData(offset, count).unwrap();
-        if parent.is_none() {
-            // Step 9.
-            // TODO: Ranges
-        }
         // Step 10.
         Ok(new_node)
     }
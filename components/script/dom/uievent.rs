@@ -12,6 +12,7 @@ use dom::bindings::js::Root;
 use dom::bindings::js::{JS, MutNullableHeap, RootedReference};
 use dom::bindings::utils::reflect_dom_object;
 use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
+use dom::mouseevent::MouseEventTypeId;
 use dom::window::Window;
 use std::cell::Cell;
 use std::default::Default;
@@ -19,7 +20,9 @@ use util::str::DOMString;
 
 #[derive(JSTraceable, PartialEq, HeapSizeOf)]
 pub enum UIEventTypeId {
-    MouseEvent,
+    FocusEvent,
+    InputEvent,
+    MouseEvent(MouseEventTypeId),
     KeyboardEvent,
     UIEvent,
 }
@@ -63,6 +63,20 @@ impl UrlHelper {
         USVString(url.serialize())
     }
 
+    // https://html.spec.whatwg.org/multipage/#ascii-serialisation-of-an-origin
+    pub fn Origin(url: &Url) -> USVString {
+        USVString(match url.scheme_data {
+            SchemeData::Relative(ref scheme_data) if url.scheme != "file" => {
+                let mut origin = format!("{}://{}", url.scheme, scheme_data.host.serialize());
+                if let Some(port) = scheme_data.port {
+                    write!(origin, ":{}", port).unwrap();
+                }
+                origin
+            },
+            _ => "null".to_owned(),
+        })
+    }
+
     // https://url.spec.whatwg.org/#dom-urlutils-password
     pub fn Password(url: &Url) -> USVString {
         USVString(url.password().unwrap_or("").to_owned())
@@ -113,6 +127,13 @@ impl UrlHelper {
         let _ = wrapper.set_scheme(&value.0);
     }
 
+    // https://url.spec.whatwg.org/#concept-url-equals with the exclude fragments flag set
+    pub fn EqualsExceptFragment(urlA: &Url, urlB: &Url) -> bool {
+        urlA.scheme == urlB.scheme &&
+        urlA.scheme_data == urlB.scheme_data &&
+        urlA.query == urlB.query
+    }
+
     // https://html.spec.whatwg.org/multipage/#same-origin
     pub fn SameOrigin(urlA: &Url, urlB: &Url) -> bool {
         if urlA.host() != urlB.host() {
@@ -24,6 +24,7 @@ use dom::bindings::codegen::InheritTypes::HTMLMetaElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLObjectElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLOptGroupElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLOptionElementCast;
+use dom::bindings::codegen::InheritTypes::HTMLOutputElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLScriptElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLSelectElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLStyleElementCast;
@@ -34,6 +35,8 @@ use dom::bindings::codegen::InheritTypes::HTMLTableSectionElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLTemplateElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLTextAreaElementCast;
 use dom::bindings::codegen::InheritTypes::HTMLTitleElementCast;
+use dom::bindings::codegen::InheritTypes::ProcessingInstructionCast;
+use dom::characterdata::CharacterDataTypeId;
 use dom::document::Document;
 use dom::element::{AttributeMutation, ElementTypeId};
 use dom::event::Event;
@@ -196,6 +199,10 @@ pub fn vtable_for<'a>(node: &'a Node) -> &'a (VirtualMethods + 'a) {
             let element = HTMLOptionElementCast::to_ref(node).unwrap();
             element as &'a (VirtualMethods + 'a)
         }
+        NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLOutputElement)) => {
+            let element = HTMLOutputElementCast::to_ref(node).unwrap();
+            element as &'a (VirtualMethods + 'a)
+        }
         NodeTypeId::Element(ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLScriptElement)) => {
             let element = HTMLScriptElementCast::to_ref(node).unwrap();
             element as &'a (VirtualMethods + 'a)
@@ -248,6 +255,9 @@ pub fn vtable_for<'a>(node: &'a Node) -> &'a (VirtualMethods + 'a) {
             let element = HTMLElementCast::to_ref(node).unwrap();
             element as &'a (VirtualMethods + 'a)
         }
+        NodeTypeId::CharacterData(CharacterDataTypeId::ProcessingInstruction) => {
+            ProcessingInstructionCast::to_ref(node).unwrap() as &'a (VirtualMethods + 'a)
+        }
         _ => {
             node as &'a (VirtualMethods + 'a)
         }
@@ -0,0 +1,144 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::MouseEventBinding::MouseEventMethods;
+use dom::bindings::codegen::Bindings::WheelEventBinding;
+use dom::bindings::codegen::Bindings::WheelEventBinding::{WheelEventConstants, WheelEventMethods};
+use dom::bindings::codegen::InheritTypes::{MouseEventCast, WheelEventDerived};
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::{Root, RootedReference};
+use dom::bindings::num::Finite;
+use dom::bindings::utils::reflect_dom_object;
+use dom::event::{Event, EventBubbles, EventCancelable, EventTypeId};
+use dom::eventtarget::EventTarget;
+use dom::mouseevent::{MouseEvent, MouseEventTypeId};
+use dom::uievent::UIEventTypeId;
+use dom::window::Window;
+use std::cell::Cell;
+use util::str::DOMString;
+
+// https://w3c.github.io/uievents/#interface-WheelEvent
+#[dom_struct]
+pub struct WheelEvent {
+    mouseevent: MouseEvent,
+    delta_x: Cell<f64>,
+    delta_y: Cell<f64>,
+    delta_z: Cell<f64>,
+    delta_mode: Cell<u32>,
+}
+
+impl WheelEventDerived for Event {
+    fn is_wheelevent(&self) -> bool {
+        *self.type_id() == EventTypeId::UIEvent(UIEventTypeId::MouseEvent(MouseEventTypeId::WheelEvent))
+    }
+}
+
+impl WheelEvent {
+    fn new_inherited() -> WheelEvent {
+        WheelEvent {
+            mouseevent: MouseEvent::new_inherited(MouseEventTypeId::WheelEvent),
+            delta_x: Cell::new(0.0),
+            delta_y: Cell::new(0.0),
+            delta_z: Cell::new(0.0),
+            delta_mode: Cell::new(WheelEventConstants::DOM_DELTA_PIXEL),
+        }
+    }
+
+    pub fn new_uninitialized(window: &Window) -> Root<WheelEvent> {
+        reflect_dom_object(box WheelEvent::new_inherited(),
+                           GlobalRef::Window(window),
+                           WheelEventBinding::Wrap)
+    }
+
+    pub fn new(window: &Window,
+               type_: DOMString,
+               canBubble: EventBubbles,
+               cancelable: EventCancelable,
+               view: Option<&Window>,
+               detail: i32,
+               screenX: i32,
+               screenY: i32,
+               clientX: i32,
+               clientY: i32,
+               ctrlKey: bool,
+               altKey: bool,
+               shiftKey: bool,
+               metaKey: bool,
+               button: i16,
+               relatedTarget: Option<&EventTarget>,
+               deltaX: f64,
+               deltaY: f64,
+               deltaZ: f64,
+               deltaMode: u32) -> Root<WheelEvent> {
+        let ev = WheelEvent::new_uninitialized(window);
+        {
+            let ev = ev.r();
+            let mouseevent = MouseEventCast::from_ref(ev);
+            mouseevent.InitMouseEvent(type_, canBubble == EventBubbles::Bubbles,
+                                      cancelable == EventCancelable::Cancelable,
+                                      view, detail,
+                                      screenX, screenY, clientX, clientY,
+                                      ctrlKey, altKey, shiftKey, metaKey,
+                                      button, relatedTarget);
+            ev.delta_x.set(deltaX);
+            ev.delta_y.set(deltaY);
+            ev.delta_z.set(deltaZ);
+            ev.delta_mode.set(deltaMode);
+        }
+        ev
+    }
+
+    pub fn Constructor(global: GlobalRef,
+                       type_: DOMString,
+                       init: &WheelEventBinding::WheelEventInit) -> Fallible<Root<WheelEvent>> {
+        let bubbles = if init.parent.parent.parent.parent.bubbles {
+            EventBubbles::Bubbles
+        } else {
+            EventBubbles::DoesNotBubble
+        };
+        let cancelable = if init.parent.parent.parent.parent.cancelable {
+            EventCancelable::Cancelable
+        } else {
+            EventCancelable::NotCancelable
+        };
+        let mouse_init = &init.parent;
+        let event = WheelEvent::new(global.as_window(), type_,
+                                    bubbles,
+                                    cancelable,
+                                    mouse_init.parent.parent.view.r(),
+                                    mouse_init.parent.parent.detail,
+                                    mouse_init.screenX, mouse_init.screenY,
+                                    mouse_init.clientX, mouse_init.clientY,
+                                    mouse_init.parent.ctrlKey, mouse_init.parent.altKey,
+                                    mouse_init.parent.shiftKey, mouse_init.parent.metaKey,
+                                    mouse_init.button, mouse_init.relatedTarget.r(),
+                                    *init.deltaX, *init.deltaY, *init.deltaZ,
+                                    init.deltaMode);
+        MouseEventCast::from_ref(event.r()).init_extended_members(mouse_init);
+        Ok(event)
+    }
+}
+
+impl WheelEventMethods for WheelEvent {
+    // https://w3c.github.io/uievents/#widl-WheelEvent-deltaX
+    fn DeltaX(&self) -> Finite<f64> {
+        Finite::wrap(self.delta_x.get())
+    }
+
+    // https://w3c.github.io/uievents/#widl-WheelEvent-deltaY
+    fn DeltaY(&self) -> Finite<f64> {
+        Finite::wrap(self.delta_y.get())
+    }
+
+    // https://w3c.github.io/uievents/#widl-WheelEvent-deltaZ
+    fn DeltaZ(&self) -> Finite<f64> {
+        Finite::wrap(self.delta_z.get())
+    }
+
+    // https://w3c.github.io/uievents/#widl-WheelEvent-deltaMode
+    fn DeltaMode(&self) -> u32 {
+        self.delta_mode.get()
+    }
+}
@@ -9,16 +9,22 @@ use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::DocumentBinding::DocumentMethods;
 use dom::bindings::codegen::Bindings::EventHandlerBinding::{EventHandlerNonNull, OnErrorEventHandlerNonNull};
 use dom::bindings::codegen::Bindings::FunctionBinding::Function;
+use dom::bindings::codegen::Bindings::HTMLIFrameElementBinding::HTMLIFrameElementMethods;
+use dom::bindings::codegen::Bindings::VoidFunctionBinding::VoidFunction;
+use dom::bindings::codegen::Bindings::WindowBinding::{IdleRequestCallback, IdleRequestOptions};
 use dom::bindings::codegen::Bindings::WindowBinding::{ScrollBehavior, ScrollToOptions};
 use dom::bindings::codegen::Bindings::WindowBinding::{self, FrameRequestCallback, WindowMethods};
-use dom::bindings::codegen::InheritTypes::{ElementCast, EventTargetCast, NodeCast, WindowDerived};
-use dom::bindings::error::Error::InvalidCharacter;
-use dom::bindings::error::{Fallible, report_pending_exception};
-use dom::bindings::global::GlobalRef;
+use dom::bindings::codegen::InheritTypes::{ElementCast, EventCast, EventTargetCast, HTMLIFrameElementCast};
+use dom::bindings::codegen::InheritTypes::{NodeCast, WindowDerived};
+use dom::bindings::error::Error::{InvalidCharacter, Syntax};
+use dom::bindings::error::{ErrorResult, Fallible, report_pending_exception};
 use dom::bindings::global::global_object_for_js_object;
+use dom::bindings::global::{GlobalRef, GlobalRoot};
 use dom::bindings::js::RootedReference;
 use dom::bindings::js::{JS, MutNullableHeap, Root};
 use dom::bindings::num::Finite;
+use dom::bindings::refcounted::Trusted;
+use dom::bindings::structuredclone::StructuredCloneData;
 use dom::bindings::utils::{GlobalStaticData, Reflectable, WindowProxyHandler};
 use dom::browsercontext::BrowsingContext;
 use dom::console::Console;
@@ -26,23 +32,37 @@ use dom::crypto::Crypto;
 use dom::cssstyledeclaration::{CSSModificationAccess, CSSStyleDeclaration};
 use dom::document::Document;
 use dom::element::Element;
+use dom::event::{Event, EventBubbles, EventCancelable};
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
+use dom::idledeadline::IdleDeadline;
+use dom::history::History;
+use dom::htmliframeelement::HTMLIFrameElement;
+use dom::intersectionobserver::IntersectionObserver;
 use dom::location::Location;
+use dom::mediaquerylist::MediaQueryList;
+use dom::messageevent::MessageEvent;
+use dom::mutationobserver::MutationObserver;
 use dom::navigator::Navigator;
-use dom::node::{TrustedNodeAddress, from_untrusted_node_address, window_from_node};
+use dom::node::{Node, TrustedNodeAddress, from_untrusted_node_address, window_from_node};
 use dom::performance::Performance;
-use dom::screen::Screen;
+use dom::performancetiming::milliseconds_since_epoch;
+use dom::resizeobserver::ResizeObserver;
+use dom::screen::{self, Screen};
+use dom::selection::Selection;
 use dom::storage::Storage;
+use dom::urlhelper::UrlHelper;
 use euclid::{Point2D, Rect, Size2D};
 use ipc_channel::ipc::{self, IpcSender};
-use js::jsapi::{Evaluate2, MutableHandleValue};
+use js::jsapi::{Evaluate2, GetScriptedCallerGlobal, MutableHandleValue, RootedValue};
 use js::jsapi::{HandleValue, JSContext};
 use js::jsapi::{JSAutoCompartment, JSAutoRequest, JS_GC, JS_GetRuntime};
+use js::jsval::UndefinedValue;
 use js::rust::CompileOptionsWrapper;
 use js::rust::Runtime;
 use layout_interface::{ContentBoxResponse, ContentBoxesResponse, ResolvedStyleResponse, ScriptReflow};
 use layout_interface::{LayoutChan, LayoutRPC, Msg, Reflow, ReflowGoal, ReflowQueryType};
 use libc;
+use microtask::{Microtask, NotifyMutationObserversMicrotask, UserMicrotask};
 use msg::compositor_msg::{LayerId, ScriptToCompositorMsg};
 use msg::constellation_msg::{ConstellationChan, LoadData, PipelineId, SubpageId, WindowSizeData, WorkerId};
 use msg::webdriver_msg::{WebDriverJSError, WebDriverJSResult};
@@ -53,16 +73,20 @@ use num::traits::ToPrimitive;
 use page::Page;
 use profile_traits::mem;
 use rustc_serialize::base64::{FromBase64, STANDARD, ToBase64};
+use script_task::ScriptTaskEventCategory::DomEvent;
+use script_task::{CommonScriptMsg, Runnable};
 use script_task::{MainThreadScriptChan, SendableMainThreadScriptChan};
-use script_task::{MainThreadScriptMsg, ScriptChan, ScriptPort, TimerSource};
+use script_task::{MainThreadScriptMsg, ScriptChan, ScriptPort, ScriptTask, TimerSource};
 use script_traits::ConstellationControlMsg;
 use selectors::parser::PseudoElement;
 use std::ascii::AsciiExt;
 use std::borrow::ToOwned;
 use std::cell::{Cell, Ref, RefCell};
+use std::cmp;
 use std::collections::HashSet;
 use std::default::Default;
 use std::ffi::CString;
+use std::i32;
 use std::io::{Write, stderr, stdout};
 use std::mem as std_mem;
 use std::rc::Rc;
@@ -72,13 +96,34 @@ use std::sync::mpsc::{Receiver, Sender, channel};
 use string_cache::Atom;
 use time;
 use timers::{IsInterval, TimerCallback, TimerId, TimerManager};
-use url::Url;
+use url::{Url, UrlParser};
 use util::geometry::{self, MAX_RECT};
 use util::str::{DOMString, HTML_SPACE_CHARACTERS};
 use util::{breakpoint, opts};
 use webdriver_handlers::jsval_to_webdriver;
 
 /// Current state of the window object
+/// The longest idle period given to an idle request callback, in milliseconds.
+///
+/// https://w3c.github.io/requestidlecallback/#why50
+const MAX_IDLE_PERIOD_MS: f64 = 50.;
+
+/// How long a user activation keeps a window transiently activated, in
+/// milliseconds.
+///
+/// https://html.spec.whatwg.org/multipage/#transient-activation-duration
+pub const TRANSIENT_ACTIVATION_DURATION_MS: f64 = 5000.;
+
+/// Whether a window whose last activation timestamp is
+/// `last_activation_timestamp` has transient activation at time `now`.
+///
+/// https://html.spec.whatwg.org/multipage/#transient-activation
+pub fn is_transiently_activated(last_activation_timestamp: Option<f64>, now: f64) -> bool {
+    last_activation_timestamp.map_or(false, |timestamp| {
+        timestamp <= now && now < timestamp + TRANSIENT_ACTIVATION_DURATION_MS
+    })
+}
+
 #[derive(JSTraceable, Copy, Clone, Debug, PartialEq, HeapSizeOf)]
 enum WindowState {
     Alive,
@@ -115,6 +160,7 @@ pub struct Window {
     control_chan: Sender<ConstellationControlMsg>,
     console: MutNullableHeap<JS<Console>>,
     crypto: MutNullableHeap<JS<Crypto>>,
+    history: MutNullableHeap<JS<History>>,
     navigator: MutNullableHeap<JS<Navigator>>,
     #[ignore_heap_size_of = "channels are hard"]
     image_cache_task: ImageCacheTask,
@@ -125,9 +171,40 @@ pub struct Window {
     browsing_context: DOMRefCell<Option<BrowsingContext>>,
     page: Rc<Page>,
     performance: MutNullableHeap<JS<Performance>>,
+    /// The wall-clock time of navigation start, in milliseconds since the Unix epoch.
     navigation_start: u64,
+    /// The value of `time::precise_time_ns()` at navigation start.
     navigation_start_precise: f64,
     screen: MutNullableHeap<JS<Screen>>,
+    /// The `MediaQueryList` objects created by `matchMedia()` that have change
+    /// listeners, which are re-evaluated whenever the viewport changes. Lists
+    /// without listeners are left out so that they can be collected.
+    media_query_lists: DOMRefCell<Vec<JS<MediaQueryList>>>,
+    /// The `IntersectionObserver` objects that observe at least one target.
+    /// https://w3c.github.io/IntersectionObserver/#document-intersectionobservers
+    intersection_observers: DOMRefCell<Vec<JS<IntersectionObserver>>>,
+    /// Whether a task to update intersection observations has been queued and
+    /// hasn't run yet.
+    intersection_observations_queued: Cell<bool>,
+    /// The `ResizeObserver` objects that observe at least one target.
+    resize_observers: DOMRefCell<Vec<JS<ResizeObserver>>>,
+    /// Whether a task to broadcast resize observations has been queued and
+    /// hasn't run yet.
+    resize_observations_queued: Cell<bool>,
+    /// The `MutationObserver` objects that observe at least one node.
+    mutation_observers: DOMRefCell<Vec<JS<MutationObserver>>>,
+    /// https://dom.spec.whatwg.org/#mutation-observer-compound-microtask-queued-flag
+    mutation_observer_microtask_queued: Cell<bool>,
+    /// The time of the last activation-triggering input event, or `None` if
+    /// there was none since the activation was last consumed.
+    ///
+    /// https://html.spec.whatwg.org/multipage/#last-activation-timestamp
+    last_activation_timestamp: Cell<Option<f64>>,
+    /// https://w3c.github.io/requestidlecallback/#dfn-idle-callback-identifier
+    idle_callback_ident: Cell<u32>,
+    /// https://w3c.github.io/requestidlecallback/#dfn-list-of-idle-request-callbacks
+    #[ignore_heap_size_of = "Rc<T> is hard"]
+    idle_request_callbacks: DOMRefCell<Vec<(u32, Rc<IdleRequestCallback>, Option<i32>)>>,
     session_storage: MutNullableHeap<JS<Storage>>,
     local_storage: MutNullableHeap<JS<Storage>>,
     timers: TimerManager,
@@ -157,6 +234,9 @@ pub struct Window {
     /// Pending resize event, if any.
     resize_event: Cell<Option<WindowSizeData>>,
 
+    /// Whether the user agent believes it currently has network connectivity.
+    online: Cell<bool>,
+
     /// Pipeline id associated with this page.
     id: PipelineId,
 
@@ -390,6 +470,11 @@ impl WindowMethods for Window {
         self.Document().r().Location()
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-history
+    fn History(&self) -> Root<History> {
+        self.history.or_init(|| History::new(self))
+    }
+
     // https://html.spec.whatwg.org/#dom-sessionstorage
     fn SessionStorage(&self) -> Root<Storage> {
         self.session_storage.or_init(|| Storage::new(&GlobalRef::Window(self), StorageType::Session))
@@ -470,6 +555,48 @@ impl WindowMethods for Window {
         self.ClearTimeout(handle);
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-queuemicrotask
+    fn QueueMicrotask(&self, callback: Rc<VoidFunction>) {
+        ScriptTask::enqueue_microtask(Microtask::User(UserMicrotask {
+            callback: callback,
+            pipeline: self.id,
+        }));
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-window-postmessage
+    fn PostMessage(&self, cx: *mut JSContext, message: HandleValue, targetOrigin: DOMString) -> ErrorResult {
+        let source = incumbent_window(cx);
+        let source_url = match source {
+            Some(ref source) => source.r().get_url(),
+            None => self.get_url(),
+        };
+
+        // Steps 4-5.
+        let target_origin = match &*targetOrigin {
+            "*" => None,
+            "/" => Some(source_url.clone()),
+            url => match UrlParser::new().parse(url) {
+                Ok(url) => Some(url),
+                Err(_) => return Err(Syntax),
+            },
+        };
+
+        // Steps 6-8.
+        let data = try!(StructuredCloneData::write(cx, message));
+
+        // Step 10.
+        let script_chan = self.script_chan();
+        let task = box PostMessageTask {
+            target: Trusted::new(cx, self, script_chan.clone()),
+            source: source.map(|source| Trusted::new(cx, source.r(), script_chan.clone())),
+            origin: UrlHelper::Origin(&source_url).0,
+            target_origin: target_origin,
+            data: data,
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+        Ok(())
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-window
     fn Window(&self) -> Root<Window> {
         Root::from_ref(self)
@@ -485,6 +612,11 @@ impl WindowMethods for Window {
         self.Window()
     }
 
+    // https://html.spec.whatwg.org/multipage/#dom-length
+    fn Length(&self) -> u32 {
+        self.child_windows().len() as u32
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-parent
     fn Parent(&self) -> Root<Window> {
         self.parent().unwrap_or(self.Window())
@@ -514,6 +646,12 @@ impl WindowMethods for Window {
     // https://html.spec.whatwg.org/multipage/#handler-window-onunload
     event_handler!(unload, GetOnunload, SetOnunload);
 
+    // https://html.spec.whatwg.org/multipage/#handler-window-ononline
+    event_handler!(online, GetOnonline, SetOnonline);
+
+    // https://html.spec.whatwg.org/multipage/#handler-window-onoffline
+    event_handler!(offline, GetOnoffline, SetOnoffline);
+
     // https://html.spec.whatwg.org/multipage/#handler-onerror
     error_event_handler!(error, GetOnerror, SetOnerror);
 
@@ -522,6 +660,11 @@ impl WindowMethods for Window {
         self.screen.or_init(|| Screen::new(self))
     }
 
+    // https://drafts.csswg.org/cssom-view/#dom-window-matchmedia
+    fn MatchMedia(&self, query: DOMString) -> Root<MediaQueryList> {
+        MediaQueryList::new(self, query)
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-windowbase64-btoa
     fn Btoa(&self, btoa: DOMString) -> Fallible<DOMString> {
         base64_btoa(btoa)
@@ -551,6 +694,43 @@ impl WindowMethods for Window {
         doc.r().cancel_animation_frame(ident);
     }
 
+    // https://w3c.github.io/requestidlecallback/#the-requestidlecallback-method
+    fn RequestIdleCallback(&self, callback: Rc<IdleRequestCallback>, options: &IdleRequestOptions) -> u32 {
+        // Steps 2-4.
+        let ident = self.idle_callback_ident.get() + 1;
+        self.idle_callback_ident.set(ident);
+
+        // Step 6.
+        let timer = options.timeout.and_then(|timeout| {
+            if timeout == 0 {
+                return None;
+            }
+            let window = Trusted::new(self.get_cx(), self, self.script_chan.clone());
+            let callback = TimerCallback::IdleCallbackTimerCallback(window, ident);
+            Some(self.schedule_callback(callback, cmp::min(timeout, i32::MAX as u32) as i32))
+        });
+
+        // Step 5.
+        self.idle_request_callbacks.borrow_mut().push((ident, callback, timer));
+
+        // Step 7.
+        ident
+    }
+
+    // https://w3c.github.io/requestidlecallback/#the-cancelidlecallback-method
+    fn CancelIdleCallback(&self, handle: u32) {
+        let timer = {
+            let mut callbacks = self.idle_request_callbacks.borrow_mut();
+            match callbacks.iter().position(|&(ident, _, _)| ident == handle) {
+                Some(index) => callbacks.remove(index).2,
+                None => return,
+            }
+        };
+        if let Some(timer) = timer {
+            self.ClearTimeout(timer);
+        }
+    }
+
     // https://html.spec.whatwg.org/multipage/#dom-window-captureevents
     fn CaptureEvents(&self) {
         // This method intentionally does nothing
@@ -609,6 +789,11 @@ impl WindowMethods for Window {
         CSSStyleDeclaration::new(self, element, pseudo, CSSModificationAccess::Readonly)
     }
 
+    // https://w3c.github.io/selection-api/#dom-window-getselection
+    fn GetSelection(&self) -> Option<Root<Selection>> {
+        self.Document().r().GetSelection()
+    }
+
     // https://drafts.csswg.org/cssom-view/#dom-window-innerheight
     //TODO Include Scrollbar
     fn InnerHeight(&self) -> i32 {
@@ -745,10 +930,7 @@ impl WindowMethods for Window {
 
     // https://drafts.csswg.org/cssom-view/#dom-window-devicepixelratio
     fn DevicePixelRatio(&self) -> Finite<f64> {
-        let dpr = self.window_size.get()
-                                  .map(|data| data.device_pixel_ratio.get())
-                                  .unwrap_or(1.0f32);
-        Finite::wrap(dpr as f64)
+        Finite::wrap(self.device_pixel_ratio() as f64)
     }
 }
 
@@ -876,6 +1058,8 @@ impl Window {
         // TODO (farodin91): Raise an event to stop the current_viewport
         let size = self.current_viewport.get().size;
         self.current_viewport.set(Rect::new(Point2D::new(Au::from_f32_px(x), Au::from_f32_px(y)), size));
+        self.Document().r().process_lazy_iframes();
+        self.update_intersection_observations();
 
         self.compositor.send(ScriptToCompositorMsg::ScrollFragmentPoint(
                                                          self.pipeline(), LayerId::null(), point, smooth)).unwrap()
@@ -887,6 +1071,19 @@ impl Window {
         recv.recv().unwrap_or((Size2D::zero(), Point2D::zero()))
     }
 
+    /// Returns the size of the screen and of the area of it available to
+    /// windows, in device pixels.
+    pub fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>) {
+        let (send, recv) = ipc::channel::<(Size2D<u32>, Size2D<u32>)>().unwrap();
+        self.compositor.send(ScriptToCompositorMsg::GetScreenSize(send)).unwrap();
+        recv.recv().unwrap_or((Size2D::zero(), Size2D::zero()))
+    }
+
+    pub fn device_pixel_ratio(&self) -> f32 {
+        screen::sanitize_device_pixel_ratio(
+            self.window_size.get().map(|data| data.device_pixel_ratio.get()))
+    }
+
     /// Reflows the page unconditionally. This method will wait for the layout thread to complete
     /// (but see the `TODO` below). If there is no window size yet, the page is presumed invisible
     /// and no reflow is performed.
@@ -953,6 +1150,11 @@ impl Window {
 
         self.pending_reflow_count.set(0);
 
+        if goal == ReflowGoal::ForDisplay {
+            self.queue_intersection_observations();
+            self.queue_resize_observations();
+        }
+
         if let Some(marker) = marker {
             self.emit_timeline_marker(marker.end());
         }
@@ -1008,6 +1210,20 @@ impl Window {
         &*self.layout_rpc
     }
 
+    /// Returns the nodes containing `point`, in page coordinates, topmost first.
+    pub fn nodes_from_point_query(&self, point: Point2D<f32>) -> Vec<Root<Node>> {
+        // Hit testing uses the stacking context, which is only built for display.
+        self.reflow(ReflowGoal::ForDisplay,
+                    ReflowQueryType::NoQuery,
+                    ReflowReason::Query);
+        self.join_layout();
+        let js_runtime = self.js_runtime.borrow();
+        let js_runtime = js_runtime.as_ref().unwrap();
+        self.layout_rpc.nodes_from_point(point).into_iter().map(|node_address| {
+            from_untrusted_node_address(js_runtime.rt(), node_address)
+        }).collect()
+    }
+
     pub fn content_box_query(&self, content_box_request: TrustedNodeAddress) -> Rect<Au> {
         self.reflow(ReflowGoal::ForScriptQuery,
                     ReflowQueryType::ContentBoxQuery(content_box_request),
@@ -1072,9 +1288,24 @@ impl Window {
     }
 
     /// Commence a new URL load which will either replace this window or scroll to a fragment.
-    pub fn load_url(&self, url: Url) {
+    /// Navigates this window to `url`. If `replace` is true, the new document
+    /// replaces the current session history entry instead of adding one.
+    pub fn load_url(&self, url: Url, replace: bool) {
+        let mut load_data = LoadData::new(url);
+        load_data.referrer = self.Document().referrer_for(&load_data.url);
         self.main_thread_script_chan().send(
-            MainThreadScriptMsg::Navigate(self.id, LoadData::new(url))).unwrap();
+            MainThreadScriptMsg::Navigate(self.id, load_data, replace)).unwrap();
+    }
+
+    /// Runs `callback` after `timeout` milliseconds, through the same timers as
+    /// `setTimeout()`.
+    pub fn schedule_callback(&self, callback: TimerCallback, timeout: i32) -> i32 {
+        self.timers.set_timeout_or_interval(callback,
+                                            vec![],
+                                            timeout,
+                                            IsInterval::NonInterval,
+                                            TimerSource::FromWindow(self.id.clone()),
+                                            self.script_chan.clone())
     }
 
     pub fn handle_fire_timer(&self, timer_id: TimerId) {
@@ -1123,6 +1354,10 @@ impl Window {
         self.constellation_chan.clone()
     }
 
+    pub fn navigation_start_precise(&self) -> f64 {
+        self.navigation_start_precise
+    }
+
     pub fn windowproxy_handler(&self) -> WindowProxyHandler {
         WindowProxyHandler(self.dom_static.windowproxy_handler.0)
     }
@@ -1150,12 +1385,249 @@ impl Window {
         self.resize_event.set(Some(event));
     }
 
+    // https://drafts.csswg.org/cssom-view/#evaluate-media-queries-and-report-changes
+    pub fn evaluate_media_queries_and_report_changes(&self) {
+        let media_query_lists = self.media_query_lists.borrow().iter()
+                                    .map(|list| list.root())
+                                    .collect::<Vec<_>>();
+        for media_query_list in media_query_lists {
+            media_query_list.r().evaluate_and_report_changes();
+        }
+    }
+
+    pub fn register_media_query_list(&self, media_query_list: &MediaQueryList) {
+        let media_query_list = JS::from_ref(media_query_list);
+        let mut media_query_lists = self.media_query_lists.borrow_mut();
+        if !media_query_lists.contains(&media_query_list) {
+            media_query_lists.push(media_query_list);
+        }
+    }
+
+    pub fn unregister_media_query_list(&self, media_query_list: &MediaQueryList) {
+        let media_query_list = JS::from_ref(media_query_list);
+        self.media_query_lists.borrow_mut().retain(|list| *list != media_query_list);
+    }
+
+    pub fn register_intersection_observer(&self, observer: &IntersectionObserver) {
+        let observer = JS::from_ref(observer);
+        let mut observers = self.intersection_observers.borrow_mut();
+        if !observers.contains(&observer) {
+            observers.push(observer);
+        }
+    }
+
+    pub fn unregister_intersection_observer(&self, observer: &IntersectionObserver) {
+        let observer = JS::from_ref(observer);
+        self.intersection_observers.borrow_mut().retain(|registered| *registered != observer);
+    }
+
+    /// Queues a task to update intersection observations, unless one is
+    /// already queued, so that layout changes are noticed once the reflow
+    /// that caused them is done.
+    pub fn queue_intersection_observations(&self) {
+        if self.intersection_observers.borrow().is_empty() || self.intersection_observations_queued.get() {
+            return;
+        }
+        self.intersection_observations_queued.set(true);
+
+        let script_chan = self.script_chan();
+        let task = box IntersectionObservationTask {
+            window: Trusted::new(self.get_cx(), self, script_chan.clone()),
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
+    // https://w3c.github.io/IntersectionObserver/#run-the-update-intersection-observations-steps
+    pub fn update_intersection_observations(&self) {
+        self.intersection_observations_queued.set(false);
+        if self.intersection_observers.borrow().is_empty() {
+            return;
+        }
+
+        // Step 1.
+        let time = *self.Performance().r().Now();
+
+        // Step 2.
+        let observers = self.intersection_observers.borrow().iter()
+                            .map(|observer| observer.root())
+                            .collect::<Vec<_>>();
+        for observer in observers {
+            observer.r().update_observations(time);
+        }
+    }
+
+    pub fn register_resize_observer(&self, observer: &ResizeObserver) {
+        let observer = JS::from_ref(observer);
+        let mut observers = self.resize_observers.borrow_mut();
+        if !observers.contains(&observer) {
+            observers.push(observer);
+        }
+    }
+
+    pub fn unregister_resize_observer(&self, observer: &ResizeObserver) {
+        let observer = JS::from_ref(observer);
+        self.resize_observers.borrow_mut().retain(|registered| *registered != observer);
+    }
+
+    /// Queues a task to broadcast resize observations, unless one is already
+    /// queued. Script can't run during a reflow, so observations are
+    /// delivered once layout has settled.
+    pub fn queue_resize_observations(&self) {
+        if self.resize_observers.borrow().is_empty() || self.resize_observations_queued.get() {
+            return;
+        }
+        self.resize_observations_queued.set(true);
+
+        let script_chan = self.script_chan();
+        let task = box ResizeObservationTask {
+            window: Trusted::new(self.get_cx(), self, script_chan.clone()),
+        };
+        let _ = script_chan.send(CommonScriptMsg::RunnableMsg(DomEvent, task));
+    }
+
+    // https://drafts.csswg.org/resize-observer/#broadcast-active-resize-observations
+    fn broadcast_resize_observations(&self) {
+        self.resize_observations_queued.set(false);
+        let observers = self.resize_observers.borrow().iter()
+                            .map(|observer| observer.root())
+                            .collect::<Vec<_>>();
+        for observer in observers {
+            observer.r().broadcast_active_observations();
+        }
+    }
+
+    pub fn register_mutation_observer(&self, observer: &MutationObserver) {
+        let observer = JS::from_ref(observer);
+        let mut observers = self.mutation_observers.borrow_mut();
+        if !observers.contains(&observer) {
+            observers.push(observer);
+        }
+    }
+
+    pub fn unregister_mutation_observer(&self, observer: &MutationObserver) {
+        let observer = JS::from_ref(observer);
+        self.mutation_observers.borrow_mut().retain(|registered| *registered != observer);
+    }
+
+    pub fn mutation_observers(&self) -> Vec<Root<MutationObserver>> {
+        self.mutation_observers.borrow().iter().map(|observer| observer.root()).collect()
+    }
+
+    /// Enqueues a microtask to notify the mutation observers, unless one is
+    /// already queued.
+    // https://dom.spec.whatwg.org/#queue-a-mutation-observer-compound-microtask
+    pub fn queue_mutation_observer_microtask(&self) {
+        // Step 1.
+        if self.mutation_observer_microtask_queued.get() {
+            return;
+        }
+
+        // Steps 2-3.
+        self.mutation_observer_microtask_queued.set(true);
+        ScriptTask::enqueue_microtask(Microtask::NotifyMutationObservers(NotifyMutationObserversMicrotask {
+            window: Trusted::new(self.get_cx(), self, self.script_chan()),
+            pipeline: self.id,
+        }));
+    }
+
+    // https://dom.spec.whatwg.org/#notify-mutation-observers
+    pub fn notify_mutation_observers(&self) {
+        // Step 1.
+        self.mutation_observer_microtask_queued.set(false);
+
+        // Steps 2-5.
+        for observer in self.mutation_observers() {
+            observer.r().notify();
+        }
+    }
+
+    /// Records that an activation-triggering input event was dispatched to
+    /// this window's document.
+    ///
+    /// https://html.spec.whatwg.org/multipage/#activation-notification
+    pub fn notify_activation(&self) {
+        self.last_activation_timestamp.set(Some(*self.Performance().r().Now()));
+    }
+
+    /// https://html.spec.whatwg.org/multipage/#transient-activation
+    pub fn has_transient_activation(&self) -> bool {
+        is_transiently_activated(self.last_activation_timestamp.get(), *self.Performance().r().Now())
+    }
+
+    /// https://html.spec.whatwg.org/multipage/#consume-user-activation
+    pub fn consume_user_activation(&self) {
+        self.last_activation_timestamp.set(None);
+    }
+
+    /// Runs the idle request callback with the given identifier, unless it has
+    /// already run or been cancelled.
+    ///
+    /// https://w3c.github.io/requestidlecallback/#invoke-idle-callback-timeout-algorithm
+    pub fn invoke_idle_callback(&self, ident: u32, did_timeout: bool) {
+        let (callback, timer) = {
+            let mut callbacks = self.idle_request_callbacks.borrow_mut();
+            match callbacks.iter().position(|&(callback_ident, _, _)| callback_ident == ident) {
+                Some(index) => {
+                    let (_, callback, timer) = callbacks.remove(index);
+                    (callback, timer)
+                },
+                None => return,
+            }
+        };
+
+        // A callback run during an idle period no longer needs its timeout.
+        if !did_timeout {
+            if let Some(timer) = timer {
+                self.ClearTimeout(timer);
+            }
+        }
+
+        // A timed out callback gets no idle time at all.
+        let now = *self.Performance().r().Now();
+        let deadline = if did_timeout { now } else { now + MAX_IDLE_PERIOD_MS };
+        let deadline = IdleDeadline::new(self, deadline, did_timeout);
+        let _ = callback.Call__(deadline.r(), ExceptionHandling::Report);
+    }
+
+    /// Runs the idle request callbacks that were requested before this idle
+    /// period started. Callbacks requested while they run wait for the next
+    /// idle period.
+    ///
+    /// https://w3c.github.io/requestidlecallback/#start-an-idle-period-algorithm
+    pub fn start_idle_period(&self) {
+        let idents: Vec<u32> = self.idle_request_callbacks.borrow().iter()
+                                   .map(|&(ident, _, _)| ident)
+                                   .collect();
+        for ident in idents {
+            self.invoke_idle_callback(ident, false);
+        }
+    }
+
     pub fn steal_resize_event(&self) -> Option<WindowSizeData> {
         let event = self.resize_event.get();
         self.resize_event.set(None);
         event
     }
 
+    pub fn is_online(&self) -> bool {
+        self.online.get()
+    }
+
+    // https://html.spec.whatwg.org/multipage/#dom-navigator-online
+    pub fn set_online(&self, online: bool) {
+        if self.online.get() == online {
+            return;
+        }
+        self.online.set(online);
+
+        let event_type = if online { "online" } else { "offline" };
+        let event = Event::new(GlobalRef::Window(self),
+                               event_type.to_owned(),
+                               EventBubbles::DoesNotBubble,
+                               EventCancelable::NotCancelable);
+        event.r().fire(EventTargetCast::from_ref(self));
+    }
+
     pub fn set_page_clip_rect_with_new_viewport(&self, viewport: Rect<f32>) -> bool {
         let rect = geometry::f32_rect_to_au_rect(viewport.clone());
         self.current_viewport.set(rect);
@@ -1188,8 +1660,19 @@ impl Window {
     }
 
     // https://html.spec.whatwg.org/multipage/#accessing-other-browsing-contexts
-    pub fn IndexedGetter(&self, _index: u32, _found: &mut bool) -> Option<Root<Window>> {
-        None
+    pub fn IndexedGetter(&self, index: u32, found: &mut bool) -> Option<Root<Window>> {
+        let window = self.child_windows().into_iter().nth(index as usize);
+        *found = window.is_some();
+        window
+    }
+
+    // https://html.spec.whatwg.org/multipage/#named-access-on-the-window-object
+    pub fn NamedGetter(&self, name: DOMString, found: &mut bool) -> Option<Root<Window>> {
+        // TODO: the other named objects: embed, form, img and object elements
+        //       with that name, and elements with that id.
+        let window = self.child_frame_named(&name).and_then(|iframe| iframe.r().GetContentWindow());
+        *found = window.is_some();
+        window
     }
 
     pub fn thaw(&self) {
@@ -1241,6 +1724,32 @@ impl Window {
         self.current_state.get() == WindowState::Alive
     }
 
+    /// Returns the windows of the child browsing contexts of this window's
+    /// document, in tree order.
+    fn child_windows(&self) -> Vec<Root<Window>> {
+        let document = self.Document();
+        let root = NodeCast::from_ref(document.r());
+        let windows = root.traverse_preorder()
+                          .filter_map(HTMLIFrameElementCast::to_root)
+                          .filter_map(|iframe| iframe.r().GetContentWindow())
+                          .collect();
+        windows
+    }
+
+    /// Returns the iframe in this window's document whose browsing context
+    /// is named `name`.
+    fn child_frame_named(&self, name: &str) -> Option<Root<HTMLIFrameElement>> {
+        let document = self.Document();
+        let root = NodeCast::from_ref(document.r());
+        let iframe = root.traverse_preorder()
+                         .filter_map(HTMLIFrameElementCast::to_root)
+                         .find(|iframe| {
+                             let element = ElementCast::from_ref(iframe.r());
+                             &*element.get_string_attribute(&atom!("name")) == name
+                         });
+        iframe
+    }
+
     pub fn parent(&self) -> Option<Root<Window>> {
         let browsing_context = self.browsing_context();
         let browsing_context = browsing_context.as_ref().unwrap();
@@ -1287,6 +1796,7 @@ impl Window {
             control_chan: control_chan,
             console: Default::default(),
             crypto: Default::default(),
+            history: Default::default(),
             compositor: compositor,
             page: page,
             navigator: Default::default(),
@@ -1295,9 +1805,19 @@ impl Window {
             devtools_chan: devtools_chan,
             browsing_context: DOMRefCell::new(None),
             performance: Default::default(),
-            navigation_start: time::get_time().sec as u64,
+            navigation_start: milliseconds_since_epoch(),
             navigation_start_precise: time::precise_time_ns() as f64,
             screen: Default::default(),
+            media_query_lists: DOMRefCell::new(vec![]),
+            intersection_observers: DOMRefCell::new(vec![]),
+            intersection_observations_queued: Cell::new(false),
+            resize_observers: DOMRefCell::new(vec![]),
+            resize_observations_queued: Cell::new(false),
+            mutation_observers: DOMRefCell::new(vec![]),
+            mutation_observer_microtask_queued: Cell::new(false),
+            last_activation_timestamp: Cell::new(None),
+            idle_callback_ident: Cell::new(0),
+            idle_request_callbacks: DOMRefCell::new(vec![]),
             session_storage: Default::default(),
             local_storage: Default::default(),
             timers: TimerManager::new(),
@@ -1313,6 +1833,7 @@ impl Window {
             fragment_name: DOMRefCell::new(None),
             last_reflow_id: Cell::new(0),
             resize_event: Cell::new(None),
+            online: Cell::new(true),
             next_subpage_id: Cell::new(SubpageId(0)),
             layout_chan: layout_chan,
             layout_rpc: layout_rpc,
@@ -1386,6 +1907,84 @@ fn debug_reflow_events(goal: &ReflowGoal, query_type: &ReflowQueryType, reason:
     println!("{}", debug_msg);
 }
 
+/// Returns the window whose script called into the DOM, if the caller is a
+/// script running in a window.
+#[allow(unsafe_code)]
+fn incumbent_window(cx: *mut JSContext) -> Option<Root<Window>> {
+    let global = unsafe { GetScriptedCallerGlobal(cx) };
+    if global.is_null() {
+        return None;
+    }
+    match global_object_for_js_object(global) {
+        GlobalRoot::Window(window) => Some(window),
+        GlobalRoot::Worker(_) => None,
+    }
+}
+
+/// The task queued by `postMessage()` to deliver a message to a window.
+struct PostMessageTask {
+    target: Trusted<Window>,
+    source: Option<Trusted<Window>>,
+    /// The serialised origin of the source.
+    origin: DOMString,
+    /// The URL whose origin the target's document must have, or `None` for "*".
+    target_origin: Option<Url>,
+    data: StructuredCloneData,
+}
+
+impl Runnable for PostMessageTask {
+    // https://html.spec.whatwg.org/multipage/#dom-window-postmessage steps 10.1-10.6
+    fn handler(self: Box<PostMessageTask>) {
+        let this = *self;
+        let target = this.target.root();
+        let target = target.r();
+
+        // Step 10.1.
+        if let Some(ref target_origin) = this.target_origin {
+            if !UrlHelper::SameOrigin(target_origin, &target.get_url()) {
+                return;
+            }
+        }
+
+        // Steps 10.2-10.6.
+        let source = this.source.map(|source| source.root());
+        let global = GlobalRef::Window(target);
+        let cx = global.get_cx();
+        let _ar = JSAutoRequest::new(cx);
+        let _ac = JSAutoCompartment::new(cx, target.reflector().get_jsobject().get());
+        let mut message = RootedValue::new(cx, UndefinedValue());
+        this.data.read(global, message.handle_mut());
+
+        let messageevent = MessageEvent::new(global, "message".to_owned(), false, false,
+                                             message.handle(), this.origin, "".to_owned(),
+                                             source.r());
+        let event = EventCast::from_ref(messageevent.r());
+        event.fire(EventTargetCast::from_ref(target));
+    }
+}
+
+struct IntersectionObservationTask {
+    window: Trusted<Window>,
+}
+
+impl Runnable for IntersectionObservationTask {
+    fn handler(self: Box<IntersectionObservationTask>) {
+        let window = self.window.root();
+        window.r().update_intersection_observations();
+    }
+}
+
+struct ResizeObservationTask {
+    window: Trusted<Window>,
+}
+
+impl Runnable for ResizeObservationTask {
+    fn handler(self: Box<ResizeObservationTask>) {
+        let window = self.window.root();
+        window.r().broadcast_resize_observations();
+    }
+}
+
 impl WindowDerived for EventTarget {
     fn is_window(&self) -> bool {
         self.type_id() == &EventTargetTypeId::Window
@@ -28,6 +28,7 @@ use std::cell::Cell;
 use std::default::Default;
 use std::rc::Rc;
 use std::sync::mpsc::Receiver;
+use time;
 use timers::{IsInterval, TimerCallback, TimerId, TimerManager};
 use url::{Url, UrlParser};
 use util::str::DOMString;
@@ -83,6 +84,9 @@ pub struct WorkerGlobalScope {
 
     #[ignore_heap_size_of = "Defined in std"]
     constellation_chan: ConstellationChan,
+
+    /// The value of `time::precise_time_ns()` when this worker was created.
+    time_origin: f64,
 }
 
 impl WorkerGlobalScope {
@@ -109,6 +113,7 @@ impl WorkerGlobalScope {
             from_devtools_receiver: from_devtools_receiver,
             devtools_wants_updates: Cell::new(false),
             constellation_chan: init.constellation_chan,
+            time_origin: time::precise_time_ns() as f64,
         }
     }
 
@@ -132,6 +137,10 @@ impl WorkerGlobalScope {
         self.constellation_chan.clone()
     }
 
+    pub fn time_origin(&self) -> f64 {
+        self.time_origin
+    }
+
     pub fn get_cx(&self) -> *mut JSContext {
         self.runtime.cx()
     }
@@ -330,6 +339,17 @@ impl WorkerGlobalScope {
         }
     }
 
+    /// Runs `callback` after `timeout` milliseconds, through the same timers as
+    /// `setTimeout()`.
+    pub fn schedule_callback(&self, callback: TimerCallback, timeout: i32) -> i32 {
+        self.timers.set_timeout_or_interval(callback,
+                                            vec![],
+                                            timeout,
+                                            IsInterval::NonInterval,
+                                            TimerSource::FromWorker,
+                                            self.script_chan())
+    }
+
     pub fn handle_fire_timer(&self, timer_id: TimerId) {
         self.timers.fire_timer(timer_id, self);
     }
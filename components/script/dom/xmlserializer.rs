@@ -0,0 +1,161 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use dom::bindings::codegen::Bindings::XMLSerializerBinding;
+use dom::bindings::codegen::Bindings::XMLSerializerBinding::XMLSerializerMethods;
+use dom::bindings::codegen::InheritTypes::{CharacterDataCast, DocumentTypeCast};
+use dom::bindings::codegen::Bindings::HTMLTemplateElementBinding::HTMLTemplateElementMethods;
+use dom::bindings::codegen::InheritTypes::{ElementCast, HTMLTemplateElementCast};
+use dom::bindings::codegen::InheritTypes::{NodeCast, ProcessingInstructionCast};
+use dom::bindings::error::Fallible;
+use dom::bindings::global::GlobalRef;
+use dom::bindings::js::Root;
+use dom::bindings::utils::{Reflector, reflect_dom_object};
+use dom::characterdata::CharacterDataTypeId;
+use dom::node::{Node, NodeSiblingIterator, NodeTypeId};
+use html5ever::serialize::TraversalScope;
+use html5ever::serialize::TraversalScope::{ChildrenOnly, IncludeNode};
+use parse::xml::{XmlElement, XmlName, XmlNode, serialize_xml};
+use std::borrow::ToOwned;
+use util::str::DOMString;
+
+#[dom_struct]
+pub struct XMLSerializer {
+    reflector_: Reflector,
+}
+
+impl XMLSerializer {
+    fn new_inherited() -> XMLSerializer {
+        XMLSerializer {
+            reflector_: Reflector::new(),
+        }
+    }
+
+    pub fn new(global: GlobalRef) -> Root<XMLSerializer> {
+        reflect_dom_object(box XMLSerializer::new_inherited(), global,
+                           XMLSerializerBinding::Wrap)
+    }
+
+    pub fn Constructor(global: GlobalRef) -> Fallible<Root<XMLSerializer>> {
+        Ok(XMLSerializer::new(global))
+    }
+}
+
+impl XMLSerializerMethods for XMLSerializer {
+    // https://w3c.github.io/DOM-Parsing/#dom-xmlserializer-serializetostring
+    fn SerializeToString(&self, root: &Node) -> DOMString {
+        serialize_node(root, IncludeNode)
+    }
+}
+
+/// Serializes `node`, or only its children, as XML.
+pub fn serialize_node(node: &Node, traversal_scope: TraversalScope) -> DOMString {
+    let mut nodes = vec![];
+    let mut open = vec![];
+    match traversal_scope {
+        IncludeNode => push_xml_node(node, &mut open, &mut nodes),
+        ChildrenOnly => open.push((xml_children(node), None)),
+    }
+    while let Some(child) = next_child(&mut open, &mut nodes) {
+        push_xml_node(child.r(), &mut open, &mut nodes);
+    }
+    serialize_xml(&nodes)
+}
+
+/// The nodes whose children are being converted, innermost last, with the
+/// children left to convert. Elements are kept here rather than on the call
+/// stack, so that deeply nested trees can't overflow it.
+type OpenNodes = Vec<(NodeSiblingIterator, Option<XmlElement>)>;
+
+fn xml_children(node: &Node) -> NodeSiblingIterator {
+    // https://github.com/w3c/DOM-Parsing/issues/1
+    if let Some(template) = HTMLTemplateElementCast::to_ref(node) {
+        let content = template.Content();
+        return NodeCast::from_ref(content.r()).children();
+    }
+    node.children()
+}
+
+/// Adds `node` to the innermost open element, or to `nodes` if there is none.
+fn append(open: &mut OpenNodes, nodes: &mut Vec<XmlNode>, node: XmlNode) {
+    match open.last_mut() {
+        Some(&mut (_, Some(ref mut parent))) => parent.children.push(node),
+        _ => nodes.push(node),
+    }
+}
+
+/// Returns the next node to convert, closing the open nodes whose children
+/// have all been converted.
+fn next_child(open: &mut OpenNodes, nodes: &mut Vec<XmlNode>) -> Option<Root<Node>> {
+    loop {
+        let child = match open.last_mut() {
+            Some(&mut (ref mut children, _)) => children.next(),
+            None => return None,
+        };
+        if child.is_some() {
+            return child;
+        }
+        if let (_, Some(element)) = open.pop().unwrap() {
+            append(open, nodes, XmlNode::Element(element));
+        }
+    }
+}
+
+/// Converts `node` into the representation `serialize_xml` works on. Documents
+/// and document fragments contribute their children. Elements are opened, and
+/// get their children as the traversal reaches them.
+fn push_xml_node(node: &Node, open: &mut OpenNodes, nodes: &mut Vec<XmlNode>) {
+    let xml_node = match node.type_id() {
+        NodeTypeId::Document | NodeTypeId::DocumentFragment(_) => {
+            open.push((xml_children(node), None));
+            return;
+        },
+        NodeTypeId::DocumentType => {
+            let doctype = DocumentTypeCast::to_ref(node).unwrap();
+            XmlNode::Doctype {
+                name: doctype.name().clone(),
+                public_id: doctype.public_id().clone(),
+                system_id: doctype.system_id().clone(),
+            }
+        },
+        NodeTypeId::Element(_) => {
+            let element = ElementCast::to_ref(node).unwrap();
+            let name = XmlName {
+                namespace: (*element.namespace().0).to_owned(),
+                prefix: element.prefix().clone(),
+                local: (**element.local_name()).to_owned(),
+            };
+            let attributes = element.attrs().iter().map(|attr| {
+                let attr = attr.root();
+                let name = XmlName {
+                    namespace: (*attr.r().namespace().0).to_owned(),
+                    prefix: attr.r().prefix().as_ref().map(|prefix| (**prefix).to_owned()),
+                    local: (**attr.r().local_name()).to_owned(),
+                };
+                (name, (**attr.r().value()).to_owned())
+            }).collect();
+            let element = XmlElement {
+                name: name,
+                attributes: attributes,
+                children: vec![],
+            };
+            open.push((xml_children(node), Some(element)));
+            return;
+        },
+        NodeTypeId::CharacterData(CharacterDataTypeId::Text) => {
+            let data = (*CharacterDataCast::to_ref(node).unwrap().data()).clone();
+            XmlNode::Text(data)
+        },
+        NodeTypeId::CharacterData(CharacterDataTypeId::Comment) => {
+            let data = (*CharacterDataCast::to_ref(node).unwrap().data()).clone();
+            XmlNode::Comment(data)
+        },
+        NodeTypeId::CharacterData(CharacterDataTypeId::ProcessingInstruction) => {
+            let pi = ProcessingInstructionCast::to_ref(node).unwrap();
+            let data = (*CharacterDataCast::from_ref(pi).data()).clone();
+            XmlNode::ProcessingInstruction(pi.target().clone(), data)
+        },
+    };
+    append(open, nodes, xml_node);
+}
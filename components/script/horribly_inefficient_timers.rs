@@ -17,11 +17,13 @@ pub fn oneshot(duration_ms: u32) -> Receiver<()> {
     rx
 }
 
-pub fn periodic(duration_ms: u32) -> Receiver<()> {
+/// Like `oneshot`, but keeps ticking from the same thread, asking `duration_ms`
+/// for the delay before each tick.
+pub fn periodic<F>(mut duration_ms: F) -> Receiver<()> where F: FnMut() -> u32 + Send + 'static {
     let (tx, rx) = channel();
     spawn(move || {
         loop {
-            sleep_ms(duration_ms);
+            sleep_ms(duration_ms());
             if tx.send(()).is_err() {
                 break
             }
@@ -23,6 +23,8 @@ use script_traits::{ConstellationControlMsg, LayoutControlMsg};
 use script_traits::{OpaqueScriptLayoutChannel, StylesheetLoadResponder, UntrustedNodeAddress};
 use selectors::parser::PseudoElement;
 use std::any::Any;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{Receiver, Sender, channel};
 use string_cache::Atom;
 use style::animation::PropertyAnimation;
@@ -35,10 +37,19 @@ pub use dom::node::TrustedNodeAddress;
 /// Asynchronous messages that script can send to layout.
 pub enum Msg {
     /// Adds the given stylesheet to the document.
-    AddStylesheet(Stylesheet, MediaQueryList),
+    AddStylesheet(Arc<Stylesheet>, MediaQueryList),
 
-    /// Adds the given stylesheet to the document.
-    LoadStylesheet(Url, MediaQueryList, PendingAsyncLoad, Box<StylesheetLoadResponder + Send>),
+    /// Removes the stylesheet with the given disabled flag from the document.
+    RemoveStylesheet(Arc<AtomicBool>),
+
+    /// Notifies layout that a stylesheet was enabled or disabled, so that
+    /// styles need to be recomputed on the next reflow.
+    StylesheetsChanged,
+
+    /// Loads the stylesheet at the given URL and adds it to the document. The stylesheet's
+    /// disabled flag is shared with the owner node.
+    LoadStylesheet(Url, MediaQueryList, PendingAsyncLoad, Box<StylesheetLoadResponder + Send>,
+                   Arc<AtomicBool>),
 
     /// Adds a @viewport rule (translated from a <META name="viewport"> element) to the document.
     AddMetaViewport(ViewportRule),
@@ -111,6 +122,8 @@ pub trait LayoutRPC {
     /// Requests the node containing the point of interest
     fn hit_test(&self, node: TrustedNodeAddress, point: Point2D<f32>) -> Result<HitTestResponse, ()>;
     fn mouse_over(&self, node: TrustedNodeAddress, point: Point2D<f32>) -> Result<MouseOverResponse, ()>;
+    /// Requests all the nodes containing the point of interest, topmost first.
+    fn nodes_from_point(&self, point: Point2D<f32>) -> Vec<UntrustedNodeAddress>;
     /// Query layout for the resolved value of a given CSS property
     fn resolved_style(&self) -> ResolvedStyleResponse;
     fn offset_parent(&self) -> OffsetParentResponse;
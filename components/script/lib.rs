@@ -87,13 +87,14 @@ pub mod dom;
 mod horribly_inefficient_timers;
 pub mod layout_interface;
 mod mem;
+pub mod microtask;
 mod network_listener;
 pub mod page;
 pub mod parse;
 #[allow(unsafe_code)]
 pub mod script_task;
 pub mod textinput;
-mod timers;
+pub mod timers;
 mod webdriver_handlers;
 
 use dom::bindings::codegen::RegisterBindings;
@@ -5,6 +5,7 @@
 //! Routines for handling measuring the memory usage of arbitrary DOM nodes.
 
 use dom::bindings::codegen::InheritTypes::*;
+use dom::documentfragment::DocumentFragmentTypeId;
 use dom::element::ElementTypeId;
 use dom::eventtarget::{EventTarget, EventTargetTypeId};
 use dom::htmlelement::HTMLElementTypeId;
@@ -238,14 +239,19 @@ pub fn heap_size_of_eventtarget(target: &EventTarget) -> usize {
         &EventTargetTypeId::Node(NodeTypeId::Element(
         ElementTypeId::HTMLElement(HTMLElementTypeId::HTMLUnknownElement))) =>
             heap_size_of_self_and_children(HTMLUnknownElementCast::to_ref(target).unwrap()),
+        &EventTargetTypeId::AbortSignal => 0,
+        &EventTargetTypeId::Animation => 0,
         &EventTargetTypeId::WebSocket => 0,
         &EventTargetTypeId::Worker => 0,
         &EventTargetTypeId::FileReader => 0,
+        &EventTargetTypeId::MediaQueryList => 0,
         &EventTargetTypeId::WorkerGlobalScope(_) => 0,
         &EventTargetTypeId::XMLHttpRequestEventTarget(_) => 0,
         &EventTargetTypeId::Node(NodeTypeId::DocumentType) =>
             heap_size_of_self_and_children(DocumentTypeCast::to_ref(target).unwrap()),
-        &EventTargetTypeId::Node(NodeTypeId::DocumentFragment) =>
+        &EventTargetTypeId::Node(NodeTypeId::DocumentFragment(DocumentFragmentTypeId::DocumentFragment)) =>
             heap_size_of_self_and_children(DocumentFragmentCast::to_ref(target).unwrap()),
+        &EventTargetTypeId::Node(NodeTypeId::DocumentFragment(DocumentFragmentTypeId::ShadowRoot)) =>
+            heap_size_of_self_and_children(ShadowRootCast::to_ref(target).unwrap()),
     }
 }
@@ -0,0 +1,101 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Implementation of [microtasks](https://html.spec.whatwg.org/multipage/#microtask) and
+//! microtask queues. The event loop owning a queue is responsible for enqueuing
+//! microtasks and for performing checkpoints at the appropriate times.
+
+use dom::bindings::callback::ExceptionHandling;
+use dom::bindings::cell::DOMRefCell;
+use dom::bindings::codegen::Bindings::VoidFunctionBinding::VoidFunction;
+use dom::bindings::refcounted::Trusted;
+use dom::window::Window;
+use msg::constellation_msg::PipelineId;
+use std::cell::Cell;
+use std::mem;
+use std::rc::Rc;
+
+/// A queue of microtasks, run in the order they were enqueued.
+#[derive(JSTraceable)]
+#[privatize]
+pub struct MicrotaskQueue {
+    microtask_queue: DOMRefCell<Vec<Microtask>>,
+    performing_a_microtask_checkpoint: Cell<bool>,
+}
+
+#[derive(JSTraceable)]
+pub enum Microtask {
+    User(UserMicrotask),
+    NotifyMutationObservers(NotifyMutationObserversMicrotask),
+}
+
+/// A microtask that comes from a `queueMicrotask()` call.
+#[derive(JSTraceable)]
+pub struct UserMicrotask {
+    pub callback: Rc<VoidFunction>,
+    /// The pipeline of the window that queued the microtask.
+    pub pipeline: PipelineId,
+}
+
+/// A microtask that delivers the records queued for a window's mutation
+/// observers.
+/// https://dom.spec.whatwg.org/#queue-a-mutation-observer-compound-microtask
+#[derive(JSTraceable)]
+pub struct NotifyMutationObserversMicrotask {
+    pub window: Trusted<Window>,
+    pub pipeline: PipelineId,
+}
+
+impl MicrotaskQueue {
+    pub fn new() -> MicrotaskQueue {
+        MicrotaskQueue {
+            microtask_queue: DOMRefCell::new(vec![]),
+            performing_a_microtask_checkpoint: Cell::new(false),
+        }
+    }
+
+    /// https://html.spec.whatwg.org/multipage/#enqueue-a-microtask
+    pub fn enqueue(&self, job: Microtask) {
+        self.microtask_queue.borrow_mut().push(job);
+    }
+
+    /// Runs every queued microtask, including those queued by the microtasks
+    /// themselves. Microtasks belonging to a pipeline for which `is_alive`
+    /// returns false are dropped.
+    ///
+    /// https://html.spec.whatwg.org/multipage/#perform-a-microtask-checkpoint
+    pub fn checkpoint<F>(&self, is_alive: F) where F: Fn(PipelineId) -> bool {
+        // Step 1.
+        if self.performing_a_microtask_checkpoint.get() {
+            return;
+        }
+
+        // Step 2.
+        self.performing_a_microtask_checkpoint.set(true);
+
+        // Steps 3-5.
+        while !self.microtask_queue.borrow().is_empty() {
+            let pending = mem::replace(&mut *self.microtask_queue.borrow_mut(), vec![]);
+            for job in pending {
+                match job {
+                    Microtask::User(job) => {
+                        if is_alive(job.pipeline) {
+                            // An exception is reported, and does not stop the
+                            // remaining microtasks from running.
+                            let _ = job.callback.Call__(ExceptionHandling::Report);
+                        }
+                    }
+                    Microtask::NotifyMutationObservers(job) => {
+                        if is_alive(job.pipeline) {
+                            job.window.root().r().notify_mutation_observers();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Step 9.
+        self.performing_a_microtask_checkpoint.set(false);
+    }
+}
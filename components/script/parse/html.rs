@@ -160,7 +160,11 @@ impl<'a> TreeSink for servohtmlparser::Sink {
         let node: Root<Node> = node.root();
         let script: Option<&HTMLScriptElement> = HTMLScriptElementCast::to_ref(node.r());
         if let Some(script) = script {
-            return script.prepare();
+            let parser = self.document.root().r().get_current_parser();
+            parser.r().map(|parser| parser.enter_script());
+            let state = script.prepare();
+            parser.r().map(|parser| parser.exit_script());
+            return state;
         }
         NextParserState::Continue
     }
@@ -218,7 +222,8 @@ impl<'a> Serializable for &'a Node {
                 Ok(())
             },
 
-            (ChildrenOnly, NodeTypeId::Document) => {
+            (ChildrenOnly, NodeTypeId::Document) |
+            (ChildrenOnly, NodeTypeId::DocumentFragment(_)) => {
                 for handle in node.children() {
                     try!(handle.r().serialize(serializer, IncludeNode));
                 }
@@ -248,7 +253,7 @@ impl<'a> Serializable for &'a Node {
                 serializer.write_processing_instruction(&pi.target(), &data)
             },
 
-            (IncludeNode, NodeTypeId::DocumentFragment) => Ok(()),
+            (IncludeNode, NodeTypeId::DocumentFragment(_)) => Ok(()),
 
             (IncludeNode, NodeTypeId::Document) => panic!("Can't serialize Document node itself"),
         }
@@ -0,0 +1,960 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! A small non-validating XML parser.
+//!
+//! It understands the XML declaration, doctypes (whose internal subset is
+//! skipped), namespaced elements and attributes, character and predefined
+//! entity references, CDATA sections, comments and processing instructions.
+//! Anything that is not well-formed is reported as an `XmlError`.
+//!
+//! `serialize_xml` turns the same node representation back into markup.
+
+use dom::bindings::codegen::Bindings::NodeBinding::NodeMethods;
+use dom::bindings::codegen::InheritTypes::NodeCast;
+use dom::bindings::js::Root;
+use dom::bindings::utils::{XMLName, xml_name_type};
+use dom::comment::Comment;
+use dom::document::Document;
+use dom::documenttype::DocumentType;
+use dom::element::{Element, ElementCreator};
+use dom::node::Node;
+use dom::processinginstruction::ProcessingInstruction;
+use dom::text::Text;
+use std::ascii::AsciiExt;
+use std::borrow::ToOwned;
+use std::mem;
+use string_cache::{Atom, Namespace, QualName};
+
+const XML_NAMESPACE: &'static str = "http://www.w3.org/XML/1998/namespace";
+const XMLNS_NAMESPACE: &'static str = "http://www.w3.org/2000/xmlns/";
+const XHTML_NAMESPACE: &'static str = "http://www.w3.org/1999/xhtml";
+const PARSERERROR_NAMESPACE: &'static str = "http://www.mozilla.org/newlayout/xml/parsererror.xml";
+
+/// A node of a parsed XML document.
+#[derive(Debug, PartialEq)]
+pub enum XmlNode {
+    Doctype { name: String, public_id: String, system_id: String },
+    Element(XmlElement),
+    Text(String),
+    Comment(String),
+    ProcessingInstruction(String, String),
+}
+
+/// A namespace-resolved element or attribute name.
+#[derive(Debug, PartialEq)]
+pub struct XmlName {
+    pub namespace: String,
+    pub prefix: Option<String>,
+    pub local: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct XmlElement {
+    pub name: XmlName,
+    pub attributes: Vec<(XmlName, String)>,
+    pub children: Vec<XmlNode>,
+}
+
+impl Drop for XmlElement {
+    /// Drops the descendants one at a time, so that dropping a deeply nested
+    /// tree doesn't recurse once per level.
+    fn drop(&mut self) {
+        let mut descendants = mem::replace(&mut self.children, vec![]);
+        while let Some(node) = descendants.pop() {
+            if let XmlNode::Element(mut element) = node {
+                descendants.extend(mem::replace(&mut element.children, vec![]));
+            }
+        }
+    }
+}
+
+/// A well-formedness error, with the 1-based line and column it was found at.
+#[derive(Debug, PartialEq)]
+pub struct XmlError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Parses a complete XML document into its top-level nodes.
+pub fn parse_xml(input: &str) -> Result<Vec<XmlNode>, XmlError> {
+    // https://www.w3.org/TR/xml/#sec-line-ends
+    let input = input.replace("\r\n", "\n").replace("\r", "\n");
+    let mut parser = XmlParser {
+        input: &input,
+        pos: 0,
+        scopes: vec![],
+    };
+    parser.document()
+}
+
+/// Parses `input` into the empty `document`. If `input` is not well-formed,
+/// the document instead gets a `parsererror` root element describing why.
+pub fn parse_xml_into(document: &Document, input: &str) {
+    let document_node = NodeCast::from_ref(document);
+    match parse_xml(input) {
+        Ok(nodes) => append_xml_nodes(document, document_node, &nodes),
+        Err(error) => {
+            let name = QualName {
+                ns: Namespace(Atom::from_slice(PARSERERROR_NAMESPACE)),
+                local: Atom::from_slice("parsererror"),
+            };
+            let parsererror = Element::create(name, None, document, ElementCreator::ParserCreated);
+            let parsererror = NodeCast::from_ref(parsererror.r());
+            let message = format!("XML Parsing Error: {}\nLine Number {}, Column {}:",
+                                  error.message, error.line, error.column);
+            let text = Text::new(message, document);
+            assert!(parsererror.AppendChild(NodeCast::from_ref(text.r())).is_ok());
+            assert!(document_node.AppendChild(parsererror).is_ok());
+        },
+    }
+}
+
+/// Appends `nodes` and their descendants to `parent`, walking the tree with an
+/// explicit stack rather than recursing.
+fn append_xml_nodes(document: &Document, parent: &Node, nodes: &[XmlNode]) {
+    let mut stack = vec![(Root::from_ref(parent), nodes.iter())];
+    loop {
+        let node = match stack.last_mut() {
+            Some(&mut (_, ref mut children)) => children.next(),
+            None => break,
+        };
+        let node = match node {
+            Some(node) => node,
+            None => {
+                stack.pop();
+                continue;
+            },
+        };
+        let child = create_xml_node(document, node);
+        assert!(stack.last().unwrap().0.r().AppendChild(child.r()).is_ok());
+        if let XmlNode::Element(ref element) = *node {
+            if !element.children.is_empty() {
+                stack.push((child, element.children.iter()));
+            }
+        }
+    }
+}
+
+/// Creates the DOM node for `node`, without its children.
+fn create_xml_node(document: &Document, node: &XmlNode) -> Root<Node> {
+    fn qualname(name: &XmlName) -> QualName {
+        QualName {
+            ns: Namespace(Atom::from_slice(&name.namespace)),
+            local: Atom::from_slice(&name.local),
+        }
+    }
+
+    fn prefix(name: &XmlName) -> Option<Atom> {
+        name.prefix.as_ref().map(|prefix| Atom::from_slice(prefix))
+    }
+
+    match *node {
+        XmlNode::Doctype { ref name, ref public_id, ref system_id } => {
+            let doctype = DocumentType::new(name.clone(), Some(public_id.clone()),
+                                            Some(system_id.clone()), document);
+            NodeCast::from_root(doctype)
+        },
+        XmlNode::Element(ref element) => {
+            let elem = Element::create(qualname(&element.name), prefix(&element.name),
+                                       document, ElementCreator::ParserCreated);
+            for &(ref name, ref value) in &element.attributes {
+                elem.r().set_attribute_from_parser(qualname(name), value.clone(), prefix(name));
+            }
+            NodeCast::from_root(elem)
+        },
+        XmlNode::Text(ref data) => NodeCast::from_root(Text::new(data.clone(), document)),
+        XmlNode::Comment(ref data) => NodeCast::from_root(Comment::new(data.clone(), document)),
+        XmlNode::ProcessingInstruction(ref target, ref data) => {
+            let pi = ProcessingInstruction::new(target.clone(), data.clone(), document);
+            NodeCast::from_root(pi)
+        },
+    }
+}
+
+// https://www.w3.org/TR/xml/#NT-S
+fn is_xml_whitespace(c: char) -> bool {
+    match c {
+        ' ' | '\t' | '\n' | '\r' => true,
+        _ => false,
+    }
+}
+
+// https://www.w3.org/TR/xml/#NT-Char
+fn is_xml_char(c: char) -> bool {
+    match c {
+        '\u{9}' | '\u{A}' | '\u{D}' | '\u{20}'...'\u{D7FF}' | '\u{E000}'...'\u{FFFD}' |
+        '\u{10000}'...'\u{10FFFF}' => true,
+        _ => false,
+    }
+}
+
+struct XmlParser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The namespace declarations of the open elements, innermost last.
+    scopes: Vec<Vec<(Option<String>, String)>>,
+}
+
+impl<'a> XmlParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), XmlError> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected \"{}\"", s)))
+        }
+    }
+
+    /// Skips whitespace, returning whether there was any.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !is_xml_whitespace(c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        self.pos != start
+    }
+
+    fn error(&self, message: &str) -> XmlError {
+        let consumed = &self.input[..self.pos];
+        XmlError {
+            message: message.to_owned(),
+            line: consumed.matches('\n').count() + 1,
+            column: consumed.rsplit('\n').next().unwrap().chars().count() + 1,
+        }
+    }
+
+    /// Consumes the input up to and including `terminator`, returning what
+    /// came before it.
+    fn take_until(&mut self, terminator: &str) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        match rest.find(terminator) {
+            Some(index) => {
+                self.pos += index + terminator.len();
+                Ok(&rest[..index])
+            },
+            None => Err(self.error(&format!("unclosed token, expected \"{}\"", terminator))),
+        }
+    }
+
+    // https://www.w3.org/TR/xml/#NT-Name
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let end = rest.find(|c: char| is_xml_whitespace(c) || "/>=?<\"'&;[".contains(c))
+                      .unwrap_or(rest.len());
+        let name = &rest[..end];
+        if xml_name_type(name) == XMLName::InvalidXMLName {
+            return Err(self.error("not well-formed"));
+        }
+        self.pos += end;
+        Ok(name)
+    }
+
+    fn quoted(&mut self) -> Result<&'a str, XmlError> {
+        if self.eat("\"") {
+            self.take_until("\"")
+        } else if self.eat("'") {
+            self.take_until("'")
+        } else {
+            Err(self.error("expected a quoted string"))
+        }
+    }
+
+    // https://www.w3.org/TR/xml/#NT-document
+    fn document(&mut self) -> Result<Vec<XmlNode>, XmlError> {
+        // https://www.w3.org/TR/xml/#NT-XMLDecl
+        if self.rest().starts_with("<?xml") &&
+           self.rest()[5..].starts_with(|c: char| is_xml_whitespace(c) || c == '?') {
+            try!(self.take_until("?>"));
+        }
+
+        let mut nodes = vec![];
+        let mut seen_doctype = false;
+        let mut seen_root = false;
+        loop {
+            self.skip_whitespace();
+            if self.at_end() {
+                break;
+            }
+            if self.eat("<!--") {
+                nodes.push(try!(self.comment()));
+            } else if self.eat("<?") {
+                nodes.push(try!(self.processing_instruction()));
+            } else if self.eat("<!DOCTYPE") {
+                if seen_doctype || seen_root {
+                    return Err(self.error("misplaced doctype"));
+                }
+                seen_doctype = true;
+                nodes.push(try!(self.doctype()));
+            } else if self.eat("<") {
+                if seen_root {
+                    return Err(self.error("junk after document element"));
+                }
+                seen_root = true;
+                nodes.push(XmlNode::Element(try!(self.element())));
+            } else {
+                return Err(self.error("syntax error"));
+            }
+        }
+
+        if !seen_root {
+            return Err(self.error("no root element found"));
+        }
+        Ok(nodes)
+    }
+
+    // https://www.w3.org/TR/xml/#NT-Comment
+    fn comment(&mut self) -> Result<XmlNode, XmlError> {
+        let data = try!(self.take_until("-->"));
+        if data.contains("--") || data.ends_with("-") {
+            return Err(self.error("comment contains \"--\""));
+        }
+        Ok(XmlNode::Comment(data.to_owned()))
+    }
+
+    // https://www.w3.org/TR/xml/#NT-PI
+    fn processing_instruction(&mut self) -> Result<XmlNode, XmlError> {
+        let target = try!(self.name());
+        if target.eq_ignore_ascii_case("xml") {
+            return Err(self.error("XML or text declaration not at start of entity"));
+        }
+        let had_whitespace = self.skip_whitespace();
+        let data = try!(self.take_until("?>"));
+        if !had_whitespace && !data.is_empty() {
+            return Err(self.error("not well-formed"));
+        }
+        Ok(XmlNode::ProcessingInstruction(target.to_owned(), data.to_owned()))
+    }
+
+    // https://www.w3.org/TR/xml/#NT-doctypedecl
+    fn doctype(&mut self) -> Result<XmlNode, XmlError> {
+        if !self.skip_whitespace() {
+            return Err(self.error("not well-formed"));
+        }
+        let name = try!(self.name()).to_owned();
+        self.skip_whitespace();
+        let mut public_id = "";
+        let mut system_id = "";
+        if self.eat("PUBLIC") {
+            self.skip_whitespace();
+            public_id = try!(self.quoted());
+            self.skip_whitespace();
+            system_id = try!(self.quoted());
+        } else if self.eat("SYSTEM") {
+            self.skip_whitespace();
+            system_id = try!(self.quoted());
+        }
+        self.skip_whitespace();
+        // The internal subset is skipped rather than interpreted.
+        if self.eat("[") {
+            try!(self.internal_subset());
+            self.skip_whitespace();
+        }
+        try!(self.expect(">"));
+        Ok(XmlNode::Doctype {
+            name: name,
+            public_id: public_id.to_owned(),
+            system_id: system_id.to_owned(),
+        })
+    }
+
+    // https://www.w3.org/TR/xml/#NT-intSubset
+    /// Skips the internal subset, up to and including its closing `]`. Literals,
+    /// comments and processing instructions are skipped whole, since they may
+    /// contain a `]` of their own.
+    fn internal_subset(&mut self) -> Result<(), XmlError> {
+        loop {
+            if self.eat("<!--") {
+                try!(self.comment());
+                continue;
+            }
+            if self.eat("<?") {
+                try!(self.take_until("?>"));
+                continue;
+            }
+            match self.peek() {
+                None => return Err(self.error("unclosed token, expected \"]\"")),
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(());
+                },
+                Some('"') => {
+                    self.pos += 1;
+                    try!(self.take_until("\""));
+                },
+                Some('\'') => {
+                    self.pos += 1;
+                    try!(self.take_until("'"));
+                },
+                Some(c) => self.pos += c.len_utf8(),
+            }
+        }
+    }
+
+    // https://www.w3.org/TR/xml/#NT-element
+    // The opening `<` has already been consumed.
+    fn element(&mut self) -> Result<XmlElement, XmlError> {
+        let (qualified_name, element, empty) = try!(self.start_tag());
+        if empty {
+            return Ok(element);
+        }
+
+        // The open elements, innermost last, with the names their end tags
+        // must match. Nested elements are kept here rather than on the call
+        // stack, so that deeply nested documents can't overflow it.
+        let mut open = vec![(qualified_name, element)];
+        let mut text = String::new();
+        loop {
+            // https://www.w3.org/TR/xml/#NT-content
+            if self.at_end() {
+                return Err(self.error("no element found"));
+            }
+            if self.eat("<![CDATA[") {
+                text.push_str(try!(self.take_until("]]>")));
+                continue;
+            }
+            if self.eat("&") {
+                text.push(try!(self.reference()));
+                continue;
+            }
+            if !self.rest().starts_with("<") {
+                let rest = self.rest();
+                let end = rest.find(|c: char| c == '<' || c == '&').unwrap_or(rest.len());
+                let chunk = &rest[..end];
+                if chunk.contains("]]>") {
+                    return Err(self.error("not well-formed"));
+                }
+                text.push_str(chunk);
+                self.pos += end;
+                continue;
+            }
+
+            if !text.is_empty() {
+                let text = XmlNode::Text(mem::replace(&mut text, String::new()));
+                open.last_mut().unwrap().1.children.push(text);
+            }
+            if self.eat("</") {
+                // End tag.
+                let end_name = try!(self.name());
+                if end_name != open.last().unwrap().0 {
+                    return Err(self.error("mismatched tag"));
+                }
+                self.skip_whitespace();
+                try!(self.expect(">"));
+                self.scopes.pop();
+                let (_, element) = open.pop().unwrap();
+                match open.last_mut() {
+                    Some(&mut (_, ref mut parent)) => parent.children.push(XmlNode::Element(element)),
+                    None => return Ok(element),
+                }
+            } else if self.eat("<!--") {
+                let comment = try!(self.comment());
+                open.last_mut().unwrap().1.children.push(comment);
+            } else if self.eat("<?") {
+                let pi = try!(self.processing_instruction());
+                open.last_mut().unwrap().1.children.push(pi);
+            } else {
+                self.pos += 1;
+                let (qualified_name, element, empty) = try!(self.start_tag());
+                if empty {
+                    open.last_mut().unwrap().1.children.push(XmlNode::Element(element));
+                } else {
+                    open.push((qualified_name, element));
+                }
+            }
+        }
+    }
+
+    // https://www.w3.org/TR/xml/#NT-STag
+    /// Parses a start tag or empty-element tag into an element without children,
+    /// returning its qualified name and whether it was an empty-element tag.
+    /// Unless it was, the element's namespace declarations stay in scope until
+    /// the caller reaches its end tag.
+    fn start_tag(&mut self) -> Result<(&'a str, XmlElement, bool), XmlError> {
+        let qualified_name = try!(self.name());
+        let mut raw_attributes: Vec<(&'a str, String)> = vec![];
+        let mut empty = false;
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            if self.eat("/>") {
+                empty = true;
+                break;
+            }
+            if self.eat(">") {
+                break;
+            }
+            if !had_whitespace {
+                return Err(self.error("not well-formed"));
+            }
+            let name = try!(self.name());
+            if raw_attributes.iter().any(|&(other, _)| other == name) {
+                return Err(self.error("duplicate attribute"));
+            }
+            self.skip_whitespace();
+            try!(self.expect("="));
+            self.skip_whitespace();
+            let value = try!(self.attribute_value());
+            raw_attributes.push((name, value));
+        }
+
+        // Namespace declarations apply to the element's own name and attributes.
+        // https://www.w3.org/TR/xml-names/#ns-decl
+        let mut scope = vec![];
+        for &(name, ref value) in &raw_attributes {
+            if name == "xmlns" {
+                if value == XML_NAMESPACE || value == XMLNS_NAMESPACE {
+                    return Err(self.error("reserved namespace"));
+                }
+                scope.push((None, value.clone()));
+            } else if name.starts_with("xmlns:") {
+                let prefix = &name["xmlns:".len()..];
+                if value.is_empty() || prefix == "xmlns" || value == XMLNS_NAMESPACE ||
+                   (prefix == "xml") != (value == XML_NAMESPACE) {
+                    return Err(self.error("reserved prefix"));
+                }
+                scope.push((Some(prefix.to_owned()), value.clone()));
+            }
+        }
+        self.scopes.push(scope);
+
+        let name = try!(self.resolve(qualified_name, true));
+        let mut attributes: Vec<(XmlName, String)> = vec![];
+        for (raw_name, value) in raw_attributes {
+            let attr_name = if raw_name == "xmlns" || raw_name.starts_with("xmlns:") {
+                let (prefix, local) = split_qualified_name(raw_name);
+                XmlName {
+                    namespace: XMLNS_NAMESPACE.to_owned(),
+                    prefix: prefix.map(|prefix| prefix.to_owned()),
+                    local: local.to_owned(),
+                }
+            } else {
+                try!(self.resolve(raw_name, false))
+            };
+            if attributes.iter().any(|&(ref other, _)| {
+                other.namespace == attr_name.namespace && other.local == attr_name.local
+            }) {
+                return Err(self.error("duplicate attribute"));
+            }
+            attributes.push((attr_name, value));
+        }
+
+        if empty {
+            self.scopes.pop();
+        }
+        let element = XmlElement {
+            name: name,
+            attributes: attributes,
+            children: vec![],
+        };
+        Ok((qualified_name, element, empty))
+    }
+
+    // https://www.w3.org/TR/xml/#NT-AttValue
+    fn attribute_value(&mut self) -> Result<String, XmlError> {
+        let quote = match self.peek() {
+            Some('"') => '"',
+            Some('\'') => '\'',
+            _ => return Err(self.error("expected a quoted attribute value")),
+        };
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unclosed token")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(value);
+                },
+                Some('<') => return Err(self.error("not well-formed")),
+                Some('&') => {
+                    self.pos += 1;
+                    value.push(try!(self.reference()));
+                },
+                // https://www.w3.org/TR/xml/#AVNormalize
+                Some(c) if is_xml_whitespace(c) => {
+                    self.pos += 1;
+                    value.push(' ');
+                },
+                Some(c) => {
+                    self.pos += c.len_utf8();
+                    value.push(c);
+                },
+            }
+        }
+    }
+
+    // https://www.w3.org/TR/xml/#NT-Reference
+    // The opening `&` has already been consumed.
+    fn reference(&mut self) -> Result<char, XmlError> {
+        let name = try!(self.take_until(";"));
+        let c = match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "apos" => Some('\''),
+            "quot" => Some('"'),
+            _ if name.starts_with("#x") => {
+                u32::from_str_radix(&name[2..], 16).ok().and_then(::std::char::from_u32)
+            },
+            _ if name.starts_with("#") => {
+                name[1..].parse::<u32>().ok().and_then(::std::char::from_u32)
+            },
+            _ => None,
+        };
+        match c {
+            // https://www.w3.org/TR/xml/#wf-Legalchar
+            Some(c) if !is_xml_char(c) => Err(self.error("reference to invalid character number")),
+            Some(c) => Ok(c),
+            None => Err(self.error("undefined entity")),
+        }
+    }
+
+    // https://www.w3.org/TR/xml-names/#scoping-defaulting
+    fn resolve(&self, qualified_name: &str, is_element: bool) -> Result<XmlName, XmlError> {
+        if xml_name_type(qualified_name) != XMLName::QName {
+            return Err(self.error("not well-formed"));
+        }
+        let (prefix, local) = split_qualified_name(qualified_name);
+        let namespace = match prefix {
+            Some("xml") => XML_NAMESPACE.to_owned(),
+            Some(prefix) => match self.lookup(Some(prefix)) {
+                Some(namespace) => namespace,
+                None => return Err(self.error("unbound prefix")),
+            },
+            None if is_element => self.lookup(None).unwrap_or(String::new()),
+            None => String::new(),
+        };
+        Ok(XmlName {
+            namespace: namespace,
+            prefix: prefix.map(|prefix| prefix.to_owned()),
+            local: local.to_owned(),
+        })
+    }
+
+    fn lookup(&self, prefix: Option<&str>) -> Option<String> {
+        for scope in self.scopes.iter().rev() {
+            for &(ref declared, ref namespace) in scope.iter().rev() {
+                if declared.as_ref().map(|declared| &**declared) == prefix {
+                    return Some(namespace.clone());
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Serializes `nodes` as XML, declaring whatever namespace prefixes are
+/// needed for the output to be namespace-well-formed.
+// https://w3c.github.io/DOM-Parsing/#dfn-xml-serialization
+pub fn serialize_xml(nodes: &[XmlNode]) -> String {
+    let mut serializer = XmlSerializer {
+        output: String::new(),
+        scopes: vec![vec![(Some("xml".to_owned()), XML_NAMESPACE.to_owned())]],
+        prefix_index: 1,
+    };
+    serializer.nodes(nodes);
+    serializer.output
+}
+
+// https://html.spec.whatwg.org/multipage/#void-elements
+fn is_void_element(local_name: &str) -> bool {
+    match local_name {
+        "area" | "base" | "basefont" | "bgsound" | "br" | "col" | "embed" | "frame" | "hr" |
+        "img" | "input" | "keygen" | "link" | "menuitem" | "meta" | "param" | "source" |
+        "track" | "wbr" => true,
+        _ => false,
+    }
+}
+
+fn escape(output: &mut String, text: &str, in_attribute: bool) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' if in_attribute => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+}
+
+struct XmlSerializer {
+    output: String,
+    /// The namespace prefixes in scope for each open element, innermost last.
+    /// A `None` prefix records the default namespace.
+    scopes: Vec<Vec<(Option<String>, String)>>,
+    /// Used to generate `ns1`, `ns2`, ... prefixes.
+    prefix_index: usize,
+}
+
+impl XmlSerializer {
+    fn nodes(&mut self, nodes: &[XmlNode]) {
+        // The open elements, innermost last, with their children left to write
+        // and the name their end tag is written with. Elements are kept here
+        // rather than on the call stack, so that deeply nested trees can't
+        // overflow it.
+        let mut open = vec![(nodes.iter(), None)];
+        loop {
+            let node = match open.last_mut() {
+                Some(&mut (ref mut children, _)) => children.next(),
+                None => break,
+            };
+            match node {
+                Some(&XmlNode::Element(ref element)) => {
+                    if let Some(qualified_name) = self.start_tag(element) {
+                        open.push((element.children.iter(), Some(qualified_name)));
+                    }
+                },
+                Some(node) => self.leaf(node),
+                None => {
+                    if let (_, Some(qualified_name)) = open.pop().unwrap() {
+                        self.output.push_str("</");
+                        self.output.push_str(&qualified_name);
+                        self.output.push('>');
+                        self.scopes.pop();
+                    }
+                },
+            }
+        }
+    }
+
+    /// Writes a node other than an element.
+    fn leaf(&mut self, node: &XmlNode) {
+        match *node {
+            // https://w3c.github.io/DOM-Parsing/#xml-serializing-a-documenttype-node
+            XmlNode::Doctype { ref name, ref public_id, ref system_id } => {
+                self.output.push_str("<!DOCTYPE ");
+                self.output.push_str(name);
+                if !public_id.is_empty() {
+                    self.output.push_str(" PUBLIC \"");
+                    self.output.push_str(public_id);
+                    self.output.push_str("\"");
+                } else if !system_id.is_empty() {
+                    self.output.push_str(" SYSTEM");
+                }
+                if !system_id.is_empty() {
+                    self.output.push_str(" \"");
+                    self.output.push_str(system_id);
+                    self.output.push_str("\"");
+                }
+                self.output.push_str(">");
+            },
+            XmlNode::Element(_) => unreachable!("elements are written by nodes()"),
+            XmlNode::Text(ref data) => escape(&mut self.output, data, false),
+            XmlNode::Comment(ref data) => {
+                self.output.push_str("<!--");
+                self.output.push_str(data);
+                self.output.push_str("-->");
+            },
+            XmlNode::ProcessingInstruction(ref target, ref data) => {
+                self.output.push_str("<?");
+                self.output.push_str(target);
+                self.output.push_str(" ");
+                self.output.push_str(data);
+                self.output.push_str("?>");
+            },
+        }
+    }
+
+    // https://w3c.github.io/DOM-Parsing/#xml-serializing-an-element-node
+    /// Writes the start tag of `element`. If the element has children, returns
+    /// the name to write its end tag with, and leaves its namespace scope open
+    /// until then. Otherwise the element is closed straight away.
+    fn start_tag(&mut self, element: &XmlElement) -> Option<String> {
+        // The element's own namespace declarations are in scope for its name.
+        let mut scope = vec![];
+        for &(ref name, ref value) in &element.attributes {
+            if name.namespace == XMLNS_NAMESPACE {
+                let prefix = if name.prefix.is_none() { None } else { Some(name.local.clone()) };
+                scope.push((prefix, value.clone()));
+            }
+        }
+        let explicit_declarations = scope.len();
+        self.scopes.push(scope);
+
+        let mut declarations = String::new();
+        let qualified_name = self.qualified_name(&element.name, true, &mut declarations);
+
+        // If the element's name needed its own default namespace declaration,
+        // that replaces any `xmlns` attribute it has.
+        // https://w3c.github.io/DOM-Parsing/#dfn-ignore-namespace-definition-attribute
+        let ignore_default_declaration =
+            self.scopes.last().unwrap()[explicit_declarations..].iter()
+                .any(|&(ref prefix, _)| prefix.is_none());
+
+        let mut attributes = String::new();
+        for &(ref name, ref value) in &element.attributes {
+            if ignore_default_declaration && name.namespace == XMLNS_NAMESPACE &&
+               name.prefix.is_none() {
+                continue;
+            }
+            let qualified_name = self.qualified_name(name, false, &mut attributes);
+            attributes.push(' ');
+            attributes.push_str(&qualified_name);
+            attributes.push_str("=\"");
+            escape(&mut attributes, value, true);
+            attributes.push('"');
+        }
+
+        self.output.push('<');
+        self.output.push_str(&qualified_name);
+        self.output.push_str(&declarations);
+        self.output.push_str(&attributes);
+
+        if !element.children.is_empty() {
+            self.output.push('>');
+            return Some(qualified_name);
+        }
+
+        if element.name.namespace != XHTML_NAMESPACE {
+            self.output.push_str("/>");
+        } else if is_void_element(&element.name.local) {
+            self.output.push_str(" />");
+        } else {
+            self.output.push_str("></");
+            self.output.push_str(&qualified_name);
+            self.output.push('>');
+        }
+        self.scopes.pop();
+        None
+    }
+
+    /// Works out how to write `name`, appending any namespace declarations
+    /// that requires to `declarations`.
+    fn qualified_name(&mut self, name: &XmlName, is_element: bool,
+                      declarations: &mut String) -> String {
+        let namespace = &*name.namespace;
+        let prefix = name.prefix.as_ref().map(|prefix| &**prefix);
+        if namespace == XML_NAMESPACE {
+            return format!("xml:{}", name.local);
+        }
+        if namespace == XMLNS_NAMESPACE {
+            return match prefix {
+                None => name.local.clone(),
+                Some(_) => format!("xmlns:{}", name.local),
+            };
+        }
+        if namespace.is_empty() {
+            if is_element && !self.lookup(None).unwrap_or("").is_empty() {
+                self.declare(None, namespace, declarations);
+            }
+            return name.local.clone();
+        }
+        if prefix.is_some() && self.lookup(prefix) == Some(namespace) {
+            return format!("{}:{}", prefix.unwrap(), name.local);
+        }
+        if is_element && prefix.is_none() {
+            if self.lookup(None) == Some(namespace) {
+                return name.local.clone();
+            }
+            if !self.declared_here(None) {
+                self.declare(None, namespace, declarations);
+                return name.local.clone();
+            }
+        }
+
+        // Prefer a prefix that is already bound to the namespace, then the
+        // name's own prefix, and only then make one up.
+        let prefix = match self.prefix_for(namespace) {
+            Some(prefix) => prefix,
+            None => {
+                let prefix = match prefix {
+                    Some(prefix) if prefix != "xml" && prefix != "xmlns" &&
+                                    !self.declared_here(Some(prefix)) => prefix.to_owned(),
+                    _ => self.generate_prefix(),
+                };
+                self.declare(Some(&prefix), namespace, declarations);
+                prefix
+            },
+        };
+        format!("{}:{}", prefix, name.local)
+    }
+
+    fn lookup(&self, prefix: Option<&str>) -> Option<&str> {
+        for scope in self.scopes.iter().rev() {
+            for &(ref declared, ref namespace) in scope.iter().rev() {
+                if declared.as_ref().map(|declared| &**declared) == prefix {
+                    return Some(namespace);
+                }
+            }
+        }
+        None
+    }
+
+    fn declared_here(&self, prefix: Option<&str>) -> bool {
+        self.scopes.last().unwrap().iter().any(|&(ref declared, _)| {
+            declared.as_ref().map(|declared| &**declared) == prefix
+        })
+    }
+
+    /// Returns a prefix currently bound to `namespace`, if any.
+    fn prefix_for(&self, namespace: &str) -> Option<String> {
+        for scope in self.scopes.iter().rev() {
+            for &(ref declared, ref bound) in scope.iter().rev() {
+                if let Some(ref declared) = *declared {
+                    if bound == namespace && self.lookup(Some(declared)) == Some(namespace) {
+                        return Some(declared.clone());
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn generate_prefix(&mut self) -> String {
+        loop {
+            let prefix = format!("ns{}", self.prefix_index);
+            self.prefix_index += 1;
+            if self.lookup(Some(&prefix)).is_none() {
+                return prefix;
+            }
+        }
+    }
+
+    fn declare(&mut self, prefix: Option<&str>, namespace: &str, declarations: &mut String) {
+        match prefix {
+            None => declarations.push_str(" xmlns=\""),
+            Some(prefix) => {
+                declarations.push_str(" xmlns:");
+                declarations.push_str(prefix);
+                declarations.push_str("=\"");
+            },
+        }
+        escape(declarations, namespace, true);
+        declarations.push('"');
+        self.scopes.last_mut().unwrap().push((prefix.map(|prefix| prefix.to_owned()),
+                                               namespace.to_owned()));
+    }
+}
+
+fn split_qualified_name(qualified_name: &str) -> (Option<&str>, &str) {
+    match qualified_name.find(':') {
+        Some(index) => (Some(&qualified_name[..index]), &qualified_name[index + 1..]),
+        None => (None, qualified_name),
+    }
+}
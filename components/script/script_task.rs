@@ -23,6 +23,8 @@ use devtools_traits::{DevtoolScriptControlMsg, DevtoolsPageInfo};
 use document_loader::{DocumentLoader, LoadType, NotifierData};
 use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::DocumentBinding::{DocumentMethods, DocumentReadyState};
+use dom::bindings::codegen::Bindings::HistoryBinding::HistoryMethods;
+use dom::bindings::codegen::Bindings::WindowBinding::WindowMethods;
 use dom::bindings::codegen::InheritTypes::{ElementCast, EventCast, EventTargetCast, NodeCast};
 use dom::bindings::conversions::FromJSValConvertible;
 use dom::bindings::conversions::StringificationBehavior;
@@ -39,12 +41,13 @@ use dom::event::{EventBubbles, EventCancelable};
 use dom::node::{Node, NodeDamage, window_from_node};
 use dom::servohtmlparser::{ParserContext, ServoHTMLParser};
 use dom::uievent::UIEvent;
+use dom::urlhelper::UrlHelper;
 use dom::window::{ReflowReason, ScriptHelpers, Window};
 use dom::worker::TrustedWorkerAddress;
 use euclid::Rect;
 use euclid::point::Point2D;
 use hyper::header::{ContentType, HttpDate};
-use hyper::header::{Headers, LastModified};
+use hyper::header::{Headers, LastModified, Referer};
 use hyper::method::Method;
 use hyper::mime::{Mime, SubLevel, TopLevel};
 use ipc_channel::ipc::{self, IpcSender};
@@ -62,6 +65,7 @@ use layout_interface::{ReflowQueryType};
 use layout_interface::{self, LayoutChan, NewLayoutTaskInfo, ReflowGoal, ScriptLayoutChan};
 use libc;
 use mem::heap_size_of_eventtarget;
+use microtask::{Microtask, MicrotaskQueue};
 use msg::compositor_msg::{LayerId, ScriptToCompositorMsg};
 use msg::constellation_msg::Msg as ConstellationMsg;
 use msg::constellation_msg::{ConstellationChan, FocusType, LoadData};
@@ -79,7 +83,7 @@ use profile_traits::mem::{self, OpaqueSender, Report, ReportKind, ReportsChan};
 use profile_traits::time::{self, ProfilerCategory, profile};
 use script_traits::CompositorEvent::{ClickEvent, ResizeEvent};
 use script_traits::CompositorEvent::{KeyEvent, MouseMoveEvent};
-use script_traits::CompositorEvent::{MouseDownEvent, MouseUpEvent};
+use script_traits::CompositorEvent::{MouseDownEvent, MouseUpEvent, WheelEvent};
 use script_traits::{CompositorEvent, ConstellationControlMsg};
 use script_traits::{InitialScriptState, MouseButton, NewLayoutInfo};
 use script_traits::{OpaqueScriptLayoutChannel, ScriptState, ScriptTaskFactory};
@@ -137,6 +141,8 @@ struct InProgressLoad {
     clip_rect: Option<Rect<f32>>,
     /// The requested URL of the load.
     url: Url,
+    /// The URL of the document which initiated the load, if any.
+    referrer: Option<Url>,
 }
 
 impl InProgressLoad {
@@ -153,6 +159,7 @@ impl InProgressLoad {
             window_size: window_size,
             clip_rect: None,
             url: url,
+            referrer: None,
         }
     }
 }
@@ -225,8 +232,9 @@ pub enum MainThreadScriptMsg {
     /// Generic message for running tasks in the ScriptTask
     MainThreadRunnableMsg(Box<MainThreadRunnable + Send>),
     /// Begins a content-initiated load on the specified pipeline (only
-    /// dispatched to ScriptTask).
-    Navigate(PipelineId, LoadData),
+    /// dispatched to ScriptTask). The flag is true when the load replaces the
+    /// current session history entry.
+    Navigate(PipelineId, LoadData, bool),
 }
 
 /// A cloneable interface for communicating with an event loop.
@@ -409,6 +417,13 @@ pub struct ScriptTask {
 
     /// List of pipelines that have been owned and closed by this script task.
     closed_pipelines: RefCell<HashSet<PipelineId>>,
+
+    /// The microtask queue shared by every window of this script task.
+    microtask_queue: MicrotaskQueue,
+
+    /// Whether scripts may run in the documents of this script task. This is
+    /// false for iframes sandboxed without `allow-scripts`.
+    scripting_enabled: bool,
 }
 
 /// In the event of task failure, all data on the stack runs its destructor. However, there
@@ -573,6 +588,13 @@ impl ScriptTask {
         });
     }
 
+    pub fn enqueue_microtask(job: Microtask) {
+        SCRIPT_TASK_ROOT.with(|root| {
+            let script_task = unsafe { &*root.borrow().unwrap() };
+            script_task.microtask_queue.enqueue(job);
+        });
+    }
+
     pub fn process_event(msg: CommonScriptMsg) {
         SCRIPT_TASK_ROOT.with(|root| {
             if let Some(script_task) = *root.borrow() {
@@ -630,6 +652,10 @@ impl ScriptTask {
             js_runtime: Rc::new(runtime),
             mouse_over_targets: DOMRefCell::new(vec!()),
             closed_pipelines: RefCell::new(HashSet::new()),
+
+            microtask_queue: MicrotaskQueue::new(),
+
+            scripting_enabled: state.scripting_enabled,
         }
     }
 
@@ -713,32 +739,13 @@ impl ScriptTask {
         // Store new resizes, and gather all other events.
         let mut sequential = vec!();
 
-        // Receive at least one message so we don't spinloop.
-        let mut event = {
-            let sel = Select::new();
-            let mut port1 = sel.handle(&self.port);
-            let mut port2 = sel.handle(&self.control_port);
-            let mut port3 = sel.handle(&self.devtools_port);
-            let mut port4 = sel.handle(&self.image_cache_port);
-            unsafe {
-                port1.add();
-                port2.add();
-                if self.devtools_chan.is_some() {
-                    port3.add();
-                }
-                port4.add();
-            }
-            let ret = sel.wait();
-            if ret == port1.id() {
-                MixedMessage::FromScript(self.port.recv().unwrap())
-            } else if ret == port2.id() {
-                MixedMessage::FromConstellation(self.control_port.recv().unwrap())
-            } else if ret == port3.id() {
-                MixedMessage::FromDevtools(self.devtools_port.recv().unwrap())
-            } else if ret == port4.id() {
-                MixedMessage::FromImageCache(self.image_cache_port.recv().unwrap())
-            } else {
-                panic!("unexpected select result")
+        // Receive at least one message so we don't spinloop. If none is
+        // waiting, the event loop is about to go idle.
+        let mut event = match self.try_recv() {
+            Some(event) => event,
+            None => {
+                self.start_idle_periods();
+                self.wait_for_msg()
             }
         };
 
@@ -794,18 +801,9 @@ impl ScriptTask {
             // If any of our input sources has an event pending, we'll perform another iteration
             // and check for more resize events. If there are no events pending, we'll move
             // on and execute the sequential non-resize events we've seen.
-            match self.control_port.try_recv() {
-                Err(_) => match self.port.try_recv() {
-                    Err(_) => match self.devtools_port.try_recv() {
-                        Err(_) => match self.image_cache_port.try_recv() {
-                            Err(_) => break,
-                            Ok(ev) => event = MixedMessage::FromImageCache(ev),
-                        },
-                        Ok(ev) => event = MixedMessage::FromDevtools(ev),
-                    },
-                    Ok(ev) => event = MixedMessage::FromScript(ev),
-                },
-                Ok(ev) => event = MixedMessage::FromConstellation(ev),
+            match self.try_recv() {
+                Some(ev) => event = ev,
+                None => break,
             }
         }
 
@@ -832,6 +830,8 @@ impl ScriptTask {
             if let Some(retval) = result {
                 return retval
             }
+
+            self.perform_a_microtask_checkpoint();
         }
 
         // Issue batched reflows on any pages that require it (e.g. if images loaded)
@@ -853,6 +853,70 @@ impl ScriptTask {
         true
     }
 
+    /// Returns a message from one of our input sources, if any is pending.
+    fn try_recv(&self) -> Option<MixedMessage> {
+        match self.control_port.try_recv() {
+            Err(_) => match self.port.try_recv() {
+                Err(_) => match self.devtools_port.try_recv() {
+                    Err(_) => match self.image_cache_port.try_recv() {
+                        Err(_) => None,
+                        Ok(ev) => Some(MixedMessage::FromImageCache(ev)),
+                    },
+                    Ok(ev) => Some(MixedMessage::FromDevtools(ev)),
+                },
+                Ok(ev) => Some(MixedMessage::FromScript(ev)),
+            },
+            Ok(ev) => Some(MixedMessage::FromConstellation(ev)),
+        }
+    }
+
+    /// Blocks until one of our input sources has a message.
+    fn wait_for_msg(&self) -> MixedMessage {
+        let sel = Select::new();
+        let mut port1 = sel.handle(&self.port);
+        let mut port2 = sel.handle(&self.control_port);
+        let mut port3 = sel.handle(&self.devtools_port);
+        let mut port4 = sel.handle(&self.image_cache_port);
+        unsafe {
+            port1.add();
+            port2.add();
+            if self.devtools_chan.is_some() {
+                port3.add();
+            }
+            port4.add();
+        }
+        let ret = sel.wait();
+        if ret == port1.id() {
+            MixedMessage::FromScript(self.port.recv().unwrap())
+        } else if ret == port2.id() {
+            MixedMessage::FromConstellation(self.control_port.recv().unwrap())
+        } else if ret == port3.id() {
+            MixedMessage::FromDevtools(self.devtools_port.recv().unwrap())
+        } else if ret == port4.id() {
+            MixedMessage::FromImageCache(self.image_cache_port.recv().unwrap())
+        } else {
+            panic!("unexpected select result")
+        }
+    }
+
+    /// Starts an idle period in every window, since there is no task waiting
+    /// to be run.
+    fn start_idle_periods(&self) {
+        let windows: Vec<_> = match *self.page.borrow() {
+            Some(ref page) => page.iter().map(|page| page.window()).collect(),
+            None => return,
+        };
+        for window in windows {
+            window.r().start_idle_period();
+        }
+        self.perform_a_microtask_checkpoint();
+    }
+
+    // https://html.spec.whatwg.org/multipage/#perform-a-microtask-checkpoint
+    fn perform_a_microtask_checkpoint(&self) {
+        self.microtask_queue.checkpoint(|pipeline| !self.closed_pipelines.borrow().contains(&pipeline));
+    }
+
     fn categorize_msg(&self, msg: &MixedMessage) -> ScriptTaskEventCategory {
         match *msg {
             MixedMessage::FromConstellation(ref inner_msg) => {
@@ -906,8 +970,8 @@ impl ScriptTask {
         match msg {
             ConstellationControlMsg::AttachLayout(_) =>
                 panic!("should have handled AttachLayout already"),
-            ConstellationControlMsg::Navigate(pipeline_id, subpage_id, load_data) =>
-                self.handle_navigate(pipeline_id, Some(subpage_id), load_data),
+            ConstellationControlMsg::Navigate(pipeline_id, subpage_id, load_data, replace) =>
+                self.handle_navigate(pipeline_id, Some(subpage_id), load_data, replace),
             ConstellationControlMsg::SendEvent(id, event) =>
                 self.handle_event(id, event),
             ConstellationControlMsg::ReflowComplete(id, reflow_id) =>
@@ -952,13 +1016,17 @@ impl ScriptTask {
                 let state = self.handle_get_current_state(pipeline_id);
                 sender.send(state).unwrap();
             }
+            ConstellationControlMsg::SetOnlineStatus(pipeline_id, online) =>
+                self.handle_set_online_status(pipeline_id, online),
+            ConstellationControlMsg::TraverseHistory(pipeline_id, delta) =>
+                self.handle_traverse_history(pipeline_id, delta),
         }
     }
 
     fn handle_msg_from_script(&self, msg: MainThreadScriptMsg) {
         match msg {
-            MainThreadScriptMsg::Navigate(id, load_data) =>
-                self.handle_navigate(id, None, load_data),
+            MainThreadScriptMsg::Navigate(id, load_data, replace) =>
+                self.handle_navigate(id, None, load_data, replace),
             MainThreadScriptMsg::ExitWindow(id) =>
                 self.handle_exit_window_msg(id),
             MainThreadScriptMsg::MainThreadRunnableMsg(runnable) =>
@@ -1072,6 +1140,8 @@ impl ScriptTask {
                     let page = get_page(page, id);
                     self.rebuild_and_force_reflow(&*page, ReflowReason::Viewport);
                 }
+                inner_page.document().r().process_lazy_iframes();
+                window.r().update_intersection_observations();
                 return;
             }
         }
@@ -1489,6 +1559,25 @@ impl ScriptTask {
         }
     }
 
+    /// Handles a change in network connectivity. Does nothing if the page no longer exists.
+    fn handle_set_online_status(&self, pipeline_id: PipelineId, online: bool) {
+        if let Some(page) = self.page.borrow().as_ref() {
+            if let Some(page) = page.find(pipeline_id) {
+                let window = page.window();
+                window.r().set_online(online);
+            }
+        }
+    }
+
+    fn handle_traverse_history(&self, pipeline_id: PipelineId, delta: i32) {
+        if let Some(page) = self.page.borrow().as_ref() {
+            if let Some(page) = page.find(pipeline_id) {
+                let window = page.window();
+                window.r().History().r().Go(delta);
+            }
+        }
+    }
+
     /// The entry point to document loading. Defines bindings, sets up the window and document
     /// objects, parses HTML and CSS, and kicks off initial layout.
     fn load(&self, metadata: Metadata, incomplete: InProgressLoad) -> Root<ServoHTMLParser> {
@@ -1622,6 +1711,10 @@ impl ScriptTask {
                                      last_modified,
                                      DocumentSource::FromParser,
                                      loader);
+        document.r().set_referrer(incomplete.referrer.map(|referrer| referrer.serialize()));
+        if !self.scripting_enabled {
+            document.r().disable_scripting();
+        }
 
         let frame_element = frame_element.r().map(ElementCast::from_ref);
         window.r().init_browsing_context(document.r(), frame_element);
@@ -1759,6 +1852,12 @@ impl ScriptTask {
                 std_mem::swap(&mut *self.mouse_over_targets.borrow_mut(), &mut *mouse_over_targets);
             }
 
+            WheelEvent(delta, point) => {
+                let page = get_page(&self.root_page(), pipeline_id);
+                let document = page.document();
+                document.r().handle_wheel_event(self.js_runtime.rt(), delta, point);
+            }
+
             KeyEvent(key, state, modifiers) => {
                 let page = get_page(&self.root_page(), pipeline_id);
                 let document = page.document();
@@ -1781,7 +1880,8 @@ impl ScriptTask {
     /// https://html.spec.whatwg.org/multipage/#navigating-across-documents
     /// The entry point for content to notify that a new load has been requested
     /// for the given pipeline (specifically the "navigate" algorithm).
-    fn handle_navigate(&self, pipeline_id: PipelineId, subpage_id: Option<SubpageId>, load_data: LoadData) {
+    fn handle_navigate(&self, pipeline_id: PipelineId, subpage_id: Option<SubpageId>, load_data: LoadData,
+                       replace: bool) {
         // Step 8.
         {
             let nurl = &load_data.url;
@@ -1789,9 +1889,12 @@ impl ScriptTask {
                 let page = get_page(&self.root_page(), pipeline_id);
                 let document = page.document();
                 let document = document.r();
-                let url = document.url();
-                if url.scheme == nurl.scheme && url.scheme_data == nurl.scheme_data &&
-                    url.query == nurl.query && load_data.method == Method::Get {
+                let is_fragment_navigation = UrlHelper::EqualsExceptFragment(&document.url(), nurl) &&
+                                             load_data.method == Method::Get;
+                if is_fragment_navigation {
+                    // https://html.spec.whatwg.org/multipage/#scroll-to-fragid
+                    // TODO: fire a hashchange event once HashChangeEvent exists.
+                    document.set_url(nurl.clone());
                     match document.find_fragment_node(&*fragment) {
                         Some(ref node) => {
                             self.scroll_fragment_point(pipeline_id, node.r());
@@ -1811,12 +1914,12 @@ impl ScriptTask {
                     doc.find_iframe(subpage_id)
                 });
                 if let Some(iframe) = iframe.r() {
-                    iframe.navigate_child_browsing_context(load_data.url);
+                    iframe.navigate_child_browsing_context(load_data.url, replace);
                 }
             }
             None => {
                 let ConstellationChan(ref const_chan) = self.constellation_chan;
-                const_chan.send(ConstellationMsg::LoadUrl(pipeline_id, load_data)).unwrap();
+                const_chan.send(ConstellationMsg::LoadUrl(pipeline_id, load_data, replace)).unwrap();
             }
         }
     }
@@ -1847,11 +1950,14 @@ impl ScriptTask {
 
         let wintarget = EventTargetCast::from_ref(window.r());
         event.fire(wintarget);
+
+        window.r().evaluate_media_queries_and_report_changes();
+        window.r().update_intersection_observations();
     }
 
     /// Initiate a non-blocking fetch for a specified resource. Stores the InProgressLoad
     /// argument until a notification is received that the fetch is complete.
-    fn start_page_load(&self, incomplete: InProgressLoad, mut load_data: LoadData) {
+    fn start_page_load(&self, mut incomplete: InProgressLoad, mut load_data: LoadData) {
         let id = incomplete.pipeline_id.clone();
         let subpage = incomplete.parent_info.clone().map(|p| p.1);
 
@@ -1876,10 +1982,16 @@ impl ScriptTask {
             load_data.url = Url::parse("about:blank").unwrap();
         }
 
+        let mut headers = Headers::new();
+        if let Some(ref referrer) = load_data.referrer {
+            headers.set(Referer(referrer.serialize()));
+        }
+        incomplete.referrer = load_data.referrer;
+
         resource_task.send(ControlMsg::Load(NetLoadData {
             url: load_data.url,
             method: load_data.method,
-            headers: Headers::new(),
+            headers: headers,
             preserved_headers: load_data.headers,
             data: load_data.data,
             cors: None,
@@ -1897,7 +2009,7 @@ impl ScriptTask {
         };
 
         let document = page.document();
-        let final_url = document.r().url();
+        let final_url = document.r().url().clone();
 
         document.r().set_ready_state(DocumentReadyState::Interactive);
 
@@ -1912,10 +2024,8 @@ impl ScriptTask {
         // No more reflow required
         page.set_reflow_status(false);
 
-        // https://html.spec.whatwg.org/multipage/#the-end step 4
-        let addr: Trusted<Document> = Trusted::new(self.get_cx(), document.r(), self.chan.clone());
-        let handler = box DocumentProgressHandler::new(addr, DocumentProgressTask::DOMContentLoaded);
-        self.chan.send(CommonScriptMsg::RunnableMsg(ScriptTaskEventCategory::DocumentEvent, handler)).unwrap();
+        // https://html.spec.whatwg.org/multipage/#the-end steps 3-4
+        document.r().process_deferred_scripts();
 
         window.r().set_fragment_name(final_url.fragment.clone());
 
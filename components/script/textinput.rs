@@ -51,6 +51,14 @@ pub enum KeyReaction {
     Nothing,
 }
 
+/// An edit that a key press makes to the content of a text input, as described by
+/// the `inputType` and `data` of the resulting input events.
+#[derive(Clone, PartialEq, Debug)]
+pub struct InputEdit {
+    pub input_type: &'static str,
+    pub data: Option<DOMString>,
+}
+
 impl Default for TextPoint {
     fn default() -> TextPoint {
         TextPoint {
@@ -352,6 +360,24 @@ impl<T: ClipboardProvider> TextInput<T> {
         self.selection_begin = None;
     }
 
+    /// The edit that `handle_keydown_aux` makes to the content for the given key, if any.
+    pub fn edit_for_key(&self, key: Key, mods: KeyModifiers) -> Option<InputEdit> {
+        let (input_type, data) = match key {
+            Key::A | Key::C if is_control_key(mods) => return None,
+            Key::V if is_control_key(mods) => ("insertFromPaste", None),
+            _ if is_printable_key(key) => ("insertText", Some(key_value(key, mods).to_owned())),
+            Key::Space => ("insertText", Some(" ".to_owned())),
+            Key::Delete => ("deleteContentForward", None),
+            Key::Backspace => ("deleteContentBackward", None),
+            Key::Enter | Key::KpEnter if self.multiline => ("insertLineBreak", None),
+            _ => return None,
+        };
+        Some(InputEdit {
+            input_type: input_type,
+            data: data,
+        })
+    }
+
     /// Process a given `KeyboardEvent` and return an action for the caller to execute.
     pub fn handle_keydown(&mut self, event: &KeyboardEvent) -> KeyReaction {
         if let Some(key) = event.get_key() {
@@ -2,12 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use dom::abortsignal::AbortSignal;
 use dom::bindings::callback::ExceptionHandling::Report;
 use dom::bindings::cell::DOMRefCell;
 use dom::bindings::codegen::Bindings::FunctionBinding::Function;
 use dom::bindings::global::global_object_for_js_object;
+use dom::bindings::refcounted::Trusted;
 use dom::bindings::utils::Reflectable;
-use dom::window::ScriptHelpers;
+use dom::window::{ScriptHelpers, Window};
 use horribly_inefficient_timers;
 use js::jsapi::{HandleValue, Heap, RootedValue};
 use js::jsval::{JSVal, UndefinedValue};
@@ -25,6 +27,14 @@ use util::mem::HeapSizeOf;
 use util::str::DOMString;
 use util::task::spawn_named;
 
+/// The timer nesting level above which short timeouts are clamped.
+///
+/// https://html.spec.whatwg.org/multipage/#timer-initialisation-steps
+const MAX_UNCLAMPED_NESTING_LEVEL: u32 = 5;
+
+/// The smallest timeout, in milliseconds, allowed above `MAX_UNCLAMPED_NESTING_LEVEL`.
+pub const MIN_CLAMPED_TIMEOUT_MS: u32 = 4;
+
 #[derive(JSTraceable, PartialEq, Eq, Copy, Clone, HeapSizeOf)]
 pub struct TimerId(i32);
 
@@ -40,7 +50,12 @@ struct TimerHandle {
 #[derive(JSTraceable, Clone)]
 pub enum TimerCallback {
     StringTimerCallback(DOMString),
-    FunctionTimerCallback(Rc<Function>)
+    FunctionTimerCallback(Rc<Function>),
+    /// Aborts the signal returned by `AbortSignal.timeout()`.
+    AbortSignalTimerCallback(Trusted<AbortSignal>),
+    /// Runs the idle request callback with the given identifier once its
+    /// timeout has passed.
+    IdleCallbackTimerCallback(Trusted<Window>, u32),
 }
 
 impl HeapSizeOf for TimerCallback {
@@ -74,6 +89,8 @@ impl TimerHandle {
 pub struct TimerManager {
     active_timers: DOMRefCell<HashMap<TimerId, TimerHandle>>,
     next_timer_handle: Cell<i32>,
+    /// The timer nesting level of the timer callback currently running, if any.
+    nesting_level: Cell<u32>,
 }
 
 
@@ -109,14 +126,33 @@ pub enum TimerControlMsg {
 struct TimerData {
     is_interval: IsInterval,
     callback: TimerCallback,
-    args: Vec<Heap<JSVal>>
+    args: Vec<Heap<JSVal>>,
+    /// The timer nesting level of the next task that runs this timer.
+    nesting_level: u32,
+}
+
+/// Returns the delay, in milliseconds, for a timer set with `timeout` from a task
+/// at the given timer nesting level.
+///
+/// https://html.spec.whatwg.org/multipage/#timer-initialisation-steps
+pub fn clamp_timeout(timeout: i32, nesting_level: u32) -> u32 {
+    // Step 9.
+    let timeout = cmp::max(0, timeout) as u32;
+
+    // Step 10.
+    if nesting_level > MAX_UNCLAMPED_NESTING_LEVEL {
+        cmp::max(timeout, MIN_CLAMPED_TIMEOUT_MS)
+    } else {
+        timeout
+    }
 }
 
 impl TimerManager {
     pub fn new() -> TimerManager {
         TimerManager {
             active_timers: DOMRefCell::new(HashMap::new()),
-            next_timer_handle: Cell::new(0)
+            next_timer_handle: Cell::new(0),
+            nesting_level: Cell::new(0),
         }
     }
 
@@ -140,7 +176,7 @@ impl TimerManager {
                                   source: TimerSource,
                                   script_chan: Box<ScriptChan + Send>)
                                   -> i32 {
-        let duration_ms = cmp::max(0, timeout) as u32;
+        let nesting_level = self.nesting_level.get();
         let handle = self.next_timer_handle.get();
         self.next_timer_handle.set(handle + 1);
 
@@ -155,9 +191,16 @@ impl TimerManager {
         }.to_owned();
         spawn_named(spawn_name, move || {
             let timeout_port = if is_interval == IsInterval::Interval {
-                horribly_inefficient_timers::periodic(duration_ms)
+                let mut nesting_level = nesting_level;
+                horribly_inefficient_timers::periodic(move || {
+                    let duration_ms = clamp_timeout(timeout, nesting_level);
+                    // Each repetition of an interval runs the timer initialisation
+                    // steps again from the task that just ran, one level deeper.
+                    nesting_level += 1;
+                    duration_ms
+                })
             } else {
-                horribly_inefficient_timers::oneshot(duration_ms)
+                horribly_inefficient_timers::oneshot(clamp_timeout(timeout, nesting_level))
             };
             let control_port = control_port;
 
@@ -179,7 +222,7 @@ impl TimerManager {
                     if is_interval == IsInterval::NonInterval {
                         break;
                     }
-                } else if id == control_handle.id() {
+                } else {
                     match control_port.recv().unwrap() {
                         TimerControlMsg::Suspend => {
                             let msg = control_port.recv().unwrap();
@@ -206,7 +249,9 @@ impl TimerManager {
             data: TimerData {
                 is_interval: is_interval,
                 callback: callback,
-                args: Vec::with_capacity(arguments.len())
+                args: Vec::with_capacity(arguments.len()),
+                // Step 11.
+                nesting_level: nesting_level + 1,
             }
         };
         self.active_timers.borrow_mut().insert(timer_id, timer);
@@ -234,15 +279,19 @@ impl TimerManager {
 
     pub fn fire_timer<T: Reflectable>(&self, timer_id: TimerId, this: &T) {
 
-        let (is_interval, callback, args): (IsInterval, TimerCallback, Vec<JSVal>) =
+        let (is_interval, callback, args, nesting_level): (IsInterval, TimerCallback, Vec<JSVal>, u32) =
             match self.active_timers.borrow().get(&timer_id) {
                 Some(timer_handle) =>
                     (timer_handle.data.is_interval,
                      timer_handle.data.callback.clone(),
-                     timer_handle.data.args.iter().map(|arg| arg.get()).collect()),
+                     timer_handle.data.args.iter().map(|arg| arg.get()).collect(),
+                     timer_handle.data.nesting_level),
                 None => return,
             };
 
+        // Timers set from within the callback are nested one level deeper.
+        self.nesting_level.set(nesting_level);
+
         match callback {
             TimerCallback::FunctionTimerCallback(function) => {
                 let arg_handles = args.iter().by_ref().map(|arg| HandleValue { ptr: arg }).collect();
@@ -254,10 +303,25 @@ impl TimerManager {
                 let mut rval = RootedValue::new(cx, UndefinedValue());
                 this.evaluate_js_on_global_with_result(&code_str, rval.handle_mut());
             }
+            TimerCallback::AbortSignalTimerCallback(signal) => {
+                signal.root().r().signal_abort();
+            }
+            TimerCallback::IdleCallbackTimerCallback(window, ident) => {
+                window.root().r().invoke_idle_callback(ident, true);
+            }
         }
 
-        if is_interval == IsInterval::NonInterval {
-            self.active_timers.borrow_mut().remove(&timer_id);
+        self.nesting_level.set(0);
+
+        match is_interval {
+            IsInterval::NonInterval => {
+                self.active_timers.borrow_mut().remove(&timer_id);
+            }
+            IsInterval::Interval => {
+                if let Some(timer_handle) = self.active_timers.borrow_mut().get_mut(&timer_id) {
+                    timer_handle.data.nesting_level += 1;
+                }
+            }
         }
     }
 }
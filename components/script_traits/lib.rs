@@ -125,8 +125,12 @@ pub enum ConstellationControlMsg {
     Freeze(PipelineId),
     /// Notifies script task to resume all its timers
     Thaw(PipelineId),
-    /// Notifies script task that a url should be loaded in this iframe.
-    Navigate(PipelineId, SubpageId, LoadData),
+    /// Notifies script task that a url should be loaded in this iframe. The flag is
+    /// true when the new document replaces the current session history entry.
+    Navigate(PipelineId, SubpageId, LoadData, bool),
+    /// Requests that the script task traverse the session history of a pipeline's
+    /// document by the given number of entries.
+    TraverseHistory(PipelineId, i32),
     /// Requests the script task forward a mozbrowser event to an iframe it owns
     MozBrowserEvent(PipelineId, SubpageId, MozBrowserEvent),
     /// Updates the current subpage id of a given iframe
@@ -144,6 +148,8 @@ pub enum ConstellationControlMsg {
     StylesheetLoadComplete(PipelineId, Url, Box<StylesheetLoadResponder + Send>),
     /// Get the current state of the script task for a given pipeline.
     GetCurrentState(Sender<ScriptState>, PipelineId),
+    /// Notifies script of a change in the network connectivity of the user agent.
+    SetOnlineStatus(PipelineId, bool),
 }
 
 /// The mouse button involved in the event.
@@ -169,6 +175,8 @@ pub enum CompositorEvent {
     MouseUpEvent(MouseButton, Point2D<f32>),
     /// The mouse was moved over a point.
     MouseMoveEvent(Point2D<f32>),
+    /// The mouse wheel was scrolled over a point, by the given delta in pixels.
+    WheelEvent(Point2D<f32>, Point2D<f32>),
     /// A key was pressed.
     KeyEvent(Key, KeyState, KeyModifiers),
 }
@@ -208,6 +216,8 @@ pub struct InitialScriptState {
     pub devtools_chan: Option<IpcSender<ScriptToDevtoolsControlMsg>>,
     /// Information about the initial window size.
     pub window_size: Option<WindowSizeData>,
+    /// Whether scripts may run in the documents of this script task.
+    pub scripting_enabled: bool,
 }
 
 /// This trait allows creating a `ScriptTask` without depending on the `script`
@@ -171,20 +171,9 @@ impl PropertyAnimation {
     }
 
     pub fn update(&self, style: &mut ComputedValues, time: f64) {
-        let progress = match self.timing_function {
-            TransitionTimingFunction::CubicBezier(p1, p2) => {
-                // See `WebCore::AnimationBase::solveEpsilon(double)` in WebKit.
-                let epsilon = 1.0 / (200.0 * (self.duration.seconds() as f64));
-                Bezier::new(Point2D::new(p1.x as f64, p1.y as f64),
-                            Point2D::new(p2.x as f64, p2.y as f64)).solve(time, epsilon)
-            }
-            TransitionTimingFunction::Steps(steps, StartEnd::Start) => {
-                (time * (steps as f64)).ceil() / (steps as f64)
-            }
-            TransitionTimingFunction::Steps(steps, StartEnd::End) => {
-                (time * (steps as f64)).floor() / (steps as f64)
-            }
-        };
+        let progress = timing_function_progress(self.timing_function,
+                                                self.duration.seconds() as f64,
+                                                time);
 
         macro_rules! match_property(
             ( $( [$name:ident; $structname:ident; $field:ident] ),* ) => {
@@ -313,6 +302,25 @@ enum AnimatedProperty {
     ZIndex(ZIndex, ZIndex),
 }
 
+/// Applies a timing function to `time`, the elapsed fraction of an animation
+/// that lasts `duration` seconds.
+pub fn timing_function_progress(timing_function: TransitionTimingFunction, duration: f64, time: f64) -> f64 {
+    match timing_function {
+        TransitionTimingFunction::CubicBezier(p1, p2) => {
+            // See `WebCore::AnimationBase::solveEpsilon(double)` in WebKit.
+            let epsilon = 1.0 / (200.0 * duration);
+            Bezier::new(Point2D::new(p1.x as f64, p1.y as f64),
+                        Point2D::new(p2.x as f64, p2.y as f64)).solve(time, epsilon)
+        }
+        TransitionTimingFunction::Steps(steps, StartEnd::Start) => {
+            (time * (steps as f64)).ceil() / (steps as f64)
+        }
+        TransitionTimingFunction::Steps(steps, StartEnd::End) => {
+            (time * (steps as f64)).floor() / (steps as f64)
+        }
+    }
+}
+
 impl AnimatedProperty {
     #[inline]
     fn does_not_animate(&self) -> bool {
@@ -13,6 +13,8 @@ use selectors::matching::{Rule, SelectorMap};
 use selectors::parser::PseudoElement;
 use smallvec::VecLike;
 use std::process;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use style_traits::viewport::ViewportConstraints;
 use stylesheets::{CSSRuleIteratorExt, Origin, Stylesheet};
 use url::Url;
@@ -26,7 +28,7 @@ pub type DeclarationBlock = GenericDeclarationBlock<Vec<PropertyDeclaration>>;
 
 pub struct Stylist {
     // List of stylesheets (including all media rules)
-    stylesheets: Vec<Stylesheet>,
+    stylesheets: Vec<Arc<Stylesheet>>,
 
     // Device that the stylist is currently evaluating against.
     pub device: Device,
@@ -84,7 +86,7 @@ impl Stylist {
     }
 
     #[inline]
-    pub fn stylesheets(&self) -> &[Stylesheet] {
+    pub fn stylesheets(&self) -> &[Arc<Stylesheet>] {
         &self.stylesheets
     }
 
@@ -189,10 +191,34 @@ impl Stylist {
     }
 
     pub fn add_stylesheet(&mut self, stylesheet: Stylesheet) {
+        self.add_shared_stylesheet(Arc::new(stylesheet));
+    }
+
+    /// Adds a stylesheet that is also referenced from elsewhere (e.g. by the
+    /// CSSOM), so that changes to its disabled flag can be observed.
+    pub fn add_shared_stylesheet(&mut self, stylesheet: Arc<Stylesheet>) {
         self.stylesheets.push(stylesheet);
         self.is_dirty = true;
     }
 
+    /// Removes the stylesheet whose disabled flag is `disabled`. Each stylesheet owns a
+    /// distinct flag, which also identifies sheets that were loaded by layout on behalf of
+    /// their owner node.
+    pub fn remove_stylesheet(&mut self, disabled: &Arc<AtomicBool>) {
+        let disabled = &**disabled as *const AtomicBool;
+        let len = self.stylesheets.len();
+        self.stylesheets.retain(|stylesheet| &*stylesheet.disabled as *const AtomicBool != disabled);
+        if self.stylesheets.len() != len {
+            self.is_dirty = true;
+        }
+    }
+
+    /// Forces the selector maps to be rebuilt on the next update, e.g. because
+    /// a stylesheet was enabled or disabled.
+    pub fn mark_dirty(&mut self) {
+        self.is_dirty = true;
+    }
+
     /// Returns the applicable CSS declarations for the given element. This corresponds to
     /// `ElementRuleCollector` in WebKit.
     ///
@@ -204,6 +230,7 @@ impl Stylist {
                                         element: &E,
                                         parent_bf: Option<&BloomFilter>,
                                         style_attribute: Option<&PropertyDeclarationBlock>,
+                                        animation_declarations: Option<&Arc<Vec<PropertyDeclaration>>>,
                                         pseudo_element: Option<PseudoElement>,
                                         applicable_declarations: &mut V)
                                         -> bool
@@ -212,6 +239,8 @@ impl Stylist {
         assert!(!self.is_dirty);
         assert!(style_attribute.is_none() || pseudo_element.is_none(),
                 "Style attributes do not apply to pseudo-elements");
+        assert!(animation_declarations.is_none() || pseudo_element.is_none(),
+                "Animations do not apply to pseudo-elements");
 
         let map = match pseudo_element {
             None => &self.element_map,
@@ -250,20 +279,27 @@ impl Stylist {
                 GenericDeclarationBlock::from_declarations(sa.normal.clone()))
         });
 
-        // Step 5: Author-supplied `!important` rules.
+        // Step 5: Animation declarations.
+        animation_declarations.map(|declarations| {
+            shareable = false;
+            applicable_declarations.push(
+                GenericDeclarationBlock::from_declarations(declarations.clone()))
+        });
+
+        // Step 6: Author-supplied `!important` rules.
         map.author.important.get_all_matching_rules(element,
                                                     parent_bf,
                                                     applicable_declarations,
                                                     &mut shareable);
 
-        // Step 6: `!important` style attributes.
+        // Step 7: `!important` style attributes.
         style_attribute.map(|sa| {
             shareable = false;
             applicable_declarations.push(
                 GenericDeclarationBlock::from_declarations(sa.important.clone()))
         });
 
-        // Step 7: User and UA `!important` rules.
+        // Step 8: User and UA `!important` rules.
         map.user.important.get_all_matching_rules(element,
                                                   parent_bf,
                                                   applicable_declarations,
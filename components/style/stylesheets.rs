@@ -15,6 +15,8 @@ use std::ascii::AsciiExt;
 use std::cell::Cell;
 use std::iter::Iterator;
 use std::slice;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use string_cache::{Atom, Namespace};
 use url::Url;
 use viewport::ViewportRule;
@@ -35,12 +37,24 @@ pub enum Origin {
 }
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Stylesheet {
     /// List of rules in the order they were found (important for
     /// cascading order)
     pub rules: Vec<CSSRule>,
     pub origin: Origin,
+    /// Whether this stylesheet has been disabled, e.g. through the CSSOM or
+    /// because its owner node no longer refers to it. A disabled stylesheet
+    /// has no effective rules. The flag may be shared with the owner node.
+    pub disabled: Arc<AtomicBool>,
+}
+
+impl PartialEq for Stylesheet {
+    fn eq(&self, other: &Stylesheet) -> bool {
+        self.rules == other.rules &&
+            self.origin == other.origin &&
+            self.is_disabled() == other.is_disabled()
+    }
 }
 
 
@@ -131,9 +145,18 @@ impl Stylesheet {
         Stylesheet {
             origin: origin,
             rules: rules,
+            disabled: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_disabled(&self, disabled: bool) {
+        self.disabled.store(disabled, Ordering::SeqCst)
+    }
+
     /// Return an iterator over all the rules within the style-sheet.
     #[inline]
     pub fn rules(&self) -> Rules {
@@ -146,9 +169,12 @@ impl Stylesheet {
     /// If a condition does not hold, its associated conditional group rule and
     /// nested rules will be skipped. Use `rules` if all rules need to be
     /// examined.
+    ///
+    /// A disabled style-sheet has no effective rules.
     #[inline]
     pub fn effective_rules<'a>(&'a self, device: &'a Device) -> Rules<'a> {
-        Rules::new(self.rules.iter(), Some(device))
+        let rules = if self.is_disabled() { &[][..] } else { &self.rules[..] };
+        Rules::new(rules.iter(), Some(device))
     }
 }
 
@@ -109,6 +109,94 @@ pub fn parse_unsigned_integer<T: Iterator<Item=char>>(input: T) -> Option<u32> {
     })
 }
 
+/// Parse a floating-point number according to
+/// <https://html.spec.whatwg.org/#rules-for-parsing-floating-point-number-values>
+pub fn parse_floating_point_number(input: &str) -> Option<f64> {
+    fn is_ascii_digit(c: &char) -> bool {
+        match *c {
+            '0'...'9' => true,
+            _ => false,
+        }
+    }
+
+    let input = input.trim_left_matches(HTML_SPACE_CHARACTERS);
+    let mut chars = input.chars().peekable();
+    let mut number = String::new();
+
+    match chars.peek() {
+        Some(&'-') => {
+            number.push('-');
+            chars.next();
+        },
+        Some(&'+') => {
+            chars.next();
+        },
+        _ => {},
+    }
+
+    while let Some(&c) = chars.peek() {
+        if !is_ascii_digit(&c) {
+            break;
+        }
+        number.push(c);
+        chars.next();
+    }
+
+    if chars.peek() == Some(&'.') {
+        chars.next();
+        let mut fraction = String::new();
+        while let Some(&c) = chars.peek() {
+            if !is_ascii_digit(&c) {
+                break;
+            }
+            fraction.push(c);
+            chars.next();
+        }
+        if !fraction.is_empty() {
+            number.push('.');
+            number.push_str(&fraction);
+        }
+    }
+
+    if !number.chars().any(|c| is_ascii_digit(&c)) {
+        return None;
+    }
+
+    if let Some(&e) = chars.peek() {
+        if e == 'e' || e == 'E' {
+            chars.next();
+            let mut exponent = String::new();
+            match chars.peek() {
+                Some(&'-') => {
+                    exponent.push('-');
+                    chars.next();
+                },
+                Some(&'+') => {
+                    chars.next();
+                },
+                _ => {},
+            }
+            let mut has_digits = false;
+            while let Some(&c) = chars.peek() {
+                if !is_ascii_digit(&c) {
+                    break;
+                }
+                exponent.push(c);
+                has_digits = true;
+                chars.next();
+            }
+            if has_digits {
+                number.push('e');
+                number.push_str(&exponent);
+            }
+        }
+    }
+
+    number.parse::<f64>().ok().and_then(|value| {
+        if value.is_finite() { Some(value) } else { None }
+    })
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum LengthOrPercentageOrAuto {
     Auto,
@@ -232,6 +232,13 @@ impl WindowMethods for Window {
         (Size2D::new(width, height), Point2D::zero())
     }
 
+    fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>) {
+        //TODO get real screen size
+        let size = self.size().to_untyped();
+        let size = Size2D::new(size.width as u32, size.height as u32);
+        (size, size)
+    }
+
     fn set_inner_size(&self, _size: Size2D<u32>) {
 
     }
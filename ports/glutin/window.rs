@@ -527,6 +527,13 @@ impl WindowMethods for Window {
         (size, origin)
     }
 
+    fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>) {
+        // glutin doesn't tell us which parts of the screen are taken by panels.
+        let (width, height) = glutin::get_primary_monitor().get_dimensions();
+        let size = Size2D::new(width, height);
+        (size, size)
+    }
+
     fn set_inner_size(&self, size: Size2D<u32>) {
         self.window.set_inner_size(size.width as u32, size.height as u32)
     }
@@ -776,6 +783,11 @@ impl WindowMethods for Window {
         (Size2D::new(width, height), Point2D::zero())
     }
 
+    fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>) {
+        let size = Size2D::new(self.width, self.height);
+        (size, size)
+    }
+
     fn create_compositor_channel(_: &Option<Rc<Window>>)
                                  -> (Box<CompositorProxy + Send>, Box<CompositorReceiver>) {
         let (sender, receiver) = channel();
@@ -795,6 +795,11 @@ impl WindowMethods for Window {
         (Size2D::new(width, height), Point2D::zero())
     }
 
+    fn screen_size(&self) -> (Size2D<u32>, Size2D<u32>) {
+        let size = Size2D::new(self.width as u32, self.height as u32);
+        (size, size)
+    }
+
     fn set_inner_size(&self, _: Size2D<u32>) {
 
     }
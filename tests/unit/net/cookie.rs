@@ -7,6 +7,7 @@ extern crate cookie as cookie_rs;
 use net::cookie::Cookie;
 use net::cookie_storage::CookieStorage;
 use net_traits::CookieSource;
+use std::borrow::ToOwned;
 use url::Url;
 
 
@@ -102,3 +103,35 @@ fn test_sort_order() {
     assert!(CookieStorage::cookie_comparator(&a_prime, &a) == Ordering::Greater);
     assert!(CookieStorage::cookie_comparator(&a, &a) == Ordering::Equal);
 }
+
+#[test]
+fn test_non_http_cookie_round_trip() {
+    let url = &Url::parse("http://example.com/foo").unwrap();
+    let mut storage = CookieStorage::new();
+
+    let cookie = cookie_rs::Cookie::parse("foo=bar; Path=/").unwrap();
+    let cookie = Cookie::new_wrapped(cookie, url, CookieSource::NonHTTP).unwrap();
+    storage.push(cookie, CookieSource::NonHTTP);
+
+    assert_eq!(storage.cookies_for_url(url, CookieSource::NonHTTP), Some("foo=bar".to_owned()));
+    assert_eq!(storage.cookies_for_url(url, CookieSource::HTTP), Some("foo=bar".to_owned()));
+}
+
+#[test]
+fn test_http_only_cookie_hidden_from_script() {
+    let url = &Url::parse("http://example.com/foo").unwrap();
+    let mut storage = CookieStorage::new();
+
+    let cookie = cookie_rs::Cookie::parse("secret=value; HttpOnly; Path=/").unwrap();
+    let cookie = Cookie::new_wrapped(cookie, url, CookieSource::HTTP).unwrap();
+    storage.push(cookie, CookieSource::HTTP);
+
+    assert_eq!(storage.cookies_for_url(url, CookieSource::HTTP), Some("secret=value".to_owned()));
+    assert_eq!(storage.cookies_for_url(url, CookieSource::NonHTTP), None);
+
+    // Script cannot overwrite an HttpOnly cookie either.
+    let cookie = cookie_rs::Cookie::parse("secret=other; Path=/").unwrap();
+    let cookie = Cookie::new_wrapped(cookie, url, CookieSource::NonHTTP).unwrap();
+    storage.push(cookie, CookieSource::NonHTTP);
+    assert_eq!(storage.cookies_for_url(url, CookieSource::HTTP), Some("secret=value".to_owned()));
+}
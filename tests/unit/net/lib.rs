@@ -17,5 +17,6 @@ extern crate util;
 #[cfg(test)] mod data_loader;
 #[cfg(test)] mod mime_classifier;
 #[cfg(test)] mod resource_task;
+#[cfg(test)] mod storage_task;
 #[cfg(test)] mod hsts;
 #[cfg(test)] mod http_loader;
@@ -0,0 +1,86 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use ipc_channel::ipc;
+use net::storage_task::StorageTaskFactory;
+use net_traits::storage_task::{StorageTask, StorageTaskMsg, StorageType};
+use std::borrow::ToOwned;
+use std::iter;
+use url::Url;
+
+fn set_item(storage_task: &StorageTask, url: &Url, name: &str, value: String)
+            -> Result<(bool, Option<String>), ()> {
+    let (sender, receiver) = ipc::channel().unwrap();
+    storage_task.send(StorageTaskMsg::SetItem(sender, url.clone(), StorageType::Local,
+                                              name.to_owned(), value)).unwrap();
+    receiver.recv().unwrap()
+}
+
+#[test]
+fn test_set_item_quota() {
+    let storage_task: StorageTask = StorageTaskFactory::new();
+    let url = Url::parse("http://example.com/").unwrap();
+    let megabyte: String = iter::repeat('x').take(1024 * 1024).collect();
+
+    for i in 0..4 {
+        assert!(set_item(&storage_task, &url, &i.to_string(), megabyte.clone()).is_ok());
+    }
+    assert!(set_item(&storage_task, &url, "4", megabyte.clone()).is_err());
+
+    // Replacing a value only counts the difference in size.
+    assert!(set_item(&storage_task, &url, "0", megabyte.clone() + "y").is_ok());
+
+    // Another origin has its own quota.
+    let other_url = Url::parse("http://example.org/").unwrap();
+    assert!(set_item(&storage_task, &other_url, "4", megabyte.clone()).is_ok());
+
+    // Removing an item frees its space.
+    let (sender, receiver) = ipc::channel().unwrap();
+    storage_task.send(StorageTaskMsg::RemoveItem(sender, url.clone(), StorageType::Local,
+                                                 "1".to_owned())).unwrap();
+    assert!(receiver.recv().unwrap().is_some());
+    assert!(set_item(&storage_task, &url, "4", megabyte.clone()).is_ok());
+
+    storage_task.send(StorageTaskMsg::Exit).unwrap();
+}
+
+#[test]
+fn test_keys() {
+    let storage_task: StorageTask = StorageTaskFactory::new();
+    let url = Url::parse("http://example.com/").unwrap();
+    set_item(&storage_task, &url, "b", "2".to_owned()).unwrap();
+    set_item(&storage_task, &url, "a", "1".to_owned()).unwrap();
+
+    let (sender, receiver) = ipc::channel().unwrap();
+    storage_task.send(StorageTaskMsg::Keys(sender, url.clone(), StorageType::Local)).unwrap();
+    assert_eq!(receiver.recv().unwrap(), vec!["a".to_owned(), "b".to_owned()]);
+
+    storage_task.send(StorageTaskMsg::Exit).unwrap();
+}
+
+#[test]
+fn test_session_and_local_storage_are_independent() {
+    let storage_task: StorageTask = StorageTaskFactory::new();
+    let url = Url::parse("http://example.com/").unwrap();
+
+    let get_item = |storage_type| {
+        let (sender, receiver) = ipc::channel().unwrap();
+        storage_task.send(StorageTaskMsg::GetItem(sender, url.clone(), storage_type,
+                                                  "key".to_owned())).unwrap();
+        receiver.recv().unwrap()
+    };
+
+    let (sender, receiver) = ipc::channel().unwrap();
+    storage_task.send(StorageTaskMsg::SetItem(sender, url.clone(), StorageType::Session,
+                                              "key".to_owned(), "session".to_owned())).unwrap();
+    receiver.recv().unwrap().unwrap();
+    assert_eq!(get_item(StorageType::Session), Some("session".to_owned()));
+    assert_eq!(get_item(StorageType::Local), None);
+
+    set_item(&storage_task, &url, "key", "local".to_owned()).unwrap();
+    assert_eq!(get_item(StorageType::Session), Some("session".to_owned()));
+    assert_eq!(get_item(StorageType::Local), Some("local".to_owned()));
+
+    storage_task.send(StorageTaskMsg::Exit).unwrap();
+}
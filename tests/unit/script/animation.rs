@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::animation::{interpolate, keyframe_value, simple_iteration_progress};
+use script::dom::bindings::codegen::Bindings::AnimatableBinding::FillMode;
+
+#[test]
+fn test_opacity_changes_mid_animation() {
+    let keyframes = vec!["0".to_owned(), "1".to_owned()];
+    let value_at = |local_time| {
+        simple_iteration_progress(local_time, 1000., 1., FillMode::Auto)
+            .map(|progress| keyframe_value(&keyframes, progress))
+    };
+    assert_eq!(value_at(0.), Some("0".to_owned()));
+    assert_eq!(value_at(500.), Some("0.5".to_owned()));
+    assert_eq!(value_at(750.), Some("0.75".to_owned()));
+    assert_eq!(value_at(1000.), None);
+}
+
+#[test]
+fn test_iterations_and_fill() {
+    assert_eq!(simple_iteration_progress(1250., 1000., 2., FillMode::None), Some(0.25));
+    assert_eq!(simple_iteration_progress(2000., 1000., 2., FillMode::None), None);
+    assert_eq!(simple_iteration_progress(2000., 1000., 2., FillMode::Forwards), Some(1.));
+    assert_eq!(simple_iteration_progress(5000., 1000., 1.5, FillMode::Both), Some(0.5));
+    assert_eq!(simple_iteration_progress(0., 0., 1., FillMode::Forwards), Some(1.));
+    assert_eq!(simple_iteration_progress(0., 0., 1., FillMode::Backwards), None);
+}
+
+#[test]
+fn test_interpolate() {
+    assert_eq!(interpolate("10px", "20px", 0.25), "12.5px");
+    assert_eq!(interpolate("-1em", "1em", 0.5), "0em");
+    assert_eq!(interpolate("10px", "50%", 0.4), "10px");
+    assert_eq!(interpolate("red", "blue", 0.5), "blue");
+}
+
+#[test]
+fn test_keyframe_value() {
+    let keyframes = vec!["0px".to_owned(), "100px".to_owned(), "0px".to_owned()];
+    assert_eq!(keyframe_value(&keyframes, 0.25), "50px");
+    assert_eq!(keyframe_value(&keyframes, 0.5), "100px");
+    assert_eq!(keyframe_value(&keyframes, 1.), "0px");
+}
@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::characterdata::find_utf16_code_unit_offset;
+
+#[test]
+fn test_utf16_offsets_of_ascii() {
+    assert_eq!(find_utf16_code_unit_offset("hello", 0), Some(0));
+    assert_eq!(find_utf16_code_unit_offset("hello", 2), Some(2));
+    assert_eq!(find_utf16_code_unit_offset("hello", 5), Some(5));
+    assert_eq!(find_utf16_code_unit_offset("hello", 6), None);
+}
+
+#[test]
+fn test_utf16_offsets_around_surrogate_pairs() {
+    // U+1D306 takes two UTF-16 code units and four UTF-8 bytes.
+    let data = "a\u{1D306}b\u{1D306}";
+    assert_eq!(find_utf16_code_unit_offset(data, 1), Some(1));
+    assert_eq!(find_utf16_code_unit_offset(data, 3), Some(5));
+    assert_eq!(find_utf16_code_unit_offset(data, 4), Some(6));
+    assert_eq!(find_utf16_code_unit_offset(data, 6), Some(10));
+    assert_eq!(find_utf16_code_unit_offset(data, 7), None);
+
+    let offset = find_utf16_code_unit_offset(data, 3).unwrap();
+    assert_eq!(&data[..offset], "a\u{1D306}");
+    assert_eq!(&data[offset..], "b\u{1D306}");
+}
+
+#[test]
+#[should_panic]
+fn test_utf16_offset_inside_a_surrogate_pair() {
+    find_utf16_code_unit_offset("a\u{1D306}b", 2);
+}
@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use rand::OsRng;
+use script::dom::bindings::error::Error;
+use script::dom::crypto::{MAX_RANDOM_VALUES_LENGTH, fill_random_values, random_uuid};
+
+#[test]
+fn test_fill_random_values_quota() {
+    let mut rng = OsRng::new().unwrap();
+
+    let mut buffer = vec![0u8; MAX_RANDOM_VALUES_LENGTH];
+    assert!(fill_random_values(&mut rng, true, &mut buffer).is_ok());
+
+    let mut buffer = vec![0u8; MAX_RANDOM_VALUES_LENGTH + 1];
+    match fill_random_values(&mut rng, true, &mut buffer) {
+        Err(Error::QuotaExceeded) => (),
+        result => panic!("expected QuotaExceeded, got {:?}", result),
+    }
+    assert!(buffer.iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_fill_random_values_rejects_float_arrays() {
+    let mut rng = OsRng::new().unwrap();
+    let mut buffer = [0u8; 16];
+    match fill_random_values(&mut rng, false, &mut buffer) {
+        Err(Error::TypeMismatch) => (),
+        result => panic!("expected TypeMismatch, got {:?}", result),
+    }
+    assert_eq!(buffer, [0u8; 16]);
+}
+
+#[test]
+fn test_fill_random_values_differs_between_calls() {
+    let mut rng = OsRng::new().unwrap();
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    fill_random_values(&mut rng, true, &mut first).unwrap();
+    fill_random_values(&mut rng, true, &mut second).unwrap();
+    assert!(first != second);
+}
+
+#[test]
+fn test_random_uuid_format() {
+    let mut rng = OsRng::new().unwrap();
+    let uuid = random_uuid(&mut rng);
+    assert_eq!(uuid.len(), 36);
+    for (i, c) in uuid.chars().enumerate() {
+        match i {
+            8 | 13 | 18 | 23 => assert_eq!(c, '-'),
+            _ => assert!(c.is_digit(16) && !c.is_uppercase(), "unexpected {:?} in {}", c, uuid),
+        }
+    }
+
+    // The version nibble is 4, and the variant bits are 10.
+    assert_eq!(&uuid[14..15], "4");
+    assert!(["8", "9", "a", "b"].contains(&&uuid[19..20]), "bad variant in {}", uuid);
+
+    assert!(random_uuid(&mut rng) != uuid);
+}
@@ -0,0 +1,63 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::document::is_registrable_domain_suffix_of_or_equal_to;
+
+#[test]
+fn test_registrable_domain_suffix_equal_hosts() {
+    assert!(is_registrable_domain_suffix_of_or_equal_to("example.com", "example.com"));
+    assert!(is_registrable_domain_suffix_of_or_equal_to("127.0.0.1", "127.0.0.1"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_empty_suffix() {
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("", "example.com"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("", ""));
+}
+
+#[test]
+fn test_registrable_domain_suffix_parent_domain() {
+    assert!(is_registrable_domain_suffix_of_or_equal_to("example.com", "www.example.com"));
+    assert!(is_registrable_domain_suffix_of_or_equal_to("example.com", "a.b.example.com"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_requires_label_boundary() {
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("ample.com", "www.example.com"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("www.example.com", "example.com"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_rejects_public_suffixes() {
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("uk", "example.uk"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("com.ac", "example.com.ac"));
+    assert!(is_registrable_domain_suffix_of_or_equal_to("example.com.ac", "www.example.com.ac"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_rejects_ip_addresses() {
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("0.0.1", "127.0.0.1"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("1]", "[::1]"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_rejects_top_level_domains() {
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("com", "example.com"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("test", "web-platform.test"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_follows_wildcard_rules() {
+    // *.ck makes every domain directly under ck a public suffix.
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("example.ck", "www.example.ck"));
+    assert!(is_registrable_domain_suffix_of_or_equal_to("a.example.ck", "www.a.example.ck"));
+}
+
+#[test]
+fn test_registrable_domain_suffix_follows_exception_rules() {
+    // !www.ck and !city.kobe.jp carve registrable domains out of wildcard rules.
+    assert!(is_registrable_domain_suffix_of_or_equal_to("www.ck", "a.www.ck"));
+    assert!(is_registrable_domain_suffix_of_or_equal_to("city.kobe.jp", "www.city.kobe.jp"));
+    assert!(!is_registrable_domain_suffix_of_or_equal_to("town.kobe.jp", "www.town.kobe.jp"));
+}
@@ -0,0 +1,47 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::htmlelement::{has_hyphen_before_lowercase, to_camel_case};
+
+#[test]
+fn test_to_camel_case() {
+    assert_eq!(to_camel_case("data-foo"), Some("foo".to_owned()));
+    assert_eq!(to_camel_case("data-foo-bar"), Some("fooBar".to_owned()));
+    assert_eq!(to_camel_case("data-foo-bar-baz"), Some("fooBarBaz".to_owned()));
+    assert_eq!(to_camel_case("data-"), Some("".to_owned()));
+}
+
+#[test]
+fn test_to_camel_case_keeps_hyphens_not_before_lowercase() {
+    assert_eq!(to_camel_case("data-foo-"), Some("foo-".to_owned()));
+    assert_eq!(to_camel_case("data-foo--bar"), Some("foo-Bar".to_owned()));
+    assert_eq!(to_camel_case("data-foo-1"), Some("foo-1".to_owned()));
+    assert_eq!(to_camel_case("data-foo-\u{e9}"), Some("foo-\u{e9}".to_owned()));
+}
+
+#[test]
+fn test_to_camel_case_rejects_non_data_names() {
+    assert_eq!(to_camel_case("foo"), None);
+    assert_eq!(to_camel_case("data"), None);
+    assert_eq!(to_camel_case("xdata-foo"), None);
+}
+
+#[test]
+fn test_to_camel_case_rejects_ascii_uppercase() {
+    assert_eq!(to_camel_case("data-fooBar"), None);
+    assert_eq!(to_camel_case("data-Foo"), None);
+    assert_eq!(to_camel_case("data-\u{c9}t\u{e9}"), Some("\u{c9}t\u{e9}".to_owned()));
+}
+
+#[test]
+fn test_has_hyphen_before_lowercase() {
+    assert!(has_hyphen_before_lowercase("foo-bar"));
+    assert!(has_hyphen_before_lowercase("-a"));
+    assert!(!has_hyphen_before_lowercase("fooBar"));
+    assert!(!has_hyphen_before_lowercase("foo-Bar"));
+    assert!(!has_hyphen_before_lowercase("foo-"));
+    assert!(!has_hyphen_before_lowercase("foo-1"));
+    assert!(!has_hyphen_before_lowercase("foo-\u{e9}"));
+    assert!(!has_hyphen_before_lowercase(""));
+}
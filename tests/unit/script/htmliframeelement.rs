@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::htmliframeelement::is_within_lazy_load_margin;
+
+#[test]
+fn test_lazy_iframe_below_the_fold_waits_until_scrolled_into_view() {
+    let (top, bottom) = (10000., 10150.);
+    let viewport_height = 800.;
+
+    assert!(!is_within_lazy_load_margin(top, bottom, 0., viewport_height));
+    assert!(!is_within_lazy_load_margin(top, bottom, 5000., 5000. + viewport_height));
+    assert!(is_within_lazy_load_margin(top, bottom, 9500., 9500. + viewport_height));
+}
+
+#[test]
+fn test_lazy_iframe_in_viewport_loads() {
+    assert!(is_within_lazy_load_margin(100., 250., 0., 800.));
+}
+
+#[test]
+fn test_lazy_iframe_scrolled_past_waits() {
+    assert!(!is_within_lazy_load_margin(100., 250., 5000., 5800.));
+}
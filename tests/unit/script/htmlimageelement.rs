@@ -0,0 +1,47 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::htmlimageelement::{ImageSource, ImageSourceDescriptor};
+use script::dom::htmlimageelement::{parse_a_srcset_attribute, select_image_source};
+use std::borrow::ToOwned;
+
+#[test]
+fn test_parse_a_srcset_attribute() {
+    assert_eq!(parse_a_srcset_attribute(""), vec![]);
+    assert_eq!(parse_a_srcset_attribute("small.png 1x, large.png 2x"), vec![
+        ImageSource { url: "small.png".to_owned(), descriptor: ImageSourceDescriptor::Density(1.) },
+        ImageSource { url: "large.png".to_owned(), descriptor: ImageSourceDescriptor::Density(2.) },
+    ]);
+    assert_eq!(parse_a_srcset_attribute("a.png,b.png 300w"), vec![
+        ImageSource { url: "a.png".to_owned(), descriptor: ImageSourceDescriptor::Density(1.) },
+        ImageSource { url: "b.png".to_owned(), descriptor: ImageSourceDescriptor::Width(300) },
+    ]);
+    // Invalid descriptors drop the candidate.
+    assert_eq!(parse_a_srcset_attribute("a.png 1x 2x, b.png 0w, c.png 10h, d.png 3x"), vec![
+        ImageSource { url: "d.png".to_owned(), descriptor: ImageSourceDescriptor::Density(3.) },
+    ]);
+}
+
+#[test]
+fn test_select_image_source_by_density() {
+    let candidates = parse_a_srcset_attribute("small.png 1x, large.png 2x");
+    let url = |dpr| select_image_source(&candidates, 0., dpr).map(|candidate| &*candidate.url);
+    assert_eq!(url(1.), Some("small.png"));
+    assert_eq!(url(1.5), Some("large.png"));
+    assert_eq!(url(2.), Some("large.png"));
+    // Nothing is dense enough, so the densest candidate wins.
+    assert_eq!(url(3.), Some("large.png"));
+}
+
+#[test]
+fn test_select_image_source_by_width() {
+    let candidates = parse_a_srcset_attribute("small.png 400w, large.png 800w");
+    let url = |source_size, dpr| {
+        select_image_source(&candidates, source_size, dpr).map(|candidate| &*candidate.url)
+    };
+    assert_eq!(url(400., 1.), Some("small.png"));
+    assert_eq!(url(400., 2.), Some("large.png"));
+    assert_eq!(url(800., 1.), Some("large.png"));
+    assert_eq!(select_image_source(&[], 400., 1.), None);
+}
@@ -0,0 +1,68 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::htmlmeterelement::{GaugeRegion, MeterValues};
+
+#[test]
+fn test_meter_defaults() {
+    let values = MeterValues::new(None, None, None, None, None, None);
+    assert_eq!(values, MeterValues {
+        minimum: 0.0,
+        maximum: 1.0,
+        actual: 0.0,
+        low: 0.0,
+        high: 1.0,
+        optimum: 0.5,
+    });
+}
+
+#[test]
+fn test_meter_value_is_clamped() {
+    let values = MeterValues::new(None, Some(10.0), Some(20.0), None, None, None);
+    assert_eq!(values.actual, 10.0);
+
+    let values = MeterValues::new(Some(5.0), Some(10.0), Some(1.0), None, None, None);
+    assert_eq!(values.actual, 5.0);
+
+    // A maximum below the minimum is raised to the minimum.
+    let values = MeterValues::new(Some(5.0), Some(2.0), Some(3.0), None, None, None);
+    assert_eq!(values.maximum, 5.0);
+    assert_eq!(values.actual, 5.0);
+}
+
+#[test]
+fn test_meter_boundaries_are_clamped() {
+    let values = MeterValues::new(Some(0.0), Some(10.0), None, Some(-5.0), Some(20.0), Some(30.0));
+    assert_eq!(values.low, 0.0);
+    assert_eq!(values.high, 10.0);
+    assert_eq!(values.optimum, 10.0);
+
+    // The high boundary is never below the low boundary.
+    let values = MeterValues::new(Some(0.0), Some(10.0), None, Some(6.0), Some(4.0), None);
+    assert_eq!(values.low, 6.0);
+    assert_eq!(values.high, 6.0);
+}
+
+#[test]
+fn test_meter_gauge_region() {
+    let region = |value, optimum| {
+        MeterValues::new(Some(0.0), Some(100.0), Some(value), Some(25.0), Some(75.0), Some(optimum))
+            .gauge_region()
+    };
+
+    // Optimum in the middle region.
+    assert_eq!(region(50.0, 50.0), GaugeRegion::Optimum);
+    assert_eq!(region(10.0, 50.0), GaugeRegion::Suboptimal);
+    assert_eq!(region(90.0, 50.0), GaugeRegion::Suboptimal);
+
+    // Optimum in the low region.
+    assert_eq!(region(10.0, 0.0), GaugeRegion::Optimum);
+    assert_eq!(region(50.0, 0.0), GaugeRegion::Suboptimal);
+    assert_eq!(region(90.0, 0.0), GaugeRegion::EvenLessGood);
+
+    // Optimum in the high region.
+    assert_eq!(region(90.0, 100.0), GaugeRegion::Optimum);
+    assert_eq!(region(50.0, 100.0), GaugeRegion::Suboptimal);
+    assert_eq!(region(10.0, 100.0), GaugeRegion::EvenLessGood);
+}
@@ -0,0 +1,40 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::htmlprogresselement::ProgressValues;
+
+#[test]
+fn test_progress_indeterminate_position() {
+    let values = ProgressValues::new(None, None);
+    assert!(values.indeterminate);
+    assert_eq!(values.position(), -1.0);
+    assert_eq!(values.current, 0.0);
+    assert_eq!(values.maximum, 1.0);
+
+    let values = ProgressValues::new(None, Some(50.0));
+    assert_eq!(values.position(), -1.0);
+}
+
+#[test]
+fn test_progress_position() {
+    assert_eq!(ProgressValues::new(Some(0.25), None).position(), 0.25);
+    assert_eq!(ProgressValues::new(Some(30.0), Some(120.0)).position(), 0.25);
+}
+
+#[test]
+fn test_progress_value_is_clamped() {
+    let values = ProgressValues::new(Some(5.0), Some(2.0));
+    assert_eq!(values.current, 2.0);
+    assert_eq!(values.position(), 1.0);
+
+    let values = ProgressValues::new(Some(-3.0), None);
+    assert_eq!(values.current, 0.0);
+    assert_eq!(values.position(), 0.0);
+}
+
+#[test]
+fn test_progress_maximum_is_positive() {
+    assert_eq!(ProgressValues::new(Some(0.5), Some(0.0)).maximum, 1.0);
+    assert_eq!(ProgressValues::new(Some(0.5), Some(-4.0)).maximum, 1.0);
+}
@@ -0,0 +1,77 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::Au;
+use euclid::point::Point2D;
+use euclid::rect::Rect;
+use euclid::size::Size2D;
+use script::dom::intersectionobserver::{MarginLength, RootMargin};
+use script::dom::intersectionobserver::{compute_intersection, intersection_ratio, threshold_index};
+
+fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect<Au> {
+    Rect::new(Point2D::new(Au::from_px(x), Au::from_px(y)),
+              Size2D::new(Au::from_px(width), Au::from_px(height)))
+}
+
+#[test]
+fn test_element_scrolled_into_view_intersects() {
+    let target = rect(0, 2000, 100, 100);
+    let thresholds = [0.];
+
+    // Initially the target is below the fold.
+    let viewport = rect(0, 0, 800, 600);
+    assert_eq!(compute_intersection(&target, &viewport), None);
+
+    // After scrolling, it is entirely within the viewport.
+    let viewport = rect(0, 1800, 800, 600);
+    let intersection = compute_intersection(&target, &viewport).unwrap();
+    assert_eq!(intersection, target);
+    let ratio = intersection_ratio(&target, &intersection);
+    assert_eq!(ratio, 1.);
+    assert_eq!(threshold_index(&thresholds, ratio), 1);
+}
+
+#[test]
+fn test_partial_intersection_ratio() {
+    let target = rect(0, 550, 100, 100);
+    let viewport = rect(0, 0, 800, 600);
+    let intersection = compute_intersection(&target, &viewport).unwrap();
+    assert_eq!(intersection, rect(0, 550, 100, 50));
+    assert_eq!(intersection_ratio(&target, &intersection), 0.5);
+}
+
+#[test]
+fn test_edge_adjacent_rects_intersect() {
+    let target = rect(0, 600, 100, 100);
+    let viewport = rect(0, 0, 800, 600);
+    assert_eq!(compute_intersection(&target, &viewport), Some(rect(0, 600, 100, 0)));
+}
+
+#[test]
+fn test_threshold_index() {
+    let thresholds = [0., 0.5, 1.];
+    assert_eq!(threshold_index(&thresholds, 0.), 1);
+    assert_eq!(threshold_index(&thresholds, 0.75), 2);
+    assert_eq!(threshold_index(&thresholds, 1.), 3);
+}
+
+#[test]
+fn test_parse_root_margin() {
+    let margin = RootMargin::parse("10px 20%").unwrap();
+    assert_eq!(margin.top, MarginLength::Px(10.));
+    assert_eq!(margin.right, MarginLength::Percent(20.));
+    assert_eq!(margin.bottom, MarginLength::Px(10.));
+    assert_eq!(margin.left, MarginLength::Percent(20.));
+
+    assert!(RootMargin::parse("").is_err());
+    assert!(RootMargin::parse("10").is_err());
+    assert!(RootMargin::parse("10em").is_err());
+    assert!(RootMargin::parse("1px 2px 3px 4px 5px").is_err());
+}
+
+#[test]
+fn test_root_margin_grows_root() {
+    let margin = RootMargin::parse("100px").unwrap();
+    assert_eq!(margin.apply(rect(0, 0, 800, 600)), rect(-100, -100, 1000, 800));
+}
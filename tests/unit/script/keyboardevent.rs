@@ -0,0 +1,29 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use msg::constellation_msg::{CONTROL, Key, KeyModifiers, SHIFT};
+use script::dom::keyboardevent::{key_value, modifier_state};
+
+#[test]
+fn test_key_value_of_printable_character() {
+    assert_eq!(key_value(Key::A, KeyModifiers::empty()), "a");
+    assert_eq!(key_value(Key::A, SHIFT), "A");
+    assert_eq!(key_value(Key::Num1, SHIFT), "!");
+}
+
+#[test]
+fn test_modifier_state_shift() {
+    assert!(modifier_state(SHIFT, &[], "Shift"));
+    assert!(!modifier_state(KeyModifiers::empty(), &[], "Shift"));
+    assert!(!modifier_state(SHIFT, &[], "Control"));
+    assert!(modifier_state(SHIFT | CONTROL, &[], "Control"));
+}
+
+#[test]
+fn test_modifier_state_lock_keys() {
+    let extra_modifiers = vec!["CapsLock".to_owned()];
+    assert!(modifier_state(KeyModifiers::empty(), &extra_modifiers, "CapsLock"));
+    assert!(!modifier_state(KeyModifiers::empty(), &extra_modifiers, "NumLock"));
+    assert!(!modifier_state(KeyModifiers::empty(), &[], "Ctrl"));
+}
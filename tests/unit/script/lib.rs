@@ -2,8 +2,35 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+extern crate app_units;
+extern crate euclid;
 extern crate script;
 extern crate msg;
+extern crate rand;
+extern crate url;
 
 #[cfg(all(test, target_pointer_width = "64"))] mod size_of;
+#[cfg(test)] mod animation;
+#[cfg(test)] mod characterdata;
+#[cfg(test)] mod crypto;
+#[cfg(test)] mod document;
+#[cfg(test)] mod htmlelement;
+#[cfg(test)] mod htmliframeelement;
+#[cfg(test)] mod htmlimageelement;
+#[cfg(test)] mod htmlmeterelement;
+#[cfg(test)] mod htmlprogresselement;
+#[cfg(test)] mod intersectionobserver;
+#[cfg(test)] mod keyboardevent;
+#[cfg(test)] mod mutationobserver;
+#[cfg(test)] mod processinginstruction;
+#[cfg(test)] mod range;
+#[cfg(test)] mod resizeobserver;
+#[cfg(test)] mod screen;
+#[cfg(test)] mod servohtmlparser;
+#[cfg(test)] mod shadowroot;
 #[cfg(test)] mod textinput;
+#[cfg(test)] mod timers;
+#[cfg(test)] mod urlhelper;
+#[cfg(test)] mod utils;
+#[cfg(test)] mod window;
+#[cfg(test)] mod xml;
@@ -0,0 +1,94 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::bindings::codegen::Bindings::MutationObserverBinding::MutationObserverInit;
+use script::dom::mutationobserver::{MutationType, ObserverOptions};
+
+fn init() -> MutationObserverInit {
+    MutationObserverInit {
+        attributeOldValue: None,
+        attributes: None,
+        characterData: None,
+        characterDataOldValue: None,
+        childList: false,
+        subtree: false,
+    }
+}
+
+#[test]
+fn test_options_require_a_mutation_type() {
+    assert!(ObserverOptions::from_init(&init()).is_err());
+    assert!(ObserverOptions::from_init(&MutationObserverInit { subtree: true, ..init() }).is_err());
+    assert!(ObserverOptions::from_init(&MutationObserverInit { attributes: Some(false), ..init() }).is_err());
+}
+
+#[test]
+fn test_old_value_options_imply_their_type() {
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        attributeOldValue: Some(true),
+        ..init()
+    }).unwrap();
+    assert!(options.attributes);
+    assert!(options.attribute_old_value);
+    assert!(!options.character_data);
+
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        characterDataOldValue: Some(false),
+        ..init()
+    }).unwrap();
+    assert!(options.character_data);
+    assert!(!options.character_data_old_value);
+
+    assert!(ObserverOptions::from_init(&MutationObserverInit {
+        attributes: Some(false),
+        attributeOldValue: Some(true),
+        childList: true,
+        ..init()
+    }).is_err());
+    assert!(ObserverOptions::from_init(&MutationObserverInit {
+        characterData: Some(false),
+        characterDataOldValue: Some(true),
+        childList: true,
+        ..init()
+    }).is_err());
+}
+
+#[test]
+fn test_attribute_observation() {
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        attributes: Some(true),
+        ..init()
+    }).unwrap();
+    assert_eq!(options.interest(MutationType::Attributes, true), Some(false));
+    assert_eq!(options.interest(MutationType::Attributes, false), None);
+    assert_eq!(options.interest(MutationType::ChildList, true), None);
+    assert_eq!(options.interest(MutationType::CharacterData, true), None);
+
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        attributeOldValue: Some(true),
+        subtree: true,
+        ..init()
+    }).unwrap();
+    assert_eq!(options.interest(MutationType::Attributes, true), Some(true));
+    assert_eq!(options.interest(MutationType::Attributes, false), Some(true));
+}
+
+#[test]
+fn test_child_list_observation() {
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        childList: true,
+        ..init()
+    }).unwrap();
+    assert_eq!(options.interest(MutationType::ChildList, true), Some(false));
+    assert_eq!(options.interest(MutationType::ChildList, false), None);
+    assert_eq!(options.interest(MutationType::Attributes, true), None);
+
+    let options = ObserverOptions::from_init(&MutationObserverInit {
+        childList: true,
+        subtree: true,
+        ..init()
+    }).unwrap();
+    assert_eq!(options.interest(MutationType::ChildList, false), Some(false));
+    assert_eq!(options.interest(MutationType::CharacterData, false), None);
+}
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::processinginstruction::{XmlStylesheet, parse_pseudo_attributes, parse_xml_stylesheet};
+use script::parse::xml::{XmlNode, parse_xml};
+
+#[test]
+fn test_parse_pseudo_attributes() {
+    assert_eq!(parse_pseudo_attributes(" href='a.css'\ttype = \"text/css\" "), Some(vec![
+        ("href".to_owned(), "a.css".to_owned()),
+        ("type".to_owned(), "text/css".to_owned()),
+    ]));
+    assert_eq!(parse_pseudo_attributes("title=\"&quot;A&quot; &amp; B\""), Some(vec![
+        ("title".to_owned(), "\"A\" & B".to_owned()),
+    ]));
+    assert_eq!(parse_pseudo_attributes(""), Some(vec![]));
+}
+
+#[test]
+fn test_parse_malformed_pseudo_attributes() {
+    assert_eq!(parse_pseudo_attributes("href"), None);
+    assert_eq!(parse_pseudo_attributes("href=a.css"), None);
+    assert_eq!(parse_pseudo_attributes("href=\"a.css"), None);
+    assert_eq!(parse_pseudo_attributes("href=\"a.css\"type=\"text/css\""), None);
+}
+
+#[test]
+fn test_xml_stylesheet_processing_instruction() {
+    let nodes = parse_xml("<?xml-stylesheet href=\"style.css\" type=\"text/css\" media=\"screen\"?><root/>").unwrap();
+    let (target, data) = match nodes[0] {
+        XmlNode::ProcessingInstruction(ref target, ref data) => (target, data),
+        _ => panic!("expected a processing instruction"),
+    };
+    assert_eq!(target, "xml-stylesheet");
+    assert_eq!(data, "href=\"style.css\" type=\"text/css\" media=\"screen\"");
+    assert_eq!(parse_xml_stylesheet(data), Some(XmlStylesheet {
+        href: "style.css".to_owned(),
+        media: "screen".to_owned(),
+        title: None,
+    }));
+}
+
+#[test]
+fn test_xml_stylesheet_without_a_css_sheet() {
+    assert_eq!(parse_xml_stylesheet("type=\"text/css\""), None);
+    assert_eq!(parse_xml_stylesheet("href=\"\""), None);
+    assert_eq!(parse_xml_stylesheet("href=\"style.xsl\" type=\"text/xsl\""), None);
+    assert_eq!(parse_xml_stylesheet("href=\"alt.css\" alternate=\"yes\" title=\"Alt\""), None);
+    assert_eq!(parse_xml_stylesheet("href=\"style.css"), None);
+}
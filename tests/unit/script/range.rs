@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::range::{merged_text_offsets, offset_after_removal, offset_after_replacement};
+
+#[test]
+fn test_offset_after_removal() {
+    // Boundary points before or right at the removed child stay put.
+    assert_eq!(offset_after_removal(0, 2), 0);
+    assert_eq!(offset_after_removal(2, 2), 2);
+    // Those after it move back by one.
+    assert_eq!(offset_after_removal(3, 2), 2);
+    assert_eq!(offset_after_removal(5, 0), 4);
+}
+
+#[test]
+fn test_offset_after_replacement() {
+    // Replacing "bc" in "abcdef" with "XYZ".
+    assert_eq!(offset_after_replacement(0, 1, 2, 3), 0);
+    assert_eq!(offset_after_replacement(1, 1, 2, 3), 1);
+    // Boundary points inside the replaced data collapse to its start.
+    assert_eq!(offset_after_replacement(2, 1, 2, 3), 1);
+    assert_eq!(offset_after_replacement(3, 1, 2, 3), 1);
+    // Those after it are shifted by the difference in length.
+    assert_eq!(offset_after_replacement(4, 1, 2, 3), 5);
+    assert_eq!(offset_after_replacement(6, 1, 2, 0), 4);
+}
+
+#[test]
+fn test_merged_text_offsets() {
+    assert_eq!(merged_text_offsets(3, &[]), Vec::<u32>::new());
+    assert_eq!(merged_text_offsets(3, &[2]), vec![3]);
+    assert_eq!(merged_text_offsets(3, &[2, 0, 4]), vec![3, 5, 5]);
+}
@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use app_units::Au;
+use euclid::point::Point2D;
+use euclid::rect::Rect;
+use euclid::side_offsets::SideOffsets2D;
+use euclid::size::Size2D;
+use script::dom::resizeobserver::content_box;
+
+fn px(value: i32) -> Au {
+    Au::from_px(value)
+}
+
+fn size(width: i32, height: i32) -> Size2D<Au> {
+    Size2D::new(px(width), px(height))
+}
+
+#[test]
+fn test_resized_container_reports_new_content_box() {
+    let padding = SideOffsets2D::new(px(10), px(20), px(10), px(20));
+    let border = SideOffsets2D::new_all_same(px(1));
+
+    let before = content_box(size(200, 100), padding, border);
+    assert_eq!(before, Rect::new(Point2D::new(px(20), px(10)), size(158, 78)));
+
+    let after = content_box(size(300, 150), padding, border);
+    assert!(after.size != before.size);
+    assert_eq!(after, Rect::new(Point2D::new(px(20), px(10)), size(258, 128)));
+}
+
+#[test]
+fn test_content_box_is_never_negative() {
+    let padding = SideOffsets2D::new_all_same(px(50));
+    let border = SideOffsets2D::new_all_same(px(0));
+    assert_eq!(content_box(size(40, 40), padding, border).size, size(0, 0));
+}
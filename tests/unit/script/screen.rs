@@ -0,0 +1,28 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::screen::{device_pixels_to_css_pixels, sanitize_device_pixel_ratio};
+
+#[test]
+fn test_device_pixel_ratio_is_positive() {
+    assert_eq!(sanitize_device_pixel_ratio(Some(2.)), 2.);
+    assert_eq!(sanitize_device_pixel_ratio(None), 1.);
+    assert_eq!(sanitize_device_pixel_ratio(Some(0.)), 1.);
+    assert_eq!(sanitize_device_pixel_ratio(Some(-1.)), 1.);
+}
+
+#[test]
+fn test_avail_width_does_not_exceed_width() {
+    for &dpr in &[1., 1.5, 2.] {
+        let width = device_pixels_to_css_pixels(1920, dpr);
+        let avail_width = device_pixels_to_css_pixels(1856, dpr);
+        assert!(avail_width <= width);
+    }
+}
+
+#[test]
+fn test_device_pixels_to_css_pixels() {
+    assert_eq!(device_pixels_to_css_pixels(1920, 1.), 1920);
+    assert_eq!(device_pixels_to_css_pixels(1920, 2.), 960);
+}
@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::servohtmlparser::script_end_tag_end;
+
+#[test]
+fn test_script_end_tag_end() {
+    let input = "<script>document.write('<p>')</script><p>after";
+    assert_eq!(script_end_tag_end(input), Some(input.find("<p>after").unwrap()));
+    assert_eq!(script_end_tag_end("</SCRIPT >rest"), Some(10));
+    assert_eq!(script_end_tag_end("</script\n>"), Some(10));
+    assert_eq!(script_end_tag_end("</scripts></script>"), Some(19));
+    assert_eq!(script_end_tag_end("<p>no scripts here</p>"), None);
+    assert_eq!(script_end_tag_end("</script"), None);
+    assert_eq!(script_end_tag_end("</scrip"), None);
+    assert_eq!(script_end_tag_end("</p></ScRiPt>"), Some(13));
+    assert_eq!(script_end_tag_end("é</é></script>x"), Some(16));
+}
@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::shadowroot::is_valid_shadow_host_name;
+
+#[test]
+fn test_builtin_shadow_hosts() {
+    for name in &["article", "aside", "blockquote", "body", "div", "footer", "h1", "h6",
+                  "header", "main", "nav", "p", "section", "span"] {
+        assert!(is_valid_shadow_host_name(name), "{} should be a valid shadow host", name);
+    }
+
+    for name in &["a", "button", "img", "input", "table", "template", "textarea", "video"] {
+        assert!(!is_valid_shadow_host_name(name), "{} should not be a valid shadow host", name);
+    }
+}
+
+#[test]
+fn test_custom_element_shadow_hosts() {
+    assert!(is_valid_shadow_host_name("my-element"));
+    assert!(is_valid_shadow_host_name("x-foo-bar"));
+
+    assert!(!is_valid_shadow_host_name("myelement"));
+    assert!(!is_valid_shadow_host_name("-my-element"));
+    assert!(!is_valid_shadow_host_name("1-element"));
+    assert!(!is_valid_shadow_host_name("My-Element"));
+    assert!(!is_valid_shadow_host_name("font-face"));
+    assert!(!is_valid_shadow_host_name("annotation-xml"));
+}
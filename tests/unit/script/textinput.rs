@@ -13,7 +13,7 @@ use msg::constellation_msg::CONTROL;
 use msg::constellation_msg::SUPER;
 use msg::constellation_msg::{Key, KeyModifiers};
 use script::clipboard_provider::DummyClipboardContext;
-use script::textinput::{TextInput, Selection, Lines, Direction};
+use script::textinput::{TextInput, Selection, Lines, Direction, InputEdit};
 use std::borrow::ToOwned;
 
 #[test]
@@ -257,3 +257,30 @@ fn test_textinput_cursor_position_correct_after_clearing_selection() {
     assert_eq!(textinput.edit_point.index, 0);
     assert_eq!(textinput.edit_point.line, 0);
 }
+
+#[test]
+fn test_textinput_edit_for_key() {
+    #[cfg(target_os = "macos")]
+    const MODIFIERS: KeyModifiers = SUPER;
+    #[cfg(not(target_os = "macos"))]
+    const MODIFIERS: KeyModifiers = CONTROL;
+
+    let textinput = TextInput::new(Lines::Single, "abc".to_owned(), DummyClipboardContext::new(""));
+    assert_eq!(textinput.edit_for_key(Key::A, KeyModifiers::empty()),
+               Some(InputEdit { input_type: "insertText", data: Some("a".to_owned()) }));
+    assert_eq!(textinput.edit_for_key(Key::Space, KeyModifiers::empty()),
+               Some(InputEdit { input_type: "insertText", data: Some(" ".to_owned()) }));
+    assert_eq!(textinput.edit_for_key(Key::Backspace, KeyModifiers::empty()),
+               Some(InputEdit { input_type: "deleteContentBackward", data: None }));
+    assert_eq!(textinput.edit_for_key(Key::Delete, KeyModifiers::empty()),
+               Some(InputEdit { input_type: "deleteContentForward", data: None }));
+    assert_eq!(textinput.edit_for_key(Key::V, MODIFIERS),
+               Some(InputEdit { input_type: "insertFromPaste", data: None }));
+    assert_eq!(textinput.edit_for_key(Key::A, MODIFIERS), None);
+    assert_eq!(textinput.edit_for_key(Key::Left, KeyModifiers::empty()), None);
+    assert_eq!(textinput.edit_for_key(Key::Enter, KeyModifiers::empty()), None);
+
+    let textinput = TextInput::new(Lines::Multiple, "abc".to_owned(), DummyClipboardContext::new(""));
+    assert_eq!(textinput.edit_for_key(Key::Enter, KeyModifiers::empty()),
+               Some(InputEdit { input_type: "insertLineBreak", data: None }));
+}
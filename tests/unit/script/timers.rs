@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::timers::{MIN_CLAMPED_TIMEOUT_MS, clamp_timeout};
+
+#[test]
+fn test_clamp_timeout_negative() {
+    assert_eq!(clamp_timeout(-10, 0), 0);
+}
+
+#[test]
+fn test_clamp_timeout_below_nesting_limit() {
+    for nesting_level in 0..6 {
+        assert_eq!(clamp_timeout(0, nesting_level), 0);
+        assert_eq!(clamp_timeout(1, nesting_level), 1);
+    }
+}
+
+#[test]
+fn test_clamp_timeout_above_nesting_limit() {
+    assert_eq!(clamp_timeout(0, 6), MIN_CLAMPED_TIMEOUT_MS);
+    assert_eq!(clamp_timeout(-1, 6), MIN_CLAMPED_TIMEOUT_MS);
+    assert_eq!(clamp_timeout(3, 100), MIN_CLAMPED_TIMEOUT_MS);
+    assert_eq!(clamp_timeout(10, 6), 10);
+}
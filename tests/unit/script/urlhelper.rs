@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::bindings::str::USVString;
+use script::dom::urlhelper::UrlHelper;
+use std::borrow::ToOwned;
+use url::Url;
+
+#[test]
+fn test_hash_change_is_fragment_navigation() {
+    let current = Url::parse("http://example.com/a/b?q=1#top").unwrap();
+    let mut target = current.clone();
+    UrlHelper::SetHash(&mut target, USVString("section".to_owned()));
+    assert!(target != current);
+    assert!(UrlHelper::EqualsExceptFragment(&current, &target));
+}
+
+#[test]
+fn test_pathname_change_is_not_fragment_navigation() {
+    let current = Url::parse("http://example.com/a/b?q=1#top").unwrap();
+    let mut target = current.clone();
+    UrlHelper::SetPathname(&mut target, USVString("/c".to_owned()));
+    assert!(!UrlHelper::EqualsExceptFragment(&current, &target));
+}
+
+#[test]
+fn test_search_change_is_not_fragment_navigation() {
+    let current = Url::parse("http://example.com/a/b?q=1#top").unwrap();
+    let mut target = current.clone();
+    UrlHelper::SetSearch(&mut target, USVString("q=2".to_owned()));
+    assert!(!UrlHelper::EqualsExceptFragment(&current, &target));
+}
@@ -0,0 +1,89 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::bindings::error::Error;
+use script::dom::bindings::utils::{XMLName, validate_and_extract, validate_qualified_name, xml_name_type};
+
+#[test]
+fn test_xml_name_type() {
+    assert!(xml_name_type("foo") == XMLName::QName);
+    assert!(xml_name_type("svg:rect") == XMLName::QName);
+    assert!(xml_name_type("a:b:c") == XMLName::Name);
+    assert!(xml_name_type("") == XMLName::InvalidXMLName);
+    assert!(xml_name_type("1foo") == XMLName::InvalidXMLName);
+    assert!(xml_name_type("foo bar") == XMLName::InvalidXMLName);
+}
+
+#[test]
+fn test_validate_and_extract_invalid_names() {
+    match validate_and_extract(None, "1foo") {
+        Err(Error::InvalidCharacter) => (),
+        _ => panic!("expected InvalidCharacterError"),
+    }
+    match validate_and_extract(None, "a:b:c") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError for a non-QName"),
+    }
+    match validate_and_extract(None, "svg:rect") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError for a prefix without a namespace"),
+    }
+    match validate_and_extract(Some("http://example.com/".to_owned()), "xmlns:foo") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError for xmlns outside the XMLNS namespace"),
+    }
+}
+
+#[test]
+fn test_validate_and_extract_namespaced_name() {
+    let (namespace, prefix, local_name) =
+        validate_and_extract(Some("http://www.w3.org/1999/xlink".to_owned()), "xlink:href").unwrap();
+    assert_eq!(&*namespace.0, "http://www.w3.org/1999/xlink");
+    assert_eq!(prefix.as_ref().map(|prefix| &**prefix), Some("xlink"));
+    assert_eq!(&*local_name, "href");
+
+    let (namespace, prefix, local_name) = validate_and_extract(None, "foo").unwrap();
+    assert_eq!(&*namespace.0, "");
+    assert!(prefix.is_none());
+    assert_eq!(&*local_name, "foo");
+}
+
+#[test]
+fn test_validate_qualified_name() {
+    assert!(validate_qualified_name("html").is_ok());
+    assert!(validate_qualified_name("svg:svg").is_ok());
+
+    match validate_qualified_name("1html") {
+        Err(Error::InvalidCharacter) => (),
+        _ => panic!("expected InvalidCharacterError"),
+    }
+    match validate_qualified_name("a:b:c") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError"),
+    }
+}
+
+#[test]
+fn test_validate_and_extract_reserved_prefixes() {
+    let (namespace, prefix, local_name) =
+        validate_and_extract(Some("http://www.w3.org/XML/1998/namespace".to_owned()), "xml:lang").unwrap();
+    assert_eq!(&*namespace.0, "http://www.w3.org/XML/1998/namespace");
+    assert_eq!(prefix.as_ref().map(|prefix| &**prefix), Some("xml"));
+    assert_eq!(&*local_name, "lang");
+
+    match validate_and_extract(Some("http://example.com/".to_owned()), "xml:lang") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError for xml outside the XML namespace"),
+    }
+
+    let (_, prefix, local_name) =
+        validate_and_extract(Some("http://www.w3.org/2000/xmlns/".to_owned()), "xmlns").unwrap();
+    assert!(prefix.is_none());
+    assert_eq!(&*local_name, "xmlns");
+
+    match validate_and_extract(Some("http://www.w3.org/2000/xmlns/".to_owned()), "foo:bar") {
+        Err(Error::Namespace) => (),
+        _ => panic!("expected NamespaceError for the XMLNS namespace without an xmlns prefix"),
+    }
+}
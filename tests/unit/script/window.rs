@@ -0,0 +1,23 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::dom::window::{TRANSIENT_ACTIVATION_DURATION_MS, is_transiently_activated};
+
+#[test]
+fn test_no_activation_is_not_transient() {
+    assert!(!is_transiently_activated(None, 0.));
+    assert!(!is_transiently_activated(None, 1000.));
+}
+
+#[test]
+fn test_activation_is_transient_for_its_duration() {
+    assert!(is_transiently_activated(Some(1000.), 1000.));
+    assert!(is_transiently_activated(Some(1000.), 1000. + TRANSIENT_ACTIVATION_DURATION_MS - 1.));
+    assert!(!is_transiently_activated(Some(1000.), 1000. + TRANSIENT_ACTIVATION_DURATION_MS));
+}
+
+#[test]
+fn test_activation_in_the_future_is_not_transient() {
+    assert!(!is_transiently_activated(Some(1000.), 999.));
+}
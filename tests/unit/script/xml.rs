@@ -0,0 +1,190 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use script::parse::xml::{XmlElement, XmlName, XmlNode, parse_xml, serialize_xml};
+
+fn root_element(nodes: &[XmlNode]) -> &XmlElement {
+    nodes.iter().filter_map(|node| match *node {
+        XmlNode::Element(ref element) => Some(element),
+        _ => None,
+    }).next().unwrap()
+}
+
+#[test]
+fn test_parse_well_formed_xml() {
+    let nodes = parse_xml("<?xml version=\"1.0\"?>\n<!-- c --><root a=\"1 &amp; 2\">x<![CDATA[<y>]]>&#x7A;<?pi data?></root>").unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0], XmlNode::Comment(" c ".to_owned()));
+
+    let root = root_element(&nodes);
+    assert_eq!(root.name, XmlName { namespace: "".to_owned(), prefix: None, local: "root".to_owned() });
+    assert_eq!(root.attributes[0].1, "1 & 2");
+    assert_eq!(root.children, vec![
+        XmlNode::Text("x<y>z".to_owned()),
+        XmlNode::ProcessingInstruction("pi".to_owned(), "data".to_owned()),
+    ]);
+}
+
+#[test]
+fn test_parse_doctype() {
+    let nodes = parse_xml("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Strict//EN\" \"xhtml1-strict.dtd\"><html/>").unwrap();
+    assert_eq!(nodes[0], XmlNode::Doctype {
+        name: "html".to_owned(),
+        public_id: "-//W3C//DTD XHTML 1.0 Strict//EN".to_owned(),
+        system_id: "xhtml1-strict.dtd".to_owned(),
+    });
+}
+
+#[test]
+fn test_parse_svg_namespaces() {
+    let nodes = parse_xml("<svg xmlns=\"http://www.w3.org/2000/svg\" \
+                           xmlns:xlink=\"http://www.w3.org/1999/xlink\">\
+                           <use xlink:href=\"#a\" x=\"1\"/></svg>").unwrap();
+    let svg = root_element(&nodes);
+    assert_eq!(svg.name.namespace, "http://www.w3.org/2000/svg");
+    assert_eq!(svg.attributes[0].0.namespace, "http://www.w3.org/2000/xmlns/");
+
+    let element = match svg.children[0] {
+        XmlNode::Element(ref element) => element,
+        _ => panic!("expected an element"),
+    };
+    assert_eq!(element.name.namespace, "http://www.w3.org/2000/svg");
+    assert_eq!(element.attributes[0].0, XmlName {
+        namespace: "http://www.w3.org/1999/xlink".to_owned(),
+        prefix: Some("xlink".to_owned()),
+        local: "href".to_owned(),
+    });
+    // Unprefixed attributes are never in the default namespace.
+    assert_eq!(element.attributes[1].0.namespace, "");
+}
+
+#[test]
+fn test_parse_malformed_xml() {
+    assert!(parse_xml("").is_err());
+    assert!(parse_xml("<a>").is_err());
+    assert!(parse_xml("<a></b>").is_err());
+    assert!(parse_xml("<a/><b/>").is_err());
+    assert!(parse_xml("<a x='1' x='2'/>").is_err());
+    assert!(parse_xml("<a>&nbsp;</a>").is_err());
+    assert!(parse_xml("<p:a/>").is_err());
+    assert!(parse_xml("<a b=\"<\"/>").is_err());
+    assert!(parse_xml("<a><?xml version=\"1.0\"?></a>").is_err());
+
+    let error = parse_xml("<a>\n  <b></c>\n</a>").unwrap_err();
+    assert_eq!(error.line, 2);
+}
+
+#[test]
+fn test_parse_invalid_character_references() {
+    assert!(parse_xml("<a>&#0;</a>").is_err());
+    assert!(parse_xml("<a>&#x0;</a>").is_err());
+    assert!(parse_xml("<a b=\"&#1;\"/>").is_err());
+    assert!(parse_xml("<a>&#xFFFE;</a>").is_err());
+    assert!(parse_xml("<a>&#9;&#x10FFFF;</a>").is_ok());
+}
+
+#[test]
+fn test_parse_internal_subset() {
+    let nodes = parse_xml("<!DOCTYPE a [<!ENTITY e \"]\"><!-- ] --><?pi ]?><!ATTLIST a b CDATA ']'>]><a/>")
+        .unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert!(parse_xml("<!DOCTYPE a [<!ENTITY e \"]\">").is_err());
+}
+
+#[test]
+fn test_parse_deeply_nested_elements() {
+    let depth = 100000;
+    let mut source = String::new();
+    for _ in 0..depth {
+        source.push_str("<a>");
+    }
+    for _ in 0..depth {
+        source.push_str("</a>");
+    }
+    let nodes = parse_xml(&source).unwrap();
+
+    let mut element = root_element(&nodes);
+    let mut levels = 1;
+    while let Some(&XmlNode::Element(ref child)) = element.children.first() {
+        element = child;
+        levels += 1;
+    }
+    assert_eq!(levels, depth);
+}
+
+fn name(namespace: &str, prefix: Option<&str>, local: &str) -> XmlName {
+    XmlName {
+        namespace: namespace.to_owned(),
+        prefix: prefix.map(|prefix| prefix.to_owned()),
+        local: local.to_owned(),
+    }
+}
+
+fn element(name: XmlName, attributes: Vec<(XmlName, String)>, children: Vec<XmlNode>) -> XmlNode {
+    XmlNode::Element(XmlElement {
+        name: name,
+        attributes: attributes,
+        children: children,
+    })
+}
+
+#[test]
+fn test_serialize_round_trip() {
+    let source = "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\">\
+                  <use xlink:href=\"#a\"/><text>a &amp; b</text><!-- c --></svg>";
+    let nodes = parse_xml(source).unwrap();
+    let serialized = serialize_xml(&nodes);
+    assert_eq!(serialized, source);
+    assert_eq!(parse_xml(&serialized).unwrap(), nodes);
+}
+
+#[test]
+fn test_serialize_generates_namespace_declarations() {
+    let nodes = vec![element(name("urn:a", None, "x"),
+                             vec![(name("urn:b", None, "y"), "1".to_owned()),
+                                  (name("urn:c", Some("p"), "z"), "2".to_owned())],
+                             vec![element(name("urn:a", Some("q"), "w"), vec![], vec![]),
+                                  element(name("", None, "v"), vec![], vec![])])];
+    assert_eq!(serialize_xml(&nodes),
+               "<x xmlns=\"urn:a\" xmlns:ns1=\"urn:b\" ns1:y=\"1\" xmlns:p=\"urn:c\" p:z=\"2\">\
+                <q:w xmlns:q=\"urn:a\"/><v xmlns=\"\"/></x>");
+}
+
+#[test]
+fn test_serialize_escaping_and_empty_elements() {
+    let xhtml = "http://www.w3.org/1999/xhtml";
+    let nodes = vec![element(name(xhtml, None, "div"),
+                             vec![(name("", None, "title"), "\"<&>\"".to_owned())],
+                             vec![XmlNode::Text("1 < 2 & 3 > 2".to_owned()),
+                                  element(name(xhtml, None, "br"), vec![], vec![]),
+                                  element(name(xhtml, None, "span"), vec![], vec![])])];
+    assert_eq!(serialize_xml(&nodes),
+               "<div xmlns=\"http://www.w3.org/1999/xhtml\" title=\"&quot;&lt;&amp;&gt;&quot;\">\
+                1 &lt; 2 &amp; 3 &gt; 2<br /><span></span></div>");
+}
+
+#[test]
+fn test_serialize_replaces_conflicting_default_namespace_declaration() {
+    let xmlns = "http://www.w3.org/2000/xmlns/";
+    let nodes = vec![element(name("urn:a", None, "x"), vec![],
+                             vec![element(name("", None, "y"),
+                                          vec![(name(xmlns, None, "xmlns"), "urn:b".to_owned())],
+                                          vec![])])];
+    assert_eq!(serialize_xml(&nodes), "<x xmlns=\"urn:a\"><y xmlns=\"\"/></x>");
+}
+
+#[test]
+fn test_serialize_deeply_nested_elements() {
+    let depth = 100000;
+    let mut source = String::new();
+    for _ in 0..depth {
+        source.push_str("<a>");
+    }
+    source.push_str("text");
+    for _ in 0..depth {
+        source.push_str("</a>");
+    }
+    let nodes = parse_xml(&source).unwrap();
+    assert_eq!(serialize_xml(&nodes), source);
+}
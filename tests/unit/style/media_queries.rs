@@ -3,6 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use app_units::Au;
+use cssparser::Parser;
 use euclid::size::Size2D;
 use std::borrow::ToOwned;
 use style::media_queries::*;
@@ -418,3 +419,17 @@ fn test_matching_invalid() {
     media_query_test(&device, "@media screen and (height: 100px) { a { color: red; } }", 0);
     media_query_test(&device, "@media not print and (width: 100) { a { color: red; } }", 0);
 }
+
+#[test]
+fn test_matching_parsed_list() {
+    let device = Device {
+        media_type: MediaType::Screen,
+        viewport_size: Size2D::typed(800.0, 600.0),
+    };
+
+    let matches = |query: &str| parse_media_query_list(&mut Parser::new(query)).evaluate(&device);
+    assert!(!matches("(min-width: 10000px)"));
+    assert!(matches("(max-width: 10000px)"));
+    assert!(matches("screen and (min-width: 800px)"));
+    assert!(!matches("print"));
+}
@@ -3,12 +3,15 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use cssparser;
+use euclid::size::Size2D;
 use selectors::parser::*;
 use std::borrow::ToOwned;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use string_cache::Atom;
+use style::media_queries::{Device, MediaType};
 use style::properties::{PropertyDeclaration, PropertyDeclarationBlock, DeclaredValue, longhands};
-use style::stylesheets::{CSSRule, StyleRule, Origin, Stylesheet};
+use style::stylesheets::{CSSRule, CSSRuleIteratorExt, StyleRule, Origin, Stylesheet};
 use url::Url;
 
 
@@ -25,6 +28,7 @@ fn test_parse_stylesheet() {
     let stylesheet = Stylesheet::from_str(css, url, Origin::UserAgent);
     assert_eq!(stylesheet, Stylesheet {
         origin: Origin::UserAgent,
+        disabled: Arc::new(AtomicBool::new(false)),
         rules: vec![
             CSSRule::Namespace(None, ns!(HTML)),
             CSSRule::Style(StyleRule {
@@ -138,3 +142,20 @@ fn test_parse_stylesheet() {
         ],
     });
 }
+
+#[test]
+fn test_disabled_stylesheet_has_no_effective_rules() {
+    let css = "p { color: red; } @media screen { div { color: blue; } }";
+    let url = Url::parse("about::test").unwrap();
+    let stylesheet = Stylesheet::from_str(css, url, Origin::Author);
+    let device = Device::new(MediaType::Screen, Size2D::typed(800., 600.));
+    assert_eq!(stylesheet.effective_rules(&device).style().count(), 2);
+
+    stylesheet.set_disabled(true);
+    assert!(stylesheet.is_disabled());
+    assert_eq!(stylesheet.effective_rules(&device).style().count(), 0);
+    assert_eq!(stylesheet.rules().style().count(), 2);
+
+    stylesheet.set_disabled(false);
+    assert_eq!(stylesheet.effective_rules(&device).style().count(), 2);
+}
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use util::str::{split_html_space_chars, str_join};
+use util::str::{parse_floating_point_number, split_html_space_chars, str_join};
 
 
 #[test]
@@ -11,6 +11,12 @@ pub fn split_html_space_chars_whitespace() {
     assert!(split_html_space_chars("\u{0020}\u{0009}\u{000a}\u{000c}\u{000d}").collect::<Vec<_>>().is_empty());
 }
 
+#[test]
+pub fn split_html_space_chars_collapse() {
+    let actual = str_join(split_html_space_chars("\u{000a}  foo \u{0009}\u{000c}bar\u{000d} baz "), " ");
+    assert_eq!(actual, "foo bar baz");
+}
+
 #[test]
 pub fn test_str_join_empty() {
     let slice = [] as [&str; 0];
@@ -34,3 +40,19 @@ pub fn test_str_join_many() {
     let expected = "-alpha--beta-gamma-";
     assert_eq!(actual, expected);
 }
+
+#[test]
+pub fn test_parse_floating_point_number() {
+    assert_eq!(parse_floating_point_number("1"), Some(1.0));
+    assert_eq!(parse_floating_point_number("  -2.5"), Some(-2.5));
+    assert_eq!(parse_floating_point_number("+.5"), Some(0.5));
+    assert_eq!(parse_floating_point_number("1e3"), Some(1000.0));
+    assert_eq!(parse_floating_point_number("1.5E-1"), Some(0.15));
+    assert_eq!(parse_floating_point_number("7px"), Some(7.0));
+    assert_eq!(parse_floating_point_number("3e"), Some(3.0));
+    assert_eq!(parse_floating_point_number(""), None);
+    assert_eq!(parse_floating_point_number("-"), None);
+    assert_eq!(parse_floating_point_number("."), None);
+    assert_eq!(parse_floating_point_number("abc"), None);
+    assert_eq!(parse_floating_point_number("1e400"), None);
+}
@@ -0,0 +1 @@
+log.push("defer 2");